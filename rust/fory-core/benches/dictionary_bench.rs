@@ -0,0 +1,58 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fory_core::fory::Fory;
+
+fn benchmark_dictionary_strings(c: &mut Criterion) {
+    let column: Vec<String> = (0..100_000)
+        .map(|i| format!("category-{:02}", i % 50))
+        .collect();
+    let plain = Fory::default();
+    let dictionary = Fory::default().with_dictionary_strings(true);
+    let plain_bytes = plain.serialize(&column).unwrap();
+    let dictionary_bytes = dictionary.serialize(&column).unwrap();
+    println!(
+        "100k strings, 50 distinct: {} bytes plain, {} bytes with a dictionary",
+        plain_bytes.len(),
+        dictionary_bytes.len()
+    );
+
+    c.bench_function("Serialize 100k Vec<String>", |b| {
+        b.iter(|| plain.serialize(black_box(&column)).unwrap())
+    });
+    c.bench_function("Serialize 100k Vec<String> with a dictionary", |b| {
+        b.iter(|| dictionary.serialize(black_box(&column)).unwrap())
+    });
+    c.bench_function("Deserialize 100k Vec<String>", |b| {
+        b.iter(|| {
+            plain
+                .deserialize::<Vec<String>>(black_box(&plain_bytes))
+                .unwrap()
+        })
+    });
+    c.bench_function("Deserialize 100k Vec<String> with a dictionary", |b| {
+        b.iter(|| {
+            dictionary
+                .deserialize::<Vec<String>>(black_box(&dictionary_bytes))
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, benchmark_dictionary_strings);
+criterion_main!(benches);
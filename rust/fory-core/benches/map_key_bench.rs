@@ -0,0 +1,67 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fory_core::fory::Fory;
+use std::collections::BTreeMap;
+
+fn benchmark_map_key_prefix_sharing(c: &mut Criterion) {
+    let map: BTreeMap<String, i32> = (0..10_000)
+        .map(|i| {
+            (
+                format!(
+                    "org.apache.fory.benchmark.metrics.service.latency.p{:05}",
+                    i
+                ),
+                i,
+            )
+        })
+        .collect();
+    let plain = Fory::default();
+    let shared = Fory::default().with_map_key_prefix_sharing(true);
+    let plain_bytes = plain.serialize(&map).unwrap();
+    let shared_bytes = shared.serialize(&map).unwrap();
+    println!(
+        "10k keys: {} bytes plain, {} bytes with shared prefixes",
+        plain_bytes.len(),
+        shared_bytes.len()
+    );
+
+    c.bench_function("Serialize 10k-key BTreeMap", |b| {
+        b.iter(|| plain.serialize(black_box(&map)).unwrap())
+    });
+    c.bench_function("Serialize 10k-key BTreeMap with shared prefixes", |b| {
+        b.iter(|| shared.serialize(black_box(&map)).unwrap())
+    });
+    c.bench_function("Deserialize 10k-key BTreeMap", |b| {
+        b.iter(|| {
+            plain
+                .deserialize::<BTreeMap<String, i32>>(black_box(&plain_bytes))
+                .unwrap()
+        })
+    });
+    c.bench_function("Deserialize 10k-key BTreeMap with shared prefixes", |b| {
+        b.iter(|| {
+            shared
+                .deserialize::<BTreeMap<String, i32>>(black_box(&shared_bytes))
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, benchmark_map_key_prefix_sharing);
+criterion_main!(benches);
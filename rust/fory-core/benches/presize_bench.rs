@@ -0,0 +1,46 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fory_core::fory::Fory;
+use fory_core::serializer::Serializer;
+use std::collections::HashMap;
+
+fn bench_pair<T: Serializer>(c: &mut Criterion, name: &str, value: &T) {
+    let growing = Fory::default();
+    let presized = Fory::default().with_exact_presize(true);
+    c.bench_function(&format!("Serialize {}", name), |b| {
+        b.iter(|| growing.serialize(black_box(value)).unwrap())
+    });
+    c.bench_function(&format!("Serialize {} with exact presize", name), |b| {
+        b.iter(|| presized.serialize(black_box(value)).unwrap())
+    });
+}
+
+fn benchmark_exact_presize(c: &mut Criterion) {
+    bench_pair(c, "i64", &42i64);
+    bench_pair(c, "short String", &"hello".to_string());
+    let ints: Vec<i32> = (0..100_000).collect();
+    bench_pair(c, "100k Vec<i32>", &ints);
+    let strings: Vec<String> = (0..10_000).map(|i| format!("item-{}", i)).collect();
+    bench_pair(c, "10k Vec<String>", &strings);
+    let map: HashMap<String, i64> = (0..10_000).map(|i| (format!("key-{}", i), i)).collect();
+    bench_pair(c, "10k HashMap<String, i64>", &map);
+}
+
+criterion_group!(benches, benchmark_exact_presize);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fory_core::buffer::Reader;
+use fory_core::resolver::context::ReadContext;
+use fory_core::Fory;
+
+const LEN: usize = 100_000;
+
+fn new_context(bytes: &[u8]) -> ReadContext {
+    ReadContext::new_from_fory(Reader::new(bytes), &Fory::default())
+}
+
+fn benchmark_read_primitive_array(c: &mut Criterion) {
+    let ints: Vec<u8> = (0..LEN as i32).flat_map(|v| v.to_le_bytes()).collect();
+    c.bench_function("Read 100k i32 per element", |b| {
+        b.iter(|| {
+            let mut context = new_context(black_box(&ints));
+            let values: Vec<i32> = (0..LEN)
+                .map(|_| context.reader.read_i32().unwrap())
+                .collect();
+            values
+        })
+    });
+    c.bench_function("Read 100k i32 read_primitive_array", |b| {
+        b.iter(|| {
+            let mut context = new_context(black_box(&ints));
+            context.read_primitive_array::<i32>(LEN).unwrap()
+        })
+    });
+
+    let floats: Vec<u8> = (0..LEN).flat_map(|v| (v as f64).to_le_bytes()).collect();
+    c.bench_function("Read 100k f64 per element", |b| {
+        b.iter(|| {
+            let mut context = new_context(black_box(&floats));
+            let values: Vec<f64> = (0..LEN)
+                .map(|_| context.reader.read_f64().unwrap())
+                .collect();
+            values
+        })
+    });
+    c.bench_function("Read 100k f64 read_primitive_array", |b| {
+        b.iter(|| {
+            let mut context = new_context(black_box(&floats));
+            context.read_primitive_array::<f64>(LEN).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, benchmark_read_primitive_array);
+criterion_main!(benches);
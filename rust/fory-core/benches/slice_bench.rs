@@ -0,0 +1,41 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fory_core::fory::Fory;
+
+fn benchmark_slice(c: &mut Criterion) {
+    let fory = Fory::default();
+    let ints: Vec<i32> = (0..1_000_000).collect();
+    let mut out = Vec::with_capacity(8 << 20);
+    c.bench_function("serialize_to 1M Vec<i32>", |b| {
+        b.iter(|| {
+            out.clear();
+            fory.serialize_to(black_box(&ints), &mut out).unwrap()
+        })
+    });
+    c.bench_function("serialize_slice_to 1M &[i32]", |b| {
+        b.iter(|| {
+            out.clear();
+            fory.serialize_slice_to(black_box(&ints[..]), &mut out)
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, benchmark_slice);
+criterion_main!(benches);
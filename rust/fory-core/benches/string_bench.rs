@@ -0,0 +1,46 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use fory_core::fory::Fory;
+
+fn benchmark_serialize_long_strings(c: &mut Criterion) {
+    let fory = Fory::default();
+    let sizes = [100, 1000, 10000];
+    for &size in &sizes {
+        let latin1: Vec<String> = (0..100)
+            .map(|i| format!("{}-{}", i, "Hello, World! ".repeat(size / 14 + 1)))
+            .collect();
+        let name = format!("Serialize Vec<String> latin1 size {}", size);
+        c.bench_function(&name, |b| {
+            b.iter(|| fory.serialize(black_box(&latin1)).unwrap())
+        });
+    }
+    let fory = Fory::default().compress_string(true);
+    for &size in &sizes {
+        let utf8: Vec<String> = (0..100)
+            .map(|i| format!("{}-{}", i, "Hello, 世界! ".repeat(size / 16 + 1)))
+            .collect();
+        let name = format!("Serialize Vec<String> utf8 size {}", size);
+        c.bench_function(&name, |b| {
+            b.iter(|| fory.serialize(black_box(&utf8)).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, benchmark_serialize_long_strings);
+criterion_main!(benches);
@@ -23,10 +23,232 @@ use crate::meta::buffer_rw_string::{
 use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
 use std::slice;
 
+/// The bytes held by a [`Writer`]: its own `Vec`, or with the `mmap` feature a region of
+/// borrowed memory such as the spare room of a memory map while one is lent to it with
+/// [`WriteBuffer::lend`].
+///
+/// Once a value outgrows the lent region, the bytes written so far are moved to the `Vec`
+/// and writing goes on there.
+#[derive(Default)]
+pub(crate) struct WriteBuffer {
+    vec: Vec<u8>,
+    #[cfg(feature = "mmap")]
+    lent: Option<Lent>,
+}
+
+#[cfg(feature = "mmap")]
+struct Lent {
+    ptr: *mut u8,
+    len: usize,
+    cap: usize,
+}
+
+// SAFETY: a region is only lent for the duration of a call holding it mutably borrowed, see
+// `WriteBuffer::lend`, so the buffer owns its bytes as far as other threads can observe.
+#[cfg(feature = "mmap")]
+unsafe impl Send for WriteBuffer {}
+
+impl WriteBuffer {
+    /// Writes into `region` instead of the own `Vec`, starting from its beginning.
+    ///
+    /// # Safety
+    ///
+    /// `region` must outlive the lending, which ends with [`WriteBuffer::take_lent`].
+    #[cfg(feature = "mmap")]
+    pub(crate) unsafe fn lend(&mut self, region: &mut [u8]) {
+        self.lent = Some(Lent {
+            ptr: region.as_mut_ptr(),
+            len: 0,
+            cap: region.len(),
+        });
+    }
+
+    /// Ends the lending started by [`WriteBuffer::lend`].
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written to the region, or `None` if they did not fit and were
+    /// moved to the own `Vec`.
+    #[cfg(feature = "mmap")]
+    pub(crate) fn take_lent(&mut self) -> Option<usize> {
+        self.lent.take().map(|lent| lent.len)
+    }
+
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        #[cfg(feature = "mmap")]
+        if let Some(lent) = &self.lent {
+            return lent.len;
+        }
+        self.vec.len()
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline(always)]
+    pub(crate) fn clear(&mut self) {
+        #[cfg(feature = "mmap")]
+        if let Some(lent) = &mut self.lent {
+            lent.len = 0;
+            return;
+        }
+        self.vec.clear()
+    }
+
+    #[inline(always)]
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        #[cfg(feature = "mmap")]
+        if let Some(lent) = &self.lent {
+            if lent.cap - lent.len < additional {
+                self.spill(additional);
+            }
+            return;
+        }
+        self.vec.reserve(additional)
+    }
+
+    #[inline(always)]
+    pub(crate) fn as_mut_ptr(&mut self) -> *mut u8 {
+        #[cfg(feature = "mmap")]
+        if let Some(lent) = &self.lent {
+            return lent.ptr;
+        }
+        self.vec.as_mut_ptr()
+    }
+
+    /// # Safety
+    ///
+    /// As for [`Vec::set_len`], after reserving room for `new_len` bytes.
+    #[inline(always)]
+    pub(crate) unsafe fn set_len(&mut self, new_len: usize) {
+        #[cfg(feature = "mmap")]
+        if let Some(lent) = &mut self.lent {
+            lent.len = new_len;
+            return;
+        }
+        self.vec.set_len(new_len)
+    }
+
+    #[inline(always)]
+    pub(crate) fn extend_from_slice(&mut self, bytes: &[u8]) {
+        #[cfg(feature = "mmap")]
+        if self.lent.is_some() {
+            self.reserve(bytes.len());
+            if let Some(lent) = &mut self.lent {
+                // SAFETY: `reserve` left room for `bytes` in the region
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        bytes.as_ptr(),
+                        lent.ptr.add(lent.len),
+                        bytes.len(),
+                    )
+                };
+                lent.len += bytes.len();
+                return;
+            }
+        }
+        self.vec.extend_from_slice(bytes)
+    }
+
+    #[inline(always)]
+    pub(crate) fn resize(&mut self, new_len: usize, value: u8) {
+        let len = self.len();
+        if new_len <= len {
+            // SAFETY: shrinking only
+            unsafe { self.set_len(new_len) };
+            return;
+        }
+        self.reserve(new_len - len);
+        // SAFETY: `reserve` left room for `new_len` bytes
+        unsafe {
+            std::ptr::write_bytes(self.as_mut_ptr().add(len), value, new_len - len);
+            self.set_len(new_len);
+        }
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<u8> {
+        #[cfg(feature = "mmap")]
+        if self.lent.is_some() {
+            return self.to_vec();
+        }
+        self.vec
+    }
+
+    #[cfg(feature = "mmap")]
+    #[cold]
+    fn spill(&mut self, additional: usize) {
+        let lent = self.lent.take().unwrap();
+        self.vec.clear();
+        self.vec.reserve(lent.len + additional);
+        // SAFETY: the first `lent.len` bytes of the region were written
+        self.vec
+            .extend_from_slice(unsafe { slice::from_raw_parts(lent.ptr, lent.len) });
+    }
+}
+
+impl From<Vec<u8>> for WriteBuffer {
+    fn from(vec: Vec<u8>) -> Self {
+        WriteBuffer {
+            vec,
+            #[cfg(feature = "mmap")]
+            lent: None,
+        }
+    }
+}
+
+impl std::ops::Deref for WriteBuffer {
+    type Target = [u8];
+
+    #[inline(always)]
+    fn deref(&self) -> &[u8] {
+        #[cfg(feature = "mmap")]
+        if let Some(lent) = &self.lent {
+            // SAFETY: the first `lent.len` bytes of the region were written
+            return unsafe { slice::from_raw_parts(lent.ptr, lent.len) };
+        }
+        &self.vec
+    }
+}
+
+impl std::ops::DerefMut for WriteBuffer {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut [u8] {
+        #[cfg(feature = "mmap")]
+        if let Some(lent) = &mut self.lent {
+            // SAFETY: the first `lent.len` bytes of the region were written
+            return unsafe { slice::from_raw_parts_mut(lent.ptr, lent.len) };
+        }
+        &mut self.vec
+    }
+}
+
+impl std::io::Write for WriteBuffer {
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    #[inline(always)]
+    fn write_all(&mut self, bytes: &[u8]) -> std::io::Result<()> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
 #[derive(Default)]
 pub struct Writer {
-    pub(crate) bf: Vec<u8>,
-    reserved: usize,
+    pub(crate) bf: WriteBuffer,
+    /// Set while `bf` was allocated with the exact output size, so that the size hints
+    /// passed to `reserve` cannot grow it.
+    pub(crate) exact: bool,
 }
 
 impl Writer {
@@ -38,7 +260,7 @@ impl Writer {
 
     #[inline(always)]
     pub fn dump(&self) -> Vec<u8> {
-        self.bf.clone()
+        self.bf.to_vec()
     }
 
     #[inline(always)]
@@ -53,9 +275,9 @@ impl Writer {
 
     #[inline(always)]
     pub fn reserve(&mut self, additional: usize) {
-        self.reserved += additional;
-        if self.bf.capacity() < self.reserved {
-            self.bf.reserve(self.reserved);
+        // `Vec::reserve` is a no-op when the spare capacity already fits `additional`
+        if !self.exact {
+            self.bf.reserve(additional);
         }
     }
 
@@ -64,6 +286,36 @@ impl Writer {
         self.bf.resize(self.bf.len() + len, 0);
     }
 
+    /// Writes `len` zero bytes to be filled in later and returns their offset.
+    #[inline(always)]
+    pub fn reserve_placeholder(&mut self, len: usize) -> usize {
+        let offset = self.bf.len();
+        self.skip(len);
+        offset
+    }
+
+    /// Overwrites 4 bytes at `offset` with `value` in little-endian order.
+    #[inline(always)]
+    pub fn patch_u32(&mut self, offset: usize, value: u32) {
+        self.set_bytes(offset, &value.to_le_bytes());
+    }
+
+    /// Overwrites 5 bytes at `offset` with `value` as a varuint32 padded to its maximum
+    /// width, so that it can be read back with `read_varuint32`.
+    #[inline(always)]
+    pub fn patch_varuint32(&mut self, offset: usize, value: u32) {
+        self.set_bytes(
+            offset,
+            &[
+                (value & 0x7F) as u8 | 0x80,
+                ((value >> 7) & 0x7F) as u8 | 0x80,
+                ((value >> 14) & 0x7F) as u8 | 0x80,
+                ((value >> 21) & 0x7F) as u8 | 0x80,
+                (value >> 28) as u8,
+            ],
+        );
+    }
+
     #[inline(always)]
     pub fn set_bytes(&mut self, offset: usize, data: &[u8]) {
         self.bf
@@ -81,7 +333,7 @@ impl Writer {
 
     #[inline(always)]
     pub fn write_u8(&mut self, value: u8) {
-        self.bf.write_u8(value).unwrap();
+        WriteBytesExt::write_u8(&mut self.bf, value).unwrap();
     }
 
     #[inline(always)]
@@ -303,8 +555,8 @@ impl Writer {
             let b0 = (value & 0x7F) | 0x80;
             let b1 = ((value >> 7) & 0x7F) | 0x80;
             let b2 = value >> 14;
-            let combined = b0 | (b1 << 8) | (b2 << 16);
-            self.write_u32(combined as u32);
+            self.write_u16((b0 | (b1 << 8)) as u16);
+            self.write_u8(b2 as u8);
         } else if value < 0x10000000 {
             let b0 = (value & 0x7F) | 0x80;
             let b1 = ((value >> 7) & 0x7F) | 0x80;
@@ -318,8 +570,9 @@ impl Writer {
             let b2 = ((value >> 14) & 0x7F) | 0x80;
             let b3 = ((value >> 21) & 0x7F) | 0x80;
             let b4 = value >> 28;
-            let combined = b0 | (b1 << 8) | (b2 << 16) | (b3 << 24) | (b4 << 32);
-            self.write_u64(combined);
+            let combined = b0 | (b1 << 8) | (b2 << 16) | (b3 << 24);
+            self.write_u32(combined as u32);
+            self.write_u8(b4 as u8);
         }
     }
 
@@ -339,6 +592,80 @@ impl Writer {
     }
 }
 
+/// A destination for serialized bytes, such as a counting writer, a memory map or a
+/// compressing stream, for [`Fory::serialize_to`](crate::fory::Fory::serialize_to).
+///
+/// Values are still encoded into a pooled [`Writer`] first, since chunk headers and meta
+/// offsets are patched after the fact, and the finished bytes are then handed to the
+/// sink. Only `write_bytes` and `position` must be implemented.
+pub trait ByteWriter {
+    /// Appends `bytes` to the sink.
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error>;
+
+    /// Number of bytes written to the sink so far.
+    fn position(&self) -> usize;
+
+    fn write_u8(&mut self, value: u8) -> Result<(), Error> {
+        self.write_bytes(&[value])
+    }
+
+    /// Writes `value` with the same varint encoding as [`Writer::write_varuint32`].
+    fn write_varuint32(&mut self, mut value: u32) -> Result<(), Error> {
+        let mut bytes = [0u8; 5];
+        let mut len = 0;
+        while value >= 0x80 {
+            bytes[len] = (value as u8) | 0x80;
+            value >>= 7;
+            len += 1;
+        }
+        bytes[len] = value as u8;
+        self.write_bytes(&bytes[..=len])
+    }
+
+    /// Hints that `additional` more bytes are about to be written.
+    fn reserve(&mut self, _additional: usize) {}
+}
+
+impl ByteWriter for Writer {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        Writer::write_bytes(self, bytes);
+        Ok(())
+    }
+
+    fn position(&self) -> usize {
+        self.len()
+    }
+
+    fn write_u8(&mut self, value: u8) -> Result<(), Error> {
+        Writer::write_u8(self, value);
+        Ok(())
+    }
+
+    fn write_varuint32(&mut self, value: u32) -> Result<(), Error> {
+        Writer::write_varuint32(self, value);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Writer::reserve(self, additional)
+    }
+}
+
+impl ByteWriter for Vec<u8> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn position(&self) -> usize {
+        self.len()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional)
+    }
+}
+
 pub struct Reader {
     pub(crate) bf: *const u8,
     len: usize,
@@ -391,6 +718,16 @@ impl Reader {
         self.cursor
     }
 
+    /// Moves the cursor to `cursor`, which may be at most the length of the buffer.
+    #[inline(always)]
+    pub fn set_cursor(&mut self, cursor: usize) -> Result<(), Error> {
+        if cursor > self.len {
+            return Err(Error::BufferOutOfBound(cursor, 0, self.len));
+        }
+        self.cursor = cursor;
+        Ok(())
+    }
+
     #[inline(always)]
     fn check_bound(&self, n: usize) -> Result<(), Error> {
         // The upper layer guarantees it is non-null
@@ -660,21 +997,24 @@ impl Reader {
 
         let mut result = 0u64;
         let mut shift = 0;
-        while self.cursor < self.len {
+        loop {
+            // the input must not end before the terminating byte
+            self.check_bound(1)?;
             let b = {
-                // read_u8 but no need to check bound
                 let ptr = self.ptr_at(self.cursor);
                 self.move_next(1);
                 unsafe { *ptr }
             };
+            if shift == 28 {
+                // the fifth byte carries the remaining 8 bits without a continuation flag
+                result |= (b as u64) << shift;
+                break;
+            }
             result |= ((b & 0x7F) as u64) << shift;
             if (b & 0x80) == 0 {
                 break;
             }
             shift += 7;
-            if shift >= 36 {
-                return Err(Error::EncodeError("varuint36small overflow".into()));
-            }
         }
         Ok(result)
     }
@@ -722,3 +1062,82 @@ impl Reader {
 
 unsafe impl Send for Reader {}
 unsafe impl Sync for Reader {}
+
+/// A source of serialized bytes, such as a stream that yields data in pieces, for
+/// [`Fory::deserialize_from`](crate::fory::Fory::deserialize_from).
+///
+/// Type meta is read at offsets into the data, so the value is gathered into a buffer
+/// before it is decoded. `read_bytes`, `remaining`, `position` and `set_position` must be
+/// implemented.
+pub trait ByteReader {
+    /// Reads up to `buf.len()` bytes into `buf` and returns how many were read, which is
+    /// 0 once the source is exhausted.
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<usize, Error>;
+
+    /// Number of bytes left in the source.
+    fn remaining(&self) -> usize;
+
+    /// Number of bytes read from the source so far.
+    fn position(&self) -> usize;
+
+    fn set_position(&mut self, position: usize) -> Result<(), Error>;
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        let mut byte = [0u8];
+        match self.read_bytes(&mut byte)? {
+            0 => Err(Error::UnexpectedEof("the source is exhausted".into())),
+            _ => Ok(byte[0]),
+        }
+    }
+
+    /// Reads a value written by [`Writer::write_varuint32`].
+    fn read_varuint32(&mut self) -> Result<u32, Error> {
+        let mut result = 0u32;
+        for i in 0..5 {
+            let byte = self.read_u8()?;
+            result |= ((byte & 0x7f) as u32) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+        }
+        Err(Error::InvalidData(
+            "varuint32 is longer than 5 bytes".into(),
+        ))
+    }
+
+    fn skip_bytes(&mut self, len: usize) -> Result<(), Error> {
+        self.set_position(self.position() + len)
+    }
+}
+
+impl ByteReader for Reader {
+    fn read_bytes(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let len = buf.len().min(self.len - self.cursor);
+        buf[..len].copy_from_slice(Reader::read_bytes(self, len)?);
+        Ok(len)
+    }
+
+    fn remaining(&self) -> usize {
+        self.len - self.cursor
+    }
+
+    fn position(&self) -> usize {
+        self.cursor
+    }
+
+    fn set_position(&mut self, position: usize) -> Result<(), Error> {
+        self.set_cursor(position)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Reader::read_u8(self)
+    }
+
+    fn read_varuint32(&mut self) -> Result<u32, Error> {
+        Reader::read_varuint32(self)
+    }
+
+    fn skip_bytes(&mut self, len: usize) -> Result<(), Error> {
+        self.skip(len)
+    }
+}
@@ -53,6 +53,60 @@ pub enum Error {
     #[error("{0}")]
     DepthExceed(Cow<'static, str>),
 
+    #[error("{0}")]
+    UnexpectedEof(Cow<'static, str>),
+
+    #[error("Interior NUL byte at position {0}")]
+    InteriorNul(usize),
+
+    #[error("Unsupported format version: found {0}, expected {1}")]
+    UnsupportedVersion(u8, u8),
+
+    #[error("Type id {0} rejected by the type observer")]
+    TypeRejected(u32),
+
+    /// A trait object of an unknown type was skipped under the unknown-type policy. Maps
+    /// drop the entry holding it; anywhere else the read fails with this error.
+    #[error("Skipped a trait object of unknown type id {0}")]
+    SkippedUnknownType(u32),
+
+    #[error("Value {0} does not fit in {1}")]
+    NumericOverflow(i64, &'static str),
+
+    #[error("More than {0} shared references")]
+    TooManyRefs(u32),
+
+    #[error("String of {0} bytes exceeds the limit of {1}")]
+    StringTooLong(usize, usize),
+
+    /// The input ended where a struct field should start, as in a buffer written before
+    /// the field was added.
+    #[error("Missing field `{name}`: the input ended before it")]
+    MissingField { name: &'static str },
+
+    /// The root value of the buffer is null, which only an `Option` can be read as.
+    #[error("Unexpected null: the root value is null, but `{0}` is not an Option")]
+    UnexpectedNull(&'static str),
+
+    #[error("{0} elements exceed the capacity of {1}")]
+    CapacityExceeded(usize, usize),
+
+    #[error("Validation failed: {0}")]
+    ValidationFailed(Cow<'static, str>),
+
+    #[error("Need more data: the input ended after {0} bytes")]
+    NeedMoreData(usize),
+
+    #[error("Schema mismatch: the buffer has schema hash {0:#018x}, expected {1:#018x}")]
+    SchemaMismatch(u64, u64),
+
+    /// Encrypting or decrypting a `#[fory(encrypt)]` field failed, or no cipher was set.
+    #[error("Cipher error: {0}")]
+    Cipher(Cow<'static, str>),
+
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(Cow<'static, str>),
+
     /// Do not construct this variant directly; use [`Error::unknown`] instead.
     #[error("{0}")]
     Unknown(Cow<'static, str>),
@@ -15,21 +15,34 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::buffer::{Reader, Writer};
+use crate::buffer::{ByteReader, ByteWriter, Reader, Writer};
 use crate::ensure;
 use crate::error::Error;
-use crate::resolver::context::WriteContext;
+use crate::meta::murmurhash3_x64_128;
 use crate::resolver::context::{Pool, ReadContext};
+use crate::resolver::context::{ReadConfig, WriteConfig, WriteContext};
+use crate::resolver::meta_resolver::MetaWriterResolver;
 use crate::resolver::type_resolver::{TypeInfo, TypeResolver};
+use crate::serializer::borrowed::BorrowedSerializer;
+use crate::serializer::cipher::Cipher;
+use crate::serializer::handler::HandlerRef;
+use crate::serializer::map::{merge_hashmap, read_sorted_entries};
+use crate::serializer::pre_encoded::PreEncoded;
+use crate::serializer::primitive_list;
+use crate::serializer::sorted_set::{SortedSet, SortedSetElement};
 use crate::serializer::ForyDefault;
-use crate::serializer::{Serializer, StructSerializer};
+use crate::serializer::{Diff, FieldChange, Merge, Serializer, StructSerializer};
 use crate::types::config_flags::IS_NULL_FLAG;
 use crate::types::{
-    config_flags::{IS_CROSS_LANGUAGE_FLAG, IS_LITTLE_ENDIAN_FLAG},
-    Language, MAGIC_NUMBER, SIZE_OF_REF_AND_TYPE,
+    config_flags::{IS_ARRAY_ALIGNED_FLAG, IS_CROSS_LANGUAGE_FLAG, IS_LITTLE_ENDIAN_FLAG},
+    HashKind, Language, RefFlag, TypeId, TypeObserver, UnknownTypePolicy, FORMAT_VERSION,
+    MAGIC_NUMBER, SIZE_OF_REF_AND_TYPE,
 };
 use crate::util::get_ext_actual_type_id;
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, OnceLock};
 
 static EMPTY_STRING: String = String::new();
 
@@ -83,6 +96,28 @@ pub struct Fory {
     type_resolver: TypeResolver,
     compress_string: bool,
     max_dyn_depth: u32,
+    max_box_depth: u32,
+    max_ref_count: u32,
+    string_max_len: usize,
+    deterministic: bool,
+    sparse_null_encoding: bool,
+    map_key_prefix_sharing: bool,
+    dictionary_strings: bool,
+    canonical_nan: bool,
+    array_alignment: usize,
+    deque_capacity_hint: bool,
+    exact_presize: bool,
+    schema_hash: bool,
+    unknown_type_policy: UnknownTypePolicy,
+    // asserted so that the observer does not make `Fory` lose `RefUnwindSafe`: it only
+    // ever sees type ids, so a panic in it cannot leave `Fory` in a broken state
+    type_observer: Option<AssertUnwindSafe<TypeObserver>>,
+    cipher: Option<Arc<dyn Cipher>>,
+    strict_flags: bool,
+    numeric_conversion: bool,
+    strict_numerics: bool,
+    header: bool,
+    format_version: u8,
     // Lazy-initialized pools (thread-safe, one-time initialization)
     write_context_pool: OnceLock<Pool<WriteContext>>,
     read_context_pool: OnceLock<Pool<ReadContext>>,
@@ -97,6 +132,26 @@ impl Default for Fory {
             type_resolver: TypeResolver::default(),
             compress_string: false,
             max_dyn_depth: 5,
+            max_box_depth: 256,
+            max_ref_count: u32::MAX,
+            string_max_len: usize::MAX,
+            deterministic: false,
+            sparse_null_encoding: false,
+            map_key_prefix_sharing: false,
+            dictionary_strings: false,
+            canonical_nan: false,
+            array_alignment: 1,
+            deque_capacity_hint: false,
+            exact_presize: false,
+            schema_hash: false,
+            unknown_type_policy: UnknownTypePolicy::Error,
+            type_observer: None,
+            cipher: None,
+            strict_flags: false,
+            numeric_conversion: false,
+            strict_numerics: false,
+            header: false,
+            format_version: FORMAT_VERSION,
             write_context_pool: OnceLock::new(),
             read_context_pool: OnceLock::new(),
         }
@@ -241,186 +296,1833 @@ impl Fory {
         self
     }
 
+    /// Sets the maximum number of `Box`es a deserialized value may nest within each other.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_box_depth` - The maximum nesting depth of boxes, such as the nodes of a
+    ///   recursive `Option<Box<Node>>` list. Each level is a nested call while reading, so
+    ///   this bounds the stack an untrusted buffer can make deserialization take.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `256`.
+    ///
+    /// # Behavior
+    ///
+    /// Reading a box nested one level deeper than the limit fails with
+    /// [`Error::DepthExceed`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// // allow linked lists of up to 10 000 nodes, on a thread with a large enough stack
+    /// let fory = Fory::default().max_box_depth(10_000);
+    /// ```
+    pub fn max_box_depth(mut self, max_box_depth: u32) -> Self {
+        self.max_box_depth = max_box_depth;
+        self
+    }
+
+    /// Sets the maximum number of shared references a deserialized value may hold.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_ref_count` - The maximum size of the reference table, which gets one entry for
+    ///   every `Rc`, `Arc` or weak pointer value read with reference tracking. This bounds
+    ///   the memory an untrusted buffer can make the table take.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `u32::MAX`, i.e. effectively unlimited.
+    ///
+    /// # Behavior
+    ///
+    /// Reading one more shared value than the limit fails with [`Error::TooManyRefs`]
+    /// before it is added to the table.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().max_ref_count(10_000);
+    /// ```
+    pub fn max_ref_count(mut self, max_ref_count: u32) -> Self {
+        self.max_ref_count = max_ref_count;
+        self
+    }
+
+    /// Sets the maximum length of a decoded string.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_len` - The maximum length in bytes of a string read from the buffer. This
+    ///   bounds the memory an untrusted buffer can make a single string take.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `usize::MAX`, i.e. effectively unlimited.
+    ///
+    /// # Behavior
+    ///
+    /// A string whose length prefix exceeds the limit fails with [`Error::StringTooLong`]
+    /// before anything is allocated for it. This includes borrowed strings, and map keys
+    /// rebuilt from a shared prefix are held to the limit as a whole.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_string_max_len(1 << 20);
+    /// ```
+    pub fn with_string_max_len(mut self, max_len: usize) -> Self {
+        self.string_max_len = max_len;
+        self
+    }
+
+    /// Enables or disables deterministic output for unordered containers.
+    ///
+    /// # Arguments
+    ///
+    /// * `deterministic` - If `true`, `HashMap` entries are written sorted by key so that
+    ///   equal maps always produce identical bytes regardless of insertion order. Keys are
+    ///   ordered by [`Serializer::fory_cmp_serialized`], which by default compares the
+    ///   serialized key bytes lexicographically. Float keys, e.g. in a transparent
+    ///   newtype, are ordered by `total_cmp`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().deterministic(true);
+    /// ```
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Enables or disables the sparse encoding of nulls in collections of `Option`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sparse` - If `true`, a collection such as `Vec<Option<T>>` that contains `None`
+    ///   is written as a bitmap of the null positions followed by only the present values,
+    ///   instead of a ref flag byte before every element. This saves space when nulls are
+    ///   rare. Readers decode either layout regardless of this setting.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_sparse_null_encoding(true);
+    /// ```
+    pub fn with_sparse_null_encoding(mut self, sparse: bool) -> Self {
+        self.sparse_null_encoding = sparse;
+        self
+    }
+
+    /// Enables or disables key prefix sharing for `BTreeMap<String, V>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sharing` - If `true`, each `String` key of a `BTreeMap` is written as the length
+    ///   of the prefix it shares with the previous key of the same chunk, followed by only
+    ///   the remaining suffix. Since the keys are sorted, neighbours tend to share long
+    ///   prefixes, e.g. paths or namespaced identifiers. Readers rebuild the owned keys and
+    ///   decode either layout regardless of this setting.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_map_key_prefix_sharing(true);
+    /// ```
+    pub fn with_map_key_prefix_sharing(mut self, sharing: bool) -> Self {
+        self.map_key_prefix_sharing = sharing;
+        self
+    }
+
+    /// Enables or disables the dictionary encoding of `Vec<String>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `dictionary` - If `true`, a `Vec<String>` is written as its distinct strings, in
+    ///   order of first appearance, followed by the index of each element's string. This
+    ///   shrinks columnar data with few distinct values, e.g. categories or status codes,
+    ///   at the cost of hashing every element. Readers decode either layout regardless of
+    ///   this setting.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_dictionary_strings(true);
+    /// let column = vec!["ok".to_string(), "error".to_string(), "ok".to_string()];
+    /// let bytes = fory.serialize(&column).unwrap();
+    /// assert_eq!(fory.deserialize::<Vec<String>>(&bytes).unwrap(), column);
+    /// ```
+    pub fn with_dictionary_strings(mut self, dictionary: bool) -> Self {
+        self.dictionary_strings = dictionary;
+        self
+    }
+
+    /// Enables or disables the canonicalization of NaN floats on write.
+    ///
+    /// # Arguments
+    ///
+    /// * `canonical` - If `true`, every NaN `f32` or `f64`, alone or in a `Vec`, is
+    ///   written as the bits of `f32::NAN` or `f64::NAN`, whatever its sign and payload.
+    ///   Producers emit different NaN bit patterns, so this keeps semantically equal data
+    ///   byte-identical, e.g. for content hashing.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_canonical_nan(true);
+    /// let negative_nan = -f64::NAN;
+    /// assert_eq!(
+    ///     fory.serialize(&negative_nan).unwrap(),
+    ///     fory.serialize(&f64::NAN).unwrap()
+    /// );
+    /// ```
+    pub fn with_canonical_nan(mut self, canonical: bool) -> Self {
+        self.canonical_nan = canonical;
+        self
+    }
+
+    /// Aligns the payload of every primitive array, e.g. a `Vec<f64>`, to `alignment` bytes.
+    ///
+    /// Padding goes between the byte length and the payload, preceded by a byte counting
+    /// it, so that a reader of a suitably aligned buffer, such as a memory-mapped file, can
+    /// reinterpret the payload in place. Offsets are relative to the start of the
+    /// serialized data. A flag in the header marks the padding, so any reader skips it
+    /// whatever its own alignment.
+    ///
+    /// # Arguments
+    ///
+    /// * `alignment` - A power of two up to 256. `1` writes no padding.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alignment` is not a power of two or is larger than 256.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_array_alignment(8);
+    /// let bytes = fory.serialize(&vec![1.5f64, 2.5]).unwrap();
+    /// assert_eq!(fory.deserialize::<Vec<f64>>(&bytes).unwrap(), [1.5, 2.5]);
+    /// ```
+    pub fn with_array_alignment(mut self, alignment: usize) -> Self {
+        assert!(
+            alignment.is_power_of_two() && alignment <= 256,
+            "array alignment must be a power of two up to 256, got {alignment}"
+        );
+        self.array_alignment = alignment;
+        self
+    }
+
+    /// Writes the capacity of every non-empty `VecDeque` next to its elements, so that
+    /// reading it, fresh or with [`Fory::deserialize_into`], preallocates as much room as
+    /// the original.
+    ///
+    /// The elements are written from front to back either way, so the read deque iterates
+    /// in the original order. The hint is flagged in the list header, so the reader needs
+    /// no configuration, and can also read such a deque as another list type or skip it as
+    /// an unknown field in compatible mode. The capacity it reserves is capped at
+    /// [`MAX_DEQUE_CAPACITY_HINT`](crate::serializer::collection::MAX_DEQUE_CAPACITY_HINT)
+    /// elements, so that a forged hint cannot exhaust memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `hint` - If `true`, the capacity is written before the elements.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    /// use std::collections::VecDeque;
+    ///
+    /// let fory = Fory::default().with_deque_capacity_hint(true);
+    /// let mut deque = VecDeque::with_capacity(64);
+    /// deque.extend([1, 2, 3]);
+    /// let bytes = fory.serialize(&deque).unwrap();
+    /// let read: VecDeque<i32> = fory.deserialize(&bytes).unwrap();
+    /// assert!(read.capacity() >= 64);
+    /// ```
+    pub fn with_deque_capacity_hint(mut self, hint: bool) -> Self {
+        self.deque_capacity_hint = hint;
+        self
+    }
+
+    /// Sets how polymorphic reads handle values whose type is not registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - One of:
+    ///   - [`UnknownTypePolicy::Error`]: fail the read. This is the default.
+    ///   - [`UnknownTypePolicy::Skip`]: skip the value and yield `()` in its place.
+    ///   - [`UnknownTypePolicy::Preserve`]: keep the value as a
+    ///     [`RawValue`](crate::serializer::any::RawValue), which serializes back to the
+    ///     same bytes. This lets a proxy forward types it does not know about.
+    ///
+    /// Unknown values can only be skipped or preserved when they carry type meta, which
+    /// requires compatible mode on both sides.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    /// use fory_core::types::UnknownTypePolicy;
+    ///
+    /// let fory = Fory::default()
+    ///     .compatible(true)
+    ///     .with_unknown_type_policy(UnknownTypePolicy::Preserve);
+    /// ```
+    pub fn with_unknown_type_policy(mut self, policy: UnknownTypePolicy) -> Self {
+        self.unknown_type_policy = policy;
+        self
+    }
+
+    /// Sets a callback that is consulted with every type id read from the input.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Called with each type id as it is read, both for the values being
+    ///   deserialized and for values that are skipped. Returning `ControlFlow::Break`
+    ///   aborts the read with [`Error::TypeRejected`]. This allows restricting untrusted
+    ///   input to an allow-list of types.
+    ///
+    /// Only type ids that are written on the wire are observed. Fields of a struct whose
+    /// types are declared statically are read without a type id.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::types::TypeId;
+    /// use fory_core::Fory;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let fory = Fory::default().with_type_observer(Box::new(|type_id| {
+    ///     if type_id == TypeId::STRING as u32 {
+    ///         ControlFlow::Continue(())
+    ///     } else {
+    ///         ControlFlow::Break(())
+    ///     }
+    /// }));
+    /// ```
+    pub fn with_type_observer(
+        mut self,
+        observer: Box<dyn Fn(u32) -> ControlFlow<()> + Send + Sync>,
+    ) -> Self {
+        self.type_observer = Some(AssertUnwindSafe(Arc::from(observer)));
+        self
+    }
+
+    /// Sets the cipher that encrypts the fields marked `#[fory(encrypt)]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cipher` - Encrypts the serialized bytes of each such field on write and decrypts
+    ///   them on read. The ciphertext is written with its length, so it may be longer than
+    ///   the plaintext, e.g. to hold a nonce and an authentication tag.
+    ///
+    /// Both peers must use the same cipher. Without one, writing or reading a struct with
+    /// an encrypted field fails with [`Error::Cipher`]. Compatible mode cannot skip an
+    /// encrypted field as an unknown field, since the type meta records its declared type.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, ignore
+    /// use fory_core::error::Error;
+    /// use fory_core::serializer::cipher::Cipher;
+    /// use fory_core::Fory;
+    /// use fory_derive::ForyObject;
+    ///
+    /// struct Aead(Key);
+    ///
+    /// impl Cipher for Aead {
+    ///     fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> { self.0.seal(plaintext) }
+    ///     fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Error> { self.0.open(ciphertext) }
+    /// }
+    ///
+    /// #[derive(ForyObject)]
+    /// struct Account {
+    ///     name: String,
+    ///     #[fory(encrypt)]
+    ///     iban: String,
+    /// }
+    ///
+    /// let mut fory = Fory::default().with_cipher(Box::new(Aead(key)));
+    /// fory.register::<Account>(1)?;
+    /// ```
+    pub fn with_cipher(mut self, cipher: Box<dyn Cipher>) -> Self {
+        self.cipher = Some(Arc::from(cipher));
+        self
+    }
+
+    /// Sets whether [`Fory::serialize`] sizes its output buffer exactly before writing.
+    ///
+    /// # Arguments
+    ///
+    /// * `exact_presize` - If `true`, `serialize` first measures the value with
+    ///   [`Fory::serialized_size`], then writes it straight into a `Vec` of that capacity,
+    ///   so the returned buffer is never reallocated and has no spare capacity. If `false`
+    ///   (default), the value is written into a pooled buffer that keeps its capacity
+    ///   between calls and copied out into an exactly sized `Vec`.
+    ///
+    /// The dry run costs about one extra serialization, while the default only pays for
+    /// a `memcpy` once the pooled buffer has grown. In `benches/presize_bench.rs` the
+    /// default is about twice as fast for scalars, strings and maps, and presizing only
+    /// wins for large primitive arrays, whose serialization is itself a `memcpy`. Hence
+    /// the default.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_exact_presize(true);
+    /// ```
+    pub fn with_exact_presize(mut self, exact_presize: bool) -> Self {
+        self.exact_presize = exact_presize;
+        self
+    }
+
+    /// Sets whether buffers start with a hash of the schema of their root type.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema_hash` - If `true`, an 8-byte hash follows the header, and reading a
+    ///   buffer whose hash differs from the one of the requested type fails with
+    ///   [`Error::SchemaMismatch`] before any data is parsed. For a registered struct or
+    ///   enum the hash covers its type def, i.e. its field names and field types, for
+    ///   other types only their type id, so a change inside a nested struct is not caught.
+    ///   If `false` (default), no hash is written.
+    ///
+    /// Only consistent mode writes the hash, compatible mode already describes the schema
+    /// in its type meta and reads evolved types. Both peers must set the same value.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_schema_hash(true);
+    /// ```
+    pub fn with_schema_hash(mut self, schema_hash: bool) -> Self {
+        self.schema_hash = schema_hash;
+        self
+    }
+
+    /// Sets whether flag types reject bits they do not define.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict_flags` - If `true`, reading a value of a type implemented with
+    ///   [`impl_flags_serializer!`](crate::impl_flags_serializer) fails with
+    ///   [`Error::InvalidData`] when it has unknown bits set. If `false` (default), unknown
+    ///   bits are dropped, e.g. when a newer peer defines more flags.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_strict_flags(true);
+    /// ```
+    pub fn with_strict_flags(mut self, strict_flags: bool) -> Self {
+        self.strict_flags = strict_flags;
+        self
+    }
+
+    /// Sets whether compatible mode converts integer fields whose width changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `numeric_conversion` - If `true`, a struct field that the peer declared as another
+    ///   of `i8`, `i16`, `i32` and `i64` than this side is read and converted, e.g. after a
+    ///   counter was widened from `i32` to `i64`, and so is each element of a `Vec` of
+    ///   them, also inside an `Option`. Narrowing keeps the low bits like an `as` cast unless
+    ///   [`Fory::with_strict_numerics`] is set. If `false` (default), such a field is
+    ///   skipped and left at its default value, as for any other type change.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().compatible(true).with_numeric_conversion(true);
+    /// ```
+    pub fn with_numeric_conversion(mut self, numeric_conversion: bool) -> Self {
+        self.numeric_conversion = numeric_conversion;
+        self
+    }
+
+    /// Sets whether lossy integer conversions fail instead of truncating.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict_numerics` - If `true`, a value converted to a narrower type under
+    ///   [`Fory::with_numeric_conversion`] that does not fit in it fails with
+    ///   [`Error::NumericOverflow`]. If `false` (default), it is truncated to its low bits,
+    ///   so that e.g. `i64` 4294967297 reads as `i32` 1.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default()
+    ///     .compatible(true)
+    ///     .with_numeric_conversion(true)
+    ///     .with_strict_numerics(true);
+    /// ```
+    pub fn with_strict_numerics(mut self, strict_numerics: bool) -> Self {
+        self.strict_numerics = strict_numerics;
+        self
+    }
+
+    /// Sets how the type meta of compatible structs writes their field names.
+    ///
+    /// # Arguments
+    ///
+    /// * `field_name_hash` - [`HashKind::Off`] writes each field name as a meta string.
+    ///   [`HashKind::Hash32`] and [`HashKind::Hash64`] write a hash of each name as the
+    ///   tag id of the field instead, which makes the type meta of structs with long field names smaller at the risk
+    ///   of two names hashing equal. Both peers must agree on this setting.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is [`HashKind::Off`].
+    ///
+    /// # Notes
+    ///
+    /// This must be set before registering types. Registering a struct with two field
+    /// names of the same hash fails with [`Error::TypeError`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::types::HashKind;
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default()
+    ///     .compatible(true)
+    ///     .with_field_name_hash(HashKind::Hash64);
+    /// ```
+    pub fn with_field_name_hash(mut self, field_name_hash: HashKind) -> Self {
+        self.type_resolver.set_field_name_hash(field_name_hash);
+        self
+    }
+
+    /// Enables or disables the format-version byte at the start of the output.
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - If `true`, a single byte holding the format version is written before
+    ///   everything else, and reading checks it against the expected version, returning
+    ///   [`Error::UnsupportedVersion`] on mismatch. Both peers must agree on this setting.
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Default
+    ///
+    /// The default value is `false`, which keeps the output compatible with earlier releases.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_header(true);
+    /// ```
+    pub fn with_header(mut self, header: bool) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Sets the format version written and accepted when the header is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version byte. The default is
+    ///   [`FORMAT_VERSION`](crate::types::FORMAT_VERSION).
+    ///
+    /// # Returns
+    ///
+    /// Returns `self` for method chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default().with_header(true).with_format_version(1);
+    /// ```
+    pub fn with_format_version(mut self, version: u8) -> Self {
+        self.format_version = version;
+        self
+    }
+
     /// Returns whether cross-language serialization is enabled.
     pub fn is_xlang(&self) -> bool {
         self.xlang
     }
 
-    /// Returns the current serialization mode.
+    /// Returns the current serialization mode.
+    ///
+    /// # Returns
+    ///
+    /// `ture` if the serialization mode is compatible, `false` otherwise`.
+    pub fn is_compatible(&self) -> bool {
+        self.compatible
+    }
+
+    /// Returns whether string compression is enabled.
+    ///
+    /// # Returns
+    ///
+    /// `true` if meta string compression is enabled, `false` otherwise.
+    pub fn is_compress_string(&self) -> bool {
+        self.compress_string
+    }
+
+    /// Returns whether metadata sharing is enabled.
+    ///
+    /// # Returns
+    ///
+    /// `true` if metadata sharing is enabled (automatically set based on mode), `false` otherwise.
+    pub fn is_share_meta(&self) -> bool {
+        self.share_meta
+    }
+
+    /// Returns whether deterministic output is enabled.
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Returns whether collections of `Option` use the sparse null encoding.
+    pub fn is_sparse_null_encoding(&self) -> bool {
+        self.sparse_null_encoding
+    }
+
+    /// Returns whether `BTreeMap<String, V>` keys are written with shared prefixes.
+    pub fn is_map_key_prefix_sharing(&self) -> bool {
+        self.map_key_prefix_sharing
+    }
+
+    /// Returns whether `Vec<String>` is written with a dictionary of its distinct strings.
+    pub fn is_dictionary_strings(&self) -> bool {
+        self.dictionary_strings
+    }
+
+    /// Returns whether NaN floats are written with a single canonical bit pattern.
+    pub fn is_canonical_nan(&self) -> bool {
+        self.canonical_nan
+    }
+
+    /// Returns the alignment of primitive array payloads.
+    pub fn get_array_alignment(&self) -> usize {
+        self.array_alignment
+    }
+
+    /// Returns whether `VecDeque` capacities are written next to their elements.
+    pub fn is_deque_capacity_hint(&self) -> bool {
+        self.deque_capacity_hint
+    }
+
+    /// Returns the policy for values of unregistered types in polymorphic reads.
+    pub fn get_unknown_type_policy(&self) -> UnknownTypePolicy {
+        self.unknown_type_policy
+    }
+
+    /// Returns the callback consulted with every type id read, if any.
+    pub fn get_type_observer(&self) -> Option<&TypeObserver> {
+        self.type_observer.as_deref()
+    }
+
+    /// Returns the cipher of the fields marked `#[fory(encrypt)]`, if any.
+    pub fn get_cipher(&self) -> Option<&Arc<dyn Cipher>> {
+        self.cipher.as_ref()
+    }
+
+    /// Returns whether [`Fory::serialize`] sizes its output buffer exactly before writing.
+    pub fn is_exact_presize(&self) -> bool {
+        self.exact_presize
+    }
+
+    /// Returns whether buffers start with a hash of the schema of their root type.
+    pub fn is_schema_hash(&self) -> bool {
+        self.schema_hash
+    }
+
+    /// Returns whether flag types reject bits they do not define.
+    pub fn is_strict_flags(&self) -> bool {
+        self.strict_flags
+    }
+
+    /// Returns whether compatible mode converts integer fields whose width changed.
+    pub fn is_numeric_conversion(&self) -> bool {
+        self.numeric_conversion
+    }
+
+    /// Returns whether lossy integer conversions fail instead of truncating.
+    pub fn is_strict_numerics(&self) -> bool {
+        self.strict_numerics
+    }
+
+    /// Returns whether the format-version byte is written and checked.
+    pub fn has_header(&self) -> bool {
+        self.header
+    }
+
+    /// Returns the format version written and accepted when the header is enabled.
+    pub fn get_format_version(&self) -> u8 {
+        self.format_version
+    }
+
+    /// Returns how the type meta of compatible structs writes their field names.
+    pub fn get_field_name_hash(&self) -> HashKind {
+        self.type_resolver.get_field_name_hash()
+    }
+
+    /// Returns the maximum depth for nested dynamic object serialization.
+    pub fn get_max_dyn_depth(&self) -> u32 {
+        self.max_dyn_depth
+    }
+
+    /// Returns the maximum number of `Box`es a deserialized value may nest.
+    pub fn get_max_box_depth(&self) -> u32 {
+        self.max_box_depth
+    }
+
+    /// Returns the maximum number of shared references a deserialized value may hold.
+    pub fn get_max_ref_count(&self) -> u32 {
+        self.max_ref_count
+    }
+
+    /// Returns the maximum length in bytes of a decoded string.
+    pub fn get_string_max_len(&self) -> usize {
+        self.string_max_len
+    }
+
+    /// Returns the type id written for values of type `T`, or `None` if `T` is neither
+    /// registered nor a builtin type.
+    ///
+    /// For registered types the id combines the user id with the type kind, as in
+    /// `(id << 8) + kind`, exactly as it appears on the wire.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, ignore
+    /// use fory::{Fory, ForyObject};
+    ///
+    /// #[derive(ForyObject)]
+    /// struct Point { x: i32, y: i32 }
+    ///
+    /// let mut fory = Fory::default();
+    /// assert_eq!(fory.type_id_of::<Point>(), None);
+    /// fory.register::<Point>(100).unwrap();
+    /// let bytes = fory.serialize(&Point { x: 1, y: 2 }).unwrap();
+    /// assert_eq!(fory.peek_type_id(&bytes).unwrap(), fory.type_id_of::<Point>());
+    /// ```
+    pub fn type_id_of<T: 'static>(&self) -> Option<u32> {
+        self.type_resolver
+            .get_fory_type_id(std::any::TypeId::of::<T>())
+    }
+
+    /// Returns a type resolver for type lookups.
+    pub(crate) fn get_type_resolver(&self) -> &TypeResolver {
+        &self.type_resolver
+    }
+
+    pub fn write_head<T: Serializer>(&self, is_none: bool, writer: &mut Writer) {
+        const HEAD_SIZE: usize = 10;
+        writer.reserve(T::fory_reserved_space() + SIZE_OF_REF_AND_TYPE + HEAD_SIZE);
+        if self.header {
+            writer.write_u8(self.format_version);
+        }
+        if self.xlang {
+            writer.write_u16(MAGIC_NUMBER);
+        }
+        #[cfg(target_endian = "big")]
+        let mut bitmap = 0;
+        #[cfg(target_endian = "little")]
+        let mut bitmap = IS_LITTLE_ENDIAN_FLAG;
+        if self.xlang {
+            bitmap |= IS_CROSS_LANGUAGE_FLAG;
+        }
+        if self.array_alignment > 1 {
+            bitmap |= IS_ARRAY_ALIGNED_FLAG;
+        }
+        if is_none {
+            bitmap |= IS_NULL_FLAG;
+        }
+        writer.write_u8(bitmap);
+        if is_none {
+            return;
+        }
+        if self.xlang {
+            writer.write_u8(Language::Rust as u8);
+        }
+    }
+
+    /// Whether the hash of [`Fory::with_schema_hash`] follows the header.
+    fn has_schema_hash(&self) -> bool {
+        self.schema_hash && !self.compatible
+    }
+
+    /// Hashes the type def of a registered root type, which lists its fields and their
+    /// types, and the type id of any other.
+    fn schema_hash_of(&self, rs_type_id: Option<std::any::TypeId>, type_id: u32) -> u64 {
+        let type_def = rs_type_id
+            .and_then(|id| self.type_resolver.get_type_info(id).ok())
+            .map(|info| info.get_type_def())
+            .filter(|type_def| !type_def.is_empty());
+        match type_def {
+            Some(type_def) => murmurhash3_x64_128(&type_def, 47).0,
+            None => murmurhash3_x64_128(&type_id.to_le_bytes(), 47).0,
+        }
+    }
+
+    fn root_schema_hash<T: Serializer>(&self) -> Result<u64, Error> {
+        let type_id = T::fory_get_type_id(&self.type_resolver)?;
+        Ok(self.schema_hash_of(Some(std::any::TypeId::of::<T>()), type_id))
+    }
+
+    fn read_schema_hash(&self, reader: &mut Reader, expected: u64) -> Result<(), Error> {
+        let found = reader.read_u64()?;
+        ensure!(found == expected, Error::SchemaMismatch(found, expected));
+        Ok(())
+    }
+
+    /// The value a null root reads as: `None` for an `Option`, and
+    /// [`Error::UnexpectedNull`] for any other type rather than its default. Compatible
+    /// mode reads it as the default, since it converts an `Option<T>` into a `T`.
+    fn read_null_root<T: Serializer + ForyDefault>(&self) -> Result<T, Error> {
+        ensure!(
+            T::fory_is_option() || self.compatible,
+            Error::UnexpectedNull(std::any::type_name::<T>())
+        );
+        Ok(T::fory_default())
+    }
+
+    fn read_head(&self, reader: &mut Reader) -> Result<bool, Error> {
+        Ok(self.read_head_bitmap(reader)? & IS_NULL_FLAG != 0)
+    }
+
+    /// Reads the header into `context`, which learns from it whether the primitive arrays
+    /// that follow are padded, whatever the alignment this instance writes with.
+    fn read_context_head(&self, context: &mut ReadContext) -> Result<bool, Error> {
+        let bitmap = self.read_head_bitmap(&mut context.reader)?;
+        context.set_array_aligned(bitmap & IS_ARRAY_ALIGNED_FLAG != 0);
+        Ok(bitmap & IS_NULL_FLAG != 0)
+    }
+
+    fn read_head_bitmap(&self, reader: &mut Reader) -> Result<u8, Error> {
+        if self.header {
+            let version = reader.read_u8()?;
+            ensure!(
+                version == self.format_version,
+                Error::UnsupportedVersion(version, self.format_version)
+            );
+        }
+        if self.xlang {
+            let magic_numer = reader.read_u16()?;
+            ensure!(
+                magic_numer == MAGIC_NUMBER,
+                Error::InvalidData(
+                    format!(
+                        "The fory xlang serialization must start with magic number {:X}. \
+                    Please check whether the serialization is based on the xlang protocol \
+                    and the data didn't corrupt.",
+                        MAGIC_NUMBER
+                    )
+                    .into()
+                )
+            )
+        }
+        let bitmap = reader.read_u8()?;
+        let peer_is_xlang = (bitmap & IS_CROSS_LANGUAGE_FLAG) != 0;
+        ensure!(
+            self.xlang == peer_is_xlang,
+            Error::InvalidData("header bitmap mismatch at xlang bit".into())
+        );
+        let is_little_endian = (bitmap & IS_LITTLE_ENDIAN_FLAG) != 0;
+        ensure!(
+            is_little_endian,
+            Error::InvalidData(
+                "Big endian is not supported for now, please ensure peer machine is little endian."
+                    .into()
+            )
+        );
+        let is_none = (bitmap & IS_NULL_FLAG) != 0;
+        if is_none {
+            return Ok(bitmap);
+        }
+        if peer_is_xlang {
+            let _peer_lang = reader.read_u8()?;
+        }
+        Ok(bitmap)
+    }
+
+    /// Deserializes data from a byte slice into a value of type `T`.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The target type to deserialize into. Must implement `Serializer` and `ForyDefault`.
+    ///
+    /// # Arguments
+    ///
+    /// * `bf` - The byte slice containing the serialized data.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` - The deserialized value on success.
+    /// * `Err(Error)` - An error if deserialization fails (e.g., invalid format, type mismatch).
+    ///
+    /// A null root, as written by `fory.serialize(&None::<i32>)`, reads as `None` into an
+    /// `Option` and fails with [`Error::UnexpectedNull`] for any other type, unless
+    /// compatible mode converts it into the default of that type.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if there are unread bytes remaining after successful deserialization,
+    /// indicating a potential protocol violation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, ignore
+    /// use fory::Fory;
+    /// use fory::ForyObject;
+    ///
+    /// #[derive(ForyObject)]
+    /// struct Point { x: i32, y: i32 }
+    ///
+    /// let fory = Fory::default();
+    /// let point = Point { x: 10, y: 20 };
+    /// let bytes = fory.serialize(&point);
+    /// let deserialized: Point = fory.deserialize(&bytes).unwrap();
+    /// ```
+    pub fn deserialize<T: Serializer + ForyDefault>(&self, bf: &[u8]) -> Result<T, Error> {
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(bf, self.max_dyn_depth);
+        let result = self.deserialize_with_context(&mut context);
+        if result.is_ok() {
+            assert_eq!(context.reader.slice_after_cursor().len(), 0);
+        }
+        context.reset();
+        pool.put(context);
+        result
+    }
+
+    /// Deserializes a [`SortedSet`] of integers, ready for membership tests by binary search.
+    ///
+    /// This is the same as `deserialize::<SortedSet<T>>`, named for readers who want a
+    /// lookup structure without building a `HashSet` from the bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::{Fory, SortedSet};
+    ///
+    /// let fory = Fory::default();
+    /// let ids: SortedSet<u64> = (1000..1100).collect();
+    /// let bytes = fory.serialize(&ids).unwrap();
+    /// let ids = fory.deserialize_sorted_set::<u64>(&bytes).unwrap();
+    /// assert!(ids.contains(&1042));
+    /// ```
+    pub fn deserialize_sorted_set<T: SortedSetElement>(
+        &self,
+        bf: &[u8],
+    ) -> Result<SortedSet<T>, Error> {
+        self.deserialize(bf)
+    }
+
+    /// Deserializes a value from a prefix of its bytes that may still be incomplete.
+    ///
+    /// When `bf` ends in the middle of the value, for example halfway through a
+    /// collection, this returns [`Error::NeedMoreData`] instead of a bounds error. The
+    /// caller keeps the bytes it has, appends the next chunk and calls this again with the
+    /// longer buffer; decoding restarts from the beginning of the prefix.
+    ///
+    /// On success the value is returned with the number of bytes it took. Any bytes after
+    /// them are left alone, so a buffer holding the start of the next value can be drained
+    /// from that offset.
+    ///
+    /// A declared length that can never be satisfied also reports `NeedMoreData`, so
+    /// callers fed from an untrusted stream should cap how much they buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::error::Error;
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default();
+    /// let bytes = fory.serialize(&vec![1i64, 2, 3]).unwrap();
+    /// let partial = fory.deserialize_resumable::<Vec<i64>>(&bytes[..bytes.len() - 1]);
+    /// assert!(matches!(partial, Err(Error::NeedMoreData(_))));
+    /// let (value, consumed) = fory.deserialize_resumable::<Vec<i64>>(&bytes).unwrap();
+    /// assert_eq!(value, vec![1, 2, 3]);
+    /// assert_eq!(consumed, bytes.len());
+    /// ```
+    pub fn deserialize_resumable<T: Serializer + ForyDefault>(
+        &self,
+        bf: &[u8],
+    ) -> Result<(T, usize), Error> {
+        self.deserialize_prefix(bf)
+    }
+
+    /// Deserializes one value from a custom source.
+    ///
+    /// Bytes are gathered from `reader` in growing chunks, however small the pieces the
+    /// source yields, until they hold a whole value. The source is then set back to the end
+    /// of the value, so that the values that follow it can be read by further calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnexpectedEof`] if the source ends before the value does, or
+    /// before the bytes it announced.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::buffer::Reader;
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default();
+    /// let bytes = fory.serialize(&7i32).unwrap();
+    /// let value: i32 = fory.deserialize_from(&mut Reader::new(&bytes)).unwrap();
+    /// assert_eq!(value, 7);
+    /// ```
+    pub fn deserialize_from<T: Serializer + ForyDefault, R: ByteReader + ?Sized>(
+        &self,
+        reader: &mut R,
+    ) -> Result<T, Error> {
+        let start = reader.position();
+        let mut buffer = Vec::new();
+        let mut chunk = 256;
+        loop {
+            let mut filled = buffer.len();
+            let announced = reader.remaining();
+            buffer.resize(filled + chunk.min(announced), 0);
+            while filled < buffer.len() {
+                match reader.read_bytes(&mut buffer[filled..])? {
+                    0 => {
+                        return Err(Error::UnexpectedEof(
+                            format!("the source ended after {filled} of {} bytes", buffer.len())
+                                .into(),
+                        ))
+                    }
+                    n => filled += n,
+                }
+            }
+            match self.deserialize_prefix::<T>(&buffer) {
+                Ok((value, consumed)) => {
+                    reader.set_position(start + consumed)?;
+                    return Ok(value);
+                }
+                Err(Error::NeedMoreData(_)) if announced > chunk => chunk *= 2,
+                Err(Error::NeedMoreData(_)) => {
+                    return Err(Error::UnexpectedEof(
+                        format!("the source ended after {filled} bytes, within a value").into(),
+                    ))
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Deserializes the value at the start of `bf` and returns it with the number of bytes
+    /// it took, reporting [`Error::NeedMoreData`] if `bf` ends within it.
+    fn deserialize_prefix<T: Serializer + ForyDefault>(
+        &self,
+        bf: &[u8],
+    ) -> Result<(T, usize), Error> {
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(bf, self.max_dyn_depth);
+        let result = self
+            .deserialize_with_context(&mut context)
+            .map(|value| (value, context.position()));
+        context.reset();
+        pool.put(context);
+        result.map_err(|err| match err {
+            Error::BufferOutOfBound(cursor, n, len) if cursor + n > len => {
+                Error::NeedMoreData(bf.len())
+            }
+            Error::UnexpectedEof(_) => Error::NeedMoreData(bf.len()),
+            err => err,
+        })
+    }
+
+    /// Deserializes a value that borrows from `bf` instead of copying out of it.
+    ///
+    /// The returned value is tied to the lifetime of `bf`, so string and byte fields are
+    /// read in place. See [`BorrowedSerializer`] for the supported types.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidData`] if the root value is null, since borrowed types have
+    /// no default to fall back to.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default();
+    /// let bytes = fory.serialize(&"in place".to_string()).unwrap();
+    /// let s: &str = fory.deserialize_borrowed(&bytes).unwrap();
+    /// assert_eq!(s, "in place");
+    /// ```
+    pub fn deserialize_borrowed<'a, T: BorrowedSerializer<'a>>(
+        &self,
+        bf: &'a [u8],
+    ) -> Result<T, Error> {
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(bf, self.max_dyn_depth);
+        let result = self.deserialize_borrowed_with_context(&mut context, bf);
+        if result.is_ok() {
+            assert_eq!(context.reader.slice_after_cursor().len(), 0);
+        }
+        context.reset();
+        pool.put(context);
+        result
+    }
+
+    fn deserialize_borrowed_with_context<'a, T: BorrowedSerializer<'a>>(
+        &self,
+        context: &mut ReadContext,
+        bf: &'a [u8],
+    ) -> Result<T, Error> {
+        let is_none = self.read_context_head(context)?;
+        ensure!(
+            !is_none,
+            Error::InvalidData("cannot borrow from a null root value".into())
+        );
+        if self.has_schema_hash() {
+            let expected = self.schema_hash_of(None, T::fory_type_id() as u32);
+            self.read_schema_hash(&mut context.reader, expected)?;
+        }
+        let mut bytes_to_skip = 0;
+        if context.is_compatible() {
+            let meta_offset = context.reader.read_i32()?;
+            if meta_offset != -1 {
+                bytes_to_skip = context.load_meta(meta_offset as usize)?;
+            }
+        }
+        let result = T::fory_read_borrowed(context, bf);
+        if bytes_to_skip > 0 {
+            context.reader.skip(bytes_to_skip)?;
+        }
+        result
+    }
+
+    /// Reads the type id of the root value in `bf` without deserializing it.
+    ///
+    /// Returns `None` if the root value is null.
+    pub fn peek_type_id(&self, bf: &[u8]) -> Result<Option<u32>, Error> {
+        let mut reader = Reader::new(bf);
+        if self.read_head(&mut reader)? {
+            return Ok(None);
+        }
+        if self.has_schema_hash() {
+            reader.skip(8)?;
+        }
+        if self.compatible {
+            reader.read_i32()?;
+        }
+        if reader.read_i8()? == RefFlag::Null as i8 {
+            return Ok(None);
+        }
+        Ok(Some(reader.read_varuint32()?))
+    }
+
+    fn read_context_pool(&self) -> &Pool<ReadContext> {
+        self.read_context_pool.get_or_init(|| {
+            let type_resolver = self.type_resolver.clone();
+            let config = self.read_config();
+            let factory =
+                move || ReadContext::new(Reader::new(&[]), type_resolver.clone(), config.clone());
+            Pool::new(factory)
+        })
+    }
+
+    pub(crate) fn read_config(&self) -> ReadConfig {
+        ReadConfig {
+            compatible: self.compatible,
+            share_meta: self.share_meta,
+            xlang: self.xlang,
+            max_dyn_depth: self.max_dyn_depth,
+            max_box_depth: self.max_box_depth,
+            unknown_type_policy: self.unknown_type_policy,
+            type_observer: self.get_type_observer().cloned(),
+            strict_flags: self.strict_flags,
+            numeric_conversion: self.numeric_conversion,
+            strict_numerics: self.strict_numerics,
+            max_ref_count: self.max_ref_count,
+            string_max_len: self.string_max_len,
+            cipher: self.cipher.clone(),
+        }
+    }
+
+    /// Deserializes data from a byte slice into an existing value.
+    ///
+    /// Unlike [`Fory::deserialize`], the target is refilled in place, so containers such as
+    /// `HashMap` can reuse the allocations they already own. Values that own none, such as
+    /// enums, whose variants carry no payload, are replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `bf` - The byte slice containing the serialized data.
+    /// * `target` - The value to deserialize into.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    /// use std::collections::HashMap;
+    ///
+    /// let fory = Fory::default();
+    /// let bytes = fory.serialize(&HashMap::from([(1, 2)])).unwrap();
+    /// let mut map: HashMap<i32, i32> = HashMap::with_capacity(64);
+    /// fory.deserialize_into(&bytes, &mut map).unwrap();
+    /// assert_eq!(map[&1], 2);
+    /// ```
+    pub fn deserialize_into<T: Serializer + ForyDefault>(
+        &self,
+        bf: &[u8],
+        target: &mut T,
+    ) -> Result<(), Error> {
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(bf, self.max_dyn_depth);
+        let result = self.deserialize_into_with_context(&mut context, target);
+        if result.is_ok() {
+            assert_eq!(context.reader.slice_after_cursor().len(), 0);
+        }
+        context.reset();
+        pool.put(context);
+        result
+    }
+
+    pub fn deserialize_into_with_context<T: Serializer + ForyDefault>(
+        &self,
+        context: &mut ReadContext,
+        target: &mut T,
+    ) -> Result<(), Error> {
+        let is_none = self.read_context_head(context)?;
+        if is_none {
+            *target = self.read_null_root()?;
+            return Ok(());
+        }
+        if self.has_schema_hash() {
+            let expected = self.root_schema_hash::<T>()?;
+            self.read_schema_hash(&mut context.reader, expected)?;
+        }
+        let mut bytes_to_skip = 0;
+        if context.is_compatible() {
+            let meta_offset = context.reader.read_i32()?;
+            if meta_offset != -1 {
+                bytes_to_skip = context.load_meta(meta_offset as usize)?;
+            }
+        }
+        let result = target.fory_read_into(context, false);
+        if bytes_to_skip > 0 {
+            context.reader.skip(bytes_to_skip)?;
+        }
+        context.ref_reader.resolve_callbacks();
+        result
+    }
+
+    /// Deserializes data into an existing struct and reports which of its fields changed.
     ///
-    /// # Returns
+    /// The target is replaced by the value read, after comparing the two field by field
+    /// with [`Diff::fory_diff`].
     ///
-    /// `ture` if the serialization mode is compatible, `false` otherwise`.
-    pub fn is_compatible(&self) -> bool {
-        self.compatible
-    }
-
-    /// Returns whether string compression is enabled.
+    /// # Arguments
     ///
-    /// # Returns
+    /// * `bf` - The byte slice containing the serialized data.
+    /// * `target` - The value to refill.
     ///
-    /// `true` if meta string compression is enabled, `false` otherwise.
-    pub fn is_compress_string(&self) -> bool {
-        self.compress_string
+    /// # Examples
+    ///
+    /// ```rust, ignore
+    /// use fory_core::Fory;
+    /// use fory_derive::{ForyDiff, ForyObject};
+    ///
+    /// #[derive(ForyObject, ForyDiff, Debug, PartialEq)]
+    /// struct Config {
+    ///     name: String,
+    ///     retries: i32,
+    /// }
+    ///
+    /// let mut fory = Fory::default();
+    /// fory.register::<Config>(1).unwrap();
+    /// let bytes = fory.serialize(&Config { name: "a".into(), retries: 3 }).unwrap();
+    /// let mut config = Config { name: "a".into(), retries: 0 };
+    /// let changes = fory.deserialize_into_diff(&bytes, &mut config).unwrap();
+    /// assert_eq!(changes[0].field, "retries");
+    /// ```
+    pub fn deserialize_into_diff<T: Serializer + ForyDefault + Diff>(
+        &self,
+        bf: &[u8],
+        target: &mut T,
+    ) -> Result<Vec<FieldChange>, Error> {
+        let value: T = self.deserialize(bf)?;
+        let changes = target.fory_diff(&value);
+        *target = value;
+        Ok(changes)
     }
 
-    /// Returns whether metadata sharing is enabled.
+    /// Merges a partial update into an existing struct.
     ///
-    /// # Returns
+    /// Only the fields present in the serialized data are assigned; fields the writer's
+    /// version of the struct does not have keep their current values. This relies on the
+    /// field framing of compatible mode, so both peers must enable it.
     ///
-    /// `true` if metadata sharing is enabled (automatically set based on mode), `false` otherwise.
-    pub fn is_share_meta(&self) -> bool {
-        self.share_meta
+    /// # Arguments
+    ///
+    /// * `bf` - The byte slice containing the serialized update.
+    /// * `target` - The value to merge into.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidData`] if compatible mode is not enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, ignore
+    /// use fory_core::Fory;
+    /// use fory_derive::{ForyMerge, ForyObject};
+    ///
+    /// #[derive(ForyObject, ForyMerge, Debug, PartialEq)]
+    /// struct Config {
+    ///     name: String,
+    ///     retries: i32,
+    /// }
+    ///
+    /// let mut fory = Fory::default().compatible(true);
+    /// fory.register::<Config>(1).unwrap();
+    /// let update = Config { name: "b".into(), retries: 3 };
+    /// let bytes = fory.serialize(&update).unwrap();
+    /// let mut config = Config { name: "a".into(), retries: 0 };
+    /// fory.merge_into(&bytes, &mut config).unwrap();
+    /// assert_eq!(config, update);
+    /// ```
+    pub fn merge_into<T: Merge>(&self, bf: &[u8], target: &mut T) -> Result<(), Error> {
+        ensure!(
+            self.compatible,
+            Error::InvalidData("merge_into requires compatible mode".into())
+        );
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(bf, self.max_dyn_depth);
+        let result = self.merge_into_with_context(&mut context, target);
+        if result.is_ok() {
+            assert_eq!(context.reader.slice_after_cursor().len(), 0);
+        }
+        context.reset();
+        pool.put(context);
+        result
     }
 
-    /// Returns the maximum depth for nested dynamic object serialization.
-    pub fn get_max_dyn_depth(&self) -> u32 {
-        self.max_dyn_depth
+    pub fn merge_into_with_context<T: Merge>(
+        &self,
+        context: &mut ReadContext,
+        target: &mut T,
+    ) -> Result<(), Error> {
+        let is_none = self.read_context_head(context)?;
+        if is_none {
+            return Ok(());
+        }
+        let mut bytes_to_skip = 0;
+        let meta_offset = context.reader.read_i32()?;
+        if meta_offset != -1 {
+            bytes_to_skip = context.load_meta(meta_offset as usize)?;
+        }
+        let result = target.fory_merge(context);
+        if bytes_to_skip > 0 {
+            context.reader.skip(bytes_to_skip)?;
+        }
+        context.ref_reader.resolve_callbacks();
+        result
     }
 
-    /// Returns a type resolver for type lookups.
-    pub(crate) fn get_type_resolver(&self) -> &TypeResolver {
-        &self.type_resolver
+    /// Merges serialized map entries into an existing `HashMap`.
+    ///
+    /// Keys present in the data are inserted or overwritten, and keys the data does not have
+    /// keep their current values. [`Fory::deserialize_into`] clears the map first instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `bf` - The byte slice containing the serialized map.
+    /// * `target` - The map to merge into.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    /// use std::collections::HashMap;
+    ///
+    /// let fory = Fory::default();
+    /// let bytes = fory.serialize(&HashMap::from([(2, 20), (3, 30)])).unwrap();
+    /// let mut map = HashMap::from([(1, 1), (2, 2)]);
+    /// fory.merge_map_into(&bytes, &mut map).unwrap();
+    /// assert_eq!(map, HashMap::from([(1, 1), (2, 20), (3, 30)]));
+    /// ```
+    pub fn merge_map_into<K, V>(&self, bf: &[u8], target: &mut HashMap<K, V>) -> Result<(), Error>
+    where
+        K: Serializer + ForyDefault + Eq + std::hash::Hash,
+        V: Serializer + ForyDefault,
+    {
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(bf, self.max_dyn_depth);
+        let result = self.merge_map_into_with_context(&mut context, target);
+        if result.is_ok() {
+            assert_eq!(context.reader.slice_after_cursor().len(), 0);
+        }
+        context.reset();
+        pool.put(context);
+        result
     }
 
-    pub fn write_head<T: Serializer>(&self, is_none: bool, writer: &mut Writer) {
-        const HEAD_SIZE: usize = 10;
-        writer.reserve(T::fory_reserved_space() + SIZE_OF_REF_AND_TYPE + HEAD_SIZE);
-        if self.xlang {
-            writer.write_u16(MAGIC_NUMBER);
-        }
-        #[cfg(target_endian = "big")]
-        let mut bitmap = 0;
-        #[cfg(target_endian = "little")]
-        let mut bitmap = IS_LITTLE_ENDIAN_FLAG;
-        if self.xlang {
-            bitmap |= IS_CROSS_LANGUAGE_FLAG;
-        }
+    pub fn merge_map_into_with_context<K, V>(
+        &self,
+        context: &mut ReadContext,
+        target: &mut HashMap<K, V>,
+    ) -> Result<(), Error>
+    where
+        K: Serializer + ForyDefault + Eq + std::hash::Hash,
+        V: Serializer + ForyDefault,
+    {
+        let is_none = self.read_context_head(context)?;
         if is_none {
-            bitmap |= IS_NULL_FLAG;
+            return Ok(());
         }
-        writer.write_u8(bitmap);
-        if is_none {
-            return;
+        if self.has_schema_hash() {
+            let expected = self.root_schema_hash::<HashMap<K, V>>()?;
+            self.read_schema_hash(&mut context.reader, expected)?;
         }
-        if self.xlang {
-            writer.write_u8(Language::Rust as u8);
+        let mut bytes_to_skip = 0;
+        if context.is_compatible() {
+            let meta_offset = context.reader.read_i32()?;
+            if meta_offset != -1 {
+                bytes_to_skip = context.load_meta(meta_offset as usize)?;
+            }
         }
+        let result = merge_hashmap(target, context);
+        if bytes_to_skip > 0 {
+            context.reader.skip(bytes_to_skip)?;
+        }
+        context.ref_reader.resolve_callbacks();
+        result
     }
 
-    fn read_head(&self, reader: &mut Reader) -> Result<bool, Error> {
-        if self.xlang {
-            let magic_numer = reader.read_u16()?;
-            ensure!(
-                magic_numer == MAGIC_NUMBER,
-                Error::InvalidData(
-                    format!(
-                        "The fory xlang serialization must start with magic number {:X}. \
-                    Please check whether the serialization is based on the xlang protocol \
-                    and the data didn't corrupt.",
-                        MAGIC_NUMBER
-                    )
-                    .into()
-                )
-            )
+    /// Deserializes a serialized map into its entries sorted by key instead of a `HashMap`,
+    /// e.g. for binary search or for iterating in a stable order without hashing the keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `bf` - The byte slice containing the serialized map.
+    ///
+    /// # Returns
+    ///
+    /// The entries of the map, sorted by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidData`] if a key occurs more than once, and
+    /// [`Error::UnexpectedNull`] if the map is null outside compatible mode.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    /// use std::collections::HashMap;
+    ///
+    /// let fory = Fory::default();
+    /// let bytes = fory.serialize(&HashMap::from([(3, 30), (1, 10), (2, 20)])).unwrap();
+    /// let entries: Vec<(i32, i32)> = fory.deserialize_as_sorted_vec(&bytes).unwrap();
+    /// assert_eq!(entries, [(1, 10), (2, 20), (3, 30)]);
+    /// ```
+    pub fn deserialize_as_sorted_vec<K, V>(&self, bf: &[u8]) -> Result<Vec<(K, V)>, Error>
+    where
+        K: Serializer + ForyDefault + Ord,
+        V: Serializer + ForyDefault,
+    {
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(bf, self.max_dyn_depth);
+        let result = self.deserialize_as_sorted_vec_with_context(&mut context);
+        if result.is_ok() {
+            assert_eq!(context.reader.slice_after_cursor().len(), 0);
         }
-        let bitmap = reader.read_u8()?;
-        let peer_is_xlang = (bitmap & IS_CROSS_LANGUAGE_FLAG) != 0;
-        ensure!(
-            self.xlang == peer_is_xlang,
-            Error::InvalidData("header bitmap mismatch at xlang bit".into())
-        );
-        let is_little_endian = (bitmap & IS_LITTLE_ENDIAN_FLAG) != 0;
-        ensure!(
-            is_little_endian,
-            Error::InvalidData(
-                "Big endian is not supported for now, please ensure peer machine is little endian."
-                    .into()
-            )
-        );
-        let is_none = (bitmap & IS_NULL_FLAG) != 0;
+        context.reset();
+        pool.put(context);
+        result
+    }
+
+    pub fn deserialize_as_sorted_vec_with_context<K, V>(
+        &self,
+        context: &mut ReadContext,
+    ) -> Result<Vec<(K, V)>, Error>
+    where
+        K: Serializer + ForyDefault + Ord,
+        V: Serializer + ForyDefault,
+    {
+        let is_none = self.read_context_head(context)?;
         if is_none {
-            return Ok(true);
+            return self.read_null_root();
         }
-        if peer_is_xlang {
-            let _peer_lang = reader.read_u8()?;
+        if self.has_schema_hash() {
+            let expected = self.schema_hash_of(None, TypeId::MAP as u32);
+            self.read_schema_hash(&mut context.reader, expected)?;
+        }
+        let mut bytes_to_skip = 0;
+        if context.is_compatible() {
+            let meta_offset = context.reader.read_i32()?;
+            if meta_offset != -1 {
+                bytes_to_skip = context.load_meta(meta_offset as usize)?;
+            }
+        }
+        let result = read_sorted_entries(context);
+        if bytes_to_skip > 0 {
+            context.reader.skip(bytes_to_skip)?;
         }
-        Ok(false)
+        context.ref_reader.resolve_callbacks();
+        result
     }
 
-    /// Deserializes data from a byte slice into a value of type `T`.
+    /// Deserializes only the fields of the projection `P` out of data written as `T`.
     ///
-    /// # Type Parameters
+    /// `P` is a struct declaring a subset of the fields of `T`. Fields of `T` that `P`
+    /// does not declare are skipped without being materialized, and fields of `P`
+    /// missing from the data get their default values. This relies on the field framing
+    /// of compatible mode, and both `T` and `P` must be registered. `T` should not
+    /// contain itself, since nested values of `T` would be projected too.
     ///
-    /// * `T` - The target type to deserialize into. Must implement `Serializer` and `ForyDefault`.
+    /// # Errors
     ///
-    /// # Arguments
+    /// Returns [`Error::InvalidData`] if compatible mode is not enabled, and
+    /// [`Error::TypeMismatch`] if the data was not written as `T`.
     ///
-    /// * `bf` - The byte slice containing the serialized data.
+    /// # Examples
     ///
-    /// # Returns
+    /// ```rust, ignore
+    /// use fory_core::Fory;
+    /// use fory_derive::ForyObject;
     ///
-    /// * `Ok(T)` - The deserialized value on success.
-    /// * `Err(Error)` - An error if deserialization fails (e.g., invalid format, type mismatch).
+    /// #[derive(ForyObject)]
+    /// struct User { id: i64, name: String, bio: String, avatar: Vec<u8> }
+    ///
+    /// #[derive(ForyObject, Debug, PartialEq)]
+    /// struct UserName { id: i64, name: String }
+    ///
+    /// let mut fory = Fory::default().compatible(true);
+    /// fory.register::<User>(1).unwrap();
+    /// fory.register::<UserName>(2).unwrap();
+    /// let user = User { id: 7, name: "ada".into(), bio: "...".into(), avatar: vec![0; 1024] };
+    /// let bytes = fory.serialize(&user).unwrap();
+    /// let name: UserName = fory.deserialize_projection::<User, UserName>(&bytes).unwrap();
+    /// assert_eq!(name, UserName { id: 7, name: "ada".into() });
+    /// ```
+    pub fn deserialize_projection<T: Serializer, P: Serializer + ForyDefault>(
+        &self,
+        bf: &[u8],
+    ) -> Result<P, Error> {
+        ensure!(
+            self.compatible,
+            Error::InvalidData("deserialize_projection requires compatible mode".into())
+        );
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(bf, self.max_dyn_depth);
+        let result = self.deserialize_projection_with_context::<T, P>(&mut context);
+        if result.is_ok() {
+            assert_eq!(context.reader.slice_after_cursor().len(), 0);
+        }
+        context.reset();
+        pool.put(context);
+        result
+    }
+
+    pub fn deserialize_projection_with_context<T: Serializer, P: Serializer + ForyDefault>(
+        &self,
+        context: &mut ReadContext,
+    ) -> Result<P, Error> {
+        let is_none = self.read_context_head(context)?;
+        if is_none {
+            return self.read_null_root();
+        }
+        let mut bytes_to_skip = 0;
+        let meta_offset = context.reader.read_i32()?;
+        if meta_offset != -1 {
+            bytes_to_skip = context.load_meta(meta_offset as usize)?;
+        }
+        // peek at the root type info, and point the field ids of its type meta at `P`
+        let mut root = Reader::new(context.reader.slice_after_cursor());
+        let ref_flag = root.read_i8()?;
+        if ref_flag == RefFlag::NotNullValue as i8 || ref_flag == RefFlag::RefValue as i8 {
+            let expected = T::fory_get_type_id(&self.type_resolver)?;
+            let remote_type_id = root.read_varuint32()?;
+            ensure!(
+                remote_type_id == expected,
+                Error::TypeMismatch(expected, remote_type_id)
+            );
+            let meta_index = root.read_varuint32()? as usize;
+            ensure!(
+                meta_index < context.meta_resolver.reading_type_defs.len(),
+                Error::InvalidData(format!("type meta index {meta_index} out of range").into())
+            );
+            let type_info = self
+                .type_resolver
+                .get_type_info(std::any::TypeId::of::<P>())?;
+            let projected = context
+                .get_meta(meta_index)
+                .project(type_info, self.type_resolver.get_field_name_hash());
+            context.meta_resolver.reading_type_defs[meta_index] = Arc::new(projected);
+        }
+        let result = <P as Serializer>::fory_read(context, false);
+        if bytes_to_skip > 0 {
+            context.reader.skip(bytes_to_skip)?;
+        }
+        context.ref_reader.resolve_callbacks();
+        result
+    }
+
+    /// Serializes `record` and converts the data into a JSON tree for inspection.
     ///
-    /// # Panics
+    /// This is not the binary format: it is a self-describing view of what `record`
+    /// serializes to, built from the field names and types of the type metas. Structs
+    /// carry their type tag as a string in a `"$type"` member and binary data becomes
+    /// base64; see [`crate::serializer::json`] for the full mapping.
     ///
-    /// Panics in debug mode if there are unread bytes remaining after successful deserialization,
-    /// indicating a potential protocol violation.
+    /// The root must be a struct, and the instance must be in compatible mode, since
+    /// only then is the data described by type metas.
     ///
     /// # Examples
     ///
     /// ```rust, ignore
-    /// use fory::Fory;
-    /// use fory::ForyObject;
+    /// use fory::{Fory, ForyObject};
     ///
     /// #[derive(ForyObject)]
     /// struct Point { x: i32, y: i32 }
     ///
-    /// let fory = Fory::default();
-    /// let point = Point { x: 10, y: 20 };
-    /// let bytes = fory.serialize(&point);
-    /// let deserialized: Point = fory.deserialize(&bytes).unwrap();
+    /// let mut fory = Fory::default().compatible(true);
+    /// fory.register_by_name::<Point>("Point").unwrap();
+    /// let json = fory.to_debug_json(&Point { x: 1, y: 2 }).unwrap();
+    /// assert_eq!(json["x"], 1);
+    /// assert_eq!(json["$type"], "Point");
     /// ```
-    pub fn deserialize<T: Serializer + ForyDefault>(&self, bf: &[u8]) -> Result<T, Error> {
-        let pool = self.read_context_pool.get_or_init(|| {
-            let type_resolver = self.type_resolver.clone();
-            let compatible = self.compatible;
-            let share_meta = self.share_meta;
-            let xlang = self.xlang;
-            let max_dyn_depth = self.max_dyn_depth;
-
-            let factory = move || {
-                let reader = Reader::new(&[]);
-                ReadContext::new(
-                    reader,
-                    type_resolver.clone(),
-                    compatible,
-                    share_meta,
-                    xlang,
-                    max_dyn_depth,
-                )
-            };
-            Pool::new(factory)
-        });
+    #[cfg(feature = "json")]
+    pub fn to_debug_json<T: Serializer>(&self, record: &T) -> Result<serde_json::Value, Error> {
+        ensure!(
+            self.compatible,
+            Error::InvalidData("to_debug_json requires compatible mode".into())
+        );
+        let type_id = T::fory_get_type_id(&self.type_resolver)?;
+        use crate::types::TypeId;
+        const COMPATIBLE_STRUCT_ID: u32 = TypeId::COMPATIBLE_STRUCT as u32;
+        ensure!(
+            type_id & 0xff == COMPATIBLE_STRUCT_ID
+                || type_id == TypeId::NAMED_COMPATIBLE_STRUCT as u32,
+            Error::TypeError("to_debug_json expects a struct".into())
+        );
+        let bytes = self.serialize(record)?;
+        let pool = self.read_context_pool();
         let mut context = pool.get();
-        context.init(bf, self.max_dyn_depth);
-        let result = self.deserialize_with_context(&mut context);
-        if result.is_ok() {
-            assert_eq!(context.reader.slice_after_cursor().len(), 0);
-        }
+        context.init(&bytes, self.max_dyn_depth);
+        let result = self.to_debug_json_with_context(&mut context, type_id);
         context.reset();
         pool.put(context);
         result
     }
 
+    #[cfg(feature = "json")]
+    fn to_debug_json_with_context(
+        &self,
+        context: &mut ReadContext,
+        type_id: u32,
+    ) -> Result<serde_json::Value, Error> {
+        let is_none = self.read_context_head(context)?;
+        if is_none {
+            return Ok(serde_json::Value::Null);
+        }
+        let meta_offset = context.reader.read_i32()?;
+        if meta_offset != -1 {
+            context.load_meta(meta_offset as usize)?;
+        }
+        let root = crate::meta::FieldType::new(type_id, false, Vec::new());
+        crate::serializer::json::read_json_value(context, &root, true)
+    }
+
+    /// Deserializes the next value written by [`Fory::serialize_with_context`] from the
+    /// reader of `context`.
+    ///
+    /// The ref table of `context` is kept across calls, so a shared `Rc` or `Arc` written
+    /// once in a session is resolved to the same pointer in every value that refers to it.
+    /// Read the values in the order they were written, with one context for the session.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - A read context over the session's bytes, e.g. from
+    ///   [`ReadContext::new_from_fory`].
+    ///
+    /// # Returns
+    ///
+    /// The next value in the session.
     pub fn deserialize_with_context<T: Serializer + ForyDefault>(
         &self,
         context: &mut ReadContext,
     ) -> Result<T, Error> {
-        let is_none = self.read_head(&mut context.reader)?;
+        let is_none = self.read_context_head(context)?;
         if is_none {
-            return Ok(T::fory_default());
+            return self.read_null_root();
+        }
+        if self.has_schema_hash() {
+            let expected = self.root_schema_hash::<T>()?;
+            self.read_schema_hash(&mut context.reader, expected)?;
         }
         let mut bytes_to_skip = 0;
         if context.is_compatible() {
@@ -465,40 +2167,367 @@ impl Fory {
     /// let bytes = fory.serialize(&point);
     /// ```
     pub fn serialize<T: Serializer>(&self, record: &T) -> Result<Vec<u8>, Error> {
-        let pool = self.write_context_pool.get_or_init(|| {
-            let type_resolver = self.type_resolver.clone();
-            let compatible = self.compatible;
-            let share_meta = self.share_meta;
-            let compress_string = self.compress_string;
-            let xlang = self.xlang;
-
-            let factory = move || {
-                let writer = Writer::default();
-                WriteContext::new(
-                    writer,
-                    type_resolver.clone(),
-                    compatible,
-                    share_meta,
-                    compress_string,
-                    xlang,
-                )
-            };
-            Pool::new(factory)
-        });
+        if self.exact_presize {
+            return self.serialize_presized(record);
+        }
+        self.serialize_in_place(record, |bytes| Ok(bytes.to_vec()))
+    }
+
+    /// Returns the number of bytes [`Fory::serialize`] writes for `record`.
+    ///
+    /// This is a dry run of the serialization into a pooled buffer, so it costs about as
+    /// much as serializing the value.
+    pub fn serialized_size<T: Serializer>(&self, record: &T) -> Result<usize, Error> {
+        self.serialize_in_place(record, |bytes| Ok(bytes.len()))
+    }
+
+    /// Serializes `value` as it would be written for a struct field, to splice it into later
+    /// writes with [`PreEncoded`] instead of serializing it again each time.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidData`] if compatible mode, dictionary strings or an array
+    /// alignment are enabled, since they make the encoding of a value depend on what was
+    /// written before it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, ignore
+    /// use fory_core::{Fory, PreEncoded};
+    /// use fory_derive::ForyObject;
+    ///
+    /// #[derive(ForyObject)]
+    /// struct Page { title: String, footer: Footer }
+    ///
+    /// #[derive(ForyObject)]
+    /// struct CachedPage { title: String, footer: PreEncoded }
+    ///
+    /// let footer: PreEncoded = fory.pre_encode(&Footer::load())?;
+    /// let bytes = fory.serialize(&CachedPage { title: "home".into(), footer })?;
+    /// let page: Page = fory.deserialize(&bytes)?;
+    /// ```
+    pub fn pre_encode<T: Serializer>(&self, value: &T) -> Result<PreEncoded, Error> {
+        ensure!(
+            !self.compatible && !self.dictionary_strings && self.array_alignment == 1,
+            Error::InvalidData(
+                "pre_encode requires compatible mode, dictionary strings and array alignment \
+                to be off"
+                    .into()
+            )
+        );
+        let pool = self.write_context_pool();
+        let mut context = pool.get();
+        let result = T::fory_write_type_info(&mut context, true)
+            .and_then(|_| value.fory_write_data(&mut context, true));
+        let bytes = context.writer.dump();
+        context.reset();
+        pool.put(context);
+        result.map(|_| PreEncoded(bytes))
+    }
+
+    /// Appends `event` to `buffer` as one frame of an append-only log, prefixed with its
+    /// serialized length as a little-endian `u32`.
+    ///
+    /// Each frame is a complete serialization, so frames written by different processes
+    /// can be concatenated and read back with [`Fory::read_events`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::fory::Fory;
+    ///
+    /// let fory = Fory::default();
+    /// let mut log = Vec::new();
+    /// fory.append_event(&mut log, &"created".to_string()).unwrap();
+    /// fory.append_event(&mut log, &"deleted".to_string()).unwrap();
+    /// let events: Vec<String> = fory.read_events(&log).collect::<Result<_, _>>().unwrap();
+    /// assert_eq!(events, ["created", "deleted"]);
+    /// ```
+    pub fn append_event<T: Serializer>(
+        &self,
+        buffer: &mut Vec<u8>,
+        event: &T,
+    ) -> Result<(), Error> {
+        self.serialize_in_place(event, |bytes| {
+            let len = u32::try_from(bytes.len())
+                .map_err(|_| Error::EncodeError("event larger than 4 GiB".into()))?;
+            buffer.reserve(4 + bytes.len());
+            buffer.extend_from_slice(&len.to_le_bytes());
+            buffer.extend_from_slice(bytes);
+            Ok(())
+        })
+    }
+
+    /// Iterates over the events written to `buffer` by [`Fory::append_event`], in order.
+    ///
+    /// A truncated final frame, e.g. from a crash in the middle of an append, yields an
+    /// [`Error::UnexpectedEof`] as the last item. The iterator stops after the first error.
+    pub fn read_events<'a, T: Serializer + ForyDefault>(
+        &'a self,
+        buffer: &'a [u8],
+    ) -> impl Iterator<Item = Result<T, Error>> + 'a {
+        let mut rest = buffer;
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            let frame = rest
+                .get(..4)
+                .map(|len| u32::from_le_bytes(len.try_into().unwrap()) as usize)
+                .and_then(|len| rest.get(4..4 + len));
+            match frame {
+                Some(bytes) => {
+                    rest = &rest[4 + bytes.len()..];
+                    Some(self.deserialize(bytes))
+                }
+                None => {
+                    let remaining = rest.len();
+                    rest = &[];
+                    Some(Err(Error::UnexpectedEof(
+                        format!("truncated event frame of {remaining} bytes").into(),
+                    )))
+                }
+            }
+        })
+    }
+
+    /// Serializes `record` into a custom sink.
+    ///
+    /// The value is encoded into a pooled buffer, since chunk headers and meta offsets are
+    /// patched after the fact, and the finished bytes are then copied to `writer` in one
+    /// call. A counting writer thus measures the output, and a memory map or a compressing
+    /// stream receives it without a `Vec` allocated per call, though not without the copy.
+    /// [`Fory::serialize_slice_to`] avoids the copy for large primitive slices.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written to `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default();
+    /// let mut out = Vec::new();
+    /// let len = fory.serialize_to(&"hello".to_string(), &mut out).unwrap();
+    /// assert_eq!(len, out.len());
+    /// assert_eq!(fory.deserialize::<String>(&out).unwrap(), "hello");
+    /// ```
+    pub fn serialize_to<T: Serializer, W: ByteWriter + ?Sized>(
+        &self,
+        record: &T,
+        writer: &mut W,
+    ) -> Result<usize, Error> {
+        self.serialize_in_place(record, |bytes| {
+            writer.reserve(bytes.len());
+            writer.write_bytes(bytes)?;
+            Ok(bytes.len())
+        })
+    }
+
+    /// Serializes a slice of primitives into a custom sink without staging its elements.
+    ///
+    /// Only the header is encoded into the pooled buffer, the elements are handed to
+    /// `writer` straight from `slice`. Appending a large array to an
+    /// [`MmapWriter`](crate::mmap::MmapWriter) thus copies it once instead of twice, which
+    /// halves the time for a million `i32` in `benches/slice_bench.rs`. The
+    /// output is that of [`Fory::serialize_to`] with `slice.to_vec()`, except that a
+    /// `&[u8]` is written as `BINARY`, which [`Fory::deserialize_borrowed`] reads back as
+    /// a `&[u8]` in place. Slices of other types, and float slices holding NaNs while
+    /// canonical NaNs are enabled, take the regular path.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written to `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    ///
+    /// let fory = Fory::default();
+    /// let values = [1i32, 2, 3];
+    /// let mut out = Vec::new();
+    /// fory.serialize_slice_to(&values, &mut out).unwrap();
+    /// assert_eq!(fory.deserialize::<Vec<i32>>(&out).unwrap(), values);
+    /// ```
+    pub fn serialize_slice_to<T: Serializer + Copy, W: ByteWriter + ?Sized>(
+        &self,
+        slice: &[T],
+        writer: &mut W,
+    ) -> Result<usize, Error> {
+        let Some(type_id) = primitive_list::slice_type_id(slice, self.canonical_nan) else {
+            return self.serialize_to(&slice.to_vec(), writer);
+        };
+        let bytes = primitive_list::as_bytes(slice);
+        let pool = self.write_context_pool();
+        let mut context = pool.get();
+        self.write_head::<Vec<T>>(false, &mut context.writer);
+        if self.has_schema_hash() {
+            let schema_hash = self.schema_hash_of(None, type_id as u32);
+            context.writer.write_u64(schema_hash);
+        }
+        if self.compatible {
+            context.writer.write_i32(-1);
+        }
+        context.writer.write_i8(RefFlag::NotNullValue as i8);
+        context.writer.write_varuint32(type_id as u32);
+        context.writer.write_varuint32(bytes.len() as u32);
+        if type_id != TypeId::BINARY && !bytes.is_empty() {
+            primitive_list::write_padding(&mut context.writer, self.array_alignment);
+        }
+        let head = &context.writer.bf;
+        writer.reserve(head.len() + bytes.len());
+        let result = writer
+            .write_bytes(head)
+            .and_then(|_| writer.write_bytes(bytes))
+            .map(|_| head.len() + bytes.len());
+        context.reset();
+        pool.put(context);
+        result
+    }
+
+    /// Serializes `record` straight into a buffer allocated with its [`Fory::serialized_size`].
+    fn serialize_presized<T: Serializer>(&self, record: &T) -> Result<Vec<u8>, Error> {
+        let size = self.serialized_size(record)?;
+        let pool = self.write_context_pool();
+        let mut context = pool.get();
+        let buffer = std::mem::replace(&mut context.writer.bf, Vec::with_capacity(size).into());
+        context.writer.exact = true;
+        let result = self.write_with_context(record, &mut context);
+        context.writer.exact = false;
+        let bytes = std::mem::replace(&mut context.writer.bf, buffer);
+        context.reset();
+        pool.put(context);
+        result.map(|_| bytes.into_vec())
+    }
+
+    /// Serializes `record` with a pooled context and hands the written bytes to `f`
+    /// before the context buffer is reused.
+    pub(crate) fn serialize_in_place<T: Serializer, R>(
+        &self,
+        record: &T,
+        f: impl FnOnce(&[u8]) -> Result<R, Error>,
+    ) -> Result<R, Error> {
+        let pool = self.write_context_pool();
+        let mut context = pool.get();
+        self.write_with_context(record, &mut context)?;
+        let result = f(&context.writer.bf);
+        context.reset();
+        pool.put(context);
+        result
+    }
+
+    /// Serializes `record` with a pooled context that writes straight into `region`.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written to the start of `region`, or, if the value did not fit,
+    /// all of its bytes, which were moved to a buffer of their own.
+    #[cfg(feature = "mmap")]
+    pub(crate) fn serialize_into_slice<T: Serializer>(
+        &self,
+        record: &T,
+        region: &mut [u8],
+    ) -> Result<Result<usize, Vec<u8>>, Error> {
+        let pool = self.write_context_pool();
         let mut context = pool.get();
-        let result = self.serialize_with_context(record, &mut context)?;
+        // SAFETY: the region is taken back below, before `region` goes out of scope
+        unsafe { context.writer.bf.lend(region) };
+        let result = self.write_with_context(record, &mut context);
+        let written = match context.writer.bf.take_lent() {
+            Some(len) => Ok(len),
+            None => Err(std::mem::take(&mut context.writer.bf).into_vec()),
+        };
         context.reset();
         pool.put(context);
-        Ok(result)
+        result.map(|_| written)
+    }
+
+    fn write_context_pool(&self) -> &Pool<WriteContext> {
+        self.write_context_pool.get_or_init(|| {
+            let type_resolver = self.type_resolver.clone();
+            let config = self.write_config();
+            let factory =
+                move || WriteContext::new(Writer::default(), type_resolver.clone(), config.clone());
+            Pool::new(factory)
+        })
     }
 
+    pub(crate) fn write_config(&self) -> WriteConfig {
+        WriteConfig {
+            compatible: self.compatible,
+            share_meta: self.share_meta,
+            compress_string: self.compress_string,
+            xlang: self.xlang,
+            deterministic: self.deterministic,
+            sparse_null_encoding: self.sparse_null_encoding,
+            map_key_prefix_sharing: self.map_key_prefix_sharing,
+            dictionary_strings: self.dictionary_strings,
+            canonical_nan: self.canonical_nan,
+            array_alignment: self.array_alignment,
+            deque_capacity_hint: self.deque_capacity_hint,
+            cipher: self.cipher.clone(),
+        }
+    }
+
+    /// Appends `record` to the session held by `context` and returns all bytes written to
+    /// it so far.
+    ///
+    /// The ref table of `context` is kept across calls, so an `Rc` or `Arc` shared by
+    /// several top-level values is written in full only the first time and as a reference
+    /// to it afterwards. Read the session back with one [`ReadContext`] and
+    /// [`Fory::deserialize_with_context`], in the same order.
+    ///
+    /// # Arguments
+    ///
+    /// * `record` - The value to append.
+    /// * `context` - A write context kept for the session, e.g. from
+    ///   [`WriteContext::new_from_fory`].
+    ///
+    /// # Returns
+    ///
+    /// A copy of every value written with `context` so far.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::buffer::{Reader, Writer};
+    /// use fory_core::fory::Fory;
+    /// use fory_core::resolver::context::{ReadContext, WriteContext};
+    /// use std::sync::Arc;
+    ///
+    /// let fory = Fory::default();
+    /// let shared = Arc::new("shared".to_string());
+    /// let mut context = WriteContext::new_from_fory(Writer::default(), &fory);
+    /// fory.serialize_with_context(&shared, &mut context).unwrap();
+    /// let bytes = fory.serialize_with_context(&shared, &mut context).unwrap();
+    ///
+    /// let mut context = ReadContext::new_from_fory(Reader::new(&bytes), &fory);
+    /// let first: Arc<String> = fory.deserialize_with_context(&mut context).unwrap();
+    /// let second: Arc<String> = fory.deserialize_with_context(&mut context).unwrap();
+    /// assert!(Arc::ptr_eq(&first, &second));
+    /// ```
     pub fn serialize_with_context<T: Serializer>(
         &self,
         record: &T,
         context: &mut WriteContext,
     ) -> Result<Vec<u8>, Error> {
+        self.write_with_context(record, context)?;
+        Ok(context.writer.dump())
+    }
+
+    fn write_with_context<T: Serializer>(
+        &self,
+        record: &T,
+        context: &mut WriteContext,
+    ) -> Result<(), Error> {
         let is_none = record.fory_is_none();
         self.write_head::<T>(is_none, &mut context.writer);
+        if !is_none && self.has_schema_hash() {
+            context.writer.write_u64(self.root_schema_hash::<T>()?);
+        }
         let meta_start_offset = context.writer.len();
         if !is_none {
             if context.is_compatible() {
@@ -509,7 +2538,102 @@ impl Fory {
                 context.write_meta(meta_start_offset);
             }
         }
-        Ok(context.writer.dump())
+        Ok(())
+    }
+
+    /// Writes the type defs of all registered types, with `T` first if it is registered, as
+    /// a schema to store once next to many buffers from [`Fory::serialize_without_schema`].
+    ///
+    /// The schema pins the meta table indices that those buffers refer to, so it must be
+    /// written by a `Fory` with the same registrations as the one writing the data. The
+    /// reader may have evolved its types, as in any compatible mode stream.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidData`] if compatible mode is not enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, ignore
+    /// use fory_core::Fory;
+    /// use fory_derive::ForyObject;
+    ///
+    /// #[derive(ForyObject, Debug, PartialEq)]
+    /// struct Event {
+    ///     name: String,
+    ///     value: i64,
+    /// }
+    ///
+    /// let mut fory = Fory::default().compatible(true);
+    /// fory.register::<Event>(1).unwrap();
+    /// let schema = fory.write_schema::<Event>().unwrap();
+    /// let event = Event { name: "tick".into(), value: 1 };
+    /// let data = fory.serialize_without_schema(&event).unwrap();
+    /// assert_eq!(fory.deserialize_with_schema::<Event>(&schema, &data).unwrap(), event);
+    /// ```
+    pub fn write_schema<T: Serializer>(&self) -> Result<Vec<u8>, Error> {
+        let mut resolver = MetaWriterResolver::default();
+        for type_id in self.schema_type_ids::<T>()? {
+            resolver.push(type_id, &self.type_resolver)?;
+        }
+        let mut writer = Writer::default();
+        resolver.to_bytes(&mut writer);
+        Ok(writer.dump())
+    }
+
+    /// Serializes `record` without the type defs covered by [`Fory::write_schema`], for
+    /// reading back with [`Fory::deserialize_with_schema`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidData`] if compatible mode is not enabled.
+    pub fn serialize_without_schema<T: Serializer>(&self, record: &T) -> Result<Vec<u8>, Error> {
+        let mut context = WriteContext::new_from_fory(Writer::default(), self);
+        context.seed_meta(&self.schema_type_ids::<T>()?)?;
+        self.serialize_with_context(record, &mut context)
+    }
+
+    /// Deserializes `data` written by [`Fory::serialize_without_schema`], taking the type
+    /// defs it omits from `schema`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidData`] if compatible mode is not enabled.
+    pub fn deserialize_with_schema<T: Serializer + ForyDefault>(
+        &self,
+        schema: &[u8],
+        data: &[u8],
+    ) -> Result<T, Error> {
+        ensure!(
+            self.compatible,
+            Error::InvalidData("schemas require compatible mode".into())
+        );
+        let pool = self.read_context_pool();
+        let mut context = pool.get();
+        context.init(data, self.max_dyn_depth);
+        let result = context
+            .load_schema(schema)
+            .and_then(|_| self.deserialize_with_context(&mut context));
+        if result.is_ok() {
+            assert_eq!(context.reader.slice_after_cursor().len(), 0);
+        }
+        context.reset();
+        pool.put(context);
+        result
+    }
+
+    fn schema_type_ids<T: Serializer>(&self) -> Result<Vec<std::any::TypeId>, Error> {
+        ensure!(
+            self.compatible,
+            Error::InvalidData("schemas require compatible mode".into())
+        );
+        let root = std::any::TypeId::of::<T>();
+        let mut type_ids = self.type_resolver.registered_type_ids();
+        if let Some(index) = type_ids.iter().position(|type_id| *type_id == root) {
+            type_ids.remove(index);
+            type_ids.insert(0, root);
+        }
+        Ok(type_ids)
     }
 
     /// Registers a struct type with a numeric type ID for serialization.
@@ -730,6 +2854,117 @@ impl Fory {
     ) -> Result<(), Error> {
         self.register_serializer_by_namespace::<T>("", type_name)
     }
+
+    /// Registers a handler, e.g. a closure, that a [`HandlerRef`] with the same id
+    /// deserializes to.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `F` - The handler type, typically a trait object such as
+    ///   `dyn Fn(Request) -> Response + Send + Sync`. It is part of the lookup, so the
+    ///   `HandlerRef<F>` read must name the same type.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - A unique numeric identifier for the handler. Only the id is serialized.
+    /// * `handler` - The handler to resolve the id to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeError`] if a handler with `id` is already registered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    /// use std::sync::Arc;
+    ///
+    /// let mut fory = Fory::default();
+    /// fory.register_handler::<dyn Fn(&str) -> String + Send + Sync>(
+    ///     7,
+    ///     Arc::new(|name| format!("hello, {}", name)),
+    /// )
+    /// .unwrap();
+    /// ```
+    pub fn register_handler<F: ?Sized + Send + Sync + 'static>(
+        &mut self,
+        id: u32,
+        handler: Arc<F>,
+    ) -> Result<(), Error> {
+        self.type_resolver.register_handler(id, Arc::new(handler))
+    }
+
+    /// Registers a well-known string, so that a `Cow<'static, str>` equal to it is serialized
+    /// as just `id`, and deserialized as a `Cow::Borrowed` of `value`.
+    ///
+    /// Both peers must register the same strings with the same ids. Other strings are
+    /// written in full after a marker, so the set can be a subset of the values in use,
+    /// e.g. the common values of an enum-like field.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeError`] if `id` or `value` is already registered.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fory_core::Fory;
+    /// use std::borrow::Cow;
+    ///
+    /// let mut fory = Fory::default();
+    /// fory.register_static_string(0, "GET").unwrap();
+    /// fory.register_static_string(1, "POST").unwrap();
+    /// let bytes = fory.serialize(&Cow::Borrowed("POST")).unwrap();
+    /// let method: Cow<'static, str> = fory.deserialize(&bytes).unwrap();
+    /// assert!(matches!(method, Cow::Borrowed("POST")));
+    /// ```
+    pub fn register_static_string(&mut self, id: u32, value: &'static str) -> Result<(), Error> {
+        self.type_resolver.register_static_string(id, value)
+    }
+
+    /// Writes the registered enum `T` as the name of its variant instead of its ordinal,
+    /// for peers that exchange enums as strings, such as JSON-based services.
+    ///
+    /// Call this after registering `T`, before registering the structs that hold it and
+    /// before the first serialization. Both peers must do the same to exchange such an
+    /// enum. The type meta of a struct records its fields of type `T` as strings, so that a
+    /// peer in compatible mode that lacks such a field, or reads it by ordinal, skips it. A name that matches no variant is
+    /// rejected on read, also when `T` has a `#[fory(unknown)]` catch-all, which cannot
+    /// itself be written by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeError`] if `T` is not registered as an enum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust, ignore
+    /// use fory_core::Fory;
+    /// use fory_derive::ForyObject;
+    ///
+    /// #[derive(ForyObject, Debug, PartialEq)]
+    /// enum Level { Low, High }
+    ///
+    /// let mut fory = Fory::default();
+    /// fory.register::<Level>(100).unwrap();
+    /// fory.register_enum_as_string::<Level>().unwrap();
+    /// let bytes = fory.serialize(&Level::High).unwrap();
+    /// assert!(bytes.ends_with(b"High"));
+    /// ```
+    pub fn register_enum_as_string<T: Serializer + 'static>(&mut self) -> Result<(), Error> {
+        self.type_resolver
+            .register_enum_as_string(std::any::TypeId::of::<T>(), std::any::type_name::<T>())
+    }
+
+    /// Returns a [`HandlerRef`] to the handler registered with `id`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TypeError`] if no handler of type `F` is registered with `id`.
+    pub fn handler_ref<F: ?Sized + 'static>(&self, id: u32) -> Result<HandlerRef<F>, Error> {
+        let handler = self.type_resolver.get_handler::<F>(id)?;
+        Ok(HandlerRef::new(id, handler))
+    }
 }
 
 pub fn write_data<T: Serializer>(
@@ -746,3 +2981,37 @@ pub fn read_data<T: Serializer + ForyDefault>(
 ) -> Result<T, Error> {
     T::fory_read_data(context, is_field)
 }
+
+/// Registers several monomorphizations of a generic struct in one go, assigning
+/// consecutive ids starting at `first_id` in the order the type arguments are listed.
+///
+/// Ids stay stable as long as new type arguments are appended to the end of the
+/// list. The struct must take a single type parameter. Evaluates to
+/// `Result<(), Error>`, stopping at the first registration that fails.
+///
+/// # Examples
+///
+/// ```rust, ignore
+/// use fory::{register_generic, Fory, ForyObject};
+///
+/// #[derive(ForyObject)]
+/// struct Wrapper<T> { value: T }
+///
+/// let mut fory = Fory::default();
+/// // Registers `Wrapper<i32>` with id 100 and `Wrapper<String>` with id 101.
+/// register_generic!(fory, Wrapper, [i32, String], 100).unwrap();
+/// ```
+#[macro_export]
+macro_rules! register_generic {
+    ($fory:expr, $generic:ident, [$($arg:ty),+ $(,)?], $first_id:expr) => {{
+        let mut ids = ($first_id as u32)..;
+        let mut result: Result<(), $crate::error::Error> = Ok(());
+        $(
+            let id = ids.next().unwrap();
+            if result.is_ok() {
+                result = $fory.register::<$generic<$arg>>(id);
+            }
+        )+
+        result
+    }};
+}
@@ -180,6 +180,8 @@ pub mod buffer;
 pub mod error;
 pub mod fory;
 pub mod meta;
+#[cfg(feature = "mmap")]
+pub mod mmap;
 pub mod resolver;
 pub mod row;
 pub mod serializer;
@@ -193,6 +195,10 @@ pub use crate::buffer::{Reader, Writer};
 pub use crate::fory::Fory;
 pub use crate::resolver::context::{ReadContext, WriteContext};
 pub use crate::resolver::type_resolver::TypeResolver;
+pub use crate::serializer::cipher::Cipher;
+pub use crate::serializer::ordered_map::OrderedHashMap;
+pub use crate::serializer::pre_encoded::PreEncoded;
+pub use crate::serializer::sorted_set::SortedSet;
 pub use crate::serializer::weak::{ArcWeak, RcWeak};
-pub use crate::serializer::{ForyDefault, Serializer};
+pub use crate::serializer::{Diff, FieldChange, ForyDefault, Merge, Serializer};
 pub use crate::types::{RefFlag, TypeId};
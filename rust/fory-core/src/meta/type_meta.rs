@@ -22,7 +22,7 @@ use crate::meta::{
     FIELD_NAME_ENCODER, NAMESPACE_DECODER, TYPE_NAME_DECODER,
 };
 use crate::resolver::type_resolver::{TypeInfo, TypeResolver};
-use crate::types::{TypeId, PRIMITIVE_TYPES};
+use crate::types::{HashKind, TypeId, INTEGER_ARRAY_TYPES, INTEGER_TYPES, PRIMITIVE_TYPES};
 use std::clone::Clone;
 use std::cmp::min;
 use std::collections::HashMap;
@@ -30,6 +30,9 @@ use std::collections::HashMap;
 const SMALL_NUM_FIELDS_THRESHOLD: usize = 0b11111;
 const REGISTER_BY_NAME_FLAG: u8 = 0b100000;
 const FIELD_NAME_SIZE_THRESHOLD: usize = 0b1111;
+// encoding bits of a field identified by a tag id instead of its name, whose hash is
+// written as the tag id, see `HashKind`
+const FIELD_NAME_TAG_ID_ENCODING: u8 = 0b11;
 
 const BIG_NAME_THRESHOLD: usize = 0b111111;
 
@@ -62,6 +65,8 @@ pub struct FieldType {
     pub type_id: u32,
     pub nullable: bool,
     pub generics: Vec<FieldType>,
+    // the `N` of a `FIXED_BINARY` or `FIXED_LIST`, whose data has no length prefix
+    pub length: u32,
 }
 
 impl FieldType {
@@ -70,9 +75,19 @@ impl FieldType {
             type_id,
             nullable,
             generics,
+            length: 0,
         }
     }
 
+    pub fn with_length(mut self, length: u32) -> FieldType {
+        self.length = length;
+        self
+    }
+
+    fn is_fixed_length(type_id: u32) -> bool {
+        type_id == TypeId::FIXED_BINARY as u32 || type_id == TypeId::FIXED_LIST as u32
+    }
+
     fn to_bytes(&self, writer: &mut Writer, write_flag: bool, nullable: bool) -> Result<(), Error> {
         let mut header = self.type_id;
         if write_flag {
@@ -83,8 +98,14 @@ impl FieldType {
             }
         }
         writer.write_varuint32(header);
+        if Self::is_fixed_length(self.type_id) {
+            writer.write_varuint32(self.length);
+        }
         match self.type_id {
-            x if x == TypeId::LIST as u32 || x == TypeId::SET as u32 => {
+            x if x == TypeId::LIST as u32
+                || x == TypeId::SET as u32
+                || x == TypeId::FIXED_LIST as u32 =>
+            {
                 let generic = self.generics.first().unwrap();
                 generic.to_bytes(writer, true, false)?;
             }
@@ -105,40 +126,32 @@ impl FieldType {
         nullable: Option<bool>,
     ) -> Result<Self, Error> {
         let header = reader.read_varuint32()?;
-        let type_id;
-        let _nullable;
-        if read_flag {
-            type_id = header >> 2;
+        let (type_id, _nullable) = if read_flag {
             // let tracking_ref = (header & 1) != 0;
-            _nullable = (header & 2) != 0;
+            (header >> 2, (header & 2) != 0)
         } else {
-            type_id = header;
-            _nullable = nullable.unwrap();
-        }
-        Ok(match type_id {
-            x if x == TypeId::LIST as u32 || x == TypeId::SET as u32 => {
-                let generic = Self::from_bytes(reader, true, None)?;
-                Self {
-                    type_id,
-                    nullable: _nullable,
-                    generics: vec![generic],
-                }
+            (header, nullable.unwrap())
+        };
+        let length = if Self::is_fixed_length(type_id) {
+            reader.read_varuint32()?
+        } else {
+            0
+        };
+        let generics = match type_id {
+            x if x == TypeId::LIST as u32
+                || x == TypeId::SET as u32
+                || x == TypeId::FIXED_LIST as u32 =>
+            {
+                vec![Self::from_bytes(reader, true, None)?]
             }
             x if x == TypeId::MAP as u32 => {
                 let key_generic = Self::from_bytes(reader, true, None)?;
                 let val_generic = Self::from_bytes(reader, true, None)?;
-                Self {
-                    type_id,
-                    nullable: _nullable,
-                    generics: vec![key_generic, val_generic],
-                }
+                vec![key_generic, val_generic]
             }
-            _ => Self {
-                type_id,
-                nullable: _nullable,
-                generics: vec![],
-            },
-        })
+            _ => vec![],
+        };
+        Ok(Self::new(type_id, _nullable, generics).with_length(length))
     }
 }
 
@@ -147,6 +160,10 @@ pub struct FieldInfo {
     pub field_id: i16,
     pub field_name: String,
     pub field_type: FieldType,
+    // set instead of `field_name` when the writer hashed the field names
+    pub name_hash: Option<u64>,
+    // set on a local field whose read converts integers of another width, never written
+    pub converts_integers: bool,
 }
 
 impl FieldInfo {
@@ -155,9 +172,18 @@ impl FieldInfo {
             field_id: -1i16,
             field_name: field_name.to_string(),
             field_type,
+            name_hash: None,
+            converts_integers: false,
         }
     }
 
+    /// Marks a local field whose read converts integers that the peer wrote with another
+    /// width, so that [`TypeMetaLayer`] keeps its field id for such a peer.
+    pub fn with_integer_conversion(mut self) -> FieldInfo {
+        self.converts_integers = true;
+        self
+    }
+
     fn u8_to_encoding(value: u8) -> Result<Encoding, Error> {
         match value {
             0x00 => Ok(Encoding::Utf8),
@@ -173,7 +199,22 @@ impl FieldInfo {
         let header = reader.read_u8()?;
         let nullable = (header & 2) != 0;
         // let ref_tracking = (header & 1) != 0;
-        let encoding = Self::u8_to_encoding((header >> 6) & 0b11)?;
+        let encoding_bits = (header >> 6) & 0b11;
+        if encoding_bits == FIELD_NAME_TAG_ID_ENCODING {
+            // the size bits start the tag id, and no name follows the type
+            let mut tag_id = ((header >> 2) & FIELD_NAME_SIZE_THRESHOLD as u8) as u64;
+            if tag_id == FIELD_NAME_SIZE_THRESHOLD as u64 {
+                tag_id += reader.read_varuint64()?;
+            }
+            let field_type = FieldType::from_bytes(reader, false, Option::from(nullable))?;
+            return Ok(FieldInfo {
+                field_id: -1i16,
+                field_name: String::new(),
+                field_type,
+                name_hash: Some(tag_id),
+                converts_integers: false,
+            });
+        }
         let mut name_size = ((header >> 2) & FIELD_NAME_SIZE_THRESHOLD as u8) as usize;
         if name_size == FIELD_NAME_SIZE_THRESHOLD {
             name_size += reader.read_varuint32()? as usize;
@@ -183,7 +224,7 @@ impl FieldInfo {
         let field_type = FieldType::from_bytes(reader, false, Option::from(nullable))?;
 
         let field_name_bytes = reader.read_bytes(name_size)?;
-
+        let encoding = Self::u8_to_encoding(encoding_bits)?;
         let field_name = FIELD_NAME_DECODER
             .decode(field_name_bytes, encoding)
             .unwrap();
@@ -191,12 +232,17 @@ impl FieldInfo {
             field_id: -1i16,
             field_name: field_name.original,
             field_type,
+            name_hash: None,
+            converts_integers: false,
         })
     }
 
-    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+    fn to_bytes(&self, field_name_hash: HashKind) -> Result<Vec<u8>, Error> {
         // field_bytes: | header | type_info | field_name |
         let mut writer = Writer::default();
+        if let Some(tag_id) = field_name_hash.hash(&self.field_name) {
+            return self.to_tag_id_bytes(writer, tag_id);
+        }
         // header: | field_name_encoding:2bits | size:4bits | nullability:1bit | ref_tracking:1bit |
         let meta_string =
             FIELD_NAME_ENCODER.encode_with_encodings(&self.field_name, FIELD_NAME_ENCODINGS)?;
@@ -225,11 +271,31 @@ impl FieldInfo {
         writer.write_bytes(name_encoded);
         Ok(writer.dump())
     }
+
+    fn to_tag_id_bytes(&self, mut writer: Writer, tag_id: u64) -> Result<Vec<u8>, Error> {
+        // field_bytes: | header | type_info |
+        // header: | TAG_ID:2bits | tag_id:4bits | nullability:1bit | ref_tracking:1bit |
+        let threshold = FIELD_NAME_SIZE_THRESHOLD as u64;
+        let mut header: u8 =
+            (FIELD_NAME_TAG_ID_ENCODING << 6) | ((min(threshold, tag_id) as u8) << 2);
+        let nullable = self.field_type.nullable;
+        if nullable {
+            header |= 2;
+        }
+        writer.write_u8(header);
+        if tag_id >= threshold {
+            writer.write_varuint64(tag_id - threshold);
+        }
+        self.field_type.to_bytes(&mut writer, false, nullable)?;
+        Ok(writer.dump())
+    }
 }
 
 impl PartialEq for FieldType {
     fn eq(&self, other: &Self) -> bool {
-        self.type_id == other.type_id && self.generics == other.generics
+        self.type_id == other.type_id
+            && self.length == other.length
+            && self.generics == other.generics
     }
 }
 
@@ -331,7 +397,7 @@ impl TypeMetaLayer {
         Self::read_name(reader, &TYPE_NAME_DECODER, TYPE_NAME_ENCODINGS)
     }
 
-    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+    fn to_bytes(&self, field_name_hash: HashKind) -> Result<Vec<u8>, Error> {
         // layer_bytes:| meta_header | fields meta |
         let mut writer = Writer::default();
         let num_fields = self.field_infos.len();
@@ -352,12 +418,12 @@ impl TypeMetaLayer {
             writer.write_varuint32(self.type_id);
         }
         for field in self.field_infos.iter() {
-            writer.write_bytes(field.to_bytes()?.as_slice());
+            writer.write_bytes(field.to_bytes(field_name_hash)?.as_slice());
         }
         Ok(writer.dump())
     }
 
-    fn sort_field_infos(field_infos: Vec<FieldInfo>) -> Vec<FieldInfo> {
+    pub(crate) fn sort_field_infos(field_infos: Vec<FieldInfo>) -> Vec<FieldInfo> {
         let fields_len = field_infos.len();
         // group
         let mut primitive_fields = Vec::new();
@@ -378,7 +444,7 @@ impl TypeMetaLayer {
 
             if PRIMITIVE_TYPES.contains(&type_id) {
                 primitive_fields.push(field_info);
-            } else if TypeId::LIST as u32 == type_id {
+            } else if TypeId::LIST as u32 == type_id || TypeId::FIXED_LIST as u32 == type_id {
                 list_fields.push(field_info);
             } else if TypeId::SET as u32 == type_id {
                 set_fields.push(field_info);
@@ -399,8 +465,10 @@ impl TypeMetaLayer {
                 TypeId::INT16 => 2,
                 TypeId::INT32 => 4,
                 TypeId::VAR_INT32 => 4,
+                TypeId::FIXED_INT32 => 4,
                 TypeId::INT64 => 8,
                 TypeId::VAR_INT64 => 8,
+                TypeId::FIXED_INT64 => 8,
                 TypeId::FLOAT16 => 2,
                 TypeId::FLOAT32 => 4,
                 TypeId::FLOAT64 => 8,
@@ -488,16 +556,22 @@ impl TypeMetaLayer {
         for _ in 0..num_fields {
             field_infos.push(FieldInfo::from_bytes(reader)?);
         }
-        let mut sorted_field_infos = Self::sort_field_infos(field_infos);
+        // hashed names cannot be sorted, but the writer wrote the fields in data order anyway
+        let mut sorted_field_infos = if field_infos.iter().any(|f| f.name_hash.is_some()) {
+            field_infos
+        } else {
+            Self::sort_field_infos(field_infos)
+        };
 
+        let field_name_hash = type_resolver.get_field_name_hash();
         if register_by_name {
             if let Some(type_info_current) =
                 type_resolver.get_type_info_by_name(&namespace.original, &type_name.original)
             {
-                Self::assign_field_ids(type_info_current, &mut sorted_field_infos);
+                Self::assign_field_ids(type_info_current, &mut sorted_field_infos, field_name_hash);
             }
         } else if let Some(type_info_current) = type_resolver.get_type_info_by_id(type_id) {
-            Self::assign_field_ids(type_info_current, &mut sorted_field_infos);
+            Self::assign_field_ids(type_info_current, &mut sorted_field_infos, field_name_hash);
         }
         // if no type found, keep all fields id as -1 to be skipped.
         Ok(TypeMetaLayer::new(
@@ -509,7 +583,11 @@ impl TypeMetaLayer {
         ))
     }
 
-    fn assign_field_ids(type_info_current: &TypeInfo, field_infos: &mut [FieldInfo]) {
+    fn assign_field_ids(
+        type_info_current: &TypeInfo,
+        field_infos: &mut [FieldInfo],
+        field_name_hash: HashKind,
+    ) {
         // convert to map: fiend_name -> field_info
         let field_info_map = type_info_current
             .get_type_meta()
@@ -517,11 +595,28 @@ impl TypeMetaLayer {
             .iter()
             .map(|field_info| (field_info.field_name.clone(), field_info.clone()))
             .collect::<HashMap<String, FieldInfo>>();
+        let hashed_names = field_info_map
+            .keys()
+            .filter_map(|name| Some((field_name_hash.hash(name)?, name)))
+            .collect::<HashMap<u64, &String>>();
         for field in field_infos.iter_mut() {
+            if let Some(name) = field.name_hash.and_then(|hash| hashed_names.get(&hash)) {
+                field.field_name = name.to_string();
+            }
             match field_info_map.get(&field.field_name.clone()) {
                 Some(local_field_info) => {
-                    if field.field_type.type_id != local_field_info.field_type.type_id
-                        || field.field_type.generics != local_field_info.field_type.generics
+                    // integers of another width keep the field id if the local field
+                    // converts them, so that it can decide whether to
+                    let convertible = |types: &[u32]| {
+                        types.contains(&field.field_type.type_id)
+                            && types.contains(&local_field_info.field_type.type_id)
+                    };
+                    let convertible = local_field_info.converts_integers
+                        && (convertible(&INTEGER_TYPES) || convertible(&INTEGER_ARRAY_TYPES));
+                    if !convertible
+                        && (field.field_type.type_id != local_field_info.field_type.type_id
+                            || field.field_type.length != local_field_info.field_type.length
+                            || field.field_type.generics != local_field_info.field_type.generics)
                     {
                         field.field_id = -1;
                     } else {
@@ -632,6 +727,23 @@ impl TypeMeta {
         })
     }
 
+    /// Returns a copy of this type meta whose field ids refer to the fields of
+    /// `type_info` instead of those of the type the data was written as.
+    pub(crate) fn project(&self, type_info: &TypeInfo, field_name_hash: HashKind) -> TypeMeta {
+        let mut field_infos = self.layer.field_infos.clone();
+        TypeMetaLayer::assign_field_ids(type_info, &mut field_infos, field_name_hash);
+        TypeMeta {
+            hash: self.hash,
+            layer: TypeMetaLayer::new(
+                self.layer.type_id,
+                self.layer.namespace.clone(),
+                self.layer.type_name.clone(),
+                self.layer.register_by_name,
+                field_infos,
+            ),
+        }
+    }
+
     pub fn skip_bytes(reader: &mut Reader, header: i64) -> Result<(), Error> {
         let mut meta_size = header & META_SIZE_MASK;
         if meta_size == META_SIZE_MASK {
@@ -641,13 +753,21 @@ impl TypeMeta {
     }
 
     pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.to_bytes_with_field_name_hash(HashKind::Off)
+    }
+
+    /// Writes the type meta with its field names hashed as `field_name_hash` requests.
+    pub(crate) fn to_bytes_with_field_name_hash(
+        &self,
+        field_name_hash: HashKind,
+    ) -> Result<Vec<u8>, Error> {
         // | global_binary_header | layers_bytes |
         let mut result = Writer::default();
         let mut layers_writer = Writer::default();
         // for layer in self.layers.iter() {
         //     layers_writer.bytes(layer.to_bytes()?.as_slice());
         // }
-        layers_writer.write_bytes(self.layer.to_bytes()?.as_slice());
+        layers_writer.write_bytes(self.layer.to_bytes(field_name_hash)?.as_slice());
         // global_binary_header:| hash:50bits | is_compressed:1bit | write_fields_meta:1bit | meta_size:12bits |
         let meta_size = layers_writer.len() as i64;
         let mut header: i64 = min(META_SIZE_MASK, meta_size);
@@ -0,0 +1,174 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Serialization into memory-mapped files.
+//!
+//! Enabled by the `mmap` feature. [`MmapWriter`] appends serialized values to a
+//! [`memmap2::MmapMut`], either a fixed-size map that errors once it is full, or a
+//! file-backed map that grows the file and remaps when more room is needed.
+//! Reading needs no special support: a [`memmap2::Mmap`] derefs to `&[u8]`, so it can
+//! be passed to [`Fory::deserialize`] or wrapped in a [`crate::buffer::Reader`] for a
+//! [`crate::resolver::context::ReadContext`].
+
+use crate::buffer::ByteWriter;
+use crate::error::Error;
+use crate::fory::Fory;
+use crate::serializer::Serializer;
+use memmap2::MmapMut;
+use std::fs::File;
+
+const MIN_GROW_SIZE: usize = 4096;
+
+pub struct MmapWriter {
+    mmap: MmapMut,
+    // `Some` if the map may grow by extending and remapping this file
+    file: Option<File>,
+    len: usize,
+}
+
+impl MmapWriter {
+    /// Creates a writer over a fixed-size map. Writes past its end return
+    /// [`Error::BufferOutOfBound`].
+    pub fn fixed(mmap: MmapMut) -> MmapWriter {
+        MmapWriter {
+            mmap,
+            file: None,
+            len: 0,
+        }
+    }
+
+    /// Creates a writer that maps `file` from its start and extends it as needed.
+    /// The file must be opened for reading and writing.
+    pub fn growable(file: File) -> Result<MmapWriter, Error> {
+        if file.metadata()?.len() == 0 {
+            file.set_len(MIN_GROW_SIZE as u64)?;
+        }
+        let mmap = unsafe { MmapMut::map_mut(&file)? };
+        Ok(MmapWriter {
+            mmap,
+            file: Some(file),
+            len: 0,
+        })
+    }
+
+    /// Number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Size of the current mapping.
+    pub fn capacity(&self) -> usize {
+        self.mmap.len()
+    }
+
+    /// The bytes written so far.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.mmap[..self.len]
+    }
+
+    /// Appends `bytes` to the map, growing it first if it is file-backed.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let end = self.len + bytes.len();
+        if end > self.mmap.len() {
+            self.grow(end)?;
+        }
+        self.mmap[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    /// Serializes `record` straight into the map after the bytes written so far.
+    ///
+    /// A value that outgrows the map is finished in a buffer of its own, then copied into
+    /// the grown map, or dropped with [`Error::BufferOutOfBound`] if the map is fixed-size.
+    ///
+    /// # Returns
+    ///
+    /// The offset of the value in the map, to be used for reading it back.
+    pub fn serialize<T: Serializer>(&mut self, fory: &Fory, record: &T) -> Result<usize, Error> {
+        let offset = self.len;
+        match fory.serialize_into_slice(record, &mut self.mmap[offset..])? {
+            Ok(len) => self.len += len,
+            Err(bytes) => self.write_bytes(&bytes)?,
+        }
+        Ok(offset)
+    }
+
+    /// Appends a slice of primitives to the map, copying the elements from `slice`
+    /// directly, see [`Fory::serialize_slice_to`].
+    ///
+    /// # Returns
+    ///
+    /// The offset of the value in the map, to be used for reading it back.
+    pub fn serialize_slice<T: Serializer + Copy>(
+        &mut self,
+        fory: &Fory,
+        slice: &[T],
+    ) -> Result<usize, Error> {
+        let offset = self.len;
+        fory.serialize_slice_to(slice, self)?;
+        Ok(offset)
+    }
+
+    /// Flushes the written bytes to the backing file.
+    pub fn flush(&self) -> Result<(), Error> {
+        Ok(self.mmap.flush_range(0, self.len)?)
+    }
+
+    /// Flushes the map and, if it is file-backed, truncates the file to the written length.
+    ///
+    /// # Returns
+    ///
+    /// The number of bytes written.
+    pub fn finish(self) -> Result<usize, Error> {
+        self.flush()?;
+        let MmapWriter { mmap, file, len } = self;
+        drop(mmap);
+        if let Some(file) = file {
+            file.set_len(len as u64)?;
+        }
+        Ok(len)
+    }
+
+    fn grow(&mut self, required: usize) -> Result<(), Error> {
+        let Some(file) = &self.file else {
+            return Err(Error::BufferOutOfBound(
+                self.len,
+                required - self.len,
+                self.mmap.len(),
+            ));
+        };
+        let new_len = required.max(self.mmap.len() * 2).max(MIN_GROW_SIZE);
+        file.set_len(new_len as u64)?;
+        self.mmap = unsafe { MmapMut::map_mut(file)? };
+        Ok(())
+    }
+}
+
+impl ByteWriter for MmapWriter {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        MmapWriter::write_bytes(self, bytes)
+    }
+
+    fn position(&self) -> usize {
+        self.len
+    }
+}
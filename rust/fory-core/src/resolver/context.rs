@@ -16,6 +16,7 @@
 // under the License.
 
 use crate::buffer::{Reader, Writer};
+use crate::ensure;
 
 use crate::error::Error;
 use crate::fory::Fory;
@@ -26,15 +27,51 @@ use crate::resolver::metastring_resolver::{
 };
 use crate::resolver::ref_resolver::{RefReader, RefWriter};
 use crate::resolver::type_resolver::{Harness, TypeResolver};
+use crate::serializer::cipher::Cipher;
+use crate::types::{Pod, TypeObserver, UnknownTypePolicy};
 use std::sync::{Arc, Mutex};
 
+/// The options of a [`Fory`] that a [`WriteContext`] needs, taken when the context is
+/// created.
+#[derive(Clone)]
+pub(crate) struct WriteConfig {
+    pub(crate) compatible: bool,
+    pub(crate) share_meta: bool,
+    pub(crate) compress_string: bool,
+    pub(crate) xlang: bool,
+    pub(crate) deterministic: bool,
+    pub(crate) sparse_null_encoding: bool,
+    pub(crate) map_key_prefix_sharing: bool,
+    pub(crate) dictionary_strings: bool,
+    pub(crate) canonical_nan: bool,
+    pub(crate) array_alignment: usize,
+    pub(crate) deque_capacity_hint: bool,
+    pub(crate) cipher: Option<Arc<dyn Cipher>>,
+}
+
+/// The options of a [`Fory`] that a [`ReadContext`] needs, taken when the context is
+/// created.
+#[derive(Clone)]
+pub(crate) struct ReadConfig {
+    pub(crate) compatible: bool,
+    pub(crate) share_meta: bool,
+    pub(crate) xlang: bool,
+    pub(crate) max_dyn_depth: u32,
+    pub(crate) max_box_depth: u32,
+    pub(crate) unknown_type_policy: UnknownTypePolicy,
+    pub(crate) type_observer: Option<TypeObserver>,
+    pub(crate) strict_flags: bool,
+    pub(crate) numeric_conversion: bool,
+    pub(crate) strict_numerics: bool,
+    pub(crate) max_ref_count: u32,
+    pub(crate) string_max_len: usize,
+    pub(crate) cipher: Option<Arc<dyn Cipher>>,
+}
+
 pub struct WriteContext {
     // Replicated environment fields (direct access, no Arc indirection for flags)
     type_resolver: TypeResolver,
-    compatible: bool,
-    share_meta: bool,
-    compress_string: bool,
-    xlang: bool,
+    config: WriteConfig,
 
     // Context-specific fields
     pub writer: Writer,
@@ -44,20 +81,14 @@ pub struct WriteContext {
 }
 
 impl WriteContext {
-    pub fn new(
+    pub(crate) fn new(
         writer: Writer,
         type_resolver: TypeResolver,
-        compatible: bool,
-        share_meta: bool,
-        compress_string: bool,
-        xlang: bool,
+        config: WriteConfig,
     ) -> WriteContext {
         WriteContext {
             type_resolver,
-            compatible,
-            share_meta,
-            compress_string,
-            xlang,
+            config,
             writer,
             meta_resolver: MetaWriterResolver::default(),
             meta_string_resolver: MetaStringWriterResolver::default(),
@@ -66,17 +97,11 @@ impl WriteContext {
     }
 
     pub fn new_from_fory(writer: Writer, fory: &Fory) -> WriteContext {
-        WriteContext {
-            type_resolver: fory.get_type_resolver().clone(),
-            compatible: fory.is_compatible(),
-            share_meta: fory.is_share_meta(),
-            compress_string: fory.is_compress_string(),
-            xlang: fory.is_xlang(),
+        WriteContext::new(
             writer,
-            meta_resolver: MetaWriterResolver::default(),
-            meta_string_resolver: MetaStringWriterResolver::default(),
-            ref_writer: RefWriter::new(),
-        }
+            fory.get_type_resolver().clone(),
+            fory.write_config(),
+        )
     }
 
     /// Get type resolver
@@ -88,25 +113,79 @@ impl WriteContext {
     /// Check if compatible mode is enabled
     #[inline(always)]
     pub fn is_compatible(&self) -> bool {
-        self.compatible
+        self.config.compatible
     }
 
     /// Check if meta sharing is enabled
     #[inline(always)]
     pub fn is_share_meta(&self) -> bool {
-        self.share_meta
+        self.config.share_meta
     }
 
     /// Check if string compression is enabled
     #[inline(always)]
     pub fn is_compress_string(&self) -> bool {
-        self.compress_string
+        self.config.compress_string
     }
 
     /// Check if cross-language mode is enabled
     #[inline(always)]
     pub fn is_xlang(&self) -> bool {
-        self.xlang
+        self.config.xlang
+    }
+
+    /// Check if deterministic mode is enabled
+    #[inline(always)]
+    pub fn is_deterministic(&self) -> bool {
+        self.config.deterministic
+    }
+
+    /// Check if sparse null encoding is enabled for collections of `Option`
+    #[inline(always)]
+    pub fn is_sparse_null_encoding(&self) -> bool {
+        self.config.sparse_null_encoding
+    }
+
+    /// Check if `BTreeMap<String, V>` keys are written with shared prefixes
+    #[inline(always)]
+    pub fn is_map_key_prefix_sharing(&self) -> bool {
+        self.config.map_key_prefix_sharing
+    }
+
+    /// Check if `Vec<String>` is written with a dictionary of its distinct strings
+    #[inline(always)]
+    pub fn is_dictionary_strings(&self) -> bool {
+        self.config.dictionary_strings
+    }
+
+    /// Check if NaN floats are written with a single canonical bit pattern
+    #[inline(always)]
+    pub fn is_canonical_nan(&self) -> bool {
+        self.config.canonical_nan
+    }
+
+    /// Get the alignment of primitive array payloads
+    #[inline(always)]
+    pub fn get_array_alignment(&self) -> usize {
+        self.config.array_alignment
+    }
+
+    /// Check if `VecDeque` capacities are written next to their elements
+    #[inline(always)]
+    pub fn is_deque_capacity_hint(&self) -> bool {
+        self.config.deque_capacity_hint
+    }
+
+    /// Get the cipher of the fields marked `#[fory(encrypt)]`
+    #[inline(always)]
+    pub fn get_cipher(&self) -> Option<&Arc<dyn Cipher>> {
+        self.config.cipher.as_ref()
+    }
+
+    /// Total number of bytes written into the context buffer since the last reset
+    #[inline(always)]
+    pub fn bytes_written(&self) -> usize {
+        self.writer.len()
     }
 
     #[inline(always)]
@@ -119,12 +198,23 @@ impl WriteContext {
         self.meta_resolver.push(type_id, &self.type_resolver)
     }
 
+    /// Registers the raw bytes of a type def taken from another stream and returns its
+    /// meta index in this one.
+    #[inline(always)]
+    pub fn push_raw_meta(&mut self, type_def: &[u8]) -> usize {
+        self.meta_resolver.push_raw(type_def)
+    }
+
+    /// Seeds the meta table with the types of an external schema, which are then referenced
+    /// by index but not written with the data.
+    pub fn seed_meta(&mut self, type_ids: &[std::any::TypeId]) -> Result<(), Error> {
+        self.meta_resolver.seed(type_ids, &self.type_resolver)
+    }
+
     #[inline(always)]
     pub fn write_meta(&mut self, offset: usize) {
-        self.writer.set_bytes(
-            offset,
-            &((self.writer.len() - offset - 4) as u32).to_le_bytes(),
-        );
+        self.writer
+            .patch_u32(offset, (self.writer.len() - offset - 4) as u32);
         self.meta_resolver.to_bytes(&mut self.writer);
     }
 
@@ -190,15 +280,31 @@ impl WriteContext {
         self.ref_writer.reset();
         self.writer.reset();
     }
+
+    /// Runs `write` against a fresh writer, ref table and meta tables, and returns the
+    /// bytes it wrote. Neither the data written before nor the data written after refers
+    /// to what `write` wrote, so the bytes stand on their own.
+    pub fn write_isolated(
+        &mut self,
+        write: impl FnOnce(&mut WriteContext) -> Result<(), Error>,
+    ) -> Result<Vec<u8>, Error> {
+        let writer = std::mem::take(&mut self.writer);
+        let meta_resolver = std::mem::take(&mut self.meta_resolver);
+        let meta_string_resolver = std::mem::take(&mut self.meta_string_resolver);
+        let ref_writer = std::mem::take(&mut self.ref_writer);
+        let result = write(self);
+        let scratch = std::mem::replace(&mut self.writer, writer);
+        self.meta_resolver = meta_resolver;
+        self.meta_string_resolver = meta_string_resolver;
+        self.ref_writer = ref_writer;
+        result.map(|_| scratch.bf.into_vec())
+    }
 }
 
 pub struct ReadContext {
     // Replicated environment fields (direct access, no Arc indirection for flags)
     type_resolver: TypeResolver,
-    compatible: bool,
-    share_meta: bool,
-    xlang: bool,
-    max_dyn_depth: u32,
+    config: ReadConfig,
 
     // Context-specific fields
     pub reader: Reader,
@@ -206,44 +312,31 @@ pub struct ReadContext {
     meta_string_resolver: MetaStringReaderResolver,
     pub ref_reader: RefReader,
     current_depth: u32,
+    box_depth: u32,
+    array_aligned: bool,
 }
 
 impl ReadContext {
-    pub fn new(
+    pub(crate) fn new(
         reader: Reader,
         type_resolver: TypeResolver,
-        compatible: bool,
-        share_meta: bool,
-        xlang: bool,
-        max_dyn_depth: u32,
+        config: ReadConfig,
     ) -> ReadContext {
         ReadContext {
             type_resolver,
-            compatible,
-            share_meta,
-            xlang,
-            max_dyn_depth,
+            config,
             reader,
             meta_resolver: MetaReaderResolver::default(),
             meta_string_resolver: MetaStringReaderResolver::default(),
             ref_reader: RefReader::new(),
             current_depth: 0,
+            box_depth: 0,
+            array_aligned: false,
         }
     }
 
     pub fn new_from_fory(reader: Reader, fory: &Fory) -> ReadContext {
-        ReadContext {
-            type_resolver: fory.get_type_resolver().clone(),
-            compatible: fory.is_compatible(),
-            share_meta: fory.is_share_meta(),
-            xlang: fory.is_xlang(),
-            max_dyn_depth: fory.get_max_dyn_depth(),
-            reader,
-            meta_resolver: MetaReaderResolver::default(),
-            meta_string_resolver: MetaStringReaderResolver::default(),
-            ref_reader: RefReader::new(),
-            current_depth: 0,
-        }
+        ReadContext::new(reader, fory.get_type_resolver().clone(), fory.read_config())
     }
 
     /// Get type resolver
@@ -255,32 +348,195 @@ impl ReadContext {
     /// Check if compatible mode is enabled
     #[inline(always)]
     pub fn is_compatible(&self) -> bool {
-        self.compatible
+        self.config.compatible
     }
 
     /// Check if meta sharing is enabled
     #[inline(always)]
     pub fn is_share_meta(&self) -> bool {
-        self.share_meta
+        self.config.share_meta
     }
 
     /// Check if cross-language mode is enabled
     #[inline(always)]
     pub fn is_xlang(&self) -> bool {
-        self.xlang
+        self.config.xlang
     }
 
     /// Get maximum dynamic depth
     #[inline(always)]
     pub fn max_dyn_depth(&self) -> u32 {
-        self.max_dyn_depth
+        self.config.max_dyn_depth
+    }
+
+    /// Get the policy for values of unregistered types in polymorphic reads
+    #[inline(always)]
+    pub fn get_unknown_type_policy(&self) -> UnknownTypePolicy {
+        self.config.unknown_type_policy
+    }
+
+    /// Check if unknown bits in flag types are rejected
+    #[inline(always)]
+    pub fn is_strict_flags(&self) -> bool {
+        self.config.strict_flags
+    }
+
+    /// Check if integer fields whose width changed are converted instead of skipped
+    #[inline(always)]
+    pub fn is_numeric_conversion(&self) -> bool {
+        self.config.numeric_conversion
+    }
+
+    #[inline(always)]
+    pub fn max_ref_count(&self) -> u32 {
+        self.config.max_ref_count
+    }
+
+    #[inline(always)]
+    pub fn string_max_len(&self) -> usize {
+        self.config.string_max_len
+    }
+
+    /// Whether the header of the input says primitive array payloads are padded
+    #[inline(always)]
+    pub fn is_array_aligned(&self) -> bool {
+        self.array_aligned
+    }
+
+    #[inline(always)]
+    pub(crate) fn set_array_aligned(&mut self, array_aligned: bool) {
+        self.array_aligned = array_aligned;
+    }
+
+    /// Get the cipher of the fields marked `#[fory(encrypt)]`
+    #[inline(always)]
+    pub fn get_cipher(&self) -> Option<&Arc<dyn Cipher>> {
+        self.config.cipher.as_ref()
+    }
+
+    /// Check if lossy integer conversions are rejected
+    #[inline(always)]
+    pub fn is_strict_numerics(&self) -> bool {
+        self.config.strict_numerics
+    }
+
+    /// Reads a type id and passes it to the type observer, if one is set.
+    #[inline(always)]
+    pub fn read_type_id(&mut self) -> Result<u32, Error> {
+        let type_id = self.reader.read_varuint32()?;
+        if let Some(observer) = &self.config.type_observer {
+            ensure!(
+                observer(type_id).is_continue(),
+                Error::TypeRejected(type_id)
+            );
+        }
+        Ok(type_id)
+    }
+
+    /// Advances the reader cursor by `n` bytes without copying them.
+    ///
+    /// Returns [`Error::UnexpectedEof`] if fewer than `n` bytes remain.
+    #[inline(always)]
+    pub fn skip_bytes(&mut self, n: usize) -> Result<(), Error> {
+        let remaining = self.reader.slice_after_cursor().len();
+        if n > remaining {
+            return Err(Error::UnexpectedEof(
+                format!(
+                    "cannot skip {} bytes, only {} bytes remaining",
+                    n, remaining
+                )
+                .into(),
+            ));
+        }
+        self.reader.skip(n)
+    }
+
+    /// Reads `len` little-endian values of `T` with a single bounds check and copy.
+    ///
+    /// Returns [`Error::UnexpectedEof`] if the buffer holds fewer than `len` values.
+    pub fn read_primitive_array<T: Pod>(&mut self, len: usize) -> Result<Vec<T>, Error> {
+        let mut vec = Vec::new();
+        self.read_primitive_array_into(len, &mut vec)?;
+        Ok(vec)
+    }
+
+    // `T` must be a `Pod` type or `bool`; `Vec<T>` only knows this at runtime.
+    pub(crate) fn read_primitive_array_into<T>(
+        &mut self,
+        len: usize,
+        vec: &mut Vec<T>,
+    ) -> Result<(), Error> {
+        let elem_size = std::mem::size_of::<T>();
+        let remaining = self.reader.slice_after_cursor().len();
+        let size_bytes = len
+            .checked_mul(elem_size)
+            .filter(|n| *n <= remaining)
+            .ok_or_else(|| {
+                Error::UnexpectedEof(
+                    format!(
+                        "cannot read {} values of {} bytes, only {} bytes remaining",
+                        len, elem_size, remaining
+                    )
+                    .into(),
+                )
+            })?;
+        let src = self.reader.read_bytes(size_bytes)?;
+        vec.clear();
+        vec.reserve_exact(len);
+        unsafe {
+            // `vec` is aligned for `T` while `src` may not be, so copy bytes.
+            let dst = vec.as_mut_ptr() as *mut u8;
+            std::ptr::copy_nonoverlapping(src.as_ptr(), dst, size_bytes);
+            #[cfg(target_endian = "big")]
+            std::slice::from_raw_parts_mut(dst, size_bytes)
+                .chunks_exact_mut(elem_size)
+                .for_each(|chunk| chunk.reverse());
+            vec.set_len(len);
+        }
+        Ok(())
     }
 
     #[inline(always)]
     pub fn init(&mut self, bytes: &[u8], max_dyn_depth: u32) {
         self.reader.init(bytes);
-        self.max_dyn_depth = max_dyn_depth;
+        self.config.max_dyn_depth = max_dyn_depth;
         self.current_depth = 0;
+        self.box_depth = 0;
+        self.array_aligned = false;
+    }
+
+    /// Returns the offset of the reader in the input.
+    #[inline(always)]
+    pub fn position(&self) -> usize {
+        self.reader.get_cursor()
+    }
+
+    /// Moves the reader to `offset` in the input, e.g. to re-read a value or to jump to
+    /// one whose offset is known.
+    ///
+    /// Returns [`Error::BufferOutOfBound`] if `offset` is past the end of the input.
+    ///
+    /// The ref table and the meta table are not rewound. A value read twice registers its
+    /// references twice, so that later back-references resolve to the wrong object, and a
+    /// value jumped to may refer to type metas or references that were never read. Seek
+    /// only between values that do not share references, in streams without meta sharing,
+    /// or reset the ref reader around the re-read.
+    #[inline(always)]
+    pub fn set_position(&mut self, offset: usize) -> Result<(), Error> {
+        self.reader.set_cursor(offset)
+    }
+
+    /// Reads the next `n` bytes as a view into the input instead of copying them.
+    ///
+    /// The view borrows the context, which does not own the input. To keep the bytes past
+    /// the next read, take them as a [`ByteView`](crate::serializer::borrowed::ByteView)
+    /// with [`Fory::deserialize_borrowed`](crate::fory::Fory::deserialize_borrowed), whose
+    /// lifetime is that of the input.
+    ///
+    /// Returns [`Error::BufferOutOfBound`] if fewer than `n` bytes are left.
+    #[inline(always)]
+    pub fn read_bytes_view(&mut self, n: usize) -> Result<&[u8], Error> {
+        self.reader.read_bytes(n)
     }
 
     #[inline(always)]
@@ -290,16 +546,32 @@ impl ReadContext {
 
     #[inline(always)]
     pub fn load_meta(&mut self, offset: usize) -> Result<usize, Error> {
+        self.meta_resolver.base_offset = self.reader.get_cursor() + offset;
         self.meta_resolver.load(
             &self.type_resolver,
             &mut Reader::new(&self.reader.slice_after_cursor()[offset..]),
         )
     }
 
+    /// Loads the type defs of an external schema ahead of the ones in the data, so that the
+    /// indices match a writer seeded with [`WriteContext::seed_meta`].
+    pub fn load_schema(&mut self, schema: &[u8]) -> Result<(), Error> {
+        self.meta_resolver
+            .load(&self.type_resolver, &mut Reader::new(schema))?;
+        Ok(())
+    }
+
+    /// Returns the encoded bytes of the type def at `type_index`, as they appeared in the
+    /// meta table of the stream being read.
+    pub fn get_raw_meta(&self, type_index: usize) -> Result<&[u8], Error> {
+        let (start, end) = self.meta_resolver.get_range(type_index);
+        Ok(&self.reader.get_slice()?[start..end])
+    }
+
     pub fn read_any_typeinfo(&mut self) -> Result<Arc<Harness>, Error> {
         use crate::types::TypeId as ForyTypeId;
 
-        let fory_type_id = self.reader.read_varuint32()?;
+        let fory_type_id = self.read_type_id()?;
 
         if fory_type_id == u32::MAX {
             let namespace = self.meta_resolver.read_metastring(&mut self.reader)?;
@@ -358,11 +630,44 @@ impl ReadContext {
         Ok(())
     }
 
+    /// Fails with [`Error::TooManyRefs`] if the ref table already holds `max_ref_count`
+    /// shared values. Checked before each shared value is added to the table.
+    #[inline(always)]
+    pub fn check_ref_count(&self) -> Result<(), Error> {
+        if self.ref_reader.ref_count() >= self.config.max_ref_count as usize {
+            return Err(Error::TooManyRefs(self.config.max_ref_count));
+        }
+        Ok(())
+    }
+
     #[inline(always)]
     pub fn dec_depth(&mut self) {
         self.current_depth = self.current_depth.saturating_sub(1);
     }
 
+    /// Counts one more `Box` being read inside the current one, failing with
+    /// [`Error::DepthExceed`] past `max_box_depth`. Balanced by [`Self::dec_box_depth`].
+    #[inline(always)]
+    pub fn inc_box_depth(&mut self) -> Result<(), Error> {
+        self.box_depth += 1;
+        if self.box_depth > self.config.max_box_depth {
+            return Err(Error::DepthExceed(
+                format!(
+                    "Maximum box nesting depth ({}) exceeded. \
+                    Consider increasing max_box_depth if this is expected.",
+                    self.config.max_box_depth
+                )
+                .into(),
+            ));
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn dec_box_depth(&mut self) {
+        self.box_depth = self.box_depth.saturating_sub(1);
+    }
+
     #[inline(always)]
     pub fn reset(&mut self) {
         self.reader.reset();
@@ -26,6 +26,9 @@ use std::sync::Arc;
 pub struct MetaWriterResolver {
     type_defs: Vec<Arc<Vec<u8>>>,
     type_id_index_map: HashMap<std::any::TypeId, usize>,
+    raw_index_map: HashMap<Vec<u8>, usize>,
+    // leading type defs shipped in an external schema rather than with the data
+    seeded: usize,
 }
 
 const MAX_PARSED_NUM_TYPE_DEFS: usize = 8192;
@@ -49,20 +52,47 @@ impl MetaWriterResolver {
         }
     }
 
+    pub fn push_raw(&mut self, type_def: &[u8]) -> usize {
+        if let Some(index) = self.raw_index_map.get(type_def) {
+            return *index;
+        }
+        let index = self.type_defs.len();
+        self.type_defs.push(Arc::new(type_def.to_vec()));
+        self.raw_index_map.insert(type_def.to_vec(), index);
+        index
+    }
+
+    /// Pushes the type defs of an external schema. They keep their indices but are left
+    /// out of [`MetaWriterResolver::to_bytes`], so only types the schema lacks are written.
+    pub fn seed(
+        &mut self,
+        type_ids: &[std::any::TypeId],
+        type_resolver: &TypeResolver,
+    ) -> Result<(), Error> {
+        for type_id in type_ids {
+            self.push(*type_id, type_resolver)?;
+        }
+        self.seeded = self.type_defs.len();
+        Ok(())
+    }
+
     pub fn to_bytes(&self, writer: &mut Writer) {
-        writer.write_varuint32(self.type_defs.len() as u32);
-        for item in &self.type_defs {
+        let type_defs = &self.type_defs[self.seeded..];
+        writer.write_varuint32(type_defs.len() as u32);
+        for item in type_defs {
             writer.write_bytes(item);
         }
     }
 
     pub fn empty(&mut self) -> bool {
-        self.type_defs.is_empty()
+        self.type_defs.len() == self.seeded
     }
 
     pub fn reset(&mut self) {
+        self.seeded = 0;
         self.type_defs.clear();
         self.type_id_index_map.clear();
+        self.raw_index_map.clear();
     }
 }
 
@@ -70,6 +100,9 @@ impl MetaWriterResolver {
 pub struct MetaReaderResolver {
     pub reading_type_defs: Vec<Arc<TypeMeta>>,
     parsed_type_defs: HashMap<i64, Arc<TypeMeta>>,
+    // absolute position of each reading type def in the input, for `RawValue`
+    reading_type_def_ranges: Vec<(usize, usize)>,
+    pub(crate) base_offset: usize,
 }
 
 impl MetaReaderResolver {
@@ -77,6 +110,10 @@ impl MetaReaderResolver {
         unsafe { self.reading_type_defs.get_unchecked(index) }
     }
 
+    pub fn get_range(&self, index: usize) -> (usize, usize) {
+        self.reading_type_def_ranges[index]
+    }
+
     pub fn load(
         &mut self,
         type_resolver: &TypeResolver,
@@ -85,6 +122,7 @@ impl MetaReaderResolver {
         let meta_size = reader.read_varuint32()?;
         // self.reading_type_defs.reserve(meta_size as usize);
         for _ in 0..meta_size {
+            let start = self.base_offset + reader.get_cursor();
             let meta_header = reader.read_i64()?;
             if let Some(type_meta) = self.parsed_type_defs.get(&meta_header) {
                 self.reading_type_defs.push(type_meta.clone());
@@ -101,6 +139,8 @@ impl MetaReaderResolver {
                 }
                 self.reading_type_defs.push(type_meta);
             }
+            self.reading_type_def_ranges
+                .push((start, self.base_offset + reader.get_cursor()));
         }
         Ok(reader.get_cursor())
     }
@@ -132,5 +172,7 @@ impl MetaReaderResolver {
 
     pub fn reset(&mut self) {
         self.reading_type_defs.clear();
+        self.reading_type_def_ranges.clear();
+        self.base_offset = 0;
     }
 }
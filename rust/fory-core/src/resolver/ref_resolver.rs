@@ -178,6 +178,11 @@ impl RefReader {
         Self::default()
     }
 
+    /// Returns the number of shared values stored or reserved so far.
+    pub fn ref_count(&self) -> usize {
+        self.refs.len()
+    }
+
     /// Reserve a reference ID slot without storing anything yet.
     ///
     /// Returns the reserved reference ID that will be used when storing the object later.
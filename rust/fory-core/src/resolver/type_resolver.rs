@@ -18,13 +18,15 @@
 use super::context::{ReadContext, WriteContext};
 use crate::error::Error;
 use crate::meta::{
-    FieldInfo, MetaString, TypeMeta, NAMESPACE_ENCODER, NAMESPACE_ENCODINGS, TYPE_NAME_ENCODER,
-    TYPE_NAME_ENCODINGS,
+    FieldInfo, MetaString, TypeMeta, TypeMetaLayer, NAMESPACE_ENCODER, NAMESPACE_ENCODINGS,
+    TYPE_NAME_ENCODER, TYPE_NAME_ENCODINGS,
 };
 use crate::serializer::{ForyDefault, Serializer, StructSerializer};
+use crate::types::{HashKind, TypeId};
 use crate::Reader;
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::{any::Any, collections::HashMap};
 
 type WriteFn = fn(&dyn Any, &mut WriteContext, is_field: bool) -> Result<(), Error>;
 type ReadFn =
@@ -124,6 +126,27 @@ impl TypeInfo {
         for (i, field_info) in sorted_field_infos.iter_mut().enumerate() {
             field_info.field_id = i as i16;
         }
+        // an enum written by variant name or fixed-width is recorded as a string or an
+        // integer, which moves its fields
+        if T::fory_sorts_fields_at_runtime() || type_resolver.has_retyped_enums() {
+            sorted_field_infos = TypeMetaLayer::sort_field_infos(sorted_field_infos);
+        }
+        let field_name_hash = type_resolver.get_field_name_hash();
+        let mut hashed_names = HashMap::new();
+        for field_info in sorted_field_infos.iter() {
+            let hash = field_name_hash.hash(&field_info.field_name);
+            if let Some(other) =
+                hash.and_then(|hash| hashed_names.insert(hash, &field_info.field_name))
+            {
+                return Err(Error::TypeError(
+                    format!(
+                        "fields `{other}` and `{}` of type `{type_name}` have the same {field_name_hash:?} name hash",
+                        field_info.field_name
+                    )
+                    .into(),
+                ));
+            }
+        }
         let type_meta = Arc::new(TypeMeta::from_fields(
             type_id,
             namespace_metastring.clone(),
@@ -131,7 +154,7 @@ impl TypeInfo {
             register_by_name,
             sorted_field_infos,
         ));
-        let type_def_bytes = type_meta.to_bytes()?;
+        let type_def_bytes = type_meta.to_bytes_with_field_name_hash(field_name_hash)?;
         Ok(TypeInfo {
             type_def: Arc::from(type_def_bytes),
             type_meta,
@@ -209,10 +232,25 @@ pub struct TypeResolver {
     type_info_map_by_name: HashMap<(String, String), TypeInfo>,
     // Fast lookup by numeric ID for common types
     type_id_index: Vec<u32>,
+    // `Arc<F>` of every registered handler, see `HandlerRef`
+    handlers: HashMap<u32, Arc<dyn Any + Send + Sync>>,
+    // registered static strings by id and by content, see the `Cow<'static, str>` serializer
+    static_strings: HashMap<u32, &'static str>,
+    static_string_ids: HashMap<&'static str, u32>,
+    // how the type meta of registered structs writes field names
+    field_name_hash: HashKind,
+    // enums written by variant name, see `Fory::register_enum_as_string`
+    string_enums: HashSet<std::any::TypeId>,
+    // fixed-width integer type ids of `#[fory(repr)]` enums, see `fory_repr_type_id`
+    repr_enums: HashMap<std::any::TypeId, u32>,
 }
 
 const NO_TYPE_ID: u32 = 1000000000;
 
+/// The `fory_type_index` of a derived generic struct, whose monomorphizations cannot
+/// share a slot in the fast lookup by index.
+pub const NO_TYPE_INDEX: u32 = u32::MAX;
+
 impl Default for TypeResolver {
     fn default() -> Self {
         let mut registry = TypeResolver {
@@ -224,6 +262,12 @@ impl Default for TypeResolver {
             type_info_map_by_id: HashMap::new(),
             type_info_map_by_name: HashMap::new(),
             type_id_index: Vec::new(),
+            handlers: HashMap::new(),
+            static_strings: HashMap::new(),
+            static_string_ids: HashMap::new(),
+            field_name_hash: HashKind::Off,
+            string_enums: HashSet::new(),
+            repr_enums: HashMap::new(),
         };
         registry.register_builtin_types().unwrap();
         registry
@@ -239,6 +283,140 @@ impl TypeResolver {
             ).into()))
     }
 
+    /// Returns the Rust type ids of all registered types that have a type def, i.e. not the
+    /// builtin ones, ordered by their Fory type id and name so that equally configured
+    /// resolvers agree on the order.
+    pub(crate) fn registered_type_ids(&self) -> Vec<std::any::TypeId> {
+        let mut entries: Vec<_> = self
+            .type_info_cache
+            .iter()
+            .filter(|(_, info)| !info.type_def.is_empty())
+            .collect();
+        entries.sort_by(|(_, a), (_, b)| {
+            (a.type_id, &a.namespace.original, &a.type_name.original).cmp(&(
+                b.type_id,
+                &b.namespace.original,
+                &b.type_name.original,
+            ))
+        });
+        entries.into_iter().map(|(type_id, _)| *type_id).collect()
+    }
+
+    pub(crate) fn register_handler(
+        &mut self,
+        id: u32,
+        handler: Arc<dyn Any + Send + Sync>,
+    ) -> Result<(), Error> {
+        if self.handlers.contains_key(&id) {
+            return Err(Error::TypeError(
+                format!("handler {} already registered", id).into(),
+            ));
+        }
+        self.handlers.insert(id, handler);
+        Ok(())
+    }
+
+    /// Returns the handler registered with `id` if it has type `F`.
+    pub fn get_handler<F: ?Sized + 'static>(&self, id: u32) -> Result<Arc<F>, Error> {
+        let handler = self.handlers.get(&id).ok_or_else(|| {
+            Error::TypeError(
+                format!("handler {} not found, maybe you forgot to register it", id).into(),
+            )
+        })?;
+        handler.downcast_ref::<Arc<F>>().cloned().ok_or_else(|| {
+            Error::TypeError(
+                format!("handler {} is not a {}", id, std::any::type_name::<F>()).into(),
+            )
+        })
+    }
+
+    pub(crate) fn register_static_string(
+        &mut self,
+        id: u32,
+        value: &'static str,
+    ) -> Result<(), Error> {
+        if id == u32::MAX {
+            return Err(Error::TypeError(
+                "static string ids must be below u32::MAX".into(),
+            ));
+        }
+        if self.static_strings.contains_key(&id) {
+            return Err(Error::TypeError(
+                format!("static string {} already registered", id).into(),
+            ));
+        }
+        if let Some(existing) = self.static_string_ids.get(value) {
+            return Err(Error::TypeError(
+                format!(
+                    "{:?} already registered as static string {}",
+                    value, existing
+                )
+                .into(),
+            ));
+        }
+        self.static_strings.insert(id, value);
+        self.static_string_ids.insert(value, id);
+        Ok(())
+    }
+
+    /// Returns how the type meta of registered structs writes field names.
+    pub fn get_field_name_hash(&self) -> HashKind {
+        self.field_name_hash
+    }
+
+    pub(crate) fn set_field_name_hash(&mut self, field_name_hash: HashKind) {
+        self.field_name_hash = field_name_hash;
+    }
+
+    pub(crate) fn register_enum_as_string(
+        &mut self,
+        rs_type_id: std::any::TypeId,
+        type_name: &str,
+    ) -> Result<(), Error> {
+        let type_id = self.get_type_info(rs_type_id)?.get_type_id() & 0xff;
+        if type_id != TypeId::ENUM as u32 && type_id != TypeId::NAMED_ENUM as u32 {
+            return Err(Error::TypeError(
+                format!("{type_name} is not registered as an enum").into(),
+            ));
+        }
+        self.string_enums.insert(rs_type_id);
+        Ok(())
+    }
+
+    /// Returns whether any enum is recorded in the type meta of structs as another type,
+    /// a string or a fixed-width integer, in which case structs with fields of user types
+    /// read and write them in the order of their type meta.
+    #[inline(always)]
+    pub fn has_retyped_enums(&self) -> bool {
+        !self.string_enums.is_empty() || !self.repr_enums.is_empty()
+    }
+
+    /// Returns the fixed-width integer type id of the `#[fory(repr)]` enum with
+    /// `rs_type_id`.
+    #[inline(always)]
+    pub fn get_repr_type_id(&self, rs_type_id: std::any::TypeId) -> Option<u32> {
+        if self.repr_enums.is_empty() {
+            return None;
+        }
+        self.repr_enums.get(&rs_type_id).copied()
+    }
+
+    /// Returns whether the enum with `rs_type_id` is written by variant name.
+    #[inline(always)]
+    pub fn is_enum_as_string(&self, rs_type_id: std::any::TypeId) -> bool {
+        !self.string_enums.is_empty() && self.string_enums.contains(&rs_type_id)
+    }
+
+    /// Returns the static string registered with `id`.
+    pub fn get_static_string(&self, id: u32) -> Option<&'static str> {
+        self.static_strings.get(&id).copied()
+    }
+
+    /// Returns the id that `value` is registered with as a static string.
+    pub fn get_static_string_id(&self, value: &str) -> Option<u32> {
+        self.static_string_ids.get(value).copied()
+    }
+
     pub fn get_type_info_by_id(&self, id: u32) -> Option<&TypeInfo> {
         self.type_info_map_by_id.get(&id)
     }
@@ -306,7 +484,6 @@ impl TypeResolver {
     }
 
     fn register_builtin_types(&mut self) -> Result<(), Error> {
-        use crate::types::TypeId;
         let namespace = NAMESPACE_ENCODER.encode_with_encodings("", NAMESPACE_ENCODINGS)?;
         let type_name = TYPE_NAME_ENCODER.encode_with_encodings("", TYPE_NAME_ENCODINGS)?;
 
@@ -429,15 +606,21 @@ impl TypeResolver {
         self.type_info_cache.insert(rs_type_id, type_info.clone());
         self.type_info_map_by_id
             .insert(type_info.type_id, type_info.clone());
-        let index = T::fory_type_index() as usize;
-        if index >= self.type_id_index.len() {
-            self.type_id_index.resize(index + 1, NO_TYPE_ID);
-        } else if self.type_id_index[index] != NO_TYPE_ID {
-            return Err(Error::TypeError(
-                format!("please:{:?} already registered", type_info.type_id).into(),
-            ));
+        if let Some(repr_type_id) = T::fory_repr_type_id() {
+            self.repr_enums.insert(rs_type_id, repr_type_id);
+        }
+        let index = T::fory_type_index();
+        if index != NO_TYPE_INDEX {
+            let index = index as usize;
+            if index >= self.type_id_index.len() {
+                self.type_id_index.resize(index + 1, NO_TYPE_ID);
+            } else if self.type_id_index[index] != NO_TYPE_ID {
+                return Err(Error::TypeError(
+                    format!("please:{:?} already registered", type_info.type_id).into(),
+                ));
+            }
+            self.type_id_index[index] = type_info.type_id;
         }
-        self.type_id_index[index] = type_info.type_id;
 
         if type_info.register_by_name {
             let namespace = &type_info.namespace;
@@ -15,15 +15,160 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::ensure;
 use crate::error::Error;
+use crate::meta::FieldType;
 use crate::resolver::context::{ReadContext, WriteContext};
 use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::skip::skip_struct_data;
 use crate::serializer::{ForyDefault, Serializer};
-use crate::types::RefFlag;
+use crate::types::{RefFlag, TypeId, UnknownTypePolicy, BASIC_TYPES, CONTAINER_TYPES};
 use std::any::Any;
 use std::rc::Rc;
 use std::sync::Arc;
 
+/// A polymorphic value of an unregistered type, kept as raw bytes under
+/// [`UnknownTypePolicy::Preserve`].
+///
+/// Writing it back into a `Box<dyn Any>`, `Rc<dyn Any>` or `Arc<dyn Any>` slot reproduces
+/// the original type id, type meta and data bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawValue {
+    type_id: u32,
+    type_def: Vec<u8>,
+    data: Vec<u8>,
+}
+
+impl RawValue {
+    /// Returns the fory type id the value was written with.
+    pub fn type_id(&self) -> u32 {
+        self.type_id
+    }
+
+    /// Returns the encoded type def of the value.
+    pub fn type_def(&self) -> &[u8] {
+        &self.type_def
+    }
+
+    /// Returns the encoded field data of the value.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    fn write(&self, context: &mut WriteContext) -> Result<(), Error> {
+        ensure!(
+            context.is_share_meta(),
+            Error::TypeError("RawValue can only be written with meta sharing enabled".into())
+        );
+        context.writer.write_varuint32(self.type_id);
+        let meta_index = context.push_raw_meta(&self.type_def);
+        context.writer.write_varuint32(meta_index as u32);
+        context.writer.write_bytes(&self.data);
+        Ok(())
+    }
+}
+
+// Whether the data of a field of this type can be copied between streams as is. Struct
+// and ext fields refer to the meta table of the stream they were read from.
+fn is_self_contained(field_type: &FieldType) -> bool {
+    match TypeId::try_from(field_type.type_id as i16) {
+        Ok(type_id) if BASIC_TYPES.contains(&type_id) || type_id == TypeId::NAMED_ENUM => true,
+        Ok(type_id) if CONTAINER_TYPES.contains(&type_id) => {
+            field_type.generics.iter().all(is_self_contained)
+        }
+        _ => field_type.type_id & 0xff == TypeId::ENUM as u32,
+    }
+}
+
+fn read_unknown_data(context: &mut ReadContext) -> Result<Box<dyn Any>, Error> {
+    let policy = context.get_unknown_type_policy();
+    read_unknown_data_with(context, policy)
+}
+
+/// Reads the type info and data of a value of an unregistered type under `policy`, which
+/// must not be [`UnknownTypePolicy::Error`].
+pub(crate) fn read_unknown_data_with(
+    context: &mut ReadContext,
+    policy: UnknownTypePolicy,
+) -> Result<Box<dyn Any>, Error> {
+    let type_id = context.read_type_id()?;
+    let internal_id = type_id & 0xff;
+    let has_meta = internal_id == TypeId::COMPATIBLE_STRUCT as u32
+        || internal_id == TypeId::NAMED_COMPATIBLE_STRUCT as u32
+        || (internal_id == TypeId::NAMED_STRUCT as u32 && context.is_share_meta());
+    ensure!(
+        has_meta,
+        Error::TypeError(format!("type {type_id} is not registered").into())
+    );
+    let meta_index = context.reader.read_varuint32()? as usize;
+    let type_meta = context.get_meta(meta_index).clone();
+    if policy == UnknownTypePolicy::Preserve {
+        for field_info in type_meta.get_field_infos().iter() {
+            ensure!(
+                is_self_contained(&field_info.field_type),
+                Error::TypeError(
+                    format!(
+                        "cannot preserve type {type_id}: field {} refers to other types",
+                        field_info.field_name
+                    )
+                    .into()
+                )
+            );
+        }
+    }
+    let data_start = context.reader.get_cursor();
+    skip_struct_data(context, &type_meta)?;
+    if policy == UnknownTypePolicy::Skip {
+        return Ok(Box::new(()));
+    }
+    let data = context.reader.get_slice()?[data_start..context.reader.get_cursor()].to_vec();
+    Ok(Box::new(RawValue {
+        type_id,
+        type_def: context.get_raw_meta(meta_index)?.to_vec(),
+        data,
+    }))
+}
+
+/// Reads the type info and data of a polymorphic value, applying the context's
+/// [`UnknownTypePolicy`] when the type is not registered.
+fn read_any_data(context: &mut ReadContext) -> Result<Box<dyn Any>, Error> {
+    let reset_cursor = context.reader.reset_cursor_to_here();
+    match context.read_any_typeinfo() {
+        Ok(harness) => harness.get_read_data_fn()(context, true),
+        Err(Error::TypeError(_))
+            if context.get_unknown_type_policy() != UnknownTypePolicy::Error =>
+        {
+            reset_cursor(&mut context.reader);
+            read_unknown_data(context)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes the type info and data of a polymorphic value. A [`RawValue`] is written back
+/// exactly as it was read.
+fn write_any_data(
+    value: &dyn Any,
+    context: &mut WriteContext,
+    is_field: bool,
+) -> Result<(), Error> {
+    if let Some(raw) = value.downcast_ref::<RawValue>() {
+        return raw.write(context);
+    }
+    let harness = context.write_any_typeinfo(value.type_id())?;
+    let serializer_fn = harness.get_write_data_fn();
+    serializer_fn(value, context, is_field)
+}
+
+fn any_type_id(value: &dyn Any, type_resolver: &TypeResolver) -> Result<u32, Error> {
+    if let Some(raw) = value.downcast_ref::<RawValue>() {
+        return Ok(raw.type_id);
+    }
+    type_resolver
+        .get_fory_type_id(value.type_id())
+        .ok_or_else(|| Error::TypeError("Type not registered".into()))
+}
+
 /// Helper function to serialize a `Box<dyn Any>`
 pub fn serialize_any_box(
     any_box: &Box<dyn Any>,
@@ -31,11 +176,7 @@ pub fn serialize_any_box(
     is_field: bool,
 ) -> Result<(), Error> {
     context.writer.write_i8(RefFlag::NotNullValue as i8);
-
-    let concrete_type_id = (**any_box).type_id();
-    let harness = context.write_any_typeinfo(concrete_type_id)?;
-    let serializer_fn = harness.get_write_data_fn();
-    serializer_fn(&**any_box, context, is_field)
+    write_any_data(&**any_box, context, is_field)
 }
 
 /// Helper function to deserialize to `Box<dyn Any>`
@@ -47,9 +188,7 @@ pub fn deserialize_any_box(context: &mut ReadContext) -> Result<Box<dyn Any>, Er
             "Expected NotNullValue for Box<dyn Any>".into(),
         ));
     }
-    let harness = context.read_any_typeinfo()?;
-    let deserializer_fn = harness.get_read_data_fn();
-    let result = deserializer_fn(context, true);
+    let result = read_any_data(context);
     context.dec_depth();
     result
 }
@@ -61,6 +200,7 @@ impl ForyDefault for Box<dyn Any> {
 }
 
 impl Serializer for Box<dyn Any> {
+    crate::read_null_as_default!();
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         serialize_any_box(self, context, is_field)
     }
@@ -82,16 +222,17 @@ impl Serializer for Box<dyn Any> {
     }
 
     fn fory_type_id_dyn(&self, type_resolver: &TypeResolver) -> Result<u32, Error> {
-        let concrete_type_id = (**self).type_id();
-        type_resolver
-            .get_fory_type_id(concrete_type_id)
-            .ok_or_else(|| Error::TypeError("Type not registered".into()))
+        any_type_id(&**self, type_resolver)
     }
 
     fn fory_is_polymorphic() -> bool {
         true
     }
 
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
     fn fory_write_type_info(_context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
         // Rc<dyn Any> is polymorphic - type info is written per element
         Ok(())
@@ -114,16 +255,14 @@ impl ForyDefault for Rc<dyn Any> {
 }
 
 impl Serializer for Rc<dyn Any> {
+    crate::read_null_as_default!();
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         if !context
             .ref_writer
             .try_write_rc_ref(&mut context.writer, self)
         {
-            let concrete_type_id = (**self).type_id();
-            let harness = context.write_any_typeinfo(concrete_type_id)?;
-            let serializer_fn = harness.get_write_data_fn();
-            serializer_fn(&**self, context, is_field)?
-        };
+            write_any_data(&**self, context, is_field)?;
+        }
         Ok(())
     }
 
@@ -149,17 +288,14 @@ impl Serializer for Rc<dyn Any> {
             }
             RefFlag::NotNullValue => {
                 context.inc_depth()?;
-                let harness = context.read_any_typeinfo()?;
-                let deserializer_fn = harness.get_read_data_fn();
-                let boxed = deserializer_fn(context, true)?;
+                let boxed = read_any_data(context)?;
                 context.dec_depth();
                 Ok(Rc::<dyn Any>::from(boxed))
             }
             RefFlag::RefValue => {
+                context.check_ref_count()?;
                 context.inc_depth()?;
-                let harness = context.read_any_typeinfo()?;
-                let deserializer_fn = harness.get_read_data_fn();
-                let boxed = deserializer_fn(context, true)?;
+                let boxed = read_any_data(context)?;
                 context.dec_depth();
                 let rc: Rc<dyn Any> = Rc::from(boxed);
                 context.ref_reader.store_rc_ref(rc.clone());
@@ -177,16 +313,17 @@ impl Serializer for Rc<dyn Any> {
     }
 
     fn fory_type_id_dyn(&self, type_resolver: &TypeResolver) -> Result<u32, Error> {
-        let concrete_type_id = (**self).type_id();
-        type_resolver
-            .get_fory_type_id(concrete_type_id)
-            .ok_or_else(|| Error::TypeError("Type not registered".into()))
+        any_type_id(&**self, type_resolver)
     }
 
     fn fory_is_polymorphic() -> bool {
         true
     }
 
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
     fn fory_write_type_info(_context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
         // Rc<dyn Any> is polymorphic - type info is written per element
         Ok(())
@@ -209,15 +346,13 @@ impl ForyDefault for Arc<dyn Any> {
 }
 
 impl Serializer for Arc<dyn Any> {
+    crate::read_null_as_default!();
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         if !context
             .ref_writer
             .try_write_arc_ref(&mut context.writer, self)
         {
-            let concrete_type_id = (**self).type_id();
-            let harness = context.write_any_typeinfo(concrete_type_id)?;
-            let serializer_fn = harness.get_write_data_fn();
-            serializer_fn(&**self, context, is_field)?;
+            write_any_data(&**self, context, is_field)?;
         }
         Ok(())
     }
@@ -244,17 +379,14 @@ impl Serializer for Arc<dyn Any> {
             }
             RefFlag::NotNullValue => {
                 context.inc_depth()?;
-                let harness = context.read_any_typeinfo()?;
-                let deserializer_fn = harness.get_read_data_fn();
-                let boxed = deserializer_fn(context, true)?;
+                let boxed = read_any_data(context)?;
                 context.dec_depth();
                 Ok(Arc::<dyn Any>::from(boxed))
             }
             RefFlag::RefValue => {
+                context.check_ref_count()?;
                 context.inc_depth()?;
-                let harness = context.read_any_typeinfo()?;
-                let deserializer_fn = harness.get_read_data_fn();
-                let boxed = deserializer_fn(context, true)?;
+                let boxed = read_any_data(context)?;
                 context.dec_depth();
                 let arc: Arc<dyn Any> = Arc::from(boxed);
                 context.ref_reader.store_arc_ref(arc.clone());
@@ -272,16 +404,17 @@ impl Serializer for Arc<dyn Any> {
     }
 
     fn fory_type_id_dyn(&self, type_resolver: &TypeResolver) -> Result<u32, Error> {
-        let concrete_type_id = (**self).type_id();
-        type_resolver
-            .get_fory_type_id(concrete_type_id)
-            .ok_or_else(|| Error::TypeError("Type not registered".into()))
+        any_type_id(&**self, type_resolver)
     }
 
     fn fory_is_polymorphic() -> bool {
         true
     }
 
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
     fn fory_write_type_info(_context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
         // Arc<dyn Any> is polymorphic - type info is written per element
         Ok(())
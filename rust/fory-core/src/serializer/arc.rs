@@ -18,15 +18,21 @@
 use crate::error::Error;
 use crate::resolver::context::{ReadContext, WriteContext};
 use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::string::write_str_data;
 use crate::serializer::{ForyDefault, Serializer};
-use crate::types::RefFlag;
+use crate::types::{RefFlag, TypeId};
 use std::sync::Arc;
 
 impl<T: Serializer + ForyDefault + Send + Sync + 'static> Serializer for Arc<T> {
+    crate::read_null_as_default!();
     fn fory_is_shared_ref() -> bool {
         true
     }
 
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         if !context
             .ref_writer
@@ -67,6 +73,7 @@ impl<T: Serializer + ForyDefault + Send + Sync + 'static> Serializer for Arc<T>
                 Arc::new(inner)
             }
             RefFlag::RefValue => {
+                context.check_ref_count()?;
                 let ref_id = context.ref_reader.reserve_ref_id();
                 let inner = T::fory_read_data(context, is_field)?;
                 let arc = Arc::new(inner);
@@ -110,3 +117,91 @@ impl<T: ForyDefault> ForyDefault for Arc<T> {
         Arc::new(T::fory_default())
     }
 }
+
+/// Shares the string like `Arc<String>` does, so clones of one `Arc<str>` are written once
+/// and read back as clones of one allocation. A string written without a ref id, such as
+/// by a peer that does not track it, is read as a fresh allocation.
+impl Serializer for Arc<str> {
+    crate::read_null_as_default!();
+
+    fn fory_is_shared_ref() -> bool {
+        true
+    }
+
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
+    fn fory_write(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        if !context
+            .ref_writer
+            .try_write_arc_ref(&mut context.writer, self)
+        {
+            write_str_data(self, context)?
+        };
+        Ok(())
+    }
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        self.fory_write(context, is_field)
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        String::fory_write_type_info(context, is_field)
+    }
+
+    fn fory_read(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        let ref_flag = context.ref_reader.read_ref_flag(&mut context.reader)?;
+
+        Ok(match ref_flag {
+            RefFlag::Null => Err(Error::InvalidRef("Arc cannot be null".into()))?,
+            RefFlag::Ref => {
+                let ref_id = context.ref_reader.read_ref_id(&mut context.reader)?;
+                context
+                    .ref_reader
+                    .get_arc_ref::<str>(ref_id)
+                    .ok_or(Error::InvalidData(
+                        format!("Arc reference {ref_id} not found").into(),
+                    ))?
+            }
+            RefFlag::NotNullValue => Arc::from(String::fory_read_data(context, is_field)?),
+            RefFlag::RefValue => {
+                context.check_ref_count()?;
+                let ref_id = context.ref_reader.reserve_ref_id();
+                let arc: Arc<str> = Arc::from(String::fory_read_data(context, is_field)?);
+                context.ref_reader.store_arc_ref_at(ref_id, arc.clone());
+                arc
+            }
+        })
+    }
+
+    fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        String::fory_read_type_info(context, is_field)
+    }
+
+    fn fory_reserved_space() -> usize {
+        4
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::STRING as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::STRING as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl ForyDefault for Arc<str> {
+    fn fory_default() -> Self {
+        Arc::from("")
+    }
+}
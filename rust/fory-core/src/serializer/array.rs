@@ -0,0 +1,172 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::ensure;
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_ref_info_data, write_ref_info_data, ForyDefault, Serializer};
+use crate::types::TypeId;
+
+/// Writes the type info of a fixed-size array: its type id followed by its length `N`,
+/// which its data goes without.
+fn write_fixed_type_info(
+    context: &mut WriteContext,
+    is_field: bool,
+    type_id: TypeId,
+    len: usize,
+) -> Result<(), Error> {
+    if is_field {
+        return Ok(());
+    }
+    context.writer.write_varuint32(type_id as u32);
+    context.writer.write_varuint32(len as u32);
+    Ok(())
+}
+
+/// Reads the type info written by [`write_fixed_type_info`], checking that the remote
+/// array has the local length `N`.
+fn read_fixed_type_info(
+    context: &mut ReadContext,
+    is_field: bool,
+    type_id: TypeId,
+    len: usize,
+) -> Result<(), Error> {
+    if is_field {
+        return Ok(());
+    }
+    let local_type_id = type_id as u32;
+    let remote_type_id = context.read_type_id()?;
+    ensure!(
+        local_type_id == remote_type_id,
+        Error::TypeMismatch(local_type_id, remote_type_id)
+    );
+    let remote_len = context.reader.read_varuint32()? as usize;
+    ensure!(
+        remote_len == len,
+        Error::InvalidData(format!("expected an array of length {len}, found {remote_len}").into())
+    );
+    Ok(())
+}
+
+// Written as `FIXED_BINARY`: exactly the `N` bytes, copied in one go both ways. The type
+// info or the type meta of a field records `N` instead of a length prefix.
+impl<const N: usize> Serializer for [u8; N] {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        context.writer.write_bytes(self);
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let mut bytes = [0u8; N];
+        bytes.copy_from_slice(context.reader.read_bytes(N)?);
+        Ok(bytes)
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        context.skip_bytes(N)
+    }
+
+    fn fory_reserved_space() -> usize {
+        N
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::FIXED_BINARY as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::FIXED_BINARY as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_fixed_type_info(context, is_field, TypeId::FIXED_BINARY, N)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_fixed_type_info(context, is_field, TypeId::FIXED_BINARY, N)
+    }
+}
+
+impl<const N: usize> ForyDefault for [u8; N] {
+    fn fory_default() -> Self {
+        [0; N]
+    }
+}
+
+// Written as `FIXED_LIST`: the `N` elements one after the other, each like an
+// `Option<T>` field, with a null or not-null flag before the value. The length is
+// recorded like that of a `[u8; N]`.
+impl<T: Serializer + ForyDefault, const N: usize> Serializer for [Option<T>; N] {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        for element in self {
+            write_ref_info_data(element, context, true, false, false)?;
+        }
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let mut elements = Self::fory_default();
+        for element in elements.iter_mut() {
+            *element = read_ref_info_data(context, true, false, false)?;
+        }
+        Ok(elements)
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        Self::fory_read_data(context, true).map(|_| ())
+    }
+
+    fn fory_reserved_space() -> usize {
+        N * (1 + T::fory_reserved_space())
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::FIXED_LIST as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::FIXED_LIST as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_fixed_type_info(context, is_field, TypeId::FIXED_LIST, N)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_fixed_type_info(context, is_field, TypeId::FIXED_LIST, N)
+    }
+}
+
+impl<T: ForyDefault, const N: usize> ForyDefault for [Option<T>; N] {
+    fn fory_default() -> Self {
+        std::array::from_fn(|_| None)
+    }
+}
@@ -0,0 +1,125 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `ArrayVec` support, enabled by the `arrayvec` feature.
+//!
+//! An `ArrayVec<T, N>` is written exactly like a `Vec<T>`, so either can be read as the
+//! other. The capacity is not written: reading a payload with more than `N` elements fails
+//! with [`Error::CapacityExceeded`] before any element is read.
+
+use crate::ensure;
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::collection::{read_collection_without_default, write_collection};
+use crate::serializer::list::check_primitive;
+use crate::serializer::{primitive_list, read_ref_info_data_into, Clear, ForyDefault, Serializer};
+use arrayvec::ArrayVec;
+
+/// Returns the number of elements of the `Vec<T>` payload at the cursor without consuming
+/// it. Primitive arrays lead with their size in bytes, other lists with their length.
+fn peek_len<T: 'static>(context: &mut ReadContext) -> Result<usize, Error> {
+    let restore = context.reader.reset_cursor_to_here();
+    let len = context.reader.read_varuint32()? as usize;
+    restore(&mut context.reader);
+    Ok(match check_primitive::<T>() {
+        Some(_) => len / std::mem::size_of::<T>(),
+        None => len,
+    })
+}
+
+fn read_array_vec<T: Serializer, const N: usize>(
+    context: &mut ReadContext,
+) -> Result<ArrayVec<T, N>, Error> {
+    let len = peek_len::<T>(context)?;
+    ensure!(len <= N, Error::CapacityExceeded(len, N));
+    match check_primitive::<T>() {
+        Some(_) => Ok(primitive_list::fory_read_data::<T>(context)?
+            .into_iter()
+            .collect()),
+        None => read_collection_without_default(context),
+    }
+}
+
+impl<T: Serializer, const N: usize> Serializer for ArrayVec<T, N> {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        match check_primitive::<T>() {
+            Some(_) => primitive_list::fory_write_data(self.as_slice(), context),
+            None => write_collection(self.iter(), context, is_field),
+        }
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        Vec::<T>::fory_write_type_info(context, is_field)
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        read_array_vec(context)
+    }
+
+    fn fory_read_data_into(
+        &mut self,
+        context: &mut ReadContext,
+        _is_field: bool,
+    ) -> Result<(), Error> {
+        *self = read_array_vec(context)?;
+        Ok(())
+    }
+
+    fn fory_read_into(&mut self, context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_ref_info_data_into(self, context, is_field, false, false)
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        Vec::<T>::fory_skip(context)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        Vec::<T>::fory_read_type_info(context, is_field)
+    }
+
+    fn fory_reserved_space() -> usize {
+        Vec::<T>::fory_reserved_space()
+    }
+
+    fn fory_get_type_id(type_resolver: &TypeResolver) -> Result<u32, Error> {
+        Vec::<T>::fory_get_type_id(type_resolver)
+    }
+
+    fn fory_type_id_dyn(&self, type_resolver: &TypeResolver) -> Result<u32, Error> {
+        Vec::<T>::fory_get_type_id(type_resolver)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<T, const N: usize> ForyDefault for ArrayVec<T, N> {
+    fn fory_default() -> Self {
+        ArrayVec::new()
+    }
+}
+
+impl<T, const N: usize> Clear for ArrayVec<T, N> {
+    fn clear(&mut self) {
+        ArrayVec::clear(self)
+    }
+}
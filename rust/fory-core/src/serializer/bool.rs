@@ -24,6 +24,8 @@ use crate::types::TypeId;
 use std::mem;
 
 impl Serializer for bool {
+    crate::read_null_as_default!();
+
     #[inline(always)]
     fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
         context.writer.write_u8(if *self { 1 } else { 0 });
@@ -35,6 +37,11 @@ impl Serializer for bool {
         Ok(context.reader.read_u8()? == 1)
     }
 
+    #[inline(always)]
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        context.skip_bytes(1)
+    }
+
     #[inline(always)]
     fn fory_reserved_space() -> usize {
         mem::size_of::<i32>()
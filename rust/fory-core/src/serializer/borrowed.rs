@@ -0,0 +1,234 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::serializer::collection::read_byte_list_len;
+use crate::serializer::string::check_string_len;
+use crate::types::{RefFlag, TypeId};
+use std::borrow::Cow;
+#[cfg(unix)]
+use std::ffi::OsStr;
+
+/// Deserialization into values that borrow from the input buffer instead of copying it.
+///
+/// The input is passed to every call next to the context reading it, so an
+/// implementation returns subslices of `input` and the borrow checker keeps the
+/// value from outliving the buffer. Implement it for a struct by reading each field
+/// with [`BorrowedSerializer::fory_read_borrowed`] in declaration order, and read it
+/// with [`Fory::deserialize_borrowed`](crate::fory::Fory::deserialize_borrowed).
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::Fory;
+///
+/// let fory = Fory::default();
+/// let bytes = fory.serialize(&"hello".to_string()).unwrap();
+/// let s: &str = fory.deserialize_borrowed(&bytes).unwrap();
+/// assert_eq!(s, "hello");
+/// ```
+pub trait BorrowedSerializer<'a>: Sized {
+    /// The type id this value is written with.
+    fn fory_type_id() -> TypeId;
+
+    /// Reads the data of the value, borrowing from `input`, the buffer `context` reads.
+    fn fory_read_borrowed_data(context: &mut ReadContext, input: &'a [u8]) -> Result<Self, Error>;
+
+    /// Reads the ref flag, the type id and the data of a non-null value.
+    fn fory_read_borrowed(context: &mut ReadContext, input: &'a [u8]) -> Result<Self, Error> {
+        read_not_null_flag(context)?;
+        let local_type_id = Self::fory_type_id() as u32;
+        let remote_type_id = context.read_type_id()?;
+        if local_type_id != remote_type_id {
+            return Err(Error::TypeMismatch(local_type_id, remote_type_id));
+        }
+        Self::fory_read_borrowed_data(context, input)
+    }
+}
+
+fn read_not_null_flag(context: &mut ReadContext) -> Result<(), Error> {
+    let ref_flag = context.reader.read_i8()?;
+    if ref_flag != RefFlag::NotNullValue as i8 && ref_flag != RefFlag::RefValue as i8 {
+        return Err(Error::InvalidData(
+            format!(
+                "borrowed values cannot be null or shared, got ref flag {}",
+                ref_flag
+            )
+            .into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Advances the reader past `len` bytes and returns them as a subslice of `input`.
+fn borrow_bytes<'a>(
+    context: &mut ReadContext,
+    input: &'a [u8],
+    len: usize,
+) -> Result<&'a [u8], Error> {
+    let view = context.read_bytes_view(len)?;
+    // the view only lives as long as the borrow of the context, so find it in `input`
+    let start = (view.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    input
+        .get(start..start.wrapping_add(len))
+        .filter(|bytes| bytes.as_ptr() == view.as_ptr())
+        .ok_or_else(|| Error::InvalidData("the input is not the buffer the context reads".into()))
+}
+
+/// Binary data viewed in place in the input buffer, e.g. a large `Vec<u8>` read without
+/// copying it.
+///
+/// It is read from what `Vec<u8>` writes, a list of bytes, and from the `BINARY` types such
+/// as the byte slices of [`Fory::serialize_slice_to`](crate::fory::Fory::serialize_slice_to).
+/// It can only be read with [`Fory::deserialize_borrowed`](crate::fory::Fory::deserialize_borrowed) or
+/// as a field of a [`BorrowedSerializer`], so that the view cannot outlive the input.
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::serializer::borrowed::ByteView;
+/// use fory_core::Fory;
+///
+/// let fory = Fory::default();
+/// let bytes = fory.serialize(&vec![7u8; 1024]).unwrap();
+/// let view: ByteView = fory.deserialize_borrowed(&bytes).unwrap();
+/// assert_eq!(view.len(), 1024);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ByteView<'a>(&'a [u8]);
+
+impl<'a> ByteView<'a> {
+    /// Returns the viewed bytes, borrowed for the lifetime of the input.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
+impl std::ops::Deref for ByteView<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.0
+    }
+}
+
+impl<'a> BorrowedSerializer<'a> for ByteView<'a> {
+    fn fory_type_id() -> TypeId {
+        TypeId::BINARY
+    }
+
+    /// Reads `BINARY` data. [`BorrowedSerializer::fory_read_borrowed`] also accepts a list.
+    fn fory_read_borrowed_data(context: &mut ReadContext, input: &'a [u8]) -> Result<Self, Error> {
+        <&'a [u8]>::fory_read_borrowed_data(context, input).map(ByteView)
+    }
+
+    fn fory_read_borrowed(context: &mut ReadContext, input: &'a [u8]) -> Result<Self, Error> {
+        read_not_null_flag(context)?;
+        let remote_type_id = context.read_type_id()?;
+        if remote_type_id == TypeId::LIST as u32 {
+            let len = read_byte_list_len(context)?;
+            borrow_bytes(context, input, len).map(ByteView)
+        } else if remote_type_id == TypeId::BINARY as u32 {
+            Self::fory_read_borrowed_data(context, input)
+        } else {
+            Err(Error::TypeMismatch(TypeId::BINARY as u32, remote_type_id))
+        }
+    }
+}
+
+impl<'a> BorrowedSerializer<'a> for Cow<'a, str> {
+    fn fory_type_id() -> TypeId {
+        TypeId::STRING
+    }
+
+    /// Borrows UTF-8 and ASCII-only latin1 payloads, and decodes the others into an owned
+    /// string.
+    fn fory_read_borrowed_data(context: &mut ReadContext, input: &'a [u8]) -> Result<Self, Error> {
+        let bitor = context.reader.read_varuint36small()?;
+        check_string_len(context, bitor >> 2)?;
+        let len = (bitor >> 2) as usize;
+        match bitor & 0b11 {
+            // latin1
+            0 => {
+                let bytes = borrow_bytes(context, input, len)?;
+                if bytes.is_ascii() {
+                    // ASCII is valid UTF-8
+                    Ok(Cow::Borrowed(std::str::from_utf8(bytes).unwrap()))
+                } else {
+                    Ok(Cow::Owned(bytes.iter().map(|&b| b as char).collect()))
+                }
+            }
+            // utf16
+            1 => Ok(Cow::Owned(context.reader.read_utf16_string(len)?)),
+            // utf8
+            2 => {
+                let bytes = borrow_bytes(context, input, len)?;
+                std::str::from_utf8(bytes)
+                    .map(Cow::Borrowed)
+                    .map_err(|e| Error::EncodingError(e.to_string().into()))
+            }
+            encoding => Err(Error::EncodingError(
+                format!("wrong encoding value: {}", encoding).into(),
+            )),
+        }
+    }
+}
+
+impl<'a> BorrowedSerializer<'a> for &'a str {
+    fn fory_type_id() -> TypeId {
+        TypeId::STRING
+    }
+
+    /// Fails with [`Error::EncodingError`] on strings that cannot be borrowed, i.e.
+    /// UTF-16 payloads and latin1 payloads with non-ASCII characters. Read those as
+    /// `Cow<str>`.
+    fn fory_read_borrowed_data(context: &mut ReadContext, input: &'a [u8]) -> Result<Self, Error> {
+        match Cow::<'a, str>::fory_read_borrowed_data(context, input)? {
+            Cow::Borrowed(s) => Ok(s),
+            Cow::Owned(_) => Err(Error::EncodingError(
+                "the string is not stored as UTF-8 and cannot be borrowed, read it as Cow<str>"
+                    .into(),
+            )),
+        }
+    }
+}
+
+impl<'a> BorrowedSerializer<'a> for &'a [u8] {
+    fn fory_type_id() -> TypeId {
+        TypeId::BINARY
+    }
+
+    fn fory_read_borrowed_data(context: &mut ReadContext, input: &'a [u8]) -> Result<Self, Error> {
+        let len = context.reader.read_varuint32()? as usize;
+        borrow_bytes(context, input, len)
+    }
+}
+
+/// Reads the raw bytes written for an `OsString`, e.g. a path, without copying them.
+/// Only Unix, where an `OsStr` is any byte sequence, can reference them in place.
+#[cfg(unix)]
+impl<'a> BorrowedSerializer<'a> for &'a OsStr {
+    fn fory_type_id() -> TypeId {
+        TypeId::BINARY
+    }
+
+    fn fory_read_borrowed_data(context: &mut ReadContext, input: &'a [u8]) -> Result<Self, Error> {
+        use std::os::unix::ffi::OsStrExt;
+        <&'a [u8]>::fory_read_borrowed_data(context, input).map(OsStr::from_bytes)
+    }
+}
@@ -0,0 +1,89 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::ops::Bound;
+
+const INCLUDED_TAG: u32 = 0;
+const EXCLUDED_TAG: u32 = 1;
+const UNBOUNDED_TAG: u32 = 2;
+
+/// `Bound` is written as a varuint32 tag (0 = `Included`, 1 = `Excluded`,
+/// 2 = `Unbounded`), followed by the payload with its own ref and type info
+/// for the bounded variants.
+impl<T: Serializer + ForyDefault> Serializer for Bound<T> {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        match self {
+            Bound::Included(v) => {
+                context.writer.write_varuint32(INCLUDED_TAG);
+                v.fory_write(context, false)
+            }
+            Bound::Excluded(v) => {
+                context.writer.write_varuint32(EXCLUDED_TAG);
+                v.fory_write(context, false)
+            }
+            Bound::Unbounded => {
+                context.writer.write_varuint32(UNBOUNDED_TAG);
+                Ok(())
+            }
+        }
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        match context.reader.read_varuint32()? {
+            INCLUDED_TAG => Ok(Bound::Included(T::fory_read(context, false)?)),
+            EXCLUDED_TAG => Ok(Bound::Excluded(T::fory_read(context, false)?)),
+            UNBOUNDED_TAG => Ok(Bound::Unbounded),
+            tag => Err(Error::InvalidData(
+                format!("unknown Bound tag: {}", tag).into(),
+            )),
+        }
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<T> ForyDefault for Bound<T> {
+    fn fory_default() -> Self {
+        Bound::Unbounded
+    }
+}
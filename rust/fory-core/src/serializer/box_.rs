@@ -22,8 +22,13 @@ use crate::resolver::type_resolver::TypeResolver;
 use crate::serializer::{ForyDefault, Serializer};
 
 impl<T: Serializer + ForyDefault> Serializer for Box<T> {
+    crate::read_null_as_default!();
+
     fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
-        Ok(Box::new(T::fory_read_data(context, is_field)?))
+        context.inc_box_depth()?;
+        let value = T::fory_read_data(context, is_field)?;
+        context.dec_box_depth();
+        Ok(Box::new(value))
     }
 
     fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
@@ -42,6 +47,14 @@ impl<T: Serializer + ForyDefault> Serializer for Box<T> {
         T::fory_reserved_space()
     }
 
+    fn fory_is_option() -> bool {
+        T::fory_is_option()
+    }
+
+    fn fory_is_none(&self) -> bool {
+        (**self).fory_is_none()
+    }
+
     fn fory_get_type_id(type_resolver: &TypeResolver) -> Result<u32, Error> {
         T::fory_get_type_id(type_resolver)
     }
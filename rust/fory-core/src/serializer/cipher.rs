@@ -0,0 +1,102 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Encryption of the struct fields marked `#[fory(encrypt)]`.
+//!
+//! Such a field is serialized into a buffer of its own, which is encrypted with the
+//! [`Cipher`] set by [`Fory::with_cipher`](crate::fory::Fory::with_cipher) and written as a
+//! not-null `BINARY` value: a ref flag, then a `varuint32` length followed by the
+//! ciphertext, so that a peer without the field skips it. Reading decrypts the ciphertext
+//! and reads the field from the plaintext, which must be consumed entirely. The plaintext
+//! buffers are zeroed once done with.
+
+use crate::buffer::Reader;
+use crate::ensure;
+use crate::error::Error;
+use crate::resolver::context::{ReadContext, WriteContext};
+use crate::types::RefFlag;
+use std::sync::atomic::{compiler_fence, Ordering};
+use std::sync::Arc;
+
+/// Encrypts and decrypts the serialized bytes of the fields marked `#[fory(encrypt)]`.
+pub trait Cipher: Send + Sync {
+    /// Returns the ciphertext of `plaintext`, which may be longer than it.
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error>;
+
+    /// Returns the plaintext of `ciphertext`. Fails, e.g. with [`Error::Cipher`], if it was
+    /// not produced by [`Cipher::encrypt`] with the same key.
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+fn cipher_of(cipher: Option<&Arc<dyn Cipher>>) -> Result<Arc<dyn Cipher>, Error> {
+    cipher
+        .cloned()
+        .ok_or_else(|| Error::Cipher("an encrypted field needs Fory::with_cipher".into()))
+}
+
+/// Overwrites `bytes` with zeros in a way the compiler cannot elide as a dead store.
+fn zeroize(bytes: &mut [u8]) {
+    for byte in bytes.iter_mut() {
+        // SAFETY: `byte` is a valid and aligned reference
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Writes what `write` writes as an encrypted field.
+pub fn write_encrypted(
+    context: &mut WriteContext,
+    write: impl FnOnce(&mut WriteContext) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let cipher = cipher_of(context.get_cipher())?;
+    let outer = std::mem::take(&mut context.writer);
+    let result = write(context);
+    let mut plaintext = std::mem::replace(&mut context.writer, outer);
+    let ciphertext = result.and_then(|()| cipher.encrypt(&plaintext.bf));
+    zeroize(&mut plaintext.bf);
+    let ciphertext = ciphertext?;
+    context.writer.write_i8(RefFlag::NotNullValue as i8);
+    context.writer.write_varuint32(ciphertext.len() as u32);
+    context.writer.write_bytes(&ciphertext);
+    Ok(())
+}
+
+/// Reads an encrypted field written by [`write_encrypted`] with `read`.
+pub fn read_encrypted<T>(
+    context: &mut ReadContext,
+    read: impl FnOnce(&mut ReadContext) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let cipher = cipher_of(context.get_cipher())?;
+    let ref_flag = context.reader.read_i8()?;
+    ensure!(
+        ref_flag == RefFlag::NotNullValue as i8,
+        Error::InvalidRef(format!("unexpected ref flag {} of an encrypted field", ref_flag).into())
+    );
+    let len = context.reader.read_varuint32()? as usize;
+    let mut plaintext = cipher.decrypt(context.reader.read_bytes(len)?)?;
+    let outer = std::mem::replace(&mut context.reader, Reader::new(&plaintext));
+    let result = read(context);
+    let inner = std::mem::replace(&mut context.reader, outer);
+    let trailing = !inner.slice_after_cursor().is_empty();
+    zeroize(&mut plaintext);
+    let value = result?;
+    ensure!(
+        !trailing,
+        Error::Cipher("trailing bytes after an encrypted field".into())
+    );
+    Ok(value)
+}
@@ -0,0 +1,67 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Framing of the struct fields marked `#[fory(with = "module")]`.
+//!
+//! Nothing is known about what the module writes, so it is written into a buffer of its
+//! own and framed as a not-null `BINARY` value: a ref flag, then a `varuint32` length
+//! followed by the bytes, so that a peer without the field skips it. Reading hands the
+//! module only those bytes, which it must consume entirely.
+
+use crate::buffer::Reader;
+use crate::ensure;
+use crate::error::Error;
+use crate::resolver::context::{ReadContext, WriteContext};
+use crate::types::RefFlag;
+
+/// Writes what `write` writes as a codec field.
+pub fn write_with(
+    context: &mut WriteContext,
+    write: impl FnOnce(&mut WriteContext) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let outer = std::mem::take(&mut context.writer);
+    let result = write(context);
+    let inner = std::mem::replace(&mut context.writer, outer);
+    result?;
+    context.writer.write_i8(RefFlag::NotNullValue as i8);
+    context.writer.write_varuint32(inner.bf.len() as u32);
+    context.writer.write_bytes(&inner.bf);
+    Ok(())
+}
+
+/// Reads a codec field written by [`write_with`] with `read`.
+pub fn read_with<T>(
+    context: &mut ReadContext,
+    read: impl FnOnce(&mut ReadContext) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let ref_flag = context.reader.read_i8()?;
+    ensure!(
+        ref_flag == RefFlag::NotNullValue as i8,
+        Error::InvalidRef(format!("unexpected ref flag {} of a codec field", ref_flag).into())
+    );
+    let len = context.reader.read_varuint32()? as usize;
+    let inner = Reader::new(context.reader.read_bytes(len)?);
+    let outer = std::mem::replace(&mut context.reader, inner);
+    let result = read(context);
+    let inner = std::mem::replace(&mut context.reader, outer);
+    let value = result?;
+    ensure!(
+        inner.slice_after_cursor().is_empty(),
+        Error::InvalidData("trailing bytes after a codec field".into())
+    );
+    Ok(value)
+}
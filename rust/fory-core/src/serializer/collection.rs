@@ -19,19 +19,142 @@ use crate::ensure;
 use crate::error::Error;
 use crate::resolver::context::ReadContext;
 use crate::resolver::context::WriteContext;
-use crate::serializer::{ForyDefault, Serializer};
+use crate::serializer::string::write_str_data;
+use crate::serializer::{
+    read_ref_info_data_or, read_ref_info_data_without_default, ForyDefault, Serializer,
+};
 use crate::types::PRIMITIVE_ARRAY_TYPES;
+use std::collections::{HashMap, VecDeque};
 
 // const TRACKING_REF: u8 = 0b1;
 
 pub const HAS_NULL: u8 = 0b10;
 
 // Whether collection elements type is declare type.
-const DECL_ELEMENT_TYPE: u8 = 0b100;
+pub(crate) const DECL_ELEMENT_TYPE: u8 = 0b100;
 
 //  Whether collection elements type same.
 pub const IS_SAME_TYPE: u8 = 0b1000;
 
+// Whether nulls are written as a bitmap followed by only the present elements.
+pub const SPARSE_NULL: u8 = 0b10000;
+
+// Whether the elements are strings written as a dictionary of the distinct values followed
+// by the dictionary index of every element.
+pub const DICTIONARY: u8 = 0b100000;
+
+// Whether the capacity of the written collection follows the header as a varuint32, see
+// `Fory::with_deque_capacity_hint`.
+pub const CAPACITY_HINT: u8 = 0b1000000;
+
+// Bitmap with bit `i` set for every null element, written after the element type info.
+fn write_null_bitmap<'a, T, I>(iter: I, len: usize, context: &mut WriteContext)
+where
+    T: Serializer + 'a,
+    I: Iterator<Item = &'a T>,
+{
+    let mut bitmap = vec![0u8; (len + 7) / 8];
+    for (i, item) in iter.enumerate() {
+        if item.fory_is_none() {
+            bitmap[i / 8] |= 1 << (i % 8);
+        }
+    }
+    context.writer.write_bytes(&bitmap);
+}
+
+pub fn read_null_bitmap(context: &mut ReadContext, len: usize) -> Result<Vec<u8>, Error> {
+    Ok(context.reader.read_bytes((len + 7) / 8)?.to_vec())
+}
+
+#[inline(always)]
+pub fn is_null_in_bitmap(bitmap: &[u8], i: usize) -> bool {
+    bitmap[i / 8] & (1 << (i % 8)) != 0
+}
+
+/// Writes `strings` as a list whose elements are indices into a dictionary of the distinct
+/// strings, in order of first appearance. See
+/// [`Fory::with_dictionary_strings`](crate::fory::Fory::with_dictionary_strings).
+pub fn write_string_dictionary(
+    strings: &[String],
+    context: &mut WriteContext,
+    is_field: bool,
+) -> Result<(), Error> {
+    context.writer.write_varuint32(strings.len() as u32);
+    if strings.is_empty() {
+        return Ok(());
+    }
+    let mut header = IS_SAME_TYPE | DICTIONARY;
+    if is_field {
+        header |= DECL_ELEMENT_TYPE;
+    }
+    context.writer.write_u8(header);
+    String::fory_write_type_info(context, is_field)?;
+    let mut dictionary: Vec<&str> = Vec::new();
+    let mut index_of: HashMap<&str, u32> = HashMap::new();
+    let indices: Vec<u32> = strings
+        .iter()
+        .map(|s| {
+            *index_of.entry(s).or_insert_with(|| {
+                dictionary.push(s);
+                dictionary.len() as u32 - 1
+            })
+        })
+        .collect();
+    context.writer.write_varuint32(dictionary.len() as u32);
+    for s in dictionary {
+        write_str_data(s, context)?;
+    }
+    for index in indices {
+        context.writer.write_varuint32(index);
+    }
+    Ok(())
+}
+
+/// Reads the dictionary and the indices that follow the header of a list written by
+/// [`write_string_dictionary`], passing each element to `push`.
+pub(crate) fn read_string_dictionary<T: 'static>(
+    context: &mut ReadContext,
+    len: usize,
+    mut push: impl FnMut(T),
+) -> Result<(), Error> {
+    ensure!(
+        std::any::TypeId::of::<T>() == std::any::TypeId::of::<String>(),
+        Error::TypeError("a dictionary-encoded list can only be read as strings".into())
+    );
+    let dictionary_len = context.reader.read_varuint32()? as usize;
+    let mut dictionary = Vec::with_capacity(capped_len(context, dictionary_len.min(len)));
+    for _ in 0..dictionary_len {
+        dictionary.push(String::fory_read_data(context, true)?);
+    }
+    for _ in 0..len {
+        let index = context.reader.read_varuint32()? as usize;
+        let s = dictionary.get(index).ok_or_else(|| {
+            Error::InvalidData(
+                format!(
+                    "dictionary index {} out of {} strings",
+                    index, dictionary_len
+                )
+                .into(),
+            )
+        })?;
+        let value: Box<dyn std::any::Any> = Box::new(s.clone());
+        push(*value.downcast::<T>().unwrap());
+    }
+    Ok(())
+}
+
+/// Skips the dictionary and the indices of a list written by [`write_string_dictionary`].
+pub fn skip_string_dictionary(context: &mut ReadContext, len: usize) -> Result<(), Error> {
+    let dictionary_len = context.reader.read_varuint32()?;
+    for _ in 0..dictionary_len {
+        String::fory_skip(context)?;
+    }
+    for _ in 0..len {
+        context.reader.read_varuint32()?;
+    }
+    Ok(())
+}
+
 pub fn write_collection_type_info(
     context: &mut WriteContext,
     is_field: bool,
@@ -50,6 +173,22 @@ pub fn write_collection<'a, T, I>(
     context: &mut WriteContext,
     is_field: bool,
 ) -> Result<(), Error>
+where
+    T: Serializer + 'a,
+    I: IntoIterator<Item = &'a T>,
+    I::IntoIter: ExactSizeIterator + Clone,
+{
+    write_collection_with_capacity_hint(iter, None, context, is_field)
+}
+
+/// Like [`write_collection`], with `capacity`, if any, written after the header of a
+/// non-empty collection and flagged with [`CAPACITY_HINT`].
+pub fn write_collection_with_capacity_hint<'a, T, I>(
+    iter: I,
+    capacity: Option<usize>,
+    context: &mut WriteContext,
+    is_field: bool,
+) -> Result<(), Error>
 where
     T: Serializer + 'a,
     I: IntoIterator<Item = &'a T>,
@@ -73,19 +212,38 @@ where
             }
         }
     }
+    let sparse_null = has_null
+        && context.is_sparse_null_encoding()
+        && !T::fory_is_polymorphic()
+        && !T::fory_is_shared_ref();
     if has_null {
         header |= HAS_NULL;
     }
+    if sparse_null {
+        header |= SPARSE_NULL;
+    }
     if is_field {
         header |= DECL_ELEMENT_TYPE;
     }
     if is_same_type {
         header |= IS_SAME_TYPE;
     }
+    if capacity.is_some() {
+        header |= CAPACITY_HINT;
+    }
     context.writer.write_u8(header);
+    if let Some(capacity) = capacity {
+        context.writer.write_varuint32(capacity as u32);
+    }
     T::fory_write_type_info(context, is_field)?;
     // context.writer.reserve((T::reserved_space() + SIZE_OF_REF_AND_TYPE) * len);
-    if T::fory_is_polymorphic() || T::fory_is_shared_ref() {
+    if sparse_null {
+        write_null_bitmap(iter.clone(), len, context);
+        for item in iter.filter(|item| !item.fory_is_none()) {
+            crate::serializer::write_ref_info_data(item, context, is_field, true, true)?;
+        }
+        Ok(())
+    } else if T::fory_is_polymorphic() || T::fory_is_shared_ref() {
         // TOTO: make it xlang compatible
         for item in iter {
             item.fory_write(context, is_field)?;
@@ -101,6 +259,56 @@ where
     }
 }
 
+/// Like [`write_collection`], but for iterators whose length is not known up front.
+///
+/// Elements are written as they are produced and the count is patched in afterwards, so
+/// nothing is buffered. The count takes a fixed 5 bytes and every element of an `Option`
+/// type carries its own null flag, since neither is known before the iterator ends.
+pub fn write_collection_streaming<T, I>(
+    iter: I,
+    context: &mut WriteContext,
+    is_field: bool,
+) -> Result<(), Error>
+where
+    T: Serializer,
+    I: IntoIterator<Item = T>,
+{
+    let mut iter = iter.into_iter().peekable();
+    if iter.peek().is_none() {
+        context.writer.write_varuint32(0);
+        return Ok(());
+    }
+    let len_offset = context.writer.reserve_placeholder(5);
+    let mut header = 0;
+    let is_same_type = !T::fory_is_polymorphic();
+    let has_null = T::fory_is_option();
+    if has_null {
+        header |= HAS_NULL;
+    }
+    if is_field {
+        header |= DECL_ELEMENT_TYPE;
+    }
+    if is_same_type {
+        header |= IS_SAME_TYPE;
+    }
+    context.writer.write_u8(header);
+    T::fory_write_type_info(context, is_field)?;
+    let mut len: u32 = 0;
+    for item in iter {
+        if T::fory_is_polymorphic() || T::fory_is_shared_ref() {
+            item.fory_write(context, is_field)?;
+        } else {
+            let skip_ref_flag = is_same_type && !has_null;
+            crate::serializer::write_ref_info_data(&item, context, is_field, skip_ref_flag, true)?;
+        }
+        len = len.checked_add(1).ok_or_else(|| {
+            Error::EncodeError("collection has more than u32::MAX elements".into())
+        })?;
+    }
+    context.writer.patch_varuint32(len_offset, len);
+    Ok(())
+}
+
 pub fn read_collection_type_info(
     context: &mut ReadContext,
     is_field: bool,
@@ -109,7 +317,7 @@ pub fn read_collection_type_info(
     if is_field {
         return Ok(());
     }
-    let remote_collection_type_id = context.reader.read_varuint32()?;
+    let remote_collection_type_id = context.read_type_id()?;
     if PRIMITIVE_ARRAY_TYPES.contains(&remote_collection_type_id) {
         return Err(Error::TypeError(
             "Vec<number> belongs to the `number_array` type, \
@@ -125,10 +333,93 @@ pub fn read_collection_type_info(
     Ok(())
 }
 
+/// Caps a length read from the input by the bytes left in it, so that a forged length
+/// cannot make the reader reserve more memory than the input warrants. Elements other
+/// than sparse nulls take at least a byte each, so an honest length is not capped.
+#[inline(always)]
+pub(crate) fn capped_len(context: &ReadContext, len: usize) -> usize {
+    len.min(context.reader.slice_after_cursor().len())
+}
+
+/// Reads the capacity hint that follows `header`, or returns 0 if it has none.
+pub fn read_capacity_hint(context: &mut ReadContext, header: u8) -> Result<usize, Error> {
+    if header & CAPACITY_HINT == 0 {
+        return Ok(0);
+    }
+    Ok(context.reader.read_varuint32()? as usize)
+}
+
+/// Reads the element type info that follows the header, if the writer wrote it once for
+/// all elements. This follows the header's `IS_SAME_TYPE` flag rather than what `T` would
+/// write, so a peer whose element type was polymorphic, with every element carrying its
+/// own type info, can still be read into a monomorphic `T`.
+fn read_elements_type_info<T: Serializer>(
+    context: &mut ReadContext,
+    header: u8,
+) -> Result<(), Error> {
+    let declared = (header & DECL_ELEMENT_TYPE) != 0;
+    let is_same_type = (header & IS_SAME_TYPE) != 0;
+    if T::fory_is_polymorphic() {
+        ensure!(
+            !is_same_type,
+            Error::TypeError(
+                "cannot read a collection written with one element type into polymorphic elements"
+                    .into()
+            )
+        );
+        T::fory_read_type_info(context, declared)
+    } else if is_same_type {
+        T::fory_read_type_info(context, declared)
+    } else {
+        Ok(())
+    }
+}
+
+/// Reads the length, header and element type info of a `Vec<u8>` and returns the length,
+/// leaving the reader at its elements. Without nulls they are the raw bytes, one per
+/// element, so that the caller can borrow them in place.
+pub(crate) fn read_byte_list_len(context: &mut ReadContext) -> Result<usize, Error> {
+    let len = context.reader.read_varuint32()? as usize;
+    if len == 0 {
+        return Ok(0);
+    }
+    let header = context.reader.read_u8()?;
+    ensure!(
+        header & (HAS_NULL | SPARSE_NULL | DICTIONARY) == 0 && header & IS_SAME_TYPE != 0,
+        Error::InvalidData(
+            format!("a list with header {header:#b} does not store its bytes contiguously").into()
+        )
+    );
+    read_capacity_hint(context, header)?;
+    read_elements_type_info::<u8>(context, header)?;
+    Ok(len)
+}
+
 pub fn read_collection<C, T>(context: &mut ReadContext) -> Result<C, Error>
 where
     T: Serializer + ForyDefault,
     C: FromIterator<T>,
+{
+    read_collection_or(context, || Ok(T::fory_default()))
+}
+
+/// Like [`read_collection`], for elements that may not implement [`ForyDefault`]. A null
+/// element is read with [`Serializer::fory_read_null`].
+pub fn read_collection_without_default<C, T>(context: &mut ReadContext) -> Result<C, Error>
+where
+    T: Serializer,
+    C: FromIterator<T>,
+{
+    read_collection_or(context, T::fory_read_null)
+}
+
+fn read_collection_or<C, T>(
+    context: &mut ReadContext,
+    null: fn() -> Result<T, Error>,
+) -> Result<C, Error>
+where
+    T: Serializer,
+    C: FromIterator<T>,
 {
     let len = context.reader.read_varuint32()?;
     if len == 0 {
@@ -136,18 +427,167 @@ where
     }
     let header = context.reader.read_u8()?;
     let declared = (header & DECL_ELEMENT_TYPE) != 0;
-    T::fory_read_type_info(context, declared)?;
+    read_capacity_hint(context, header)?;
+    read_elements_type_info::<T>(context, header)?;
     let has_null = (header & HAS_NULL) != 0;
     let is_same_type = (header & IS_SAME_TYPE) != 0;
-    if T::fory_is_polymorphic() || T::fory_is_shared_ref() {
+    if (header & DICTIONARY) != 0 {
+        let mut elements = Vec::with_capacity(capped_len(context, len as usize));
+        read_string_dictionary(context, len as usize, |element| elements.push(element))?;
+        Ok(C::from_iter(elements))
+    } else if (header & SPARSE_NULL) != 0 {
+        let bitmap = read_null_bitmap(context, len as usize)?;
+        (0..len as usize)
+            .map(|i| {
+                if is_null_in_bitmap(&bitmap, i) {
+                    null()
+                } else {
+                    read_ref_info_data_without_default(context, declared, true, true)
+                }
+            })
+            .collect::<Result<C, Error>>()
+    } else if T::fory_is_polymorphic() || T::fory_is_shared_ref() {
         (0..len)
-            .map(|_| T::fory_read(context, declared))
+            .map(|_| T::fory_read_without_default(context, declared))
             .collect::<Result<C, Error>>()
     } else {
         let skip_ref_flag = is_same_type && !has_null;
         // let skip_ref_flag = crate::serializer::get_skip_ref_flag::<T>(context.get_fory());
         (0..len)
-            .map(|_| crate::serializer::read_ref_info_data(context, declared, skip_ref_flag, true))
+            .map(|_| read_ref_info_data_or(context, declared, skip_ref_flag, is_same_type, null))
             .collect::<Result<C, Error>>()
     }
 }
+
+/// A sequence that can be refilled in place by [`refill_collection`].
+trait Refill<T> {
+    fn clear(&mut self);
+    fn reserve_exact(&mut self, additional: usize);
+    fn push(&mut self, value: T);
+}
+
+impl<T> Refill<T> for Vec<T> {
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+
+    fn reserve_exact(&mut self, additional: usize) {
+        Vec::reserve_exact(self, additional)
+    }
+
+    fn push(&mut self, value: T) {
+        Vec::push(self, value)
+    }
+}
+
+impl<T> Refill<T> for VecDeque<T> {
+    fn clear(&mut self) {
+        VecDeque::clear(self)
+    }
+
+    fn reserve_exact(&mut self, additional: usize) {
+        VecDeque::reserve_exact(self, additional)
+    }
+
+    fn push(&mut self, value: T) {
+        VecDeque::push_back(self, value)
+    }
+}
+
+/// Clears `target` and pushes the incoming elements. Room for exactly the incoming length,
+/// or for the capacity hint up to [`MAX_DEQUE_CAPACITY_HINT`] if larger, is reserved up
+/// front, so the allocation is reused when it is large enough, and a fresh `target` ends
+/// up with a capacity equal to its length without growing on the way. The length is
+/// capped by the bytes left in the input, see [`capped_len`].
+fn refill_collection<C, T>(
+    target: &mut C,
+    context: &mut ReadContext,
+    null: fn() -> Result<T, Error>,
+) -> Result<(), Error>
+where
+    C: Refill<T>,
+    T: Serializer,
+{
+    target.clear();
+    let len = context.reader.read_varuint32()?;
+    if len == 0 {
+        return Ok(());
+    }
+    let header = context.reader.read_u8()?;
+    let declared = (header & DECL_ELEMENT_TYPE) != 0;
+    let capacity = read_capacity_hint(context, header)?.min(MAX_DEQUE_CAPACITY_HINT);
+    read_elements_type_info::<T>(context, header)?;
+    let has_null = (header & HAS_NULL) != 0;
+    let is_same_type = (header & IS_SAME_TYPE) != 0;
+    target.reserve_exact(capped_len(context, len as usize).max(capacity));
+    if (header & DICTIONARY) != 0 {
+        read_string_dictionary(context, len as usize, |element| target.push(element))?;
+    } else if (header & SPARSE_NULL) != 0 {
+        let bitmap = read_null_bitmap(context, len as usize)?;
+        // a null takes a bit of the bitmap rather than a byte, so the nulls are only
+        // reserved for once the bitmap is read
+        let nulls = bitmap
+            .iter()
+            .map(|byte| byte.count_ones() as usize)
+            .sum::<usize>()
+            .min(len as usize);
+        target.reserve_exact(nulls + capped_len(context, len as usize - nulls));
+        for i in 0..len as usize {
+            target.push(if is_null_in_bitmap(&bitmap, i) {
+                null()?
+            } else {
+                read_ref_info_data_without_default(context, declared, true, true)?
+            });
+        }
+    } else if T::fory_is_polymorphic() || T::fory_is_shared_ref() {
+        for _ in 0..len {
+            target.push(T::fory_read_without_default(context, declared)?);
+        }
+    } else {
+        let skip_ref_flag = is_same_type && !has_null;
+        for _ in 0..len {
+            target.push(read_ref_info_data_or(
+                context,
+                declared,
+                skip_ref_flag,
+                is_same_type,
+                null,
+            )?);
+        }
+    }
+    Ok(())
+}
+
+/// Like [`read_collection`], but refills `vec` in place so that its allocation is reused.
+///
+/// Room for exactly the incoming length is reserved up front, so a fresh `vec` ends up
+/// with a capacity equal to its length and never reallocates while it is filled. The
+/// room is capped by the bytes left in the input, so that a forged length fails on the
+/// missing elements rather than on the allocation.
+///
+/// The elements need not implement [`ForyDefault`], a null element is read with
+/// [`Serializer::fory_read_null`].
+pub fn read_vec_data_into<T>(vec: &mut Vec<T>, context: &mut ReadContext) -> Result<(), Error>
+where
+    T: Serializer,
+{
+    refill_collection(vec, context, T::fory_read_null)
+}
+
+/// The largest capacity hint that [`read_vec_data_into`] and [`read_vec_deque_data_into`]
+/// reserve, in elements.
+pub const MAX_DEQUE_CAPACITY_HINT: usize = 1 << 16;
+
+/// Like [`read_vec_data_into`], for a `VecDeque` used as a ring buffer. Clearing keeps the
+/// capacity, so a refill with no more elements than the capacity does not reallocate. A
+/// capacity hint written after the header is reserved as well, up to
+/// [`MAX_DEQUE_CAPACITY_HINT`].
+pub fn read_vec_deque_data_into<T>(
+    deque: &mut VecDeque<T>,
+    context: &mut ReadContext,
+) -> Result<(), Error>
+where
+    T: Serializer + ForyDefault,
+{
+    refill_collection(deque, context, || Ok(T::fory_default()))
+}
@@ -0,0 +1,85 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Serializer for `compact_str::CompactString`, written exactly like a `String` so that the
+//! two can be read as each other.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::string::write_str_data;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use compact_str::CompactString;
+use std::cmp::Ordering;
+
+impl Serializer for CompactString {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        write_str_data(self.as_str(), context)
+    }
+
+    fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Ok(CompactString::from(String::fory_read_data(
+            context, is_field,
+        )?))
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        String::fory_skip(context)
+    }
+
+    fn fory_reserved_space() -> usize {
+        String::fory_reserved_space()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::STRING as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::STRING as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_cmp_serialized(&self, other: &Self, _: &mut WriteContext) -> Result<Ordering, Error> {
+        Ok(self.cmp(other))
+    }
+
+    fn fory_cmp_serialized_bytes() -> bool {
+        false
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+impl ForyDefault for CompactString {
+    fn fory_default() -> Self {
+        CompactString::default()
+    }
+}
@@ -0,0 +1,86 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::ops::ControlFlow;
+
+const CONTINUE_TAG: u32 = 0;
+const BREAK_TAG: u32 = 1;
+
+/// `ControlFlow` is written as a varuint32 tag (0 = `Continue`, 1 = `Break`)
+/// followed by the payload with its own ref and type info.
+impl<B, C> Serializer for ControlFlow<B, C>
+where
+    B: Serializer + ForyDefault,
+    C: Serializer + ForyDefault,
+{
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        match self {
+            ControlFlow::Continue(c) => {
+                context.writer.write_varuint32(CONTINUE_TAG);
+                c.fory_write(context, false)
+            }
+            ControlFlow::Break(b) => {
+                context.writer.write_varuint32(BREAK_TAG);
+                b.fory_write(context, false)
+            }
+        }
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        match context.reader.read_varuint32()? {
+            CONTINUE_TAG => Ok(ControlFlow::Continue(C::fory_read(context, false)?)),
+            BREAK_TAG => Ok(ControlFlow::Break(B::fory_read(context, false)?)),
+            tag => Err(Error::InvalidData(
+                format!("unknown ControlFlow tag: {}", tag).into(),
+            )),
+        }
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<B, C: ForyDefault> ForyDefault for ControlFlow<B, C> {
+    fn fory_default() -> Self {
+        ControlFlow::Continue(C::fory_default())
+    }
+}
@@ -27,6 +27,8 @@ use chrono::{DateTime, Days, NaiveDate, NaiveDateTime};
 use std::mem;
 
 impl Serializer for NaiveDateTime {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
         let dt = self.and_utc();
         let micros = dt.timestamp() * 1_000_000 + dt.timestamp_subsec_micros() as i64;
@@ -72,6 +74,8 @@ impl Serializer for NaiveDateTime {
 }
 
 impl Serializer for NaiveDate {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
         let days_since_epoch = self.signed_duration_since(EPOCH).num_days();
         context.writer.write_i32(days_since_epoch as i32);
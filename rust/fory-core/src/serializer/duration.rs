@@ -0,0 +1,109 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::mem;
+use std::time::Duration;
+
+/// A [`Duration`] written as a `FLOAT64` number of seconds, for peers that represent
+/// durations as fractional seconds.
+///
+/// An `f64` holds 53 bits of mantissa, so a duration keeps nanosecond precision only
+/// up to about 104 days and is rounded to the nearest representable value beyond
+/// that. On read, values that are negative, not finite or above [`Duration::MAX`] are
+/// rejected with [`Error::InvalidData`] instead of panicking like
+/// [`Duration::from_secs_f64`].
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::serializer::duration::DurationSecs;
+/// use fory_core::Fory;
+/// use std::time::Duration;
+///
+/// let fory = Fory::default();
+/// let bytes = fory.serialize(&DurationSecs(Duration::from_millis(1500))).unwrap();
+/// assert_eq!(fory.deserialize::<f64>(&bytes).unwrap(), 1.5);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DurationSecs(pub Duration);
+
+impl From<Duration> for DurationSecs {
+    fn from(duration: Duration) -> Self {
+        DurationSecs(duration)
+    }
+}
+
+impl From<DurationSecs> for Duration {
+    fn from(duration: DurationSecs) -> Self {
+        duration.0
+    }
+}
+
+impl Serializer for DurationSecs {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        self.0.as_secs_f64().fory_write_data(context, is_field)
+    }
+
+    fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        let secs = f64::fory_read_data(context, is_field)?;
+        Duration::try_from_secs_f64(secs)
+            .map(DurationSecs)
+            .map_err(|e| Error::InvalidData(format!("{} s is not a duration: {}", secs, e).into()))
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        f64::fory_skip(context)
+    }
+
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<f64>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::FLOAT64 as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::FLOAT64 as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+impl ForyDefault for DurationSecs {
+    fn fory_default() -> Self {
+        DurationSecs::default()
+    }
+}
@@ -19,6 +19,8 @@ use crate::ensure;
 use crate::error::Error;
 use crate::meta::{MetaString, TypeMeta};
 use crate::resolver::context::{ReadContext, WriteContext};
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::string::write_str_data;
 use crate::serializer::{ForyDefault, Serializer};
 use crate::types::{RefFlag, TypeId};
 
@@ -31,6 +33,38 @@ pub fn actual_type_id(type_id: u32, register_by_name: bool, _compatible: bool) -
     }
 }
 
+/// Returns whether `T` is written by variant name, see [`Fory::register_enum_as_string`].
+///
+/// [`Fory::register_enum_as_string`]: crate::fory::Fory::register_enum_as_string
+#[inline(always)]
+pub fn is_written_as_string<T: 'static>(type_resolver: &TypeResolver) -> bool {
+    type_resolver.is_enum_as_string(std::any::TypeId::of::<T>())
+}
+
+/// Returns the type id that the type meta of a struct records for a field of type `T`,
+/// which is [`TypeId::STRING`] for an enum written by variant name and the fixed-width
+/// integer type of a `#[fory(repr)]` enum, so that a peer can skip the field.
+#[inline(always)]
+pub fn field_type_id<T: Serializer>(type_resolver: &TypeResolver) -> Result<u32, Error> {
+    if is_written_as_string::<T>(type_resolver) {
+        return Ok(TypeId::STRING as u32);
+    }
+    if let Some(type_id) = type_resolver.get_repr_type_id(std::any::TypeId::of::<T>()) {
+        return Ok(type_id);
+    }
+    T::fory_get_type_id(type_resolver)
+}
+
+#[inline(always)]
+pub fn write_variant_name(name: &str, context: &mut WriteContext) -> Result<(), Error> {
+    write_str_data(name, context)
+}
+
+#[inline(always)]
+pub fn read_variant_name(context: &mut ReadContext) -> Result<String, Error> {
+    String::fory_read_data(context, true)
+}
+
 #[inline(always)]
 pub fn type_def(
     type_id: u32,
@@ -80,7 +114,7 @@ pub fn read_type_info<T: Serializer>(
         return Ok(());
     }
     let local_type_id = T::fory_get_type_id(context.get_type_resolver())?;
-    let remote_type_id = context.reader.read_varuint32()?;
+    let remote_type_id = context.read_type_id()?;
     ensure!(
         local_type_id == remote_type_id,
         Error::TypeMismatch(local_type_id, remote_type_id)
@@ -0,0 +1,164 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::log_record::{read_fields, write_fields, Fields};
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::any::Any;
+use std::fmt;
+
+/// A structured error, for propagating server errors across an RPC boundary.
+///
+/// The error is written as the code, the message and a flag telling whether details
+/// follow, then the details like the fields of a
+/// [`LogRecord`](crate::serializer::log_record::LogRecord): each value carries its own
+/// type info, so a detail can hold any type a `Box<dyn Any>` can.
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::serializer::error_payload::SerializableError;
+/// use fory_core::Fory;
+///
+/// let fory = Fory::default();
+/// let error = SerializableError::new(404, "no such user")
+///     .with_detail("user", "ada".to_string())
+///     .with_detail("retry_after_s", 30i64);
+/// let bytes = fory.serialize(&error).unwrap();
+/// let error: SerializableError = fory.deserialize(&bytes).unwrap();
+/// assert_eq!(error.code, 404);
+/// assert_eq!(error.detail::<String>("user").unwrap(), "ada");
+/// assert_eq!(error.detail::<i64>("retry_after_s"), Some(&30));
+/// ```
+#[derive(Debug)]
+pub struct SerializableError {
+    pub code: u32,
+    pub message: String,
+    pub details: Option<Fields>,
+}
+
+impl SerializableError {
+    /// Creates an error without details.
+    pub fn new(code: u32, message: impl Into<String>) -> Self {
+        SerializableError {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Captures `error` with `code`. The message is the error followed by its chain of
+    /// sources, separated by `": "`, since the sources cannot cross the wire themselves.
+    pub fn from_error(code: u32, error: &(dyn std::error::Error + 'static)) -> Self {
+        let mut message = error.to_string();
+        let mut source = error.source();
+        while let Some(cause) = source {
+            message.push_str(": ");
+            message.push_str(&cause.to_string());
+            source = cause.source();
+        }
+        SerializableError::new(code, message)
+    }
+
+    /// Adds a detail, replacing any previous value of `key`.
+    pub fn with_detail(mut self, key: impl Into<String>, value: impl Any) -> Self {
+        let key = key.into();
+        let value: Box<dyn Any> = Box::new(value);
+        let details = self.details.get_or_insert_with(Vec::new);
+        match details.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, previous)) => *previous = value,
+            None => details.push((key, value)),
+        }
+        self
+    }
+
+    /// Returns the detail named `key`, if it holds a `T`.
+    pub fn detail<T: 'static>(&self, key: &str) -> Option<&T> {
+        self.details
+            .as_ref()?
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, value)| value.downcast_ref::<T>())
+    }
+}
+
+impl fmt::Display for SerializableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for SerializableError {}
+
+impl Serializer for SerializableError {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        context.writer.write_varuint32(self.code);
+        self.message.fory_write_data(context, is_field)?;
+        context.writer.write_u8(self.details.is_some() as u8);
+        if let Some(details) = &self.details {
+            write_fields(details, context, is_field)?;
+        }
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        let code = context.reader.read_varuint32()?;
+        let message = String::fory_read_data(context, is_field)?;
+        let details = if context.reader.read_bool()? {
+            Some(read_fields(context, is_field)?)
+        } else {
+            None
+        };
+        Ok(SerializableError {
+            code,
+            message,
+            details,
+        })
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+impl ForyDefault for SerializableError {
+    fn fory_default() -> Self {
+        SerializableError::new(0, "")
+    }
+}
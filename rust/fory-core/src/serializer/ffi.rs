@@ -0,0 +1,137 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::ffi::CString;
+#[cfg(unix)]
+use std::ffi::OsString;
+use std::mem;
+
+// Written as `BINARY`: a varuint32 byte length followed by the bytes without the trailing NUL.
+impl Serializer for CString {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        let bytes = self.as_bytes();
+        context.writer.write_varuint32(bytes.len() as u32);
+        context.writer.write_bytes(bytes);
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let len = context.reader.read_varuint32()? as usize;
+        let bytes = context.reader.read_bytes(len)?;
+        CString::new(bytes).map_err(|e| Error::InteriorNul(e.nul_position()))
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        let len = context.reader.read_varuint32()? as usize;
+        context.skip_bytes(len)
+    }
+
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<i32>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::BINARY as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::BINARY as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+impl ForyDefault for CString {
+    fn fory_default() -> Self {
+        CString::default()
+    }
+}
+
+// Written as `BINARY` with the raw bytes of the string, which only Unix exposes. Read it
+// back without copying as a `&OsStr` with `Fory::deserialize_borrowed`.
+#[cfg(unix)]
+impl Serializer for OsString {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        use std::os::unix::ffi::OsStrExt;
+        let bytes = self.as_bytes();
+        context.writer.write_varuint32(bytes.len() as u32);
+        context.writer.write_bytes(bytes);
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        use std::os::unix::ffi::OsStringExt;
+        let len = context.reader.read_varuint32()? as usize;
+        Ok(OsString::from_vec(context.reader.read_bytes(len)?.to_vec()))
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        let len = context.reader.read_varuint32()? as usize;
+        context.skip_bytes(len)
+    }
+
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<i32>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::BINARY as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::BINARY as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+#[cfg(unix)]
+impl ForyDefault for OsString {
+    fn fory_default() -> Self {
+        OsString::new()
+    }
+}
@@ -0,0 +1,217 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `bitflags` support, enabled by the `bitflags` feature.
+//!
+//! A flags value is written as its bits, using the signed integer of the same width so
+//! that peers see an ordinary number. Flag types are defined in the user's crate, so the
+//! serializer is implemented per type with [`impl_flags_serializer!`](crate::impl_flags_serializer).
+//! Whether reading drops unknown bits or rejects them is set with
+//! [`Fory::with_strict_flags`](crate::fory::Fory::with_strict_flags).
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{ForyDefault, Serializer};
+use bitflags::Flags;
+use std::fmt::Debug;
+
+/// The integer types a flags value can be stored in, mapped to the signed integer with
+/// the same width that they are written as.
+pub trait FlagBits: Copy {
+    type Wire: Serializer + ForyDefault + Debug;
+
+    fn to_wire(self) -> Self::Wire;
+
+    fn from_wire(wire: Self::Wire) -> Self;
+}
+
+macro_rules! impl_flag_bits {
+    ($($ty:ty => $wire:ty),+ $(,)?) => {
+        $(
+            impl FlagBits for $ty {
+                type Wire = $wire;
+
+                #[inline(always)]
+                fn to_wire(self) -> $wire {
+                    self as $wire
+                }
+
+                #[inline(always)]
+                fn from_wire(wire: $wire) -> Self {
+                    wire as $ty
+                }
+            }
+        )+
+    };
+}
+
+impl_flag_bits!(
+    u8 => i8,
+    i8 => i8,
+    u16 => i16,
+    i16 => i16,
+    u32 => i32,
+    i32 => i32,
+    u64 => i64,
+    i64 => i64,
+);
+
+#[inline(always)]
+pub fn write_data<T>(flags: &T, context: &mut WriteContext, is_field: bool) -> Result<(), Error>
+where
+    T: Flags,
+    T::Bits: FlagBits,
+{
+    flags.bits().to_wire().fory_write_data(context, is_field)
+}
+
+#[inline(always)]
+pub fn read_data<T>(context: &mut ReadContext, is_field: bool) -> Result<T, Error>
+where
+    T: Flags,
+    T::Bits: FlagBits,
+{
+    let wire = <T::Bits as FlagBits>::Wire::fory_read_data(context, is_field)?;
+    let bits = T::Bits::from_wire(wire);
+    if !context.is_strict_flags() {
+        return Ok(T::from_bits_truncate(bits));
+    }
+    T::from_bits(bits).ok_or_else(|| {
+        Error::InvalidData(
+            format!(
+                "{:?} has bits not defined by {}",
+                bits.to_wire(),
+                std::any::type_name::<T>()
+            )
+            .into(),
+        )
+    })
+}
+
+#[inline(always)]
+pub fn skip<T>(context: &mut ReadContext) -> Result<(), Error>
+where
+    T: Flags,
+    T::Bits: FlagBits,
+{
+    <T::Bits as FlagBits>::Wire::fory_skip(context)
+}
+
+#[inline(always)]
+pub fn get_type_id<T>(type_resolver: &TypeResolver) -> Result<u32, Error>
+where
+    T: Flags,
+    T::Bits: FlagBits,
+{
+    <T::Bits as FlagBits>::Wire::fory_get_type_id(type_resolver)
+}
+
+/// Implements [`Serializer`] and [`ForyDefault`] for a type generated by
+/// `bitflags::bitflags!`, whose bits are written as a number.
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::{impl_flags_serializer, Fory};
+///
+/// bitflags::bitflags! {
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///     pub struct Permissions: u8 {
+///         const READ = 1;
+///         const WRITE = 1 << 1;
+///     }
+/// }
+///
+/// impl_flags_serializer!(Permissions);
+///
+/// let fory = Fory::default();
+/// let flags = Permissions::READ | Permissions::WRITE;
+/// let bytes = fory.serialize(&flags).unwrap();
+/// assert_eq!(fory.deserialize::<Permissions>(&bytes).unwrap(), flags);
+/// ```
+#[macro_export]
+macro_rules! impl_flags_serializer {
+    ($ty:ty) => {
+        impl $crate::serializer::Serializer for $ty {
+            $crate::read_null_as_default!();
+
+            fn fory_write_data(
+                &self,
+                context: &mut $crate::resolver::context::WriteContext,
+                is_field: bool,
+            ) -> Result<(), $crate::error::Error> {
+                $crate::serializer::flags::write_data(self, context, is_field)
+            }
+
+            fn fory_read_data(
+                context: &mut $crate::resolver::context::ReadContext,
+                is_field: bool,
+            ) -> Result<Self, $crate::error::Error> {
+                $crate::serializer::flags::read_data(context, is_field)
+            }
+
+            fn fory_skip(
+                context: &mut $crate::resolver::context::ReadContext,
+            ) -> Result<(), $crate::error::Error> {
+                $crate::serializer::flags::skip::<Self>(context)
+            }
+
+            fn fory_reserved_space() -> usize {
+                std::mem::size_of::<$ty>()
+            }
+
+            fn fory_get_type_id(
+                type_resolver: &$crate::resolver::type_resolver::TypeResolver,
+            ) -> Result<u32, $crate::error::Error> {
+                $crate::serializer::flags::get_type_id::<Self>(type_resolver)
+            }
+
+            fn fory_type_id_dyn(
+                &self,
+                type_resolver: &$crate::resolver::type_resolver::TypeResolver,
+            ) -> Result<u32, $crate::error::Error> {
+                Self::fory_get_type_id(type_resolver)
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+
+            fn fory_write_type_info(
+                context: &mut $crate::resolver::context::WriteContext,
+                is_field: bool,
+            ) -> Result<(), $crate::error::Error> {
+                $crate::serializer::write_type_info::<Self>(context, is_field)
+            }
+
+            fn fory_read_type_info(
+                context: &mut $crate::resolver::context::ReadContext,
+                is_field: bool,
+            ) -> Result<(), $crate::error::Error> {
+                $crate::serializer::read_type_info::<Self>(context, is_field)
+            }
+        }
+
+        impl $crate::serializer::ForyDefault for $ty {
+            fn fory_default() -> Self {
+                <$ty>::empty()
+            }
+        }
+    };
+}
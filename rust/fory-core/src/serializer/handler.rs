@@ -0,0 +1,145 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::fmt;
+use std::mem;
+use std::sync::Arc;
+
+/// A reference to a handler registered with
+/// [`Fory::register_handler`](crate::fory::Fory::register_handler), e.g. a closure in an
+/// RPC system.
+///
+/// Only the id is written, as an `INT32`. Reading looks the id up among the handlers of
+/// the reading `Fory`, so both peers must register the same ids, each with a handler of
+/// type `F`.
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::serializer::handler::HandlerRef;
+/// use fory_core::Fory;
+/// use std::sync::Arc;
+///
+/// type Handler = dyn Fn(i32) -> i32 + Send + Sync;
+///
+/// let mut fory = Fory::default();
+/// fory.register_handler::<Handler>(1, Arc::new(|x| x + 1)).unwrap();
+/// let bytes = fory.serialize(&fory.handler_ref::<Handler>(1).unwrap()).unwrap();
+/// let handler: HandlerRef<Handler> = fory.deserialize(&bytes).unwrap();
+/// assert_eq!((handler.get().unwrap())(41), 42);
+/// ```
+pub struct HandlerRef<F: ?Sized> {
+    id: u32,
+    handler: Option<Arc<F>>,
+}
+
+impl<F: ?Sized> HandlerRef<F> {
+    pub fn new(id: u32, handler: Arc<F>) -> Self {
+        HandlerRef {
+            id,
+            handler: Some(handler),
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the handler, or `None` for the default value read from a null.
+    pub fn get(&self) -> Option<&F> {
+        self.handler.as_deref()
+    }
+}
+
+impl<F: ?Sized> Clone for HandlerRef<F> {
+    fn clone(&self) -> Self {
+        HandlerRef {
+            id: self.id,
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+impl<F: ?Sized> Default for HandlerRef<F> {
+    /// A reference to id 0 without a handler, as read from a null.
+    fn default() -> Self {
+        HandlerRef {
+            id: 0,
+            handler: None,
+        }
+    }
+}
+
+impl<F: ?Sized> fmt::Debug for HandlerRef<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HandlerRef").field("id", &self.id).finish()
+    }
+}
+
+impl<F: ?Sized + 'static> Serializer for HandlerRef<F> {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        (self.id as i32).fory_write_data(context, is_field)
+    }
+
+    fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        let id = i32::fory_read_data(context, is_field)? as u32;
+        let handler = context.get_type_resolver().get_handler::<F>(id)?;
+        Ok(HandlerRef::new(id, handler))
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        i32::fory_skip(context)
+    }
+
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<i32>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::INT32 as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::INT32 as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+impl<F: ?Sized> ForyDefault for HandlerRef<F> {
+    fn fory_default() -> Self {
+        HandlerRef::default()
+    }
+}
@@ -29,6 +29,8 @@ use std::collections::BinaryHeap;
 use std::mem;
 
 impl<T: Serializer + ForyDefault + Ord> Serializer for BinaryHeap<T> {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         write_collection(self, context, is_field)
     }
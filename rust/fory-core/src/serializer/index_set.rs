@@ -0,0 +1,115 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! `IndexSet` support, enabled by the `indexmap` feature.
+//!
+//! Elements are written in insertion order with the `SET` type id, so peers see an ordinary
+//! set, and reading rebuilds the set in the order the elements appear on the wire. A set
+//! never contains duplicates, so a payload that repeats an element is rejected with
+//! [`Error::InvalidData`] instead of being silently deduplicated.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::collection::{
+    read_collection, read_collection_type_info, write_collection, write_collection_type_info,
+};
+use crate::serializer::{read_ref_info_data_into, Clear, ForyDefault, Serializer};
+use crate::types::TypeId;
+use indexmap::IndexSet;
+use std::hash::Hash;
+use std::mem;
+
+fn read_index_set_into<T>(set: &mut IndexSet<T>, context: &mut ReadContext) -> Result<(), Error>
+where
+    T: Serializer + ForyDefault + Eq + Hash,
+{
+    let elements: Vec<T> = read_collection(context)?;
+    set.clear();
+    set.reserve(elements.len());
+    for element in elements {
+        if !set.insert(element) {
+            return Err(Error::InvalidData(
+                "duplicate element in serialized IndexSet".into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+impl<T: Serializer + ForyDefault + Eq + Hash> Serializer for IndexSet<T> {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_collection(self, context, is_field)
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_collection_type_info(context, is_field, TypeId::SET as u32)
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let mut set = IndexSet::new();
+        read_index_set_into(&mut set, context)?;
+        Ok(set)
+    }
+
+    fn fory_read_data_into(
+        &mut self,
+        context: &mut ReadContext,
+        _is_field: bool,
+    ) -> Result<(), Error> {
+        read_index_set_into(self, context)
+    }
+
+    fn fory_read_into(&mut self, context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_ref_info_data_into(self, context, is_field, false, false)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_collection_type_info(context, is_field, TypeId::SET as u32)
+    }
+
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<i32>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::SET as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::SET as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<T> ForyDefault for IndexSet<T> {
+    fn fory_default() -> Self {
+        IndexSet::new()
+    }
+}
+
+impl<T> Clear for IndexSet<T> {
+    fn clear(&mut self) {
+        IndexSet::clear(self)
+    }
+}
@@ -0,0 +1,352 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Conversion of compatible-mode data into a JSON tree, for debugging.
+//!
+//! The data is walked the same way [`skip_field_value`](super::skip::skip_field_value)
+//! skips it, guided by the field types of the type metas, and every value is emitted
+//! instead of dropped:
+//!
+//! - structs become objects whose `"$type"` member holds the type tag as a string, either
+//!   the registered name or the registered id, followed by one member per field
+//! - enums become `{"$type": ..., "ordinal": n}`
+//! - binary data, byte arrays and `Vec<u8>` lists become base64 strings
+//! - dates and timestamps become strings, and non-finite floats become `"NaN"`, `"inf"`
+//!   or `"-inf"`
+//! - maps with string keys become objects, other maps become arrays of `[key, value]`
+//! - a shared reference seen before becomes `{"$ref": id}`
+//! - extension types become `{"$type": ...}`, since only their serializer knows the data
+
+use crate::ensure;
+use crate::error::Error;
+use crate::meta::{FieldType, TypeMeta};
+use crate::resolver::context::ReadContext;
+use crate::serializer::collection::{
+    is_null_in_bitmap, read_capacity_hint, read_null_bitmap, read_string_dictionary, DICTIONARY,
+    HAS_NULL, IS_SAME_TYPE, SPARSE_NULL,
+};
+use crate::serializer::map::{read_prefix_shared_key, KEY_NULL, KEY_PREFIX_SHARED, VALUE_NULL};
+use crate::serializer::skip::get_read_ref_flag;
+use crate::serializer::Serializer;
+use crate::types::{RefFlag, TypeId, CONTAINER_TYPES};
+use chrono::{NaiveDate, NaiveDateTime};
+use serde_json::{Map, Value};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let n = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64_ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3F] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+fn float(value: f64) -> Value {
+    match serde_json::Number::from_f64(value) {
+        Some(number) => Value::Number(number),
+        None if value.is_nan() => Value::String("NaN".into()),
+        None if value > 0.0 => Value::String("inf".into()),
+        None => Value::String("-inf".into()),
+    }
+}
+
+fn type_tag(type_meta: &TypeMeta) -> Value {
+    let name = type_meta.get_type_name().original;
+    if name.is_empty() {
+        return Value::String((type_meta.get_type_id() >> 8).to_string());
+    }
+    let namespace = type_meta.get_namespace().original;
+    if namespace.is_empty() {
+        Value::String(name)
+    } else {
+        Value::String(format!("{namespace}.{name}"))
+    }
+}
+
+fn tagged(tag: Value) -> Map<String, Value> {
+    let mut object = Map::new();
+    object.insert("$type".into(), tag);
+    object
+}
+
+macro_rules! basic_type_json {
+    ($tid:expr, $context:expr; $(($ty:ty, $id:ident, $convert:expr)),+ $(,)?) => {
+        $(
+            if $tid == TypeId::$id {
+                <$ty as Serializer>::fory_read_type_info($context, true)?;
+                let value = <$ty as Serializer>::fory_read_data($context, true)?;
+                return Ok($convert(value));
+            }
+        )+
+    };
+}
+
+fn floats<T: Into<f64>>(values: Vec<T>) -> Value {
+    Value::Array(values.into_iter().map(|v| float(v.into())).collect())
+}
+
+fn read_struct_json(context: &mut ReadContext, type_meta: &TypeMeta) -> Result<Value, Error> {
+    let mut object = tagged(type_tag(type_meta));
+    context.inc_depth()?;
+    for field_info in type_meta.get_field_infos().iter() {
+        let read_ref_flag = get_read_ref_flag(&field_info.field_type);
+        let value = read_json_value(context, &field_info.field_type, read_ref_flag)?;
+        let name = match field_info.name_hash {
+            Some(hash) if field_info.field_name.is_empty() => format!("#{hash:x}"),
+            _ => field_info.field_name.clone(),
+        };
+        object.insert(name, value);
+    }
+    context.dec_depth();
+    Ok(Value::Object(object))
+}
+
+fn read_map_json(context: &mut ReadContext, field_type: &FieldType) -> Result<Value, Error> {
+    let length = context.reader.read_varuint32()?;
+    let key_type = field_type.generics.first().unwrap();
+    let value_type = field_type.generics.get(1).unwrap();
+    let mut entries = Vec::new();
+    let mut prev_key = String::new();
+    context.inc_depth()?;
+    while entries.len() < length as usize {
+        let header = context.reader.read_u8()?;
+        if header & KEY_NULL != 0 && header & VALUE_NULL != 0 {
+            entries.push((Value::Null, Value::Null));
+            continue;
+        }
+        if header & KEY_NULL != 0 {
+            let value = read_json_value(context, value_type, false)?;
+            entries.push((Value::Null, value));
+            continue;
+        }
+        if header & VALUE_NULL != 0 {
+            let key = read_json_value(context, key_type, false)?;
+            entries.push((key, Value::Null));
+            continue;
+        }
+        let chunk_size = context.reader.read_u8()?;
+        let key_prefix_shared = header & KEY_PREFIX_SHARED != 0;
+        prev_key.clear();
+        for _ in 0..chunk_size {
+            let key = if key_prefix_shared {
+                Value::String(read_prefix_shared_key::<String>(context, &mut prev_key)?)
+            } else {
+                read_json_value(context, key_type, false)?
+            };
+            let value = read_json_value(context, value_type, false)?;
+            entries.push((key, value));
+        }
+    }
+    context.dec_depth();
+    if entries.iter().all(|(key, _)| key.is_string()) {
+        Ok(Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| match key {
+                    Value::String(key) => (key, value),
+                    _ => unreachable!(),
+                })
+                .collect(),
+        ))
+    } else {
+        Ok(Value::Array(
+            entries
+                .into_iter()
+                .map(|(key, value)| Value::Array(vec![key, value]))
+                .collect(),
+        ))
+    }
+}
+
+fn read_collection_json(context: &mut ReadContext, field_type: &FieldType) -> Result<Value, Error> {
+    let elem_type = field_type.generics.first().unwrap();
+    // `Vec<u8>` is written as a list of `INT8` elements
+    let is_bytes = field_type.type_id == TypeId::LIST as u32
+        && elem_type.type_id == TypeId::INT8 as u32
+        && !elem_type.nullable;
+    let length = context.reader.read_varuint32()? as usize;
+    if length == 0 {
+        return Ok(if is_bytes {
+            Value::String(String::new())
+        } else {
+            Value::Array(Vec::new())
+        });
+    }
+    let header = context.reader.read_u8()?;
+    read_capacity_hint(context, header)?;
+    let has_null = (header & HAS_NULL) != 0;
+    let is_same_type = (header & IS_SAME_TYPE) != 0;
+    let skip_ref_flag = is_same_type && !has_null;
+    let mut elements = Vec::new();
+    context.inc_depth()?;
+    if (header & DICTIONARY) != 0 {
+        read_string_dictionary(context, length, |s: String| elements.push(Value::String(s)))?;
+    } else if (header & SPARSE_NULL) != 0 {
+        let bitmap = read_null_bitmap(context, length)?;
+        for i in 0..length {
+            if is_null_in_bitmap(&bitmap, i) {
+                elements.push(Value::Null);
+            } else {
+                elements.push(read_json_value(context, elem_type, false)?);
+            }
+        }
+    } else {
+        for _ in 0..length {
+            elements.push(read_json_value(context, elem_type, !skip_ref_flag)?);
+        }
+    }
+    context.dec_depth();
+    if is_bytes {
+        let bytes = elements.iter().map(|e| e.as_i64().map(|b| b as u8));
+        if let Some(bytes) = bytes.collect::<Option<Vec<u8>>>() {
+            return Ok(Value::String(base64(&bytes)));
+        }
+    }
+    Ok(Value::Array(elements))
+}
+
+/// Reads the value described by `field_type` as JSON, where [`skip_field_value`] would
+/// skip it.
+///
+/// [`skip_field_value`]: super::skip::skip_field_value
+pub fn read_json_value(
+    context: &mut ReadContext,
+    field_type: &FieldType,
+    read_ref_flag: bool,
+) -> Result<Value, Error> {
+    if read_ref_flag {
+        let ref_flag = context.reader.read_i8()?;
+        if ref_flag == RefFlag::Null as i8 {
+            return Ok(Value::Null);
+        }
+        if ref_flag == RefFlag::Ref as i8 {
+            let ref_id = context.reader.read_u32()?;
+            let mut object = Map::new();
+            object.insert("$ref".into(), Value::from(ref_id));
+            return Ok(Value::Object(object));
+        }
+    }
+    let type_id_num = field_type.type_id;
+    match TypeId::try_from(type_id_num as i16) {
+        Ok(type_id) if CONTAINER_TYPES.contains(&type_id) => {
+            if type_id == TypeId::MAP {
+                read_map_json(context, field_type)
+            } else {
+                read_collection_json(context, field_type)
+            }
+        }
+        Ok(TypeId::NAMED_ENUM) => {
+            let ordinal = context.reader.read_varuint32()?;
+            let mut object = tagged(Value::String("enum".into()));
+            object.insert("ordinal".into(), Value::from(ordinal));
+            Ok(Value::Object(object))
+        }
+        Ok(TypeId::NAMED_COMPATIBLE_STRUCT) | Ok(TypeId::NAMED_EXT) => {
+            let remote_type_id = context.read_type_id()?;
+            ensure!(
+                type_id_num == remote_type_id,
+                Error::TypeMismatch(type_id_num, remote_type_id)
+            );
+            let meta_index = context.reader.read_varuint32()?;
+            let type_meta = context.get_meta(meta_index as usize).clone();
+            if type_id_num == TypeId::NAMED_COMPATIBLE_STRUCT as u32 {
+                return read_struct_json(context, &type_meta);
+            }
+            let type_resolver = context.get_type_resolver();
+            type_resolver
+                .get_ext_name_harness(&type_meta.get_namespace(), &type_meta.get_type_name())?
+                .get_read_data_fn()(context, true)?;
+            Ok(Value::Object(tagged(type_tag(&type_meta))))
+        }
+        Ok(type_id) => {
+            basic_type_json!(type_id, context;
+                (bool, BOOL, Value::from),
+                (i8, INT8, Value::from),
+                (i16, INT16, Value::from),
+                (i32, INT32, Value::from),
+                (i64, INT64, Value::from),
+                (f32, FLOAT32, |v: f32| float(v as f64)),
+                (f64, FLOAT64, float),
+                (String, STRING, Value::from),
+                (NaiveDate, LOCAL_DATE, |v: NaiveDate| Value::String(v.to_string())),
+                (NaiveDateTime, TIMESTAMP, |v: NaiveDateTime| Value::String(v.to_string())),
+                (Vec<u8>, BINARY, |v: Vec<u8>| Value::String(base64(&v))),
+                (Vec<bool>, BOOL_ARRAY, Value::from),
+                (Vec<u8>, INT8_ARRAY, |v: Vec<u8>| Value::String(base64(&v))),
+                (Vec<i16>, INT16_ARRAY, Value::from),
+                (Vec<i32>, INT32_ARRAY, Value::from),
+                (Vec<i64>, INT64_ARRAY, Value::from),
+                (Vec<f32>, FLOAT32_ARRAY, floats),
+                (Vec<f64>, FLOAT64_ARRAY, floats),
+            );
+            Err(Error::TypeError(
+                format!("cannot convert {type_id:?} data to JSON").into(),
+            ))
+        }
+        Err(_) => {
+            let internal_id = type_id_num & 0xff;
+            const COMPATIBLE_STRUCT_ID: u32 = TypeId::COMPATIBLE_STRUCT as u32;
+            const EXT_ID: u32 = TypeId::EXT as u32;
+            const ENUM_ID: u32 = TypeId::ENUM as u32;
+            if internal_id == COMPATIBLE_STRUCT_ID {
+                let remote_type_id = context.read_type_id()?;
+                let meta_index = context.reader.read_varuint32()?;
+                let type_meta = context.get_meta(meta_index as usize).clone();
+                ensure!(
+                    type_meta.get_type_id() == remote_type_id,
+                    Error::TypeMismatch(type_meta.get_type_id(), remote_type_id)
+                );
+                read_struct_json(context, &type_meta)
+            } else if internal_id == ENUM_ID {
+                let ordinal = context.reader.read_varuint32()?;
+                let mut object = tagged(Value::String((type_id_num >> 8).to_string()));
+                object.insert("ordinal".into(), Value::from(ordinal));
+                Ok(Value::Object(object))
+            } else if internal_id == EXT_ID {
+                let remote_type_id = context.read_type_id()?;
+                ensure!(
+                    type_id_num == remote_type_id,
+                    Error::TypeMismatch(type_id_num, remote_type_id)
+                );
+                context.inc_depth()?;
+                let type_resolver = context.get_type_resolver();
+                type_resolver
+                    .get_ext_harness(type_id_num)?
+                    .get_read_data_fn()(context, true)?;
+                context.dec_depth();
+                Ok(Value::Object(tagged(Value::String(
+                    (type_id_num >> 8).to_string(),
+                ))))
+            } else {
+                Err(Error::TypeError(
+                    format!("cannot convert data of type id {type_id_num} to JSON").into(),
+                ))
+            }
+        }
+    }
+}
@@ -20,17 +20,20 @@ use crate::resolver::context::ReadContext;
 use crate::resolver::context::WriteContext;
 use crate::resolver::type_resolver::TypeResolver;
 use crate::serializer::primitive_list;
-use crate::serializer::{ForyDefault, Serializer};
+use crate::serializer::{read_ref_info_data_into, ForyDefault, Serializer};
 use crate::types::TypeId;
 use std::any::TypeId as RsTypeId;
+use std::borrow::Cow;
 use std::collections::{LinkedList, VecDeque};
 use std::mem;
 
 use super::collection::{
-    read_collection, read_collection_type_info, write_collection, write_collection_type_info,
+    read_collection, read_collection_type_info, read_collection_without_default,
+    read_vec_data_into, read_vec_deque_data_into, write_collection, write_collection_type_info,
+    write_collection_with_capacity_hint, write_string_dictionary,
 };
 
-fn check_primitive<T: 'static>() -> Option<TypeId> {
+pub(crate) fn check_primitive<T: 'static>() -> Option<TypeId> {
     Some(match RsTypeId::of::<T>() {
         id if id == RsTypeId::of::<bool>() => TypeId::BOOL_ARRAY,
         id if id == RsTypeId::of::<i8>() => TypeId::INT8_ARRAY,
@@ -43,11 +46,20 @@ fn check_primitive<T: 'static>() -> Option<TypeId> {
     })
 }
 
-impl<T: Serializer + ForyDefault> Serializer for Vec<T> {
+// Elements are read whole, so unlike the other collections `T` needs no `ForyDefault`;
+// a null element is read with `Serializer::fory_read_null`.
+impl<T: Serializer> Serializer for Vec<T> {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         match check_primitive::<T>() {
             Some(_) => primitive_list::fory_write_data(self, context),
-            None => write_collection(self, context, is_field),
+            None => match self.as_any().downcast_ref::<Vec<String>>() {
+                Some(strings) if context.is_dictionary_strings() => {
+                    write_string_dictionary(strings, context, is_field)
+                }
+                _ => write_collection(self, context, is_field),
+            },
         }
     }
 
@@ -61,7 +73,34 @@ impl<T: Serializer + ForyDefault> Serializer for Vec<T> {
     fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
         match check_primitive::<T>() {
             Some(_) => primitive_list::fory_read_data(context),
-            None => read_collection(context),
+            None => {
+                // collecting through `Result` loses the size hint and over-allocates
+                let mut vec = Vec::new();
+                read_vec_data_into(&mut vec, context)?;
+                Ok(vec)
+            }
+        }
+    }
+
+    fn fory_read_data_into(
+        &mut self,
+        context: &mut ReadContext,
+        _is_field: bool,
+    ) -> Result<(), Error> {
+        match check_primitive::<T>() {
+            Some(_) => primitive_list::fory_read_data_into(self, context),
+            None => read_vec_data_into(self, context),
+        }
+    }
+
+    fn fory_read_into(&mut self, context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_ref_info_data_into(self, context, is_field, false, false)
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        match check_primitive::<T>() {
+            Some(_) => primitive_list::fory_skip::<T>(context),
+            None => read_collection_without_default::<Vec<T>, T>(context).map(|_| ()),
         }
     }
 
@@ -107,9 +146,84 @@ impl<T> ForyDefault for Vec<T> {
     }
 }
 
+// Written exactly like `Vec<T>`. Reading always yields `Cow::Owned`, and reading into an
+// owned value reuses its `Vec`.
+impl<T: Serializer + ForyDefault + Clone> Serializer for Cow<'static, [T]> {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        match check_primitive::<T>() {
+            Some(_) => primitive_list::fory_write_data(self, context),
+            None => write_collection(self.iter(), context, is_field),
+        }
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        Vec::<T>::fory_write_type_info(context, is_field)
+    }
+
+    fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Ok(Cow::Owned(Vec::<T>::fory_read_data(context, is_field)?))
+    }
+
+    fn fory_read_data_into(
+        &mut self,
+        context: &mut ReadContext,
+        is_field: bool,
+    ) -> Result<(), Error> {
+        match self {
+            Cow::Owned(vec) => vec.fory_read_data_into(context, is_field),
+            Cow::Borrowed(_) => {
+                *self = Self::fory_read_data(context, is_field)?;
+                Ok(())
+            }
+        }
+    }
+
+    fn fory_read_into(&mut self, context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_ref_info_data_into(self, context, is_field, false, false)
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        Vec::<T>::fory_skip(context)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        Vec::<T>::fory_read_type_info(context, is_field)
+    }
+
+    fn fory_reserved_space() -> usize {
+        Vec::<T>::fory_reserved_space()
+    }
+
+    fn fory_get_type_id(type_resolver: &TypeResolver) -> Result<u32, Error> {
+        Vec::<T>::fory_get_type_id(type_resolver)
+    }
+
+    fn fory_type_id_dyn(&self, type_resolver: &TypeResolver) -> Result<u32, Error> {
+        Vec::<T>::fory_get_type_id(type_resolver)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<T: Clone> ForyDefault for Cow<'static, [T]> {
+    fn fory_default() -> Self {
+        Cow::Borrowed(&[])
+    }
+}
+
 impl<T: Serializer + ForyDefault> Serializer for VecDeque<T> {
+    crate::read_null_as_default!();
+
+    /// With [`Fory::with_deque_capacity_hint`](crate::fory::Fory::with_deque_capacity_hint),
+    /// the capacity follows the list header, flagged with
+    /// [`CAPACITY_HINT`](super::collection::CAPACITY_HINT).
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
-        write_collection(self, context, is_field)
+        let capacity = context.is_deque_capacity_hint().then_some(self.capacity());
+        write_collection_with_capacity_hint(self, capacity, context, is_field)
     }
 
     fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
@@ -117,7 +231,21 @@ impl<T: Serializer + ForyDefault> Serializer for VecDeque<T> {
     }
 
     fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
-        read_collection(context)
+        let mut deque = VecDeque::new();
+        read_vec_deque_data_into(&mut deque, context)?;
+        Ok(deque)
+    }
+
+    fn fory_read_data_into(
+        &mut self,
+        context: &mut ReadContext,
+        _is_field: bool,
+    ) -> Result<(), Error> {
+        read_vec_deque_data_into(self, context)
+    }
+
+    fn fory_read_into(&mut self, context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_ref_info_data_into(self, context, is_field, false, false)
     }
 
     fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
@@ -148,6 +276,8 @@ impl<T> ForyDefault for VecDeque<T> {
 }
 
 impl<T: Serializer + ForyDefault> Serializer for LinkedList<T> {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         write_collection(self, context, is_field)
     }
@@ -0,0 +1,189 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::any::Any;
+
+/// The severity of a [`LogRecord`], ordered from the most to the least severe like the
+/// levels of the `log` crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum LogLevel {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl TryFrom<u8> for LogLevel {
+    type Error = Error;
+
+    fn try_from(level: u8) -> Result<Self, Error> {
+        Ok(match level {
+            1 => LogLevel::Error,
+            2 => LogLevel::Warn,
+            3 => LogLevel::Info,
+            4 => LogLevel::Debug,
+            5 => LogLevel::Trace,
+            _ => {
+                return Err(Error::InvalidData(
+                    format!("unknown log level {}", level).into(),
+                ))
+            }
+        })
+    }
+}
+
+/// Key-value fields whose values are dynamic, as held by [`LogRecord`].
+pub type Fields = Vec<(String, Box<dyn Any>)>;
+
+/// A pre-formatted structured log record, for shipping logs between processes.
+///
+/// The record is written as the level byte, the target and the message, followed by the
+/// number of key-value fields and each key and value. Values are dynamic: each carries
+/// its own type info, so a field can hold any type a `Box<dyn Any>` can, including
+/// registered structs.
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::serializer::log_record::{LogLevel, LogRecord};
+/// use fory_core::Fory;
+///
+/// let fory = Fory::default();
+/// let record = LogRecord::new(LogLevel::Warn, "db::pool", "connection slow")
+///     .with_field("latency_ms", 250i64);
+/// let bytes = fory.serialize(&record).unwrap();
+/// let record: LogRecord = fory.deserialize(&bytes).unwrap();
+/// assert_eq!(record.level, LogLevel::Warn);
+/// assert_eq!(record.field::<i64>("latency_ms"), Some(&250));
+/// ```
+#[derive(Debug)]
+pub struct LogRecord {
+    pub level: LogLevel,
+    pub target: String,
+    pub message: String,
+    pub fields: Fields,
+}
+
+impl LogRecord {
+    /// Creates a record without fields.
+    pub fn new(level: LogLevel, target: impl Into<String>, message: impl Into<String>) -> Self {
+        LogRecord {
+            level,
+            target: target.into(),
+            message: message.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Appends a key-value field.
+    pub fn with_field(mut self, key: impl Into<String>, value: impl Any) -> Self {
+        self.fields.push((key.into(), Box::new(value)));
+        self
+    }
+
+    /// Returns the value of the first field named `key`, if it holds a `T`.
+    pub fn field<T: 'static>(&self, key: &str) -> Option<&T> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .and_then(|(_, value)| value.downcast_ref::<T>())
+    }
+}
+
+/// Writes the number of key-value fields and each key and dynamic value.
+pub(crate) fn write_fields(
+    fields: &[(String, Box<dyn Any>)],
+    context: &mut WriteContext,
+    is_field: bool,
+) -> Result<(), Error> {
+    context.writer.write_varuint32(fields.len() as u32);
+    for (key, value) in fields {
+        key.fory_write_data(context, is_field)?;
+        value.fory_write(context, false)?;
+    }
+    Ok(())
+}
+
+/// Reads the fields written by [`write_fields`].
+pub(crate) fn read_fields(context: &mut ReadContext, is_field: bool) -> Result<Fields, Error> {
+    let len = context.reader.read_varuint32()?;
+    let mut fields = Vec::new();
+    for _ in 0..len {
+        let key = String::fory_read_data(context, is_field)?;
+        let value = <Box<dyn Any> as Serializer>::fory_read(context, false)?;
+        fields.push((key, value));
+    }
+    Ok(fields)
+}
+
+impl Serializer for LogRecord {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        context.writer.write_u8(self.level as u8);
+        self.target.fory_write_data(context, is_field)?;
+        self.message.fory_write_data(context, is_field)?;
+        write_fields(&self.fields, context, is_field)
+    }
+
+    fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        let level = LogLevel::try_from(context.reader.read_u8()?)?;
+        let target = String::fory_read_data(context, is_field)?;
+        let message = String::fory_read_data(context, is_field)?;
+        let fields = read_fields(context, is_field)?;
+        Ok(LogRecord {
+            level,
+            target,
+            message,
+            fields,
+        })
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+impl ForyDefault for LogRecord {
+    fn fory_default() -> Self {
+        LogRecord::new(LogLevel::Info, "", "")
+    }
+}
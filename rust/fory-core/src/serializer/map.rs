@@ -19,11 +19,14 @@ use crate::ensure;
 use crate::error::Error;
 use crate::resolver::context::{ReadContext, WriteContext};
 use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::collection::capped_len;
+use crate::serializer::string::{check_string_len, write_str_data};
 use crate::serializer::{
-    read_ref_info_data, read_type_info, write_ref_info_data, write_type_info, ForyDefault,
-    Serializer,
+    read_ref_info_data, read_ref_info_data_into, read_type_info, write_data_to_vec,
+    write_ref_info_data, write_type_info, ForyDefault, Serializer,
 };
-use crate::types::{TypeId, SIZE_OF_REF_AND_TYPE};
+use crate::types::{RefFlag, TypeId, SIZE_OF_REF_AND_TYPE};
+use std::cmp::Ordering;
 use std::collections::{BTreeMap, HashMap};
 
 const MAX_CHUNK_SIZE: u8 = 255;
@@ -34,6 +37,9 @@ const DECL_KEY_TYPE: u8 = 0b100;
 const TRACKING_VALUE_REF: u8 = 0b1000;
 pub const VALUE_NULL: u8 = 0b10000;
 const DECL_VALUE_TYPE: u8 = 0b100000;
+/// Each `String` key of the chunk is preceded by the length of the prefix it shares
+/// with the previous key of the chunk, and only the remaining suffix is written.
+pub const KEY_PREFIX_SHARED: u8 = 0b1000000;
 
 fn check_and_write_null<K: Serializer + Eq + std::hash::Hash, V: Serializer>(
     context: &mut WriteContext,
@@ -82,16 +88,26 @@ fn write_chunk_size(context: &mut WriteContext, header_offset: usize, size: u8)
     context.writer.set_bytes(header_offset + 1, &[size]);
 }
 
-fn write_map_data<'a, K, V, I>(
+/// Returns the length of the longest common prefix of `a` and `b` that ends on a char
+/// boundary.
+fn shared_prefix_len(a: &str, b: &str) -> usize {
+    let mut len = a.bytes().zip(b.bytes()).take_while(|(x, y)| x == y).count();
+    while !b.is_char_boundary(len) {
+        len -= 1;
+    }
+    len
+}
+
+pub(crate) fn write_map_data<'a, K, V, I>(
     iter: I,
     length: usize,
-
+    share_key_prefix: bool,
     context: &mut WriteContext,
     is_field: bool,
 ) -> Result<(), Error>
 where
-    K: Serializer + ForyDefault + 'a + Eq + std::hash::Hash,
-    V: Serializer + ForyDefault + 'a,
+    K: Serializer + 'a + Eq + std::hash::Hash,
+    V: Serializer + 'a,
     I: Iterator<Item = (&'a K, &'a V)>,
 {
     context.writer.write_varuint32(length as u32);
@@ -107,6 +123,9 @@ where
     let mut need_write_header = true;
     let mut skip_key_ref_flag = false;
     let mut skip_val_ref_flag = false;
+    let share_key_prefix =
+        share_key_prefix && std::any::TypeId::of::<K>() == std::any::TypeId::of::<String>();
+    let mut prev_key: &str = "";
     for (key, value) in iter {
         if need_write_header {
             if check_and_write_null(context, is_field, key, value)? {
@@ -126,6 +145,10 @@ where
             if !skip_val_ref_flag {
                 chunk_header |= TRACKING_VALUE_REF;
             }
+            if share_key_prefix {
+                chunk_header |= KEY_PREFIX_SHARED;
+                prev_key = "";
+            }
             K::fory_write_type_info(context, is_field)?;
             V::fory_write_type_info(context, is_field)?;
             context.writer.set_bytes(header_offset, &[chunk_header]);
@@ -138,7 +161,15 @@ where
             check_and_write_null(context, is_field, key, value)?;
             continue;
         }
-        if K::fory_is_polymorphic() || K::fory_is_shared_ref() {
+        if share_key_prefix {
+            let key = (key as &dyn std::any::Any)
+                .downcast_ref::<String>()
+                .unwrap();
+            let shared = shared_prefix_len(prev_key, key);
+            context.writer.write_varuint32(shared as u32);
+            write_str_data(&key[shared..], context)?;
+            prev_key = key;
+        } else if K::fory_is_polymorphic() || K::fory_is_shared_ref() {
             key.fory_write(context, is_field)?;
         } else {
             write_ref_info_data(key, context, is_field, skip_key_ref_flag, true)?;
@@ -161,86 +192,267 @@ where
     Ok(())
 }
 
+/// Sorts the entries by key using `Serializer::fory_cmp_serialized`. Keys compared by
+/// their serialized bytes are serialized once each, before sorting.
+fn sort_entries<K: Serializer, V>(
+    entries: &mut [(&K, &V)],
+    context: &mut WriteContext,
+) -> Result<(), Error> {
+    if K::fory_cmp_serialized_bytes() {
+        let mut keyed = entries
+            .iter()
+            .map(|&entry| Ok((write_data_to_vec(entry.0, context)?, entry)))
+            .collect::<Result<Vec<_>, Error>>()?;
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+        for (slot, (_, entry)) in entries.iter_mut().zip(keyed) {
+            *slot = entry;
+        }
+        return Ok(());
+    }
+    let mut error = None;
+    entries.sort_by(|a, b| match a.0.fory_cmp_serialized(b.0, context) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            error.get_or_insert(e);
+            Ordering::Equal
+        }
+    });
+    error.map_or(Ok(()), Err)
+}
+
+/// Refills `map` in place. Existing entries are dropped but the allocation is kept,
+/// and room for the incoming entries is reserved up front, so the capacity never
+/// shrinks and is at least the incoming length afterwards.
+fn read_hashmap_data_into<K, V>(
+    map: &mut HashMap<K, V>,
+    context: &mut ReadContext,
+) -> Result<(), Error>
+where
+    K: Serializer + ForyDefault + Eq + std::hash::Hash,
+    V: Serializer + ForyDefault,
+{
+    map.clear();
+    merge_hashmap_data(map, context)
+}
+
+/// Reads a map value, including its ref flag, and inserts its entries into `map`, keeping
+/// the entries whose keys the data does not have. A null map leaves `map` unchanged.
+pub fn merge_hashmap<K, V>(map: &mut HashMap<K, V>, context: &mut ReadContext) -> Result<(), Error>
+where
+    K: Serializer + ForyDefault + Eq + std::hash::Hash,
+    V: Serializer + ForyDefault,
+{
+    let ref_flag = context.reader.read_i8()?;
+    if ref_flag == RefFlag::Null as i8 {
+        return Ok(());
+    }
+    ensure!(
+        ref_flag == RefFlag::NotNullValue as i8 || ref_flag == RefFlag::RefValue as i8,
+        Error::InvalidRef(format!("Unknown ref flag, value:{ref_flag}").into())
+    );
+    HashMap::<K, V>::fory_read_type_info(context, false)?;
+    merge_hashmap_data(map, context)
+}
+
+/// Counterpart of [`read_hashmap_data_into`] that does not clear `map` first.
+fn merge_hashmap_data<K, V>(map: &mut HashMap<K, V>, context: &mut ReadContext) -> Result<(), Error>
+where
+    K: Serializer + ForyDefault + Eq + std::hash::Hash,
+    V: Serializer + ForyDefault,
+{
+    let len = context.reader.read_varuint32()?;
+    map.reserve(capped_len(context, len as usize));
+    read_map_entries(context, len, |key, value| {
+        map.insert(key, value);
+    })
+}
+
+/// Reads a map value, including its ref flag, into its entries sorted by key. A null map
+/// reads as no entries.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidData`] if a key occurs more than once.
+pub fn read_sorted_entries<K, V>(context: &mut ReadContext) -> Result<Vec<(K, V)>, Error>
+where
+    K: Serializer + ForyDefault + Ord,
+    V: Serializer + ForyDefault,
+{
+    let ref_flag = context.reader.read_i8()?;
+    if ref_flag == RefFlag::Null as i8 {
+        return Ok(Vec::new());
+    }
+    ensure!(
+        ref_flag == RefFlag::NotNullValue as i8 || ref_flag == RefFlag::RefValue as i8,
+        Error::InvalidRef(format!("Unknown ref flag, value:{ref_flag}").into())
+    );
+    let remote_type_id = context.read_type_id()?;
+    ensure!(
+        remote_type_id == TypeId::MAP as u32,
+        Error::TypeMismatch(TypeId::MAP as u32, remote_type_id)
+    );
+    let len = context.reader.read_varuint32()?;
+    let mut entries: Vec<(K, V)> = Vec::with_capacity(capped_len(context, len as usize));
+    read_map_entries(context, len, |key, value| entries.push((key, value)))?;
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    ensure!(
+        entries.windows(2).all(|pair| pair[0].0 != pair[1].0),
+        Error::InvalidData("duplicate key in serialized map".into())
+    );
+    Ok(entries)
+}
+
+/// Reads the `len` entries of map data and hands them to `insert` in the order they were
+/// written.
+pub(crate) fn read_map_entries<K, V>(
+    context: &mut ReadContext,
+    len: u32,
+    mut insert: impl FnMut(K, V),
+) -> Result<(), Error>
+where
+    K: Serializer + ForyDefault,
+    V: Serializer + ForyDefault,
+{
+    if len == 0 {
+        return Ok(());
+    }
+    let mut len_counter = 0;
+    let mut prev_key = String::new();
+    loop {
+        if len_counter == len {
+            break;
+        }
+        let header = context.reader.read_u8()?;
+        if header & KEY_NULL != 0 && header & VALUE_NULL != 0 {
+            insert(K::fory_default(), V::fory_default());
+            len_counter += 1;
+            continue;
+        }
+        let key_declared = (header & DECL_KEY_TYPE) != 0;
+        let value_declared = (header & DECL_VALUE_TYPE) != 0;
+        if header & KEY_NULL != 0 {
+            let skip_ref_flag = if value_declared {
+                crate::serializer::get_skip_ref_flag::<V>(context.get_type_resolver())?
+            } else {
+                false
+            };
+            let value = read_ref_info_data(context, value_declared, skip_ref_flag, false)?;
+            insert(K::fory_default(), value);
+            len_counter += 1;
+            continue;
+        }
+        if header & VALUE_NULL != 0 {
+            let skip_ref_flag = if key_declared {
+                crate::serializer::get_skip_ref_flag::<K>(context.get_type_resolver())?
+            } else {
+                false
+            };
+            let key = read_ref_info_data(context, key_declared, skip_ref_flag, false)?;
+            insert(key, V::fory_default());
+            len_counter += 1;
+            continue;
+        }
+        let chunk_size = context.reader.read_u8()?;
+        K::fory_read_type_info(context, key_declared)?;
+        V::fory_read_type_info(context, value_declared)?;
+
+        let cur_len = len_counter + chunk_size as u32;
+        ensure!(
+            cur_len <= len,
+            Error::InvalidData(
+                format!("current length {} exceeds total length {}", cur_len, len).into()
+            )
+        );
+        assert!(len_counter + chunk_size as u32 <= len);
+        let key_prefix_shared = header & KEY_PREFIX_SHARED != 0;
+        prev_key.clear();
+        for _ in 0..chunk_size {
+            let key = if key_prefix_shared {
+                read_prefix_shared_key(context, &mut prev_key)?
+            } else if K::fory_is_polymorphic() {
+                K::fory_read(context, key_declared)?
+            } else {
+                // let skip_ref_flag = crate::serializer::get_skip_ref_flag::<K>(context.get_fory());
+                read_ref_info_data(context, key_declared, true, true)?
+            };
+            let value = if V::fory_is_polymorphic() {
+                match V::fory_read(context, value_declared) {
+                    // a trait object of an unknown type skipped under the policy
+                    Err(Error::SkippedUnknownType(_)) => continue,
+                    value => value?,
+                }
+            } else {
+                // let skip_ref_flag = crate::serializer::get_skip_ref_flag::<V>(context.get_fory());
+                read_ref_info_data(context, value_declared, true, true)?
+            };
+            insert(key, value);
+        }
+        len_counter += chunk_size as u32;
+    }
+    Ok(())
+}
+
+/// Reads a key written under [`KEY_PREFIX_SHARED`], rebuilding it from the prefix of
+/// `prev_key`, which then holds the new key.
+pub(crate) fn read_prefix_shared_key<K: 'static>(
+    context: &mut ReadContext,
+    prev_key: &mut String,
+) -> Result<K, Error> {
+    let shared = context.reader.read_varuint32()? as usize;
+    ensure!(
+        prev_key.is_char_boundary(shared),
+        Error::InvalidData(
+            format!(
+                "shared key prefix {} is not within previous key of {} bytes",
+                shared,
+                prev_key.len()
+            )
+            .into()
+        )
+    );
+    let suffix = String::fory_read_data(context, false)?;
+    check_string_len(context, (shared + suffix.len()) as u64)?;
+    prev_key.truncate(shared);
+    prev_key.push_str(&suffix);
+    let mut key = Some(prev_key.clone());
+    (&mut key as &mut dyn std::any::Any)
+        .downcast_mut::<Option<K>>()
+        .and_then(Option::take)
+        .ok_or_else(|| Error::InvalidData("prefix-shared map keys must be String".into()))
+}
+
 impl<K: Serializer + ForyDefault + Eq + std::hash::Hash, V: Serializer + ForyDefault> Serializer
     for HashMap<K, V>
 {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
-        write_map_data(self.iter(), self.len(), context, is_field)
+        if context.is_deterministic() {
+            let mut entries: Vec<_> = self.iter().collect();
+            sort_entries(&mut entries, context)?;
+            return write_map_data(entries.into_iter(), self.len(), false, context, is_field);
+        }
+        write_map_data(self.iter(), self.len(), false, context, is_field)
     }
 
     fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
-        let len = context.reader.read_varuint32()?;
-        let mut map = HashMap::<K, V>::with_capacity(len as usize);
-        if len == 0 {
-            return Ok(map);
-        }
-        let mut len_counter = 0;
-        loop {
-            if len_counter == len {
-                break;
-            }
-            let header = context.reader.read_u8()?;
-            if header & KEY_NULL != 0 && header & VALUE_NULL != 0 {
-                map.insert(K::fory_default(), V::fory_default());
-                len_counter += 1;
-                continue;
-            }
-            let key_declared = (header & DECL_KEY_TYPE) != 0;
-            let value_declared = (header & DECL_VALUE_TYPE) != 0;
-            if header & KEY_NULL != 0 {
-                let skip_ref_flag = if value_declared {
-                    crate::serializer::get_skip_ref_flag::<V>(context.get_type_resolver())?
-                } else {
-                    false
-                };
-                let value = read_ref_info_data(context, value_declared, skip_ref_flag, false)?;
-                map.insert(K::fory_default(), value);
-                len_counter += 1;
-                continue;
-            }
-            if header & VALUE_NULL != 0 {
-                let skip_ref_flag = if key_declared {
-                    crate::serializer::get_skip_ref_flag::<K>(context.get_type_resolver())?
-                } else {
-                    false
-                };
-                let key = read_ref_info_data(context, key_declared, skip_ref_flag, false)?;
-                map.insert(key, V::fory_default());
-                len_counter += 1;
-                continue;
-            }
-            let chunk_size = context.reader.read_u8()?;
-            K::fory_read_type_info(context, key_declared)?;
-            V::fory_read_type_info(context, value_declared)?;
-
-            let cur_len = len_counter + chunk_size as u32;
-            ensure!(
-                cur_len <= len,
-                Error::InvalidData(
-                    format!("current length {} exceeds total length {}", cur_len, len).into()
-                )
-            );
-            assert!(len_counter + chunk_size as u32 <= len);
-            for _ in 0..chunk_size {
-                let key = if K::fory_is_polymorphic() {
-                    K::fory_read(context, key_declared)?
-                } else {
-                    // let skip_ref_flag = crate::serializer::get_skip_ref_flag::<K>(context.get_fory());
-                    read_ref_info_data(context, key_declared, true, true)?
-                };
-                let value = if V::fory_is_polymorphic() {
-                    V::fory_read(context, value_declared)?
-                } else {
-                    // let skip_ref_flag = crate::serializer::get_skip_ref_flag::<V>(context.get_fory());
-                    read_ref_info_data(context, value_declared, true, true)?
-                };
-                map.insert(key, value);
-            }
-            len_counter += chunk_size as u32;
-        }
+        let mut map = HashMap::<K, V>::new();
+        read_hashmap_data_into(&mut map, context)?;
         Ok(map)
     }
 
+    fn fory_read_data_into(
+        &mut self,
+        context: &mut ReadContext,
+        _is_field: bool,
+    ) -> Result<(), Error> {
+        read_hashmap_data_into(self, context)
+    }
+
+    fn fory_read_into(&mut self, context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_ref_info_data_into(self, context, is_field, false, false)
+    }
+
     fn fory_reserved_space() -> usize {
         size_of::<i32>()
     }
@@ -272,11 +484,21 @@ impl<K, V> ForyDefault for HashMap<K, V> {
     }
 }
 
+/// Entries are written in key order, so equal maps always produce identical bytes
+/// without the deterministic mode the unordered `HashMap` needs.
 impl<K: Serializer + ForyDefault + Ord + std::hash::Hash, V: Serializer + ForyDefault> Serializer
     for BTreeMap<K, V>
 {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
-        write_map_data(self.iter(), self.len(), context, is_field)
+        write_map_data(
+            self.iter(),
+            self.len(),
+            context.is_map_key_prefix_sharing(),
+            context,
+            is_field,
+        )
     }
 
     fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
@@ -286,6 +508,7 @@ impl<K: Serializer + ForyDefault + Ord + std::hash::Hash, V: Serializer + ForyDe
             return Ok(map);
         }
         let mut len_counter = 0;
+        let mut prev_key = String::new();
         loop {
             if len_counter == len {
                 break;
@@ -324,14 +547,22 @@ impl<K: Serializer + ForyDefault + Ord + std::hash::Hash, V: Serializer + ForyDe
             K::fory_read_type_info(context, key_declared)?;
             V::fory_read_type_info(context, value_declared)?;
             assert!(len_counter + chunk_size as u32 <= len);
+            let key_prefix_shared = header & KEY_PREFIX_SHARED != 0;
+            prev_key.clear();
             for _ in 0..chunk_size {
-                let key = if K::fory_is_polymorphic() {
+                let key = if key_prefix_shared {
+                    read_prefix_shared_key(context, &mut prev_key)?
+                } else if K::fory_is_polymorphic() {
                     K::fory_read(context, key_declared)?
                 } else {
                     read_ref_info_data(context, key_declared, true, true)?
                 };
                 let value = if V::fory_is_polymorphic() {
-                    V::fory_read(context, value_declared)?
+                    match V::fory_read(context, value_declared) {
+                        // a trait object of an unknown type skipped under the policy
+                        Err(Error::SkippedUnknownType(_)) => continue,
+                        value => value?,
+                    }
                 } else {
                     read_ref_info_data(context, value_declared, true, true)?
                 };
@@ -21,28 +21,64 @@ use crate::resolver::context::{ReadContext, WriteContext};
 use crate::types::{RefFlag, TypeId, PRIMITIVE_TYPES};
 use crate::{ensure, TypeResolver};
 use std::any::Any;
+use std::cmp::Ordering;
 
 pub mod any;
 mod arc;
+mod array;
+#[cfg(feature = "arrayvec")]
+mod arrayvec;
 mod bool;
+pub mod borrowed;
+mod bound;
 mod box_;
+pub mod cipher;
+pub mod codec;
 pub mod collection;
+#[cfg(feature = "compact_str")]
+mod compact_str;
+mod control_flow;
 mod datetime;
+pub mod duration;
 pub mod enum_;
+pub mod error_payload;
+mod ffi;
+#[cfg(feature = "bitflags")]
+pub mod flags;
+pub mod handler;
 mod heap;
+#[cfg(feature = "indexmap")]
+mod index_set;
+#[cfg(feature = "json")]
+pub mod json;
 mod list;
+pub mod log_record;
 pub mod map;
 mod mutex;
-mod number;
+pub mod number;
 mod option;
-mod primitive_list;
+pub mod ordered_map;
+pub mod pre_encoded;
+pub(crate) mod primitive_list;
 mod rc;
 mod refcell;
+mod reverse;
+#[cfg(feature = "saturating")]
+mod saturating;
+#[cfg(feature = "semver")]
+mod semver;
 mod set;
 pub mod skip;
+pub mod sorted_set;
+mod static_str;
 mod string;
 pub mod struct_;
+#[cfg(feature = "time")]
+mod time_;
 pub mod trait_object;
+pub mod tuple;
+#[cfg(feature = "url")]
+mod url;
 pub mod weak;
 
 #[inline(always)]
@@ -73,11 +109,42 @@ pub fn read_ref_info_data<T: Serializer + ForyDefault>(
     is_field: bool,
     skip_ref_flag: bool,
     skip_type_info: bool,
+) -> Result<T, Error> {
+    read_ref_info_data_or(context, is_field, skip_ref_flag, skip_type_info, || {
+        Ok(T::fory_default())
+    })
+}
+
+/// Like [`read_ref_info_data`], for types that may not implement [`ForyDefault`]. A null
+/// is read with [`Serializer::fory_read_null`] instead of as the default value.
+#[inline(always)]
+pub fn read_ref_info_data_without_default<T: Serializer>(
+    context: &mut ReadContext,
+    is_field: bool,
+    skip_ref_flag: bool,
+    skip_type_info: bool,
+) -> Result<T, Error> {
+    read_ref_info_data_or(
+        context,
+        is_field,
+        skip_ref_flag,
+        skip_type_info,
+        T::fory_read_null,
+    )
+}
+
+#[inline(always)]
+pub(crate) fn read_ref_info_data_or<T: Serializer>(
+    context: &mut ReadContext,
+    is_field: bool,
+    skip_ref_flag: bool,
+    skip_type_info: bool,
+    null: impl FnOnce() -> Result<T, Error>,
 ) -> Result<T, Error> {
     if !skip_ref_flag {
         let ref_flag = context.reader.read_i8()?;
         if ref_flag == RefFlag::Null as i8 {
-            Ok(T::fory_default())
+            null()
         } else if ref_flag == (RefFlag::NotNullValue as i8) {
             if !skip_type_info {
                 T::fory_read_type_info(context, is_field)?;
@@ -92,9 +159,11 @@ pub fn read_ref_info_data<T: Serializer + ForyDefault>(
         } else if ref_flag == (RefFlag::Ref as i8) {
             // This is a reference to a previously deserialized object
             // For now, just return default - this should be handled by specific types
-            Ok(T::fory_default())
+            null()
         } else {
-            unimplemented!("Unknown ref flag: {}", ref_flag)
+            Err(Error::InvalidRef(
+                format!("Unknown ref flag, value:{ref_flag}").into(),
+            ))
         }
     } else {
         if !skip_type_info {
@@ -104,6 +173,32 @@ pub fn read_ref_info_data<T: Serializer + ForyDefault>(
     }
 }
 
+/// Counterpart of [`read_ref_info_data`] that refills `target` in place.
+#[inline(always)]
+pub fn read_ref_info_data_into<T: Serializer + ForyDefault>(
+    target: &mut T,
+    context: &mut ReadContext,
+    is_field: bool,
+    skip_ref_flag: bool,
+    skip_type_info: bool,
+) -> Result<(), Error> {
+    if !skip_ref_flag {
+        let ref_flag = context.reader.read_i8()?;
+        if ref_flag == RefFlag::Null as i8 || ref_flag == RefFlag::Ref as i8 {
+            *target = T::fory_default();
+            return Ok(());
+        } else if ref_flag != RefFlag::NotNullValue as i8 && ref_flag != RefFlag::RefValue as i8 {
+            return Err(Error::InvalidRef(
+                format!("Unknown ref flag, value:{ref_flag}").into(),
+            ));
+        }
+    }
+    if !skip_type_info {
+        T::fory_read_type_info(context, is_field)?;
+    }
+    target.fory_read_data_into(context, is_field)
+}
+
 #[inline(always)]
 pub fn write_type_info<T: Serializer>(
     context: &mut WriteContext,
@@ -126,7 +221,7 @@ pub fn read_type_info<T: Serializer>(
         return Ok(());
     }
     let local_type_id = T::fory_get_type_id(context.get_type_resolver())?;
-    let remote_type_id = context.reader.read_varuint32()?;
+    let remote_type_id = context.read_type_id()?;
     ensure!(
         local_type_id == remote_type_id,
         Error::TypeMismatch(local_type_id, remote_type_id)
@@ -134,6 +229,15 @@ pub fn read_type_info<T: Serializer>(
     Ok(())
 }
 
+/// Writes the data of `value` into a scratch buffer and returns the bytes, leaving the
+/// context untouched, see [`WriteContext::write_isolated`].
+pub fn write_data_to_vec<T: Serializer>(
+    value: &T,
+    context: &mut WriteContext,
+) -> Result<Vec<u8>, Error> {
+    context.write_isolated(|context| value.fory_write_data(context, true))
+}
+
 #[inline(always)]
 pub fn get_skip_ref_flag<T: Serializer>(type_resolver: &TypeResolver) -> Result<bool, Error> {
     let elem_type_id = T::fory_get_type_id(type_resolver)?;
@@ -144,6 +248,36 @@ pub trait ForyDefault: Sized {
     fn fory_default() -> Self;
 }
 
+/// Containers that can be emptied in place while keeping their allocation, so that generic
+/// code can refill them instead of building new ones.
+pub trait Clear {
+    fn clear(&mut self);
+}
+
+impl<T> Clear for Vec<T> {
+    fn clear(&mut self) {
+        Vec::clear(self)
+    }
+}
+
+impl Clear for String {
+    fn clear(&mut self) {
+        String::clear(self)
+    }
+}
+
+impl<K, V> Clear for std::collections::HashMap<K, V> {
+    fn clear(&mut self) {
+        std::collections::HashMap::clear(self)
+    }
+}
+
+impl<T> Clear for std::collections::HashSet<T> {
+    fn clear(&mut self) {
+        std::collections::HashSet::clear(self)
+    }
+}
+
 // We can't add blanket impl for all T: Default because it conflicts with other impls.
 // For example, upstream crates may add a new impl of trait `std::default::Default` for
 // type `std::rc::Rc<(dyn std::any::Any + 'static)>` in future versions.
@@ -153,6 +287,25 @@ pub trait ForyDefault: Sized {
 //     }
 // }
 
+/// Implements [`Serializer::fory_read_null`] as the type's [`ForyDefault`], which is what
+/// [`read_ref_info_data`] reads a null as. Every built-in serializer and `#[derive(ForyObject)]`
+/// use it; manual implementations that also implement [`ForyDefault`] can do the same:
+///
+/// ```rust,ignore
+/// impl Serializer for MyType {
+///     fory_core::read_null_as_default!();
+///     // ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! read_null_as_default {
+    () => {
+        fn fory_read_null() -> Result<Self, $crate::error::Error> {
+            Ok(<Self as $crate::serializer::ForyDefault>::fory_default())
+        }
+    };
+}
+
 pub trait Serializer: 'static {
     /// Entry point of the serialization.
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error>
@@ -169,6 +322,36 @@ pub trait Serializer: 'static {
         read_ref_info_data(context, is_field, false, false)
     }
 
+    /// Entry point of deserializing into an existing value, the counterpart of `fory_read`.
+    /// Types that keep the default `fory_read` can override this with
+    /// [`read_ref_info_data_into`] to reuse their allocations.
+    fn fory_read_into(&mut self, context: &mut ReadContext, is_field: bool) -> Result<(), Error>
+    where
+        Self: Sized + ForyDefault,
+    {
+        *self = Self::fory_read(context, is_field)?;
+        Ok(())
+    }
+
+    /// Counterpart of `fory_read` for callers that cannot require [`ForyDefault`], such as
+    /// the elements of a `Vec<T>`. Types that override `fory_read` to track references
+    /// override this to call it.
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error>
+    where
+        Self: Sized,
+    {
+        read_ref_info_data_without_default(context, is_field, false, false)
+    }
+
+    /// The value a null reads as where no [`ForyDefault`] is required, such as the elements
+    /// of a `Vec<T>`. Types that have a `ForyDefault` implement this with
+    /// [`read_null_as_default!`](crate::read_null_as_default) to read a null like
+    /// [`read_ref_info_data`] does; types without one reject the null, e.g. with
+    /// [`Error::InvalidData`].
+    fn fory_read_null() -> Result<Self, Error>
+    where
+        Self: Sized;
+
     fn fory_is_option() -> bool
     where
         Self: Sized,
@@ -244,7 +427,7 @@ pub trait Serializer: 'static {
     {
         // default implementation only for ext/named_ext
         let local_type_id = Self::fory_get_type_id(context.get_type_resolver())?;
-        let remote_type_id = context.reader.read_varuint32()?;
+        let remote_type_id = context.read_type_id()?;
         ensure!(
             local_type_id == remote_type_id,
             Error::TypeMismatch(local_type_id, remote_type_id)
@@ -267,7 +450,7 @@ pub trait Serializer: 'static {
         Self: Sized,
     {
         // default logic only for ext/named_ext
-        let remote_type_id = context.reader.read_varuint32()?;
+        let remote_type_id = context.read_type_id()?;
         let local_type_id = Self::fory_get_type_id(context.get_type_resolver())?;
         ensure!(
             local_type_id == remote_type_id,
@@ -312,7 +495,58 @@ pub trait Serializer: 'static {
 
     fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error>
     where
-        Self: Sized + ForyDefault;
+        Self: Sized;
+
+    /// Reads the data into an existing value. The default implementation replaces it,
+    /// containers override this to refill themselves and keep their allocation.
+    fn fory_read_data_into(
+        &mut self,
+        context: &mut ReadContext,
+        is_field: bool,
+    ) -> Result<(), Error>
+    where
+        Self: Sized + ForyDefault,
+    {
+        *self = Self::fory_read_data(context, is_field)?;
+        Ok(())
+    }
+
+    /// Skips the data written by `fory_write_data` without materializing the value.
+    /// Fixed-width types advance the cursor directly, variable-length types read their
+    /// length prefix and then skip the payload.
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error>
+    where
+        Self: Sized + ForyDefault,
+    {
+        Self::fory_read_data(context, true).map(|_| ())
+    }
+
+    /// Defines the total order used to sort values (e.g. map keys) in deterministic mode.
+    /// The default implementation compares the serialized bytes of both values
+    /// lexicographically, which works for floats and multi-field structs alike.
+    fn fory_cmp_serialized(
+        &self,
+        other: &Self,
+        context: &mut WriteContext,
+    ) -> Result<Ordering, Error>
+    where
+        Self: Sized,
+    {
+        let this_bytes = write_data_to_vec(self, context)?;
+        let other_bytes = write_data_to_vec(other, context)?;
+        Ok(this_bytes.cmp(&other_bytes))
+    }
+
+    /// Whether [`Serializer::fory_cmp_serialized`] compares the serialized bytes, as the
+    /// default implementation does. Sorting then serializes every value once up front
+    /// instead of on every comparison. Types that override `fory_cmp_serialized` return
+    /// `false`.
+    fn fory_cmp_serialized_bytes() -> bool
+    where
+        Self: Sized,
+    {
+        true
+    }
 
     fn fory_concrete_type_id(&self) -> std::any::TypeId {
         std::any::TypeId::of::<Self>()
@@ -336,4 +570,43 @@ pub trait StructSerializer: Serializer + 'static {
     fn fory_get_sorted_field_names() -> &'static [&'static str] {
         &[]
     }
+
+    /// Whether the fields are ordered by the type ids of their runtime types rather than
+    /// as in [`StructSerializer::fory_get_sorted_field_names`], as for a generic struct
+    /// whose field types depend on its type parameters. Such a struct reads and writes
+    /// its fields in the order of its registered type meta, and keeps the field ids of
+    /// its sorted names.
+    fn fory_sorts_fields_at_runtime() -> bool {
+        false
+    }
+
+    /// The fixed-width integer type id a `#[fory(repr)]` enum writes its discriminant as,
+    /// which the type meta of a struct records for fields of the enum so that a peer
+    /// without them skips the right number of bytes.
+    fn fory_repr_type_id() -> Option<u32> {
+        None
+    }
+}
+
+/// Structs that can be updated in place from a compatible-mode payload, assigning only the
+/// fields the payload carries and leaving the others untouched. Derive it with
+/// `#[derive(ForyMerge)]` next to `ForyObject`.
+pub trait Merge: StructSerializer + ForyDefault {
+    /// Reads a struct value, including its ref flag, and merges its fields into `self`.
+    fn fory_merge(&mut self, context: &mut ReadContext) -> Result<(), Error>;
+}
+
+/// A struct field whose value was changed by
+/// [`Fory::deserialize_into_diff`](crate::fory::Fory::deserialize_into_diff).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FieldChange {
+    pub field: &'static str,
+}
+
+/// Structs whose fields can be compared one by one, so that a refill can report which of
+/// them changed. Derive it with `#[derive(ForyDiff)]` next to `ForyObject`.
+pub trait Diff {
+    /// Returns the fields whose values differ between `self` and `other`, in declaration
+    /// order.
+    fn fory_diff(&self, other: &Self) -> Vec<FieldChange>;
 }
@@ -52,6 +52,8 @@ use std::sync::Mutex;
 /// Simply delegates to the serializer for `T`, allowing thread-safe interior mutable
 /// containers to be included in serialized graphs.
 impl<T: Serializer + ForyDefault> Serializer for Mutex<T> {
+    crate::read_null_as_default!();
+
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         // Don't add ref tracking for Mutex itself, just delegate to inner type
         // The inner type will handle its own ref tracking
@@ -17,15 +17,24 @@
 
 use crate::buffer::{Reader, Writer};
 use crate::error::Error;
+use crate::meta::FieldType;
 use crate::resolver::context::ReadContext;
 use crate::resolver::context::WriteContext;
 use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::primitive_list::read_padding;
+use crate::serializer::skip::{get_read_ref_flag, skip_field_value};
 use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
-use crate::types::TypeId;
+use crate::types::{Endian, RefFlag, TypeId};
+use std::cmp::Ordering;
 
 macro_rules! impl_num_serializer {
-    ($ty:ty, $writer:expr, $reader:expr, $field_type:expr) => {
+    ($ty:ty, $writer:expr, $reader:expr, $skip:expr, $field_type:expr) => {
+        impl_num_serializer!($ty, $writer, $reader, $skip, $field_type, keep);
+    };
+    ($ty:ty, $writer:expr, $reader:expr, $skip:expr, $field_type:expr, $canonical:expr $(, $cmp:expr)?) => {
         impl Serializer for $ty {
+            crate::read_null_as_default!();
+
             #[inline]
             fn fory_write_data(
                 &self,
@@ -33,7 +42,8 @@ macro_rules! impl_num_serializer {
                 context: &mut WriteContext,
                 _is_field: bool,
             ) -> Result<(), Error> {
-                $writer(&mut context.writer, *self);
+                let value = $canonical(context, *self);
+                $writer(&mut context.writer, value);
                 Ok(())
             }
 
@@ -42,6 +52,11 @@ macro_rules! impl_num_serializer {
                 $reader(&mut context.reader)
             }
 
+            #[inline]
+            fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+                $skip(context)
+            }
+
             #[inline]
             fn fory_reserved_space() -> usize {
                 std::mem::size_of::<$ty>()
@@ -61,6 +76,22 @@ macro_rules! impl_num_serializer {
                 self
             }
 
+            $(
+                #[inline]
+                fn fory_cmp_serialized(
+                    &self,
+                    other: &Self,
+                    context: &mut WriteContext,
+                ) -> Result<Ordering, Error> {
+                    Ok($cmp(context, *self, *other))
+                }
+
+                #[inline(always)]
+                fn fory_cmp_serialized_bytes() -> bool {
+                    false
+                }
+            )?
+
             #[inline]
             fn fory_write_type_info(
                 context: &mut WriteContext,
@@ -83,19 +114,373 @@ macro_rules! impl_num_serializer {
     };
 }
 
-impl_num_serializer!(i8, Writer::write_i8, Reader::read_i8, TypeId::INT8);
-impl_num_serializer!(i16, Writer::write_i16, Reader::read_i16, TypeId::INT16);
+#[inline(always)]
+fn keep<T>(_: &WriteContext, value: T) -> T {
+    value
+}
+
+/// Replaces any NaN with `f32::NAN` if the context asks for canonical NaNs.
+#[inline(always)]
+fn canonical_f32(context: &WriteContext, value: f32) -> f32 {
+    if context.is_canonical_nan() && value.is_nan() {
+        f32::NAN
+    } else {
+        value
+    }
+}
+
+/// Replaces any NaN with `f64::NAN` if the context asks for canonical NaNs.
+#[inline(always)]
+fn canonical_f64(context: &WriteContext, value: f64) -> f64 {
+    if context.is_canonical_nan() && value.is_nan() {
+        f64::NAN
+    } else {
+        value
+    }
+}
+
+/// Orders floats as written by `f32::total_cmp`, so that deterministic maps sort `-0.0`
+/// before `0.0` and NaNs by sign and payload beyond the infinities.
+#[inline(always)]
+fn total_cmp_f32(context: &WriteContext, a: f32, b: f32) -> Ordering {
+    canonical_f32(context, a).total_cmp(&canonical_f32(context, b))
+}
+
+/// Orders floats as written by `f64::total_cmp`, see [`total_cmp_f32`].
+#[inline(always)]
+fn total_cmp_f64(context: &WriteContext, a: f64, b: f64) -> Ordering {
+    canonical_f64(context, a).total_cmp(&canonical_f64(context, b))
+}
+
+#[inline(always)]
+fn skip_fixed<T>(context: &mut ReadContext) -> Result<(), Error> {
+    context.skip_bytes(std::mem::size_of::<T>())
+}
+
+#[inline(always)]
+fn skip_varint32(context: &mut ReadContext) -> Result<(), Error> {
+    context.reader.read_varint32().map(|_| ())
+}
+
+#[inline(always)]
+fn skip_varint64(context: &mut ReadContext) -> Result<(), Error> {
+    context.reader.read_varint64().map(|_| ())
+}
+
+impl_num_serializer!(
+    i8,
+    Writer::write_i8,
+    Reader::read_i8,
+    skip_fixed::<i8>,
+    TypeId::INT8
+);
+impl_num_serializer!(
+    i16,
+    Writer::write_i16,
+    Reader::read_i16,
+    skip_fixed::<i16>,
+    TypeId::INT16
+);
 impl_num_serializer!(
     i32,
     Writer::write_varint32,
     Reader::read_varint32,
+    skip_varint32,
     TypeId::INT32
 );
 impl_num_serializer!(
     i64,
     Writer::write_varint64,
     Reader::read_varint64,
+    skip_varint64,
     TypeId::INT64
 );
-impl_num_serializer!(f32, Writer::write_f32, Reader::read_f32, TypeId::FLOAT32);
-impl_num_serializer!(f64, Writer::write_f64, Reader::read_f64, TypeId::FLOAT64);
+impl_num_serializer!(
+    f32,
+    Writer::write_f32,
+    Reader::read_f32,
+    skip_fixed::<f32>,
+    TypeId::FLOAT32,
+    canonical_f32,
+    total_cmp_f32
+);
+impl_num_serializer!(
+    f64,
+    Writer::write_f64,
+    Reader::read_f64,
+    skip_fixed::<f64>,
+    TypeId::FLOAT64,
+    canonical_f64,
+    total_cmp_f64
+);
+
+/// Unsigned integers have no type ids of their own, so they are written as the signed
+/// integer of the same width with the same bits. Peers reading them as that signed type
+/// see values above its maximum as negative numbers.
+macro_rules! impl_unsigned_serializer {
+    ($($ty:ty => $signed:ty),+ $(,)?) => {
+        $(
+            impl Serializer for $ty {
+                crate::read_null_as_default!();
+
+                #[inline]
+                fn fory_write_data(
+                    &self,
+                    context: &mut WriteContext,
+                    is_field: bool,
+                ) -> Result<(), Error> {
+                    (*self as $signed).fory_write_data(context, is_field)
+                }
+
+                #[inline]
+                fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+                    <$signed>::fory_read_data(context, is_field).map(|v| v as $ty)
+                }
+
+                #[inline]
+                fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+                    <$signed>::fory_skip(context)
+                }
+
+                #[inline]
+                fn fory_reserved_space() -> usize {
+                    std::mem::size_of::<$ty>()
+                }
+
+                #[inline]
+                fn fory_get_type_id(type_resolver: &TypeResolver) -> Result<u32, Error> {
+                    <$signed>::fory_get_type_id(type_resolver)
+                }
+
+                fn fory_type_id_dyn(&self, type_resolver: &TypeResolver) -> Result<u32, Error> {
+                    <$signed>::fory_get_type_id(type_resolver)
+                }
+
+                #[inline]
+                fn as_any(&self) -> &dyn std::any::Any {
+                    self
+                }
+
+                #[inline]
+                fn fory_write_type_info(
+                    context: &mut WriteContext,
+                    is_field: bool,
+                ) -> Result<(), Error> {
+                    write_type_info::<Self>(context, is_field)
+                }
+
+                #[inline]
+                fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+                    read_type_info::<Self>(context, is_field)
+                }
+            }
+
+            impl ForyDefault for $ty {
+                #[inline]
+                fn fory_default() -> Self {
+                    0
+                }
+            }
+        )+
+    };
+}
+
+impl_unsigned_serializer!(
+    u8 => i8,
+    u16 => i16,
+    u32 => i32,
+    u64 => i64,
+);
+
+/// Numbers that a `#[fory(endian = "...")]` field writes fixed-width in the given byte
+/// order instead of with the encoding of their serializer.
+pub trait FixedWidth: Copy {
+    /// The type id recorded for such a field in the type meta, which tells peers without
+    /// the field how many bytes to skip.
+    const FIXED_TYPE_ID: TypeId;
+
+    fn write_fixed(self, context: &mut WriteContext, endian: Endian);
+
+    fn read_fixed(context: &mut ReadContext, endian: Endian) -> Result<Self, Error>;
+}
+
+macro_rules! impl_fixed_width {
+    ($($ty:ty => $type_id:ident, $canonical:expr);+ $(;)?) => {
+        $(
+            impl FixedWidth for $ty {
+                const FIXED_TYPE_ID: TypeId = TypeId::$type_id;
+
+                #[inline]
+                fn write_fixed(self, context: &mut WriteContext, endian: Endian) {
+                    let value = $canonical(context, self);
+                    context.writer.write_bytes(&match endian {
+                        Endian::Little => value.to_le_bytes(),
+                        Endian::Big => value.to_be_bytes(),
+                    });
+                }
+
+                #[inline]
+                fn read_fixed(context: &mut ReadContext, endian: Endian) -> Result<Self, Error> {
+                    let mut bytes = [0u8; std::mem::size_of::<$ty>()];
+                    bytes.copy_from_slice(
+                        context.reader.read_bytes(std::mem::size_of::<$ty>())?,
+                    );
+                    Ok(match endian {
+                        Endian::Little => <$ty>::from_le_bytes(bytes),
+                        Endian::Big => <$ty>::from_be_bytes(bytes),
+                    })
+                }
+            }
+        )+
+    };
+}
+
+impl_fixed_width!(
+    i8 => INT8, keep;
+    u8 => INT8, keep;
+    i16 => INT16, keep;
+    u16 => INT16, keep;
+    i32 => FIXED_INT32, keep;
+    u32 => FIXED_INT32, keep;
+    i64 => FIXED_INT64, keep;
+    u64 => FIXED_INT64, keep;
+    f32 => FLOAT32, canonical_f32;
+    f64 => FLOAT64, canonical_f64;
+);
+
+/// Integer types that a struct field can be converted to when the peer declared it with
+/// another width, see [`Fory::with_numeric_conversion`](crate::fory::Fory::with_numeric_conversion).
+pub trait ConvertInt: Serializer + ForyDefault {
+    /// Returns `value` if it fits in `Self`.
+    fn from_i64_checked(value: i64) -> Option<Self>;
+
+    /// Returns the low bits of `value`, like an `as` cast.
+    fn from_i64_truncating(value: i64) -> Self;
+}
+
+macro_rules! impl_convert_int {
+    ($($ty:ty),+) => {
+        $(
+            impl ConvertInt for $ty {
+                #[inline(always)]
+                fn from_i64_checked(value: i64) -> Option<Self> {
+                    <$ty>::try_from(value).ok()
+                }
+
+                #[inline(always)]
+                fn from_i64_truncating(value: i64) -> Self {
+                    value as $ty
+                }
+            }
+        )+
+    };
+}
+
+impl_convert_int!(i8, i16, i32, i64);
+
+/// Reads a struct field that the peer wrote as the integer type of `field_type`, which
+/// differs from `T`. Without numeric conversion the value is skipped and `T`'s default
+/// is returned, as for any other type change.
+pub fn read_converted_int<T: ConvertInt>(
+    context: &mut ReadContext,
+    field_type: &FieldType,
+) -> Result<T, Error> {
+    Ok(read_nullable_converted_int(context, field_type)?.unwrap_or_else(T::fory_default))
+}
+
+/// Counterpart of [`read_converted_int`] for an `Option` field, which reads a null or
+/// skipped value as `None`.
+pub fn read_nullable_converted_int<T: ConvertInt>(
+    context: &mut ReadContext,
+    field_type: &FieldType,
+) -> Result<Option<T>, Error> {
+    let read_ref_flag = get_read_ref_flag(field_type);
+    if !context.is_numeric_conversion() {
+        skip_field_value(context, field_type, read_ref_flag)?;
+        return Ok(None);
+    }
+    if read_ref_flag && context.reader.read_i8()? == RefFlag::Null as i8 {
+        return Ok(None);
+    }
+    let value = match TypeId::try_from(field_type.type_id as i16) {
+        Ok(TypeId::INT8) => i8::fory_read_data(context, true)? as i64,
+        Ok(TypeId::INT16) => i16::fory_read_data(context, true)? as i64,
+        Ok(TypeId::INT32) => i32::fory_read_data(context, true)? as i64,
+        Ok(TypeId::INT64) => i64::fory_read_data(context, true)?,
+        _ => {
+            return Err(Error::TypeMismatch(
+                T::fory_get_type_id(context.get_type_resolver())?,
+                field_type.type_id,
+            ))
+        }
+    };
+    convert_int(context, value).map(Some)
+}
+
+/// Counterpart of [`read_converted_int`] for a `Vec` field that the peer wrote as the
+/// integer array type of `field_type`, e.g. a `Vec<i32>` read into a `Vec<i64>`. Each
+/// element is converted under the same rules as an integer field.
+pub fn read_converted_int_array<T: ConvertInt>(
+    context: &mut ReadContext,
+    field_type: &FieldType,
+) -> Result<Vec<T>, Error> {
+    Ok(read_nullable_converted_int_array(context, field_type)?.unwrap_or_default())
+}
+
+/// Counterpart of [`read_converted_int_array`] for an `Option<Vec>` field, which reads a
+/// null or skipped array as `None`.
+pub fn read_nullable_converted_int_array<T: ConvertInt>(
+    context: &mut ReadContext,
+    field_type: &FieldType,
+) -> Result<Option<Vec<T>>, Error> {
+    let read_ref_flag = get_read_ref_flag(field_type);
+    if !context.is_numeric_conversion() {
+        skip_field_value(context, field_type, read_ref_flag)?;
+        return Ok(None);
+    }
+    if read_ref_flag && context.reader.read_i8()? == RefFlag::Null as i8 {
+        return Ok(None);
+    }
+    let width = match TypeId::try_from(field_type.type_id as i16) {
+        Ok(TypeId::INT8_ARRAY) => 1,
+        Ok(TypeId::INT16_ARRAY) => 2,
+        Ok(TypeId::INT32_ARRAY) => 4,
+        Ok(TypeId::INT64_ARRAY) => 8,
+        _ => {
+            return Err(Error::TypeMismatch(
+                Vec::<T>::fory_get_type_id(context.get_type_resolver())?,
+                field_type.type_id,
+            ))
+        }
+    };
+    let size_bytes = context.reader.read_varuint32()? as usize;
+    if size_bytes % width != 0 {
+        return Err(Error::InvalidData("Invalid data length".into()));
+    }
+    if size_bytes > 0 {
+        read_padding(context)?;
+    }
+    let remaining = context.reader.slice_after_cursor().len();
+    if remaining < size_bytes {
+        return Err(Error::BufferOutOfBound(0, size_bytes, remaining));
+    }
+    let mut values = Vec::with_capacity(size_bytes / width);
+    for _ in 0..size_bytes / width {
+        let value = match width {
+            1 => context.reader.read_i8()? as i64,
+            2 => context.reader.read_i16()? as i64,
+            4 => context.reader.read_i32()? as i64,
+            _ => context.reader.read_i64()?,
+        };
+        values.push(convert_int(context, value)?);
+    }
+    Ok(Some(values))
+}
+
+fn convert_int<T: ConvertInt>(context: &ReadContext, value: i64) -> Result<T, Error> {
+    if !context.is_strict_numerics() {
+        return Ok(T::from_i64_truncating(value));
+    }
+    T::from_i64_checked(value)
+        .ok_or_else(|| Error::NumericOverflow(value, std::any::type_name::<T>()))
+}
@@ -22,8 +22,14 @@ use crate::resolver::type_resolver::TypeResolver;
 use crate::serializer::{ForyDefault, Serializer};
 
 impl<T: Serializer + ForyDefault> Serializer for Option<T> {
+    crate::read_null_as_default!();
+
     #[inline(always)]
     fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        if T::fory_is_option() {
+            // a nested option carries its own presence flag, see `fory_write_data`
+            return Ok(Some(T::fory_read(context, is_field)?));
+        }
         Ok(Some(T::fory_read_data(context, is_field)?))
     }
 
@@ -35,6 +41,11 @@ impl<T: Serializer + ForyDefault> Serializer for Option<T> {
     #[inline(always)]
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         if let Some(v) = self {
+            if T::fory_is_option() {
+                // the ref flag of `Some(None)` only records the outer presence, so the
+                // inner option writes its own to stay distinguishable from `Some(Some(_))`
+                return T::fory_write(v, context, is_field);
+            }
             T::fory_write_data(v, context, is_field)
         } else {
             unreachable!("write should be call by serialize")
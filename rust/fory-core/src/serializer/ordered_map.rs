@@ -0,0 +1,190 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A `HashMap` that remembers the order its keys were inserted in.
+
+use crate::ensure;
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::collection::capped_len;
+use crate::serializer::map::{read_map_entries, write_map_data};
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A `HashMap` that also records the insertion order of its keys, for code that
+/// needs the order back after a round trip without switching to an ordered map.
+///
+/// It is written as a `MAP` whose entries are in insertion order, so peers read it as
+/// an ordinary map. Reading keeps the order the entries were written in, so iteration
+/// is the same on both sides.
+#[derive(Clone, Debug)]
+pub struct OrderedHashMap<K, V> {
+    map: HashMap<K, V>,
+    order: Vec<K>,
+}
+
+impl<K: Eq + Hash + Clone, V> OrderedHashMap<K, V> {
+    pub fn new() -> Self {
+        OrderedHashMap {
+            map: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    /// Inserts a value, returning the previous one. Replacing a value keeps the
+    /// key's position.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let previous = self.map.insert(key.clone(), value);
+        if previous.is_none() {
+            self.order.push(key);
+        }
+        previous
+    }
+
+    /// Removes a key, shifting the keys inserted after it.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.map.remove(key)?;
+        self.order.retain(|k| k != key);
+        Some(value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.map.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.map.get_mut(key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the keys in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.order.iter()
+    }
+
+    /// Returns the entries in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.order.iter().map(move |key| (key, &self.map[key]))
+    }
+
+    /// Returns the underlying map, whose iteration order is unspecified.
+    pub fn as_map(&self) -> &HashMap<K, V> {
+        &self.map
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> Default for OrderedHashMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash, V: PartialEq> PartialEq for OrderedHashMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.order == other.order && self.map == other.map
+    }
+}
+
+impl<K: Eq + Hash + Clone, V> FromIterator<(K, V)> for OrderedHashMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+        map
+    }
+}
+
+impl<K, V> Serializer for OrderedHashMap<K, V>
+where
+    K: Serializer + ForyDefault + Eq + Hash + Clone,
+    V: Serializer + ForyDefault,
+{
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_map_data(self.iter(), self.len(), false, context, is_field)
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let len = context.reader.read_varuint32()?;
+        let capacity = capped_len(context, len as usize);
+        let mut map = HashMap::with_capacity(capacity);
+        let mut order = Vec::with_capacity(capacity);
+        let mut duplicate = false;
+        read_map_entries(context, len, |key: K, value| {
+            if map.insert(key.clone(), value).is_none() {
+                order.push(key);
+            } else {
+                duplicate = true;
+            }
+        })?;
+        ensure!(
+            !duplicate,
+            Error::InvalidData("duplicate key in serialized map".into())
+        );
+        Ok(OrderedHashMap { map, order })
+    }
+
+    fn fory_reserved_space() -> usize {
+        size_of::<i32>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::MAP as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::MAP as u32)
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<K, V> ForyDefault for OrderedHashMap<K, V> {
+    fn fory_default() -> Self {
+        OrderedHashMap {
+            map: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+}
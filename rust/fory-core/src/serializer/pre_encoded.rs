@@ -0,0 +1,78 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Pass-through serializer for bytes that were serialized ahead of time, so that a cached
+//! immutable value can be spliced into a larger one without serializing it again.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{ForyDefault, Serializer};
+use crate::types::TypeId;
+
+/// The type info and data of a value, as written for a struct field, produced by
+/// [`Fory::pre_encode`](crate::fory::Fory::pre_encode) and written back verbatim.
+///
+/// Nothing checks that the bytes are valid, so a mismatch surfaces only when the output is
+/// read back. They are valid only where the original value could have been written: the
+/// field must be declared as the pre-encoded type on the reading side, and the bytes must
+/// come from a `Fory` with the same configuration and registrations. Compatible mode,
+/// reference tracking and dictionary strings make the encoding depend on what was written
+/// before it, so bytes produced with them cannot be spliced. A `PreEncoded` is write-only:
+/// reading it fails with [`Error::TypeError`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PreEncoded(pub Vec<u8>);
+
+impl Serializer for PreEncoded {
+    crate::read_null_as_default!();
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        context.writer.write_bytes(&self.0);
+        Ok(())
+    }
+
+    fn fory_read_data(_: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        Err(Error::TypeError("PreEncoded is write-only".into()))
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    /// The type info is part of the stored bytes.
+    fn fory_write_type_info(_: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn fory_read_type_info(_: &mut ReadContext, _is_field: bool) -> Result<(), Error> {
+        Err(Error::TypeError("PreEncoded is write-only".into()))
+    }
+}
+
+impl ForyDefault for PreEncoded {
+    fn fory_default() -> Self {
+        PreEncoded(Vec::new())
+    }
+}
@@ -15,27 +15,124 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::buffer::Writer;
 use crate::ensure;
 use crate::error::Error;
 use crate::resolver::context::ReadContext;
 use crate::resolver::context::WriteContext;
+use crate::serializer::list::check_primitive;
 use crate::types::TypeId;
 
-pub fn fory_write_data<T>(this: &[T], context: &mut WriteContext) -> Result<(), Error> {
+pub fn fory_write_data<T: 'static>(this: &[T], context: &mut WriteContext) -> Result<(), Error> {
     let len_bytes = std::mem::size_of_val(this);
     context.writer.write_varuint32(len_bytes as u32);
     context.writer.reserve(len_bytes);
 
     if !this.is_empty() {
+        let alignment = context.get_array_alignment();
+        write_padding(&mut context.writer, alignment);
+        let start = context.writer.len();
         unsafe {
             let ptr = this.as_ptr() as *const u8;
             let slice = std::slice::from_raw_parts(ptr, len_bytes);
             context.writer.write_bytes(slice);
         }
+        // the values are little-endian on the wire, whatever the target
+        #[cfg(target_endian = "big")]
+        context.writer.bf[start..]
+            .chunks_exact_mut(std::mem::size_of::<T>())
+            .for_each(|chunk| chunk.reverse());
+        if context.is_canonical_nan() {
+            canonicalize_nans(this, context, start);
+        }
     }
     Ok(())
 }
 
+/// Writes what aligns the payload that follows to `alignment` bytes, see
+/// [`Fory::with_array_alignment`](crate::fory::Fory::with_array_alignment): a byte counting
+/// the padding, then the padding. Empty payloads are not padded.
+pub(crate) fn write_padding(writer: &mut Writer, alignment: usize) {
+    if alignment <= 1 {
+        return;
+    }
+    let padding = (alignment - (writer.len() + 1) % alignment) % alignment;
+    writer.write_u8(padding as u8);
+    writer.write_bytes(&[0; 255][..padding]);
+}
+
+/// Skips the padding written by [`write_padding`] before a non-empty payload, if the
+/// header says the writer padded them.
+pub(crate) fn read_padding(context: &mut ReadContext) -> Result<(), Error> {
+    if !context.is_array_aligned() {
+        return Ok(());
+    }
+    let padding = context.reader.read_u8()? as usize;
+    context.reader.skip(padding)
+}
+
+/// Returns the type id that [`Fory::serialize_slice_to`](crate::fory::Fory::serialize_slice_to)
+/// writes a slice of `T` with straight from its memory: `BINARY` for bytes and the array
+/// type for other primitives. `None` sends the slice down the regular path, as do float
+/// slices whose NaNs are to be canonicalized and multi-byte values on big-endian targets,
+/// since those bytes change on the way.
+pub(crate) fn slice_type_id<T: 'static>(this: &[T], canonical_nan: bool) -> Option<TypeId> {
+    if std::any::TypeId::of::<T>() == std::any::TypeId::of::<u8>() {
+        return Some(TypeId::BINARY);
+    }
+    let type_id = check_primitive::<T>()?;
+    if cfg!(target_endian = "big") && std::mem::size_of::<T>() > 1 {
+        return None;
+    }
+    if canonical_nan && has_nan(this) {
+        return None;
+    }
+    Some(type_id)
+}
+
+/// Views a slice of primitives as the bytes that `fory_write_data` copies.
+pub(crate) fn as_bytes<T: 'static>(this: &[T]) -> &[u8] {
+    // SAFETY: primitives have no padding and any byte may be read
+    unsafe { std::slice::from_raw_parts(this.as_ptr() as *const u8, std::mem::size_of_val(this)) }
+}
+
+fn has_nan<T: 'static>(this: &[T]) -> bool {
+    let type_id = std::any::TypeId::of::<T>();
+    if type_id == std::any::TypeId::of::<f32>() {
+        // SAFETY: `T` is `f32`
+        let floats = unsafe { std::slice::from_raw_parts(this.as_ptr() as *const f32, this.len()) };
+        floats.iter().any(|v| v.is_nan())
+    } else if type_id == std::any::TypeId::of::<f64>() {
+        // SAFETY: `T` is `f64`
+        let floats = unsafe { std::slice::from_raw_parts(this.as_ptr() as *const f64, this.len()) };
+        floats.iter().any(|v| v.is_nan())
+    } else {
+        false
+    }
+}
+
+/// Overwrites the NaNs of a float array written at `start` with `f32::NAN` or `f64::NAN`.
+fn canonicalize_nans<T: 'static>(this: &[T], context: &mut WriteContext, start: usize) {
+    let type_id = std::any::TypeId::of::<T>();
+    if type_id == std::any::TypeId::of::<f32>() {
+        // SAFETY: `T` is `f32`
+        let floats = unsafe { std::slice::from_raw_parts(this.as_ptr() as *const f32, this.len()) };
+        for (i, _) in floats.iter().enumerate().filter(|(_, v)| v.is_nan()) {
+            context
+                .writer
+                .set_bytes(start + i * 4, &f32::NAN.to_le_bytes());
+        }
+    } else if type_id == std::any::TypeId::of::<f64>() {
+        // SAFETY: `T` is `f64`
+        let floats = unsafe { std::slice::from_raw_parts(this.as_ptr() as *const f64, this.len()) };
+        for (i, _) in floats.iter().enumerate().filter(|(_, v)| v.is_nan()) {
+            context
+                .writer
+                .set_bytes(start + i * 8, &f64::NAN.to_le_bytes());
+        }
+    }
+}
+
 pub fn fory_write_type_info(
     context: &mut WriteContext,
     is_field: bool,
@@ -49,19 +146,31 @@ pub fn fory_write_type_info(
 }
 
 pub fn fory_read_data<T>(context: &mut ReadContext) -> Result<Vec<T>, Error> {
+    let mut vec = Vec::new();
+    fory_read_data_into(&mut vec, context)?;
+    Ok(vec)
+}
+
+pub fn fory_read_data_into<T>(vec: &mut Vec<T>, context: &mut ReadContext) -> Result<(), Error> {
     let size_bytes = context.reader.read_varuint32()? as usize;
     if size_bytes % std::mem::size_of::<T>() != 0 {
         return Err(Error::InvalidData("Invalid data length".into()));
     }
-    let len = size_bytes / std::mem::size_of::<T>();
-    let mut vec: Vec<T> = Vec::with_capacity(len);
-    unsafe {
-        let dst_ptr = vec.as_mut_ptr() as *mut u8;
-        let src = context.reader.read_bytes(size_bytes)?;
-        std::ptr::copy_nonoverlapping(src.as_ptr(), dst_ptr, size_bytes);
-        vec.set_len(len);
+    if size_bytes > 0 {
+        read_padding(context)?;
     }
-    Ok(vec)
+    context.read_primitive_array_into(size_bytes / std::mem::size_of::<T>(), vec)
+}
+
+pub fn fory_skip<T>(context: &mut ReadContext) -> Result<(), Error> {
+    let size_bytes = context.reader.read_varuint32()? as usize;
+    if size_bytes % std::mem::size_of::<T>() != 0 {
+        return Err(Error::InvalidData("Invalid data length".into()));
+    }
+    if size_bytes > 0 {
+        read_padding(context)?;
+    }
+    context.skip_bytes(size_bytes)
 }
 
 pub fn fory_read_type_info(
@@ -72,7 +181,7 @@ pub fn fory_read_type_info(
     if is_field {
         return Ok(());
     }
-    let remote_type_id = context.reader.read_varuint32()?;
+    let remote_type_id = context.read_type_id()?;
     if remote_type_id == TypeId::LIST as u32 {
         return Err(Error::TypeError(
             "Vec<number> belongs to the `number_array` type, \
@@ -23,10 +23,15 @@ use crate::types::RefFlag;
 use std::rc::Rc;
 
 impl<T: Serializer + ForyDefault + 'static> Serializer for Rc<T> {
+    crate::read_null_as_default!();
     fn fory_is_shared_ref() -> bool {
         true
     }
 
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         if !context
             .ref_writer
@@ -63,6 +68,7 @@ impl<T: Serializer + ForyDefault + 'static> Serializer for Rc<T> {
                 Ok(Rc::new(inner))
             }
             RefFlag::RefValue => {
+                context.check_ref_count()?;
                 let ref_id = context.ref_reader.reserve_ref_id();
                 let inner = T::fory_read_data(context, is_field)?;
                 let rc = Rc::new(inner);
@@ -24,7 +24,12 @@
 //! Unlike `Rc` and `Arc`, `RefCell` does not do reference counting, so this wrapper relies
 //! on the serialization of the contained `T` only.
 //!
-//! This is commonly used together with `Rc<RefCell<T>>` in graph structures.
+//! This is commonly used together with `Rc<RefCell<T>>` in graph structures. The `Rc`
+//! tracks the shared reference, so fields holding clones of one `Rc<RefCell<T>>` still
+//! share a single cell after a round trip.
+//!
+//! Writing a `RefCell` that is mutably borrowed fails with [`Error::EncodeError`] instead
+//! of panicking.
 //!
 //! # Example
 //! ```rust
@@ -36,13 +41,20 @@ use crate::error::Error;
 use crate::resolver::context::{ReadContext, WriteContext};
 use crate::resolver::type_resolver::TypeResolver;
 use crate::serializer::{ForyDefault, Serializer};
-use std::cell::RefCell;
+use std::cell::{Ref, RefCell};
+
+fn try_borrow<T>(cell: &RefCell<T>) -> Result<Ref<'_, T>, Error> {
+    cell.try_borrow()
+        .map_err(|_| Error::EncodeError("cannot write a RefCell that is mutably borrowed".into()))
+}
 
 /// `Serializer` impl for `RefCell<T>`
 ///
 /// Simply delegates to the serializer for `T`, allowing interior mutable
 /// containers to be included in serialized graphs.
 impl<T: Serializer + ForyDefault> Serializer for RefCell<T> {
+    crate::read_null_as_default!();
+
     fn fory_read(context: &mut ReadContext, is_field: bool) -> Result<Self, Error>
     where
         Self: Sized + ForyDefault,
@@ -61,12 +73,12 @@ impl<T: Serializer + ForyDefault> Serializer for RefCell<T> {
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         // Don't add ref tracking for RefCell itself, just delegate to inner type
         // The inner type will handle its own ref tracking
-        T::fory_write(&*self.borrow(), context, is_field)
+        T::fory_write(&*try_borrow(self)?, context, is_field)
     }
 
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         // When called from Rc, just delegate to inner type's data serialization
-        T::fory_write_data(&*self.borrow(), context, is_field)
+        T::fory_write_data(&*try_borrow(self)?, context, is_field)
     }
 
     fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
@@ -83,7 +95,7 @@ impl<T: Serializer + ForyDefault> Serializer for RefCell<T> {
     }
 
     fn fory_type_id_dyn(&self, type_resolver: &TypeResolver) -> Result<u32, Error> {
-        (*self.borrow()).fory_type_id_dyn(type_resolver)
+        try_borrow(self)?.fory_type_id_dyn(type_resolver)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
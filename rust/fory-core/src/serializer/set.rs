@@ -29,6 +29,8 @@ use std::collections::{BTreeSet, HashSet};
 use std::mem;
 
 impl<T: Serializer + ForyDefault + Eq + std::hash::Hash> Serializer for HashSet<T> {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         write_collection(self, context, is_field)
     }
@@ -69,6 +71,8 @@ impl<T> ForyDefault for HashSet<T> {
 }
 
 impl<T: Serializer + ForyDefault + Ord> Serializer for BTreeSet<T> {
+    crate::read_null_as_default!();
+
     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         write_collection(self, context, is_field)
     }
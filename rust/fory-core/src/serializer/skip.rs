@@ -17,12 +17,16 @@
 
 use crate::ensure;
 use crate::error::Error;
-use crate::meta::FieldType;
+use crate::meta::{FieldType, TypeMeta};
 use crate::resolver::context::ReadContext;
-use crate::serializer::collection::{HAS_NULL, IS_SAME_TYPE};
+use crate::serializer::collection::{
+    is_null_in_bitmap, read_capacity_hint, read_null_bitmap, skip_string_dictionary, DICTIONARY,
+    HAS_NULL, IS_SAME_TYPE, SPARSE_NULL,
+};
 use crate::serializer::Serializer;
 use crate::types::{RefFlag, TypeId, BASIC_TYPES, CONTAINER_TYPES, PRIMITIVE_TYPES};
 use chrono::{NaiveDate, NaiveDateTime};
+use std::ffi::CString;
 
 pub fn get_read_ref_flag(field_type: &FieldType) -> bool {
     let nullable = field_type.nullable;
@@ -34,7 +38,7 @@ macro_rules! basic_type_deserialize {
         $(
             if $tid == TypeId::$id {
                 <$ty as Serializer>::fory_read_type_info($context, true)?;
-                <$ty as Serializer>::fory_read_data($context, true)?;
+                <$ty as Serializer>::fory_skip($context)?;
                 return Ok(());
             }
         )+else {
@@ -52,14 +56,30 @@ pub fn skip_field_value(
 ) -> Result<(), Error> {
     if read_ref_flag {
         let ref_flag = context.reader.read_i8()?;
-        if field_type.nullable && ref_flag == (RefFlag::Null as i8) {
+        // element types are recorded without their nullability, so a null flag is taken at
+        // its word
+        if ref_flag == (RefFlag::Null as i8) {
             return Ok(());
         }
     }
     let type_id_num = field_type.type_id;
     match TypeId::try_from(type_id_num as i16) {
         Ok(type_id) => {
-            if BASIC_TYPES.contains(&type_id) {
+            if type_id == TypeId::FIXED_INT32 {
+                context.skip_bytes(4)
+            } else if type_id == TypeId::FIXED_INT64 {
+                context.skip_bytes(8)
+            } else if type_id == TypeId::FIXED_BINARY {
+                context.skip_bytes(field_type.length as usize)
+            } else if type_id == TypeId::FIXED_LIST {
+                let elem_type = field_type.generics.first().unwrap();
+                context.inc_depth()?;
+                for _ in 0..field_type.length {
+                    skip_field_value(context, elem_type, true)?;
+                }
+                context.dec_depth();
+                Ok(())
+            } else if BASIC_TYPES.contains(&type_id) {
                 basic_type_deserialize!(type_id, context;
                     (bool, BOOL),
                     (i8, INT8),
@@ -71,6 +91,7 @@ pub fn skip_field_value(
                     (String, STRING),
                     (NaiveDate, LOCAL_DATE),
                     (NaiveDateTime, TIMESTAMP),
+                    (CString, BINARY),
                     (Vec<bool> , BOOL_ARRAY),
                     (Vec<i8> , INT8_ARRAY),
                     (Vec<i16> , INT16_ARRAY),
@@ -86,13 +107,29 @@ pub fn skip_field_value(
                         return Ok(());
                     }
                     let header = context.reader.read_u8()?;
+                    read_capacity_hint(context, header)?;
                     let has_null = (header & HAS_NULL) != 0;
                     let is_same_type = (header & IS_SAME_TYPE) != 0;
                     let skip_ref_flag = is_same_type && !has_null;
                     let elem_type = field_type.generics.first().unwrap();
                     context.inc_depth()?;
-                    for _ in 0..length {
-                        skip_field_value(context, elem_type, !skip_ref_flag)?;
+                    if (header & DICTIONARY) != 0 {
+                        skip_string_dictionary(context, length)?;
+                    } else if (header & SPARSE_NULL) != 0 {
+                        let bitmap = read_null_bitmap(context, length)?;
+                        for i in 0..length {
+                            if !is_null_in_bitmap(&bitmap, i) {
+                                skip_field_value(context, elem_type, false)?;
+                            }
+                        }
+                    } else if !is_same_type {
+                        for _ in 0..length {
+                            skip_element_with_type_info(context)?;
+                        }
+                    } else {
+                        for _ in 0..length {
+                            skip_field_value(context, elem_type, !skip_ref_flag)?;
+                        }
                     }
                     context.dec_depth();
                 } else if type_id == TypeId::MAP {
@@ -131,8 +168,13 @@ pub fn skip_field_value(
                             continue;
                         }
                         let chunk_size = context.reader.read_u8()?;
+                        let key_prefix_shared =
+                            header & crate::serializer::map::KEY_PREFIX_SHARED != 0;
                         context.inc_depth()?;
                         for _ in (0..chunk_size).enumerate() {
+                            if key_prefix_shared {
+                                context.reader.read_varuint32()?;
+                            }
                             // let read_ref_flag = get_read_ref_flag(key_type);
                             skip_field_value(context, key_type, false)?;
                             // let read_ref_flag = get_read_ref_flag(value_type);
@@ -147,7 +189,7 @@ pub fn skip_field_value(
                 let _ordinal = context.reader.read_varuint32()?;
                 Ok(())
             } else if type_id == TypeId::NAMED_COMPATIBLE_STRUCT {
-                let remote_type_id = context.reader.read_varuint32()?;
+                let remote_type_id = context.read_type_id()?;
                 ensure!(
                     type_id_num == remote_type_id,
                     Error::TypeMismatch(type_id_num, remote_type_id)
@@ -163,7 +205,7 @@ pub fn skip_field_value(
                 context.dec_depth();
                 Ok(())
             } else if type_id == TypeId::NAMED_EXT {
-                let remote_type_id = context.reader.read_varuint32()?;
+                let remote_type_id = context.read_type_id()?;
                 ensure!(
                     type_id_num == remote_type_id,
                     Error::TypeMismatch(type_id_num, remote_type_id)
@@ -176,7 +218,9 @@ pub fn skip_field_value(
                     .get_read_data_fn()(context, true)?;
                 Ok(())
             } else {
-                unreachable!("unimplemented type: {:?}", type_id);
+                Err(Error::TypeError(
+                    format!("cannot skip a value of type {:?}", type_id).into(),
+                ))
             }
         }
         Err(_) => {
@@ -185,7 +229,7 @@ pub fn skip_field_value(
             const EXT_ID: u32 = TypeId::EXT as u32;
             const ENUM_ID: u32 = TypeId::ENUM as u32;
             if internal_id == COMPATIBLE_STRUCT_ID {
-                let remote_type_id = context.reader.read_varuint32()?;
+                let remote_type_id = context.read_type_id()?;
                 let meta_index = context.reader.read_varuint32()?;
                 let type_meta = context.get_meta(meta_index as usize);
                 ensure!(
@@ -202,7 +246,7 @@ pub fn skip_field_value(
             } else if internal_id == ENUM_ID {
                 let _ordinal = context.reader.read_varuint32()?;
             } else if internal_id == EXT_ID {
-                let remote_type_id = context.reader.read_varuint32()?;
+                let remote_type_id = context.read_type_id()?;
                 ensure!(
                     type_id_num == remote_type_id,
                     Error::TypeMismatch(type_id_num, remote_type_id)
@@ -220,3 +264,44 @@ pub fn skip_field_value(
         }
     }
 }
+
+/// Skips an element of a list whose elements are not of the same type, such as a tuple,
+/// which is written with its ref flag and type info.
+fn skip_element_with_type_info(context: &mut ReadContext) -> Result<(), Error> {
+    if context.reader.read_i8()? == RefFlag::Null as i8 {
+        return Ok(());
+    }
+    let cursor = context.reader.get_cursor();
+    let type_id = context.read_type_id()?;
+    ensure!(
+        !CONTAINER_TYPES.iter().any(|id| *id as u32 == type_id)
+            && type_id != TypeId::FIXED_LIST as u32,
+        Error::TypeError("cannot skip a collection whose element type is not declared".into())
+    );
+    if type_id == TypeId::FIXED_BINARY as u32 {
+        let len = context.reader.read_varuint32()? as usize;
+        return context.skip_bytes(len);
+    }
+    // struct and ext types read their type info themselves
+    if matches!(
+        TypeId::try_from((type_id & 0xff) as i16),
+        Ok(TypeId::COMPATIBLE_STRUCT
+            | TypeId::NAMED_COMPATIBLE_STRUCT
+            | TypeId::EXT
+            | TypeId::NAMED_EXT)
+    ) {
+        context.reader.set_cursor(cursor)?;
+    }
+    skip_field_value(context, &FieldType::new(type_id, false, Vec::new()), false)
+}
+
+/// Skips the field data of a struct described by `type_meta`.
+pub fn skip_struct_data(context: &mut ReadContext, type_meta: &TypeMeta) -> Result<(), Error> {
+    context.inc_depth()?;
+    for field_info in type_meta.get_field_infos().iter() {
+        let read_ref_flag = get_read_ref_flag(&field_info.field_type);
+        skip_field_value(context, &field_info.field_type, read_ref_flag)?;
+    }
+    context.dec_depth();
+    Ok(())
+}
@@ -0,0 +1,216 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A set of integers kept as a sorted array, for membership tests by binary search.
+
+use crate::ensure;
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::collections::{BTreeSet, HashSet};
+
+/// Integers that a [`SortedSet`] can hold.
+///
+/// The elements are delta-encoded through their bits as an `i64`, sign-extended for
+/// signed types, whose differences between sorted elements always fit in a `u64`.
+pub trait SortedSetElement: Copy + Ord + 'static {
+    fn to_bits(self) -> u64;
+
+    /// Returns `None` if `bits` is out of the range of `Self`.
+    fn from_bits(bits: u64) -> Option<Self>;
+}
+
+macro_rules! impl_sorted_set_element {
+    ($($ty:ty),+) => {
+        $(
+            impl SortedSetElement for $ty {
+                #[inline(always)]
+                fn to_bits(self) -> u64 {
+                    self as i64 as u64
+                }
+
+                #[inline(always)]
+                fn from_bits(bits: u64) -> Option<Self> {
+                    let value = bits as $ty;
+                    (value.to_bits() == bits).then_some(value)
+                }
+            }
+        )+
+    };
+}
+
+impl_sorted_set_element!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+/// A set of integers stored as a sorted array without duplicates.
+///
+/// It is written as the element count, the first element as a varint and the gaps to
+/// each next element as varuints, so clustered elements such as ids allocated in runs
+/// take about a byte each. Reading yields the sorted array directly, so lookups by
+/// [`SortedSet::contains`] need no hash set to be built, e.g. for an allow list that is
+/// loaded often and queried a few times.
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::Fory;
+/// use fory_core::serializer::sorted_set::SortedSet;
+///
+/// let fory = Fory::default();
+/// let set: SortedSet<u32> = [7, 3, 1000, 4].into_iter().collect();
+/// let bytes = fory.serialize(&set).unwrap();
+/// let read = fory.deserialize_sorted_set::<u32>(&bytes).unwrap();
+/// assert!(read.contains(&1000));
+/// assert!(!read.contains(&5));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SortedSet<T> {
+    elements: Vec<T>,
+}
+
+impl<T: SortedSetElement> SortedSet<T> {
+    pub fn new() -> Self {
+        SortedSet {
+            elements: Vec::new(),
+        }
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.elements.binary_search(value).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the elements in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.elements.iter()
+    }
+
+    /// Returns the elements in ascending order.
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+}
+
+impl<T: SortedSetElement> Default for SortedSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: SortedSetElement> FromIterator<T> for SortedSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut elements: Vec<T> = iter.into_iter().collect();
+        elements.sort_unstable();
+        elements.dedup();
+        SortedSet { elements }
+    }
+}
+
+impl<T: SortedSetElement> From<HashSet<T>> for SortedSet<T> {
+    fn from(set: HashSet<T>) -> Self {
+        set.into_iter().collect()
+    }
+}
+
+impl<T: SortedSetElement> From<BTreeSet<T>> for SortedSet<T> {
+    fn from(set: BTreeSet<T>) -> Self {
+        SortedSet {
+            elements: set.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: SortedSetElement> Serializer for SortedSet<T> {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        context.writer.write_varuint32(self.elements.len() as u32);
+        let mut previous = None;
+        for element in &self.elements {
+            let bits = element.to_bits();
+            match previous {
+                None => context.writer.write_varint64(bits as i64),
+                Some(previous) => context.writer.write_varuint64(bits.wrapping_sub(previous)),
+            }
+            previous = Some(bits);
+        }
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let len = context.reader.read_varuint32()? as usize;
+        // every element takes at least a byte
+        ensure!(
+            len <= context.reader.slice_after_cursor().len(),
+            Error::UnexpectedEof(format!("a sorted set of {len} elements").into())
+        );
+        let mut elements: Vec<T> = Vec::with_capacity(len);
+        let mut previous = 0u64;
+        for i in 0..len {
+            let bits = if i == 0 {
+                context.reader.read_varint64()? as u64
+            } else {
+                previous.wrapping_add(context.reader.read_varuint64()?)
+            };
+            let element = T::from_bits(bits);
+            ensure!(
+                element.is_some() && elements.last() < element.as_ref(),
+                Error::InvalidData("sorted set elements are out of order or range".into())
+            );
+            elements.extend(element);
+            previous = bits;
+        }
+        Ok(SortedSet { elements })
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::UNKNOWN as u32)
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl<T> ForyDefault for SortedSet<T> {
+    fn fory_default() -> Self {
+        SortedSet {
+            elements: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::string::{read_string_data, write_str_data, STATIC_STRING_ENCODING};
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::borrow::Cow;
+
+/// A `Cow<'static, str>` registered with
+/// [`Fory::register_static_string`](crate::fory::Fory::register_static_string) is written as
+/// a `String` header carrying the id and a fourth encoding, with no data, and read back
+/// borrowing the registered static. Any other string is written exactly like a `String`, so
+/// either is a `STRING` that can be skipped, and a literal can be read as a `String`.
+///
+/// Values are written straight from the borrow, so a map such as
+/// `HashMap<String, Cow<'static, str>>` serializes borrowed values without owning them.
+impl Serializer for Cow<'static, str> {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        match context.get_type_resolver().get_static_string_id(self) {
+            Some(id) => {
+                context
+                    .writer
+                    .write_varuint36_small((id as u64) << 2 | STATIC_STRING_ENCODING);
+                Ok(())
+            }
+            None => write_str_data(self, context),
+        }
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let bitor = context.reader.read_varuint36small()?;
+        if bitor & 0b11 != STATIC_STRING_ENCODING {
+            return Ok(Cow::Owned(read_string_data(context, bitor)?));
+        }
+        let id = bitor >> 2;
+        u32::try_from(id)
+            .ok()
+            .and_then(|id| context.get_type_resolver().get_static_string(id))
+            .map(Cow::Borrowed)
+            .ok_or_else(|| Error::InvalidData(format!("unknown static string id: {}", id).into()))
+    }
+
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        String::fory_skip(context)
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::STRING as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::STRING as u32)
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl ForyDefault for Cow<'static, str> {
+    fn fory_default() -> Self {
+        Cow::Borrowed("")
+    }
+}
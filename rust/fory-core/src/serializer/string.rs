@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::ensure;
 use crate::error::Error;
 use crate::meta::get_latin1_length;
 use crate::resolver::context::ReadContext;
@@ -22,6 +23,7 @@ use crate::resolver::context::WriteContext;
 use crate::resolver::type_resolver::TypeResolver;
 use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
 use crate::types::TypeId;
+use std::cmp::Ordering;
 use std::mem;
 
 enum StrEncoding {
@@ -30,50 +32,96 @@ enum StrEncoding {
     Utf8 = 2,
 }
 
+/// The encoding bits of a registered static string, whose header holds its id in place of
+/// the length and is followed by no data. See
+/// [`Fory::register_static_string`](crate::fory::Fory::register_static_string).
+pub(crate) const STATIC_STRING_ENCODING: u64 = 0b11;
+
+/// Writes `s` with the same encoding as a `String` value, so that it reads back as one.
+#[inline]
+pub(crate) fn write_str_data(s: &str, context: &mut WriteContext) -> Result<(), Error> {
+    let mut len = get_latin1_length(s);
+    if len >= 0 {
+        let bitor = (len as u64) << 2 | StrEncoding::Latin1 as u64;
+        context.writer.write_varuint36_small(bitor);
+        if len as usize == s.len() {
+            // ASCII is already its own latin1 encoding, append the bytes in bulk
+            context.writer.write_bytes(s.as_bytes());
+        } else {
+            context.writer.write_latin1_string(s);
+        }
+    } else if context.is_compress_string() {
+        // todo: support `writeNumUtf16BytesForUtf8Encoding` like in java
+        len = s.len() as i32;
+        let bitor = (len as u64) << 2 | StrEncoding::Utf8 as u64;
+        context.writer.write_varuint36_small(bitor);
+        context.writer.write_bytes(s.as_bytes());
+    } else {
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let bitor = (utf16.len() as u64 * 2) << 2 | StrEncoding::Utf16 as u64;
+        context.writer.write_varuint36_small(bitor);
+        context.writer.write_utf16_bytes(&utf16);
+    }
+    Ok(())
+}
+
+/// Fails with [`Error::StringTooLong`] if a string of `len` bytes exceeds the limit set by
+/// [`Fory::with_string_max_len`](crate::fory::Fory::with_string_max_len).
+#[inline(always)]
+pub(crate) fn check_string_len(context: &ReadContext, len: u64) -> Result<(), Error> {
+    ensure!(
+        len <= context.string_max_len() as u64,
+        Error::StringTooLong(len as usize, context.string_max_len())
+    );
+    Ok(())
+}
+
+/// Reads the data of a `String` whose header `bitor` has already been read.
+#[inline]
+pub(crate) fn read_string_data(context: &mut ReadContext, bitor: u64) -> Result<String, Error> {
+    let len = bitor >> 2;
+    check_string_len(context, len)?;
+    let encoding = bitor & 0b11;
+    let encoding = match encoding {
+        0 => StrEncoding::Latin1,
+        1 => StrEncoding::Utf16,
+        2 => StrEncoding::Utf8,
+        _ => {
+            return Err(Error::EncodingError(
+                format!("wrong encoding value: {}", encoding).into(),
+            ))
+        }
+    };
+    let s = match encoding {
+        StrEncoding::Latin1 => context.reader.read_latin1_string(len as usize),
+        StrEncoding::Utf16 => context.reader.read_utf16_string(len as usize),
+        StrEncoding::Utf8 => context.reader.read_utf8_string(len as usize),
+    }?;
+    Ok(s)
+}
+
 impl Serializer for String {
+    crate::read_null_as_default!();
+
     #[inline]
     fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
-        let mut len = get_latin1_length(self);
-        if len >= 0 {
-            let bitor = (len as u64) << 2 | StrEncoding::Latin1 as u64;
-            context.writer.write_varuint36_small(bitor);
-            context.writer.write_latin1_string(self);
-        } else if context.is_compress_string() {
-            // todo: support `writeNumUtf16BytesForUtf8Encoding` like in java
-            len = self.len() as i32;
-            let bitor = (len as u64) << 2 | StrEncoding::Utf8 as u64;
-            context.writer.write_varuint36_small(bitor);
-            context.writer.write_utf8_string(self);
-        } else {
-            let utf16: Vec<u16> = self.encode_utf16().collect();
-            let bitor = (utf16.len() as u64 * 2) << 2 | StrEncoding::Utf16 as u64;
-            context.writer.write_varuint36_small(bitor);
-            context.writer.write_utf16_bytes(&utf16);
-        }
-        Ok(())
+        write_str_data(self, context)
     }
 
     #[inline]
     fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
         let bitor = context.reader.read_varuint36small()?;
-        let len = bitor >> 2;
-        let encoding = bitor & 0b11;
-        let encoding = match encoding {
-            0 => StrEncoding::Latin1,
-            1 => StrEncoding::Utf16,
-            2 => StrEncoding::Utf8,
-            _ => {
-                return Err(Error::EncodingError(
-                    format!("wrong encoding value: {}", encoding).into(),
-                ))
-            }
-        };
-        let s = match encoding {
-            StrEncoding::Latin1 => context.reader.read_latin1_string(len as usize),
-            StrEncoding::Utf16 => context.reader.read_utf16_string(len as usize),
-            StrEncoding::Utf8 => context.reader.read_utf8_string(len as usize),
-        }?;
-        Ok(s)
+        read_string_data(context, bitor)
+    }
+
+    #[inline]
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        // the length prefix is in bytes for every encoding
+        let bitor = context.reader.read_varuint36small()?;
+        if bitor & 0b11 == STATIC_STRING_ENCODING {
+            return Ok(());
+        }
+        context.skip_bytes((bitor >> 2) as usize)
     }
 
     #[inline]
@@ -95,6 +143,16 @@ impl Serializer for String {
         self
     }
 
+    #[inline(always)]
+    fn fory_cmp_serialized(&self, other: &Self, _: &mut WriteContext) -> Result<Ordering, Error> {
+        Ok(self.cmp(other))
+    }
+
+    #[inline(always)]
+    fn fory_cmp_serialized_bytes() -> bool {
+        false
+    }
+
     #[inline(always)]
     fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         write_type_info::<Self>(context, is_field)
@@ -112,3 +170,68 @@ impl ForyDefault for String {
         String::new()
     }
 }
+
+impl Serializer for Box<str> {
+    crate::read_null_as_default!();
+
+    #[inline]
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        write_str_data(self, context)
+    }
+
+    #[inline]
+    fn fory_read_data(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        String::fory_read_data(context, is_field).map(String::into_boxed_str)
+    }
+
+    #[inline]
+    fn fory_skip(context: &mut ReadContext) -> Result<(), Error> {
+        String::fory_skip(context)
+    }
+
+    #[inline]
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<i32>()
+    }
+
+    #[inline(always)]
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::STRING as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::STRING as u32)
+    }
+
+    #[inline(always)]
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    #[inline(always)]
+    fn fory_cmp_serialized(&self, other: &Self, _: &mut WriteContext) -> Result<Ordering, Error> {
+        Ok(self.cmp(other))
+    }
+
+    #[inline(always)]
+    fn fory_cmp_serialized_bytes() -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    #[inline(always)]
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+impl ForyDefault for Box<str> {
+    #[inline(always)]
+    fn fory_default() -> Self {
+        Box::default()
+    }
+}
@@ -17,7 +17,9 @@
 
 use crate::ensure;
 use crate::error::Error;
+use crate::meta::murmurhash3_x64_128;
 use crate::resolver::context::{ReadContext, WriteContext};
+use crate::resolver::type_resolver::TypeResolver;
 use crate::serializer::Serializer;
 use crate::types::{RefFlag, TypeId};
 
@@ -70,7 +72,7 @@ pub fn read_type_info<T: Serializer>(
     context: &mut ReadContext,
     _is_field: bool,
 ) -> Result<(), Error> {
-    let remote_type_id = context.reader.read_varuint32()?;
+    let remote_type_id = context.read_type_id()?;
     let local_type_id = T::fory_get_type_id(context.get_type_resolver())?;
     ensure!(
         local_type_id == remote_type_id,
@@ -110,3 +112,45 @@ pub fn write<T: Serializer>(
     }
     Ok(())
 }
+
+/// Hashes the type def of `T`, which lists its fields and their types, so that a
+/// `#[fory(compact)]` struct read by position can still detect a different layout.
+fn compact_schema_hash<T: Serializer>(type_resolver: &TypeResolver) -> Result<u32, Error> {
+    let type_info = type_resolver.get_type_info(std::any::TypeId::of::<T>())?;
+    Ok(murmurhash3_x64_128(&type_info.get_type_def(), 47).0 as u32)
+}
+
+/// Writes the schema hash that precedes the fields of a `#[fory(compact)]` struct in
+/// compatible mode, in place of the field names of its type meta.
+#[inline(always)]
+pub fn write_compact_schema_hash<T: Serializer>(context: &mut WriteContext) -> Result<(), Error> {
+    let hash = compact_schema_hash::<T>(context.get_type_resolver())?;
+    context.writer.write_u32(hash);
+    Ok(())
+}
+
+/// Reads the schema hash written by [`write_compact_schema_hash`] and rejects any other.
+#[inline(always)]
+pub fn read_compact_schema_hash<T: Serializer>(context: &mut ReadContext) -> Result<(), Error> {
+    let found = context.reader.read_u32()?;
+    let expected = compact_schema_hash::<T>(context.get_type_resolver())?;
+    ensure!(
+        found == expected,
+        Error::SchemaMismatch(found as u64, expected as u64)
+    );
+    Ok(())
+}
+
+/// Fails with [`Error::MissingField`] if the input ended before the struct field `name`.
+///
+/// Fields are read by position outside compatible mode, so a buffer written before a field
+/// was added simply runs out where that field would start. A field added anywhere but last
+/// in the sorted field order cannot be told apart from other data this way.
+#[inline(always)]
+pub fn ensure_field_present(context: &ReadContext, name: &'static str) -> Result<(), Error> {
+    ensure!(
+        !context.reader.slice_after_cursor().is_empty(),
+        Error::MissingField { name }
+    );
+    Ok(())
+}
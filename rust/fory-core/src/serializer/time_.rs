@@ -0,0 +1,196 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Serializers for the date and time types of the `time` crate, written like the
+//! `chrono` types so that either crate can read what the other wrote.
+
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::{read_type_info, write_type_info, ForyDefault, Serializer};
+use crate::types::TypeId;
+use std::mem;
+use time::{Date, Duration, OffsetDateTime, UtcOffset};
+
+/// Written as the `i32` days since the epoch, like `chrono::NaiveDate`.
+impl Serializer for Date {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        let epoch = OffsetDateTime::UNIX_EPOCH.date();
+        context
+            .writer
+            .write_i32(self.to_julian_day() - epoch.to_julian_day());
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let days = context.reader.read_i32()?;
+        let epoch = OffsetDateTime::UNIX_EPOCH.date();
+        days.checked_add(epoch.to_julian_day())
+            .and_then(|julian_day| Date::from_julian_day(julian_day).ok())
+            .ok_or_else(|| {
+                Error::InvalidData(format!("Date out of range, {days} days since epoch").into())
+            })
+    }
+
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<i32>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::LOCAL_DATE as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::LOCAL_DATE as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+/// Written as a `TIMESTAMP` followed by the UTC offset: the instant as the `i64`
+/// microseconds since the epoch in UTC, exactly like `chrono::NaiveDateTime`, then the
+/// offset as `i32` seconds east of UTC.
+///
+/// Sub-microsecond precision is truncated. Because of the trailing offset the value
+/// cannot be read as a plain timestamp, nor skipped as an unknown field in compatible
+/// mode.
+impl Serializer for OffsetDateTime {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        let micros = self.unix_timestamp_nanos().div_euclid(1_000);
+        context.writer.write_i64(micros as i64);
+        context.writer.write_i32(self.offset().whole_seconds());
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let micros = context.reader.read_i64()?;
+        let offset_secs = context.reader.read_i32()?;
+        let offset = UtcOffset::from_whole_seconds(offset_secs).map_err(|e| {
+            Error::InvalidData(format!("{offset_secs} s is not a UTC offset: {e}").into())
+        })?;
+        OffsetDateTime::from_unix_timestamp_nanos(micros as i128 * 1_000)
+            .ok()
+            .and_then(|utc| utc.checked_to_offset(offset))
+            .ok_or_else(|| {
+                Error::InvalidData(format!("Date out of range, timestamp micros: {micros}").into())
+            })
+    }
+
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<i64>() + mem::size_of::<i32>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::TIMESTAMP as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::TIMESTAMP as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+/// Written as a `DURATION`: the whole seconds as an `i64` followed by the nanoseconds
+/// as an `i32` with the same sign.
+impl Serializer for Duration {
+    crate::read_null_as_default!();
+
+    fn fory_write_data(&self, context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
+        context.writer.write_i64(self.whole_seconds());
+        context.writer.write_i32(self.subsec_nanoseconds());
+        Ok(())
+    }
+
+    fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+        let secs = context.reader.read_i64()?;
+        let nanos = context.reader.read_i32()?;
+        if nanos.unsigned_abs() >= 1_000_000_000 {
+            return Err(Error::InvalidData(
+                format!("{nanos} ns is not a fraction of a second").into(),
+            ));
+        }
+        Ok(Duration::new(secs, nanos))
+    }
+
+    fn fory_reserved_space() -> usize {
+        mem::size_of::<i64>() + mem::size_of::<i32>()
+    }
+
+    fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::DURATION as u32)
+    }
+
+    fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+        Ok(TypeId::DURATION as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
+        write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+        read_type_info::<Self>(context, is_field)
+    }
+}
+
+impl ForyDefault for Date {
+    fn fory_default() -> Self {
+        OffsetDateTime::UNIX_EPOCH.date()
+    }
+}
+
+impl ForyDefault for OffsetDateTime {
+    fn fory_default() -> Self {
+        OffsetDateTime::UNIX_EPOCH
+    }
+}
+
+impl ForyDefault for Duration {
+    fn fory_default() -> Self {
+        Duration::ZERO
+    }
+}
@@ -54,7 +54,7 @@ pub fn read_trait_object_headers(context: &mut ReadContext) -> Result<u32, Error
         ));
     }
 
-    let fory_type_id = context.reader.read_varuint32()?;
+    let fory_type_id = context.read_type_id()?;
 
     if context.is_compatible()
         && (fory_type_id & 0xff == TypeId::NAMED_COMPATIBLE_STRUCT as u32
@@ -65,6 +65,34 @@ pub fn read_trait_object_headers(context: &mut ReadContext) -> Result<u32, Error
     Ok(fory_type_id)
 }
 
+/// Applies the [`UnknownTypePolicy`](crate::types::UnknownTypePolicy) to a trait object
+/// whose type id matches none of the trait's implementations, given the cursor at its
+/// type id and the error to report under `UnknownTypePolicy::Error`.
+///
+/// Otherwise the value is skipped and [`Error::SkippedUnknownType`] is returned, so that a
+/// map can drop the entry. A trait object cannot hold a
+/// [`RawValue`](crate::serializer::any::RawValue), so `Preserve` skips as well.
+#[doc(hidden)]
+pub fn skip_unknown_trait_object(
+    context: &mut ReadContext,
+    fory_type_id: u32,
+    type_info_start: usize,
+    not_registered: Error,
+) -> Error {
+    use crate::types::UnknownTypePolicy;
+
+    if context.get_unknown_type_policy() == UnknownTypePolicy::Error {
+        return not_registered;
+    }
+    if let Err(e) = context.reader.set_cursor(type_info_start) {
+        return e;
+    }
+    match crate::serializer::any::read_unknown_data_with(context, UnknownTypePolicy::Skip) {
+        Ok(_) => Error::SkippedUnknownType(fory_type_id),
+        Err(e) => e,
+    }
+}
+
 /// Helper macro for common type resolution and downcasting pattern
 #[macro_export]
 macro_rules! downcast_and_serialize {
@@ -199,6 +227,7 @@ macro_rules! register_trait_type {
 
         // 4. Serializer implementation for Box<dyn Trait> (existing functionality)
         impl $crate::serializer::Serializer for Box<dyn $trait_name> {
+            $crate::read_null_as_default!();
             fn fory_write(&self, context: &mut $crate::resolver::context::WriteContext, is_field: bool) -> Result<(), $crate::error::Error> {
                 let any_ref = <dyn $trait_name as $crate::serializer::Serializer>::as_any(&**self);
                 let concrete_type_id = any_ref.type_id();
@@ -228,6 +257,10 @@ macro_rules! register_trait_type {
                 true
             }
 
+            fn fory_read_without_default(context: &mut $crate::resolver::context::ReadContext, is_field: bool) -> Result<Self, $crate::error::Error> {
+                Self::fory_read(context, is_field)
+            }
+
             fn fory_write_type_info(_context: &mut $crate::resolver::context::WriteContext, _is_field: bool) -> Result<(), $crate::error::Error> {
                 // Box<dyn Trait> is polymorphic - type info is written per element
                 Ok(())
@@ -240,14 +273,24 @@ macro_rules! register_trait_type {
 
             fn fory_read(context: &mut $crate::resolver::context::ReadContext, is_field: bool) -> Result<Self, $crate::error::Error> {
                 context.inc_depth()?;
+                // the type id follows the ref flag
+                let type_info_start = context.reader.get_cursor() + 1;
                 let fory_type_id = $crate::serializer::trait_object::read_trait_object_headers(context)?;
-                let result = $crate::resolve_and_deserialize!(
+                let result: Result<Self, $crate::error::Error> = $crate::resolve_and_deserialize!(
                     fory_type_id, context, is_field,
                     |obj| Box::new(obj) as Box<dyn $trait_name>,
                     $trait_name, $($impl_type),+
                 );
                 context.dec_depth();
-                result
+                // every known type returns above, so this is an unknown type id
+                result.map_err(|err| {
+                    $crate::serializer::trait_object::skip_unknown_trait_object(
+                        context,
+                        fory_type_id,
+                        type_info_start,
+                        err,
+                    )
+                })
             }
 
             fn fory_read_data(_context: &mut $crate::resolver::context::ReadContext, _is_field: bool) -> Result<Self, $crate::error::Error> {
@@ -405,6 +448,7 @@ macro_rules! generate_smart_pointer_wrapper {
 macro_rules! impl_smart_pointer_serializer {
     ($wrapper_name:ident, $pointer_type:ty, $constructor_expr:expr, $trait_name:ident, $try_write_ref:ident, $get_ref:ident, $store_ref:ident, $($impl_type:ty),+) => {
         impl $crate::serializer::Serializer for $wrapper_name {
+            $crate::read_null_as_default!();
             fn fory_write(&self, context: &mut $crate::resolver::context::WriteContext, is_field: bool) -> Result<(), $crate::error::Error> {
                 if !context.ref_writer.$try_write_ref(&mut context.writer, &self.0) {
                     let any_obj = <dyn $trait_name as $crate::serializer::Serializer>::as_any(&*self.0);
@@ -459,6 +503,7 @@ macro_rules! impl_smart_pointer_serializer {
                         ))
                     }
                     RefFlag::RefValue => {
+                        context.check_ref_count()?;
                         context.inc_depth()?;
                         let harness = context.read_any_typeinfo()?;
                         let deserializer_fn = harness.get_read_data_fn();
@@ -482,7 +527,7 @@ macro_rules! impl_smart_pointer_serializer {
                 }
             }
             fn fory_read_data(context: &mut $crate::resolver::context::ReadContext, is_field: bool) -> Result<Self, $crate::error::Error> {
-                let concrete_fory_type_id = context.reader.read_varuint32()?;
+                let concrete_fory_type_id = context.read_type_id()?;
                 $crate::resolve_and_deserialize!(
                     concrete_fory_type_id, context, is_field,
                     |obj| {
@@ -509,6 +554,10 @@ macro_rules! impl_smart_pointer_serializer {
                 true
             }
 
+            fn fory_read_without_default(context: &mut $crate::resolver::context::ReadContext, is_field: bool) -> Result<Self, $crate::error::Error> {
+                Self::fory_read(context, is_field)
+            }
+
             fn fory_type_id_dyn(&self, type_resolver: &$crate::resolver::type_resolver::TypeResolver) -> Result<u32, $crate::error::Error> {
                 let any_obj = <dyn $trait_name as $crate::serializer::Serializer>::as_any(&*self.0);
                 let concrete_type_id = any_obj.type_id();
@@ -542,6 +591,7 @@ impl ForyDefault for Box<dyn Serializer> {
 }
 
 impl Serializer for Box<dyn Serializer> {
+    crate::read_null_as_default!();
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         let fory_type_id = (**self).fory_type_id_dyn(context.get_type_resolver())?;
         let concrete_type_id = (**self).fory_concrete_type_id();
@@ -566,6 +616,10 @@ impl Serializer for Box<dyn Serializer> {
         true
     }
 
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
     fn fory_write_type_info(_context: &mut WriteContext, _is_field: bool) -> Result<(), Error> {
         // Box<dyn Serializer> is polymorphic - type info is written per element
         Ok(())
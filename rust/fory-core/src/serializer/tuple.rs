@@ -0,0 +1,191 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::ensure;
+use crate::error::Error;
+use crate::resolver::context::ReadContext;
+use crate::resolver::context::WriteContext;
+use crate::resolver::type_resolver::TypeResolver;
+use crate::serializer::collection::{
+    DECL_ELEMENT_TYPE, DICTIONARY, HAS_NULL, IS_SAME_TYPE, SPARSE_NULL,
+};
+use crate::serializer::{
+    read_ref_info_data, read_type_info, write_ref_info_data, write_type_info, ForyDefault,
+    Serializer,
+};
+use crate::types::TypeId;
+use std::cmp::Ordering;
+
+/// Whether a tuple whose elements have the Rust types `type_ids` is written with the
+/// element type `T` of its first element once, as a list whose elements are of the same
+/// type: all elements must be a `T` that is neither polymorphic nor a shared reference.
+pub fn is_same_type<T: Serializer>(type_ids: &[std::any::TypeId]) -> bool {
+    !T::fory_is_polymorphic()
+        && !T::fory_is_shared_ref()
+        && type_ids
+            .iter()
+            .all(|type_id| *type_id == std::any::TypeId::of::<T>())
+}
+
+/// Writes an element like one of a list whose elements are not of the same type, with its
+/// ref flag and type info.
+fn write_element<T: Serializer>(value: &T, context: &mut WriteContext) -> Result<(), Error> {
+    if T::fory_is_polymorphic() || T::fory_is_shared_ref() {
+        return value.fory_write(context, false);
+    }
+    write_ref_info_data(value, context, false, false, false)
+}
+
+fn read_element<T: Serializer + ForyDefault>(context: &mut ReadContext) -> Result<T, Error> {
+    if T::fory_is_polymorphic() || T::fory_is_shared_ref() {
+        return T::fory_read(context, false);
+    }
+    read_ref_info_data(context, false, false, false)
+}
+
+/// Tuples are written as a `LIST` of their arity, so that peers can read or skip them like
+/// any list. When every element has the same type, e.g. a `(u32, u32)` map key, the header
+/// marks them as such and the element type info is written once, as for a `Vec`.
+/// Otherwise it marks them as not of the same type, and each element follows the previous
+/// one with its own ref flag and type info.
+///
+/// In deterministic mode tuples, e.g. composite map keys, are ordered element by element.
+///
+/// `$first` names the type of the first element, which a tuple of the same type is
+/// written as.
+macro_rules! impl_tuple_serializer {
+    ($first:ident; $($name:ident : $index:tt),+) => {
+        impl<$($name),+> Serializer for ($($name,)+)
+        where
+            $($name: Serializer + ForyDefault),+
+        {
+            crate::read_null_as_default!();
+
+            fn fory_write_data(
+                &self,
+                context: &mut WriteContext,
+                is_field: bool,
+            ) -> Result<(), Error> {
+                context.writer.write_varuint32([$($index),+].len() as u32);
+                if !is_same_type::<$first>(&[$(std::any::TypeId::of::<$name>()),+]) {
+                    context.writer.write_u8(0);
+                    $(write_element(&self.$index, context)?;)+
+                    return Ok(());
+                }
+                let has_null = [$(self.$index.fory_is_none()),+].contains(&true);
+                let mut header = IS_SAME_TYPE;
+                if has_null {
+                    header |= HAS_NULL;
+                }
+                if is_field {
+                    header |= DECL_ELEMENT_TYPE;
+                }
+                context.writer.write_u8(header);
+                $first::fory_write_type_info(context, is_field)?;
+                $(write_ref_info_data(&self.$index, context, is_field, !has_null, true)?;)+
+                Ok(())
+            }
+
+            fn fory_read_data(context: &mut ReadContext, _is_field: bool) -> Result<Self, Error> {
+                let arity = [$($index),+].len() as u32;
+                let len = context.reader.read_varuint32()?;
+                ensure!(
+                    len == arity,
+                    Error::InvalidData(
+                        format!("expected a tuple of {arity} elements, got {len}").into()
+                    )
+                );
+                let header = context.reader.read_u8()?;
+                let is_same_type_header = header & IS_SAME_TYPE != 0;
+                // elements of the same type may also have been written each with its own
+                // type info
+                let readable = if is_same_type_header {
+                    is_same_type::<$first>(&[$(std::any::TypeId::of::<$name>()),+])
+                } else {
+                    header & DECL_ELEMENT_TYPE == 0
+                };
+                ensure!(
+                    readable && header & (SPARSE_NULL | DICTIONARY) == 0,
+                    Error::InvalidData(
+                        format!("a tuple cannot be read from a list with header {header:#b}")
+                            .into()
+                    )
+                );
+                if !is_same_type_header {
+                    return Ok(($(read_element::<$name>(context)?,)+));
+                }
+                let declared = header & DECL_ELEMENT_TYPE != 0;
+                let skip_ref_flag = header & HAS_NULL == 0;
+                $first::fory_read_type_info(context, declared)?;
+                Ok(($(read_ref_info_data::<$name>(context, declared, skip_ref_flag, true)?,)+))
+            }
+
+            fn fory_cmp_serialized(
+                &self,
+                other: &Self,
+                context: &mut WriteContext,
+            ) -> Result<Ordering, Error> {
+                $(
+                    match self.$index.fory_cmp_serialized(&other.$index, context)? {
+                        Ordering::Equal => {}
+                        ordering => return Ok(ordering),
+                    }
+                )+
+                Ok(Ordering::Equal)
+            }
+
+            fn fory_cmp_serialized_bytes() -> bool {
+                false
+            }
+
+            fn fory_get_type_id(_: &TypeResolver) -> Result<u32, Error> {
+                Ok(TypeId::LIST as u32)
+            }
+
+            fn fory_type_id_dyn(&self, _: &TypeResolver) -> Result<u32, Error> {
+                Ok(TypeId::LIST as u32)
+            }
+
+            fn fory_write_type_info(
+                context: &mut WriteContext,
+                is_field: bool,
+            ) -> Result<(), Error> {
+                write_type_info::<Self>(context, is_field)
+            }
+
+            fn fory_read_type_info(context: &mut ReadContext, is_field: bool) -> Result<(), Error> {
+                read_type_info::<Self>(context, is_field)
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+
+        impl<$($name: ForyDefault),+> ForyDefault for ($($name,)+) {
+            fn fory_default() -> Self {
+                ($($name::fory_default(),)+)
+            }
+        }
+    };
+}
+
+impl_tuple_serializer!(A; A: 0, B: 1);
+impl_tuple_serializer!(A; A: 0, B: 1, C: 2);
+impl_tuple_serializer!(A; A: 0, B: 1, C: 2, D: 3);
+impl_tuple_serializer!(A; A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_tuple_serializer!(A; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
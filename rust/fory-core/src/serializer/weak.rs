@@ -308,10 +308,15 @@ unsafe impl<T: ?Sized + Send + Sync> Send for ArcWeak<T> {}
 unsafe impl<T: ?Sized + Send + Sync> Sync for ArcWeak<T> {}
 
 impl<T: Serializer + ForyDefault + 'static> Serializer for RcWeak<T> {
+    crate::read_null_as_default!();
     fn fory_is_shared_ref() -> bool {
         true
     }
 
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         if let Some(rc) = self.upgrade() {
             if context
@@ -341,6 +346,7 @@ impl<T: Serializer + ForyDefault + 'static> Serializer for RcWeak<T> {
         match ref_flag {
             RefFlag::Null => Ok(RcWeak::new()),
             RefFlag::RefValue => {
+                context.check_ref_count()?;
                 context.inc_depth()?;
                 let data = T::fory_read_data(context, is_field)?;
                 context.dec_depth();
@@ -410,10 +416,15 @@ impl<T: ForyDefault> ForyDefault for RcWeak<T> {
 }
 
 impl<T: Serializer + ForyDefault + Send + Sync + 'static> Serializer for ArcWeak<T> {
+    crate::read_null_as_default!();
     fn fory_is_shared_ref() -> bool {
         true
     }
 
+    fn fory_read_without_default(context: &mut ReadContext, is_field: bool) -> Result<Self, Error> {
+        Self::fory_read(context, is_field)
+    }
+
     fn fory_write(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
         if let Some(arc) = self.upgrade() {
             // IMPORTANT: If the target Arc was serialized already, just write a ref
@@ -446,6 +457,7 @@ impl<T: Serializer + ForyDefault + Send + Sync + 'static> Serializer for ArcWeak
         match ref_flag {
             RefFlag::Null => Ok(ArcWeak::new()),
             RefFlag::RefValue => {
+                context.check_ref_count()?;
                 context.inc_depth()?;
                 let data = T::fory_read_data(context, _is_field)?;
                 context.dec_depth();
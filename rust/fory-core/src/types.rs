@@ -18,6 +18,8 @@
 use crate::error::Error;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use std::mem;
+use std::ops::ControlFlow;
+use std::sync::Arc;
 
 #[allow(dead_code)]
 pub enum StringFlag {
@@ -84,6 +86,14 @@ pub enum TypeId {
     UNKNOWN = 63,
     // only used at receiver peer
     ForyNullable = 265,
+    // recorded in the type meta for `#[fory(endian = "...")]` integer fields, which are
+    // written fixed-width while `INT32` and `INT64` fields use varints
+    FIXED_INT32 = 266,
+    FIXED_INT64 = 267,
+    // recorded for `[u8; N]` and `[Option<T>; N]`, which are written without a length
+    // prefix, together with their length `N`
+    FIXED_BINARY = 268,
+    FIXED_LIST = 269,
 }
 
 const MAX_UNT32: u64 = (1 << 31) - 1;
@@ -122,7 +132,7 @@ pub static BASIC_TYPES: [TypeId; 18] = [
     TypeId::FLOAT64_ARRAY,
 ];
 
-pub static PRIMITIVE_TYPES: [u32; 7] = [
+pub static PRIMITIVE_TYPES: [u32; 9] = [
     TypeId::BOOL as u32,
     TypeId::INT8 as u32,
     TypeId::INT16 as u32,
@@ -130,6 +140,25 @@ pub static PRIMITIVE_TYPES: [u32; 7] = [
     TypeId::INT64 as u32,
     TypeId::FLOAT32 as u32,
     TypeId::FLOAT64 as u32,
+    TypeId::FIXED_INT32 as u32,
+    TypeId::FIXED_INT64 as u32,
+];
+
+/// The integer types, which compatible mode can convert into each other, see
+/// [`Fory::with_numeric_conversion`](crate::fory::Fory::with_numeric_conversion).
+pub static INTEGER_TYPES: [u32; 4] = [
+    TypeId::INT8 as u32,
+    TypeId::INT16 as u32,
+    TypeId::INT32 as u32,
+    TypeId::INT64 as u32,
+];
+
+/// The arrays of [`INTEGER_TYPES`], whose elements are converted in the same way.
+pub static INTEGER_ARRAY_TYPES: [u32; 4] = [
+    TypeId::INT8_ARRAY as u32,
+    TypeId::INT16_ARRAY as u32,
+    TypeId::INT32_ARRAY as u32,
+    TypeId::INT64_ARRAY as u32,
 ];
 
 pub static PRIMITIVE_ARRAY_TYPES: [u32; 8] = [
@@ -172,6 +201,9 @@ pub static PRIMITIVE_ARRAY_TYPE_MAP: &[(&str, u32, &str)] = &[
 ];
 
 pub fn is_internal_type(type_id: u32) -> bool {
+    if type_id == TypeId::FIXED_BINARY as u32 {
+        return true;
+    }
     if type_id == 0 || type_id >= TypeId::UNKNOWN as u32 {
         return false;
     }
@@ -217,6 +249,8 @@ pub mod config_flags {
     pub const IS_LITTLE_ENDIAN_FLAG: u8 = 2;
     pub const IS_CROSS_LANGUAGE_FLAG: u8 = 4;
     pub const IS_OUT_OF_BAND_FLAG: u8 = 8;
+    /// Primitive array payloads are padded, see `Fory::with_array_alignment`.
+    pub const IS_ARRAY_ALIGNED_FLAG: u8 = 16;
 }
 
 #[derive(Debug, PartialEq)]
@@ -240,6 +274,59 @@ pub enum Mode {
     Compatible,
 }
 
+/// How polymorphic reads (`Box<dyn Any>`, `Rc<dyn Any>`, `Arc<dyn Any>`) treat a value
+/// whose type is not registered on the reading side.
+///
+/// Only values that carry their own type meta, i.e. compatible structs written with
+/// meta sharing enabled, can be skipped or preserved. Anything else is still an error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnknownTypePolicy {
+    /// Skip the value's bytes and yield `()` in its place.
+    Skip,
+    /// Fail the read with [`Error::TypeError`].
+    #[default]
+    Error,
+    /// Keep the value as a [`RawValue`](crate::serializer::any::RawValue) holding its
+    /// type meta and data bytes, so that it can be written back unchanged.
+    Preserve,
+}
+
+/// How compatible mode writes the field names of a struct's type meta, see
+/// [`Fory::with_field_name_hash`](crate::fory::Fory::with_field_name_hash).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HashKind {
+    /// Write each field name as a meta string.
+    #[default]
+    Off,
+    /// Write a 32-bit hash of each field name.
+    Hash32,
+    /// Write a 64-bit hash of each field name.
+    Hash64,
+}
+
+impl HashKind {
+    /// Returns the hash of `name`, or `None` when hashing is off.
+    pub fn hash(self, name: &str) -> Option<u64> {
+        let hash = crate::meta::murmurhash3_x64_128(name.as_bytes(), 47).0;
+        match self {
+            HashKind::Off => None,
+            HashKind::Hash32 => Some(hash & 0xffff_ffff),
+            HashKind::Hash64 => Some(hash),
+        }
+    }
+}
+
+/// Callback consulted with every type id read from the input, see
+/// [`Fory::with_type_observer`](crate::fory::Fory::with_type_observer).
+pub type TypeObserver = Arc<dyn Fn(u32) -> ControlFlow<()> + Send + Sync>;
+
+/// Byte order of a fixed-width number, used by `#[fory(endian = "...")]` fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
 impl TryFrom<u8> for Language {
     type Error = Error;
 
@@ -259,7 +346,29 @@ impl TryFrom<u8> for Language {
     }
 }
 
+/// Plain numeric types whose values can be copied straight out of a buffer.
+///
+/// # Safety
+///
+/// Implementors must be valid for every bit pattern and contain no padding.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($ty:ty),+) => {
+        $(unsafe impl Pod for $ty {})+
+    };
+}
+
+impl_pod!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
 // every object start with i8 i16 reference flag and type flag
 pub const SIZE_OF_REF_AND_TYPE: usize = mem::size_of::<i8>() + mem::size_of::<i16>();
 
 pub const MAGIC_NUMBER: u16 = 0x62d4;
+
+/// Version of the wire format, written as the first byte of the output when the
+/// header is enabled with [`Fory::with_header`](crate::fory::Fory::with_header).
+///
+/// Version 1 is the format described by this crate: the optional xlang magic number,
+/// the header bitmap, the optional language byte, then the root value.
+pub const FORMAT_VERSION: u8 = 1;
@@ -15,6 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::ensure;
+use crate::error::Error;
+use crate::fory::Fory;
+use crate::serializer::{ForyDefault, Serializer};
 use crate::types::TypeId;
 use chrono::NaiveDate;
 use std::ptr;
@@ -129,3 +133,50 @@ pub fn get_ext_actual_type_id(type_id: u32, register_by_name: bool) -> u32 {
             TypeId::EXT as u32
         }
 }
+
+/// Serializes the default value of `T` with `fory` and checks that the type id written
+/// for it is `expected`.
+///
+/// This backs [`assert_fory_compatible!`](crate::assert_fory_compatible), which turns a
+/// mismatch into a panic for use in tests.
+pub fn check_type_id<T: Serializer + ForyDefault>(fory: &Fory, expected: u32) -> Result<(), Error> {
+    let bytes = fory.serialize(&T::fory_default())?;
+    let actual = fory.peek_type_id(&bytes)?.ok_or_else(|| {
+        Error::InvalidData("the default value is null, so no type id is written".into())
+    })?;
+    ensure!(actual == expected, Error::TypeMismatch(expected, actual));
+    Ok(())
+}
+
+/// Asserts that a type serializes with the given cross-language type id, to catch
+/// accidental format drift in tests.
+///
+/// The default value of the type is serialized, with `Fory::default()` or the instance
+/// given as a third argument, and the type id in front of it is compared with the
+/// expected one. Pass an instance to check registered types, whose ids combine the user
+/// id with the type kind.
+///
+/// # Examples
+///
+/// ```rust
+/// use fory_core::{assert_fory_compatible, Fory, TypeId};
+///
+/// assert_fory_compatible!(i32, TypeId::INT32);
+/// assert_fory_compatible!(String, TypeId::STRING, &Fory::default());
+/// ```
+#[macro_export]
+macro_rules! assert_fory_compatible {
+    ($ty:ty, $type_id:expr) => {
+        $crate::assert_fory_compatible!($ty, $type_id, &$crate::fory::Fory::default())
+    };
+    ($ty:ty, $type_id:expr, $fory:expr) => {
+        if let Err(e) = $crate::util::check_type_id::<$ty>($fory, $type_id as u32) {
+            panic!(
+                "`{}` does not serialize with fory type id {}: {}",
+                stringify!($ty),
+                $type_id as u32,
+                e
+            );
+        }
+    };
+}
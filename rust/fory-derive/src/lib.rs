@@ -30,8 +30,11 @@
 //!
 //! **Supported Types:**
 //! - Structs with named fields
-//! - Structs with unnamed fields (tuple structs)
+//! - Structs with unnamed fields (tuple structs), whose fields are named by
+//!   position (`0`, `1`, ...) in the type meta
 //! - Unit structs
+//! - Generic structs such as `Wrapper<T>`, where each monomorphization is
+//!   registered on its own, e.g. with `register_generic!`
 //! - Enums with variants
 //!
 //! **Example:**
@@ -63,6 +66,47 @@
 //! }
 //! ```
 //!
+//! **Attributes:**
+//! - `#[fory(unknown)]` on a single-field tuple variant such as `Unknown(u32)`
+//!   marks it as the catch-all for enum ordinals this side does not know. The
+//!   raw ordinal is kept and written back unchanged.
+//! - `#[fory(endian = "big")]` or `#[fory(endian = "little")]` on a number field
+//!   such as `u32` writes it fixed-width in that byte order instead of the default
+//!   encoding, for protocols that mix byte orders. The type meta records the
+//!   fixed width, so peers without the field skip it, but not the byte order, so
+//!   peers reading the field must declare the same attribute.
+//! - `#[fory(with = "module")]` on a field replaces its default serialization
+//!   with `module::serialize(&value, context)` and `module::deserialize(context)`,
+//!   e.g. to encode a timestamp as a string without a newtype. What the module
+//!   writes is framed as `BINARY`, so peers without the field skip it, but peers
+//!   reading it must use the same module.
+//! - `#[fory(transparent)]` on a struct with exactly one field, such as
+//!   `struct Port(u16)`, serializes it exactly like that field, so a `Port` can
+//!   be read back as a bare `u16` and vice versa. Such a struct needs no
+//!   registration. In compatible mode it cannot yet be a field of another
+//!   struct, since fields are ordered by their declared type name.
+//! - `#[fory(validate = "check")]` on a struct calls `check(&self)` after the
+//!   struct is read, and on a field calls `check(&field)`. A `check` returning
+//!   `Err(String)` fails the read with `Error::ValidationFailed`, which guards
+//!   invariants against untrusted input.
+//! - `#[fory(encrypt)]` on a field encrypts its serialized bytes with the cipher
+//!   set by `Fory::with_cipher`, and reading one without that cipher fails with
+//!   `Error::Cipher`. The type meta records the ciphertext as `BINARY`, so peers
+//!   without the field skip it.
+//! - `#[fory(compact)]` on a struct writes it by position even in compatible
+//!   mode, like in consistent mode, so a stable struct nested in an evolving one
+//!   costs no type meta with its field names. A hash of its fields precedes the
+//!   data, and a reader whose fields differ rejects it with
+//!   `Error::SchemaMismatch` instead of misreading it. Compatible mode cannot
+//!   skip such a struct as an unknown field.
+//! - `#[fory(repr)]` on a C-like enum with `#[repr(u16)]` (or any other
+//!   fixed-width integer) writes each variant's discriminant with that width
+//!   instead of as a varuint ordinal, for byte-compatibility with C structs.
+//!   Without it a `#[repr]` enum is written by ordinal like any other. Unknown discriminants are
+//!   rejected on read. The type meta of a struct records fields of such an enum
+//!   as the integer type of that width, so compatible mode skips them when the
+//!   enum is registered.
+//!
 //! ### `#[derive(ForyRow)]`
 //!
 //! Generates row-based serialization code for structs. This macro implements
@@ -198,7 +242,7 @@ mod util;
 ///     city: String,
 /// }
 /// ```
-#[proc_macro_derive(ForyObject)]
+#[proc_macro_derive(ForyObject, attributes(fory))]
 pub fn proc_macro_derive_fory_object(input: proc_macro::TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
@@ -207,6 +251,52 @@ pub fn proc_macro_derive_fory_object(input: proc_macro::TokenStream) -> TokenStr
     object::derive_serializer(&input)
 }
 
+/// Derive macro for partial-update deserialization.
+///
+/// This macro implements the `Merge` trait for a struct that also derives
+/// `ForyObject`, so that `Fory::merge_into` can assign only the fields present
+/// in a compatible-mode payload and leave the others unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use fory_derive::{ForyMerge, ForyObject};
+///
+/// #[derive(ForyObject, ForyMerge, Debug, PartialEq)]
+/// struct Settings {
+///     theme: String,
+///     font_size: i32,
+/// }
+/// ```
+#[proc_macro_derive(ForyMerge)]
+pub fn proc_macro_derive_fory_merge(input: proc_macro::TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    object::derive_merge(&input)
+}
+
+/// Derive macro for change reporting.
+///
+/// This macro implements the `Diff` trait for a struct, comparing its fields
+/// with `PartialEq`, so that `Fory::deserialize_into_diff` can report which
+/// fields a refill changed.
+///
+/// # Example
+///
+/// ```rust
+/// use fory_derive::{ForyDiff, ForyObject};
+///
+/// #[derive(ForyObject, ForyDiff, Debug, PartialEq)]
+/// struct Settings {
+///     theme: String,
+///     font_size: i32,
+/// }
+/// ```
+#[proc_macro_derive(ForyDiff)]
+pub fn proc_macro_derive_fory_diff(input: proc_macro::TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    object::derive_diff(&input)
+}
+
 /// Derive macro for row-based serialization.
 ///
 /// This macro generates code to implement the `Row` trait for the annotated
@@ -16,8 +16,150 @@
 // under the License.
 
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::DataEnum;
+use quote::{format_ident, quote};
+use syn::{Attribute, DataEnum, Fields, Ident, Variant};
+
+/// Returns true if the variant is marked with `#[fory(unknown)]`.
+pub(crate) fn is_unknown_variant(variant: &Variant) -> bool {
+    variant.attrs.iter().any(|attr| {
+        attr.path().is_ident("fory") && {
+            let mut is_unknown = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("unknown") {
+                    is_unknown = true;
+                }
+                Ok(())
+            });
+            is_unknown
+        }
+    })
+}
+
+/// Splits the variants into the known ones, paired with their ordinals, and
+/// the optional `#[fory(unknown)]` catch-all which holds the raw ordinal.
+fn split_variants(data_enum: &DataEnum) -> (Vec<(&Variant, u32)>, Option<&Variant>) {
+    let mut known = Vec::new();
+    let mut unknown = None;
+    for variant in data_enum.variants.iter() {
+        if is_unknown_variant(variant) {
+            if unknown.is_some() {
+                panic!("Only one variant can be marked with #[fory(unknown)]");
+            }
+            match &variant.fields {
+                Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {}
+                _ => panic!(
+                    "#[fory(unknown)] variant `{}` must have exactly one unnamed u32 field",
+                    variant.ident
+                ),
+            }
+            unknown = Some(variant);
+        } else {
+            let ordinal = known.len() as u32;
+            known.push((variant, ordinal));
+        }
+    }
+    (known, unknown)
+}
+
+/// Returns the integer type of a `#[repr(u16)]`-style attribute, if the enum opts in
+/// with `#[fory(repr)]`. The discriminant is then written with that width instead of
+/// as a varuint ordinal.
+fn fixed_repr(attrs: &[Attribute]) -> Option<Ident> {
+    let opted_in = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("fory"))
+        .any(|attr| {
+            let mut opted_in = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("repr") {
+                    opted_in = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::LitStr>()?;
+                }
+                Ok(())
+            });
+            opted_in
+        });
+    if !opted_in {
+        return None;
+    }
+    let mut repr = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident() {
+                let is_fixed = matches!(
+                    ident.to_string().as_str(),
+                    "u8" | "i8" | "u16" | "i16" | "u32" | "i32" | "u64" | "i64"
+                );
+                if is_fixed {
+                    repr = Some(ident.clone());
+                }
+            }
+            Ok(())
+        });
+    }
+    if repr.is_none() {
+        panic!("#[fory(repr)] needs a fixed-width integer #[repr(...)] on the enum");
+    }
+    repr
+}
+
+/// Generates the read and write of a `#[repr]` enum, whose discriminants are written
+/// fixed-width in little endian, as a C struct holding the enum lays them out.
+fn gen_repr_data(data_enum: &DataEnum, repr: &Ident) -> (TokenStream, TokenStream) {
+    let (known, unknown) = split_variants(data_enum);
+    if let Some(v) = unknown {
+        panic!(
+            "#[fory(unknown)] variant `{}` cannot be used in a #[repr({})] enum",
+            v.ident, repr
+        );
+    }
+    if let Some((v, _)) = known
+        .iter()
+        .find(|(v, _)| !matches!(v.fields, Fields::Unit))
+    {
+        panic!(
+            "#[repr({})] enum variant `{}` must be a unit variant",
+            repr, v.ident
+        );
+    }
+    let variant_idents: Vec<_> = known.iter().map(|(v, _)| &v.ident).collect();
+    let write_fn = format_ident!("write_{}", repr);
+    let read_fn = format_ident!("read_{}", repr);
+    let write = quote! {
+        context.writer.#write_fn(match self {
+            #(Self::#variant_idents => Self::#variant_idents as #repr,)*
+        });
+        Ok(())
+    };
+    let read = quote! {
+        let discriminant = context.reader.#read_fn()?;
+        #(
+            if discriminant == Self::#variant_idents as #repr {
+                return Ok(Self::#variant_idents);
+            }
+        )*
+        Err(fory_core::error::Error::UnknownEnum(
+            format!("unknown enum discriminant: {}", discriminant).into(),
+        ))
+    };
+    (write, read)
+}
+
+/// Generates the type id the type meta of a struct records for fields of a `#[repr]`
+/// enum, the integer type that skips as many bytes as the discriminant takes.
+pub fn gen_repr_type_id(attrs: &[Attribute]) -> TokenStream {
+    let Some(repr) = fixed_repr(attrs) else {
+        return quote! { None };
+    };
+    let type_id = match repr.to_string().as_str() {
+        "u8" | "i8" => quote! { INT8 },
+        "u16" | "i16" => quote! { INT16 },
+        "u32" | "i32" => quote! { FIXED_INT32 },
+        _ => quote! { FIXED_INT64 },
+    };
+    quote! { Some(fory_core::types::TypeId::#type_id as u32) }
+}
 
 pub fn gen_actual_type_id() -> TokenStream {
     quote! {
@@ -49,9 +191,69 @@ pub fn gen_read_type_info() -> TokenStream {
     }
 }
 
-pub fn gen_write_data(data_enum: &DataEnum) -> TokenStream {
-    let variant_idents: Vec<_> = data_enum.variants.iter().map(|v| &v.ident).collect();
-    let variant_values: Vec<_> = (0..variant_idents.len()).map(|v| v as u32).collect();
+/// Generates the read and write of the variant name of an enum registered with
+/// `Fory::register_enum_as_string`, which precede the ordinal encoding.
+fn gen_name_data(data_enum: &DataEnum) -> (TokenStream, TokenStream) {
+    let (known, unknown) = split_variants(data_enum);
+    let variant_idents: Vec<_> = known.iter().map(|(v, _)| &v.ident).collect();
+    let variant_names: Vec<_> = variant_idents.iter().map(|i| i.to_string()).collect();
+    let unknown_arm = unknown.map(|v| {
+        let ident = &v.ident;
+        quote! {
+            Self::#ident(ordinal) => {
+                return Err(fory_core::error::Error::UnknownEnum(
+                    format!("unknown enum value {} has no variant name", ordinal).into(),
+                ));
+            }
+        }
+    });
+    let write = quote! {
+        if fory_core::serializer::enum_::is_written_as_string::<Self>(context.get_type_resolver()) {
+            let name = match self {
+                #(Self::#variant_idents => #variant_names,)*
+                #unknown_arm
+            };
+            return fory_core::serializer::enum_::write_variant_name(name, context);
+        }
+    };
+    let read = quote! {
+        if fory_core::serializer::enum_::is_written_as_string::<Self>(context.get_type_resolver()) {
+            let name = fory_core::serializer::enum_::read_variant_name(context)?;
+            return match name.as_str() {
+                #(#variant_names => Ok(Self::#variant_idents),)*
+                _ => Err(fory_core::error::Error::UnknownEnum(
+                    format!("unknown enum variant name: {}", name).into(),
+                )),
+            };
+        }
+    };
+    (write, read)
+}
+
+pub fn gen_write_data(data_enum: &DataEnum, attrs: &[Attribute]) -> TokenStream {
+    let name_write = gen_name_data(data_enum).0;
+    let ordinal_write = gen_ordinal_write_data(data_enum, attrs);
+    quote! {
+        #name_write
+        #ordinal_write
+    }
+}
+
+fn gen_ordinal_write_data(data_enum: &DataEnum, attrs: &[Attribute]) -> TokenStream {
+    if let Some(repr) = fixed_repr(attrs) {
+        return gen_repr_data(data_enum, &repr).0;
+    }
+    let (known, unknown) = split_variants(data_enum);
+    let variant_idents: Vec<_> = known.iter().map(|(v, _)| &v.ident).collect();
+    let variant_values: Vec<_> = known.iter().map(|(_, ordinal)| *ordinal).collect();
+    let unknown_arm = unknown.map(|v| {
+        let ident = &v.ident;
+        quote! {
+            Self::#ident(ordinal) => {
+                context.writer.write_varuint32(*ordinal);
+            }
+        }
+    });
     quote! {
         Ok(match self {
             #(
@@ -59,20 +261,43 @@ pub fn gen_write_data(data_enum: &DataEnum) -> TokenStream {
                     context.writer.write_varuint32(#variant_values);
                 }
             )*
+            #unknown_arm
         })
     }
 }
 
-pub fn gen_read_data(data_enum: &DataEnum) -> TokenStream {
-    let variant_idents: Vec<_> = data_enum.variants.iter().map(|v| &v.ident).collect();
-    let variant_values: Vec<_> = (0..variant_idents.len()).map(|v| v as u32).collect();
+pub fn gen_read_data(data_enum: &DataEnum, attrs: &[Attribute]) -> TokenStream {
+    let name_read = gen_name_data(data_enum).1;
+    let ordinal_read = gen_ordinal_read_data(data_enum, attrs);
+    quote! {
+        #name_read
+        #ordinal_read
+    }
+}
+
+fn gen_ordinal_read_data(data_enum: &DataEnum, attrs: &[Attribute]) -> TokenStream {
+    if let Some(repr) = fixed_repr(attrs) {
+        return gen_repr_data(data_enum, &repr).1;
+    }
+    let (known, unknown) = split_variants(data_enum);
+    let variant_idents: Vec<_> = known.iter().map(|(v, _)| &v.ident).collect();
+    let variant_values: Vec<_> = known.iter().map(|(_, ordinal)| *ordinal).collect();
+    let fallback_arm = match unknown {
+        Some(v) => {
+            let ident = &v.ident;
+            quote! { _ => Ok(Self::#ident(ordinal)), }
+        }
+        None => quote! {
+            _ => return Err(fory_core::error::Error::UnknownEnum("unknown enum value".into())),
+        },
+    };
     quote! {
         let ordinal = context.reader.read_varuint32()?;
         match ordinal {
            #(
                #variant_values => Ok(Self::#variant_idents),
            )*
-           _ => return Err(fory_core::error::Error::UnknownEnum("unknown enum value".into())),
+           #fallback_arm
         }
     }
 }
@@ -21,7 +21,8 @@ use std::sync::atomic::{AtomicU32, Ordering};
 use syn::Field;
 
 use super::util::{
-    classify_trait_object_field, generic_tree_to_tokens, get_sort_fields_ts, parse_generic_tree,
+    classify_trait_object_field, field_encrypt, field_endian, field_name, field_with,
+    generic_tree_to_tokens, get_sort_fields_ts, integer_conversion, parse_generic_tree,
     StructField,
 };
 
@@ -58,9 +59,17 @@ fn hash(fields: &[&Field]) -> TokenStream {
     }
 }
 
-pub fn gen_actual_type_id() -> TokenStream {
-    quote! {
-        fory_core::serializer::struct_::actual_type_id(type_id, register_by_name, compatible)
+pub fn gen_actual_type_id(compact: bool) -> TokenStream {
+    if compact {
+        // a compact struct keeps its consistent mode type id, which has no type meta
+        quote! {
+            let _ = compatible;
+            fory_core::serializer::struct_::actual_type_id(type_id, register_by_name, false)
+        }
+    } else {
+        quote! {
+            fory_core::serializer::struct_::actual_type_id(type_id, register_by_name, compatible)
+        }
     }
 }
 
@@ -74,53 +83,91 @@ pub fn gen_get_sorted_field_names(fields: &[&Field]) -> TokenStream {
 pub fn gen_field_fields_info(fields: &[&Field]) -> TokenStream {
     let field_infos = fields.iter().map(|field| {
         let ty = &field.ty;
-        let name = format!("{}", field.ident.as_ref().expect("should be field name"));
+        let name = field_name(field);
+        if field_encrypt(field) {
+            // whatever the field is, it is written as its ciphertext, but it keeps its
+            // nullability, which decides how the plaintext is read
+            let generic_token = generic_tree_to_tokens(&parse_generic_tree(ty));
+            return quote! {
+                fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType::new(
+                    fory_core::types::TypeId::BINARY as u32,
+                    #generic_token.nullable,
+                    Vec::new()
+                ))
+            };
+        }
+        if field_with(field).is_some() {
+            // the codec output is written as binary, so a peer without the field skips it,
+            // but a peer with it must use the same codec
+            return quote! {
+                fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType::new(
+                    fory_core::types::TypeId::BINARY as u32,
+                    false,
+                    Vec::new()
+                ))
+            };
+        }
         match classify_trait_object_field(ty) {
+            StructField::None if field_endian(field).is_some() => {
+                quote! {
+                    fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType::new(
+                        <#ty as fory_core::serializer::number::FixedWidth>::FIXED_TYPE_ID as u32,
+                        false,
+                        Vec::new()
+                    ))
+                }
+            }
             StructField::None => {
                 let generic_tree = parse_generic_tree(ty);
                 let generic_token = generic_tree_to_tokens(&generic_tree);
-                quote! {
-                    fory_core::meta::FieldInfo::new(#name, #generic_token)
+                if integer_conversion(field).is_some() {
+                    quote! {
+                        fory_core::meta::FieldInfo::new(#name, #generic_token).with_integer_conversion()
+                    }
+                } else {
+                    quote! {
+                        fory_core::meta::FieldInfo::new(#name, #generic_token)
+                    }
                 }
             }
             StructField::VecRc(_) | StructField::VecArc(_) => {
                 quote! {
-                    fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType {
-                        type_id: fory_core::types::TypeId::LIST as u32,
-                        nullable: false,
-                        generics: vec![fory_core::meta::FieldType {
-                            type_id: fory_core::types::TypeId::UNKNOWN as u32,
-                            nullable: false,
-                            generics: Vec::new()
-                        }]
-                    })
+                    fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType::new(
+                        fory_core::types::TypeId::LIST as u32,
+                        false,
+                        vec![fory_core::meta::FieldType::new(
+                            fory_core::types::TypeId::UNKNOWN as u32,
+                            false,
+                            Vec::new()
+                        )]
+                    ))
                 }
             }
             StructField::HashMapRc(key_ty, _) | StructField::HashMapArc(key_ty, _) => {
                 let key_generic_tree = parse_generic_tree(key_ty.as_ref());
                 let key_generic_token = generic_tree_to_tokens(&key_generic_tree);
                 quote! {
-                    fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType {
-                        type_id: fory_core::types::TypeId::MAP as u32,
-                        nullable: false,
-                        generics: vec![
+                    fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType::new(
+                        fory_core::types::TypeId::MAP as u32,
+                        false,
+                        vec![
                             #key_generic_token,
-                            fory_core::meta::FieldType {
-                                type_id: fory_core::types::TypeId::UNKNOWN as u32,
-                                nullable: false,
-                                generics: Vec::new()
-                            }
+                            fory_core::meta::FieldType::new(
+                                fory_core::types::TypeId::UNKNOWN as u32,
+                                false,
+                                Vec::new()
+                            )
                         ]
-                    })
+                    ))
                 }
             }
             _ => {
                 quote! {
-                    fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType {
-                        type_id: fory_core::types::TypeId::UNKNOWN as u32,
-                        nullable: false,
-                        generics: Vec::new()
-                    })
+                    fory_core::meta::FieldInfo::new(#name, fory_core::meta::FieldType::new(
+                        fory_core::types::TypeId::UNKNOWN as u32,
+                        false,
+                        Vec::new()
+                    ))
                 }
             }
         }
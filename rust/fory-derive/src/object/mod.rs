@@ -22,4 +22,4 @@ mod serializer;
 pub(crate) mod util;
 mod write;
 
-pub use serializer::derive_serializer;
+pub use serializer::{derive_diff, derive_merge, derive_serializer};
@@ -18,11 +18,13 @@
 use fory_core::types::RefFlag;
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use syn::{Field, Type};
+use syn::Field;
 
 use super::util::{
     classify_trait_object_field, create_wrapper_types_arc, create_wrapper_types_rc,
-    extract_type_name, is_primitive_type, parse_generic_tree, skip_ref_flag, StructField,
+    extract_type_name, field_endian, field_member, field_name, field_with, integer_conversion,
+    is_primitive_type, parse_generic_tree, skip_ref_flag, wrap_field_encrypt_read,
+    IntegerConversion, StructField,
 };
 
 fn create_private_field_name(field: &Field) -> Ident {
@@ -41,13 +43,16 @@ fn declare_var(fields: &[&Field]) -> Vec<TokenStream> {
             let ty = &field.ty;
             let var_name = create_private_field_name(field);
             match classify_trait_object_field(ty) {
-                StructField::BoxDyn(_)
-                | StructField::RcDyn(_)
-                | StructField::ArcDyn(_) => {
+                StructField::BoxDyn(_) => {
                     quote! {
                         let mut #var_name: #ty = <#ty as fory_core::serializer::ForyDefault>::fory_default();
                     }
                 }
+                StructField::RcDyn(_) | StructField::ArcDyn(_) => {
+                    quote! {
+                        let mut #var_name: Option<#ty> = None;
+                    }
+                }
                 _ => {
                     if need_declared_by_option(field) {
                         quote! {
@@ -68,34 +73,50 @@ fn declare_var(fields: &[&Field]) -> Vec<TokenStream> {
         .collect()
 }
 
+// The expression that turns a field's read variable into the field value.
+fn field_value(field: &Field) -> TokenStream {
+    let var_name = create_private_field_name(field);
+    match classify_trait_object_field(&field.ty) {
+        StructField::BoxDyn(_) => {
+            quote! { #var_name }
+        }
+        StructField::RcDyn(trait_name) => {
+            let types = create_wrapper_types_rc(&trait_name);
+            let wrapper_ty = types.wrapper_ty;
+            let trait_ident = types.trait_ident;
+            quote! {
+                #var_name.unwrap_or_else(|| std::rc::Rc::<dyn #trait_ident>::from(#wrapper_ty::default()))
+            }
+        }
+        StructField::ArcDyn(trait_name) => {
+            let types = create_wrapper_types_arc(&trait_name);
+            let wrapper_ty = types.wrapper_ty;
+            let trait_ident = types.trait_ident;
+            quote! {
+                #var_name.unwrap_or_else(|| std::sync::Arc::<dyn #trait_ident>::from(#wrapper_ty::default()))
+            }
+        }
+        StructField::ContainsTraitObject => {
+            quote! { #var_name.unwrap() }
+        }
+        _ => {
+            if need_declared_by_option(field) {
+                quote! { #var_name.unwrap_or_else(fory_core::serializer::ForyDefault::fory_default) }
+            } else {
+                quote! { #var_name }
+            }
+        }
+    }
+}
+
 fn assign_value(fields: &[&Field]) -> Vec<TokenStream> {
     fields
         .iter()
         .map(|field| {
-            let name = &field.ident;
-            let var_name = create_private_field_name(field);
-            match classify_trait_object_field(&field.ty) {
-                StructField::BoxDyn(_) | StructField::RcDyn(_) | StructField::ArcDyn(_) => {
-                    quote! {
-                        #name: #var_name
-                    }
-                }
-                StructField::ContainsTraitObject => {
-                    quote! {
-                        #name: #var_name.unwrap()
-                    }
-                }
-                _ => {
-                    if need_declared_by_option(field) {
-                        quote! {
-                            #name: #var_name.unwrap_or_default()
-                        }
-                    } else {
-                        quote! {
-                            #name: #var_name
-                        }
-                    }
-                }
+            let name = field_member(field);
+            let value = field_value(field);
+            quote! {
+                #name: #value
             }
         })
         .collect()
@@ -103,6 +124,16 @@ fn assign_value(fields: &[&Field]) -> Vec<TokenStream> {
 
 fn gen_read_field(field: &Field, private_ident: &Ident) -> TokenStream {
     let ty = &field.ty;
+    if let Some(with) = field_with(field) {
+        return quote! {
+            let #private_ident = fory_core::serializer::codec::read_with(context, #with::deserialize)?;
+        };
+    }
+    if let Some(endian) = field_endian(field) {
+        return quote! {
+            let #private_ident = <#ty as fory_core::serializer::number::FixedWidth>::read_fixed(context, #endian)?;
+        };
+    }
     match classify_trait_object_field(ty) {
         StructField::BoxDyn(trait_name) => {
             let from_any_fn = format_ident!("from_any_internal_{}", trait_name);
@@ -113,7 +144,7 @@ fn gen_read_field(field: &Field, private_ident: &Ident) -> TokenStream {
                     return Err(Error::InvalidRef("Expected NotNullValue for trait object field".into()));
                 }
 
-                let fory_type_id = context.reader.read_varuint32()?;
+                let fory_type_id = context.read_type_id()?;
 
                 let harness = context.get_type_resolver()
                     .get_harness(fory_type_id)
@@ -209,14 +240,23 @@ pub fn gen_read_type_info() -> TokenStream {
     }
 }
 
+/// Reads `field` into a binding named by [`create_private_field_name`].
+fn gen_read_field_binding(field: &Field) -> TokenStream {
+    let private_ident = create_private_field_name(field);
+    let name = field_name(field);
+    let read_field = wrap_field_encrypt_read(
+        field,
+        gen_read_field(field, &private_ident),
+        Some(&private_ident),
+    );
+    quote! {
+        fory_core::serializer::struct_::ensure_field_present(context, #name)?;
+        #read_field
+    }
+}
+
 fn get_fields_loop_ts(fields: &[&Field]) -> TokenStream {
-    let read_fields_ts: Vec<_> = fields
-        .iter()
-        .map(|field| {
-            let private_ident = create_private_field_name(field);
-            gen_read_field(field, &private_ident)
-        })
-        .collect();
+    let read_fields_ts: Vec<_> = fields.iter().map(|f| gen_read_field_binding(f)).collect();
     quote! {
         #(#read_fields_ts)*
     }
@@ -233,7 +273,7 @@ pub fn gen_read_data(fields: &[&Field]) -> TokenStream {
     };
     let field_idents = fields.iter().map(|field| {
         let private_ident = create_private_field_name(field);
-        let original_ident = &field.ident;
+        let original_ident = field_member(field);
         quote! {
             #original_ident: #private_ident
         }
@@ -248,6 +288,24 @@ pub fn gen_read_data(fields: &[&Field]) -> TokenStream {
 
 fn gen_read_compatible_match_arm_body(field: &Field, var_name: &Ident) -> TokenStream {
     let ty = &field.ty;
+    if let Some(with) = field_with(field) {
+        return match classify_trait_object_field(ty) {
+            StructField::BoxDyn(_) => quote! {
+                #var_name = fory_core::serializer::codec::read_with(context, #with::deserialize)?;
+            },
+            _ if need_declared_by_option(field) => quote! {
+                #var_name = Some(fory_core::serializer::codec::read_with(context, #with::deserialize)?);
+            },
+            _ => quote! {
+                #var_name = fory_core::serializer::codec::read_with(context, #with::deserialize)?;
+            },
+        };
+    }
+    if let Some(endian) = field_endian(field) {
+        return quote! {
+            #var_name = <#ty as fory_core::serializer::number::FixedWidth>::read_fixed(context, #endian)?;
+        };
+    }
 
     match classify_trait_object_field(ty) {
         StructField::BoxDyn(trait_name) => {
@@ -258,7 +316,7 @@ fn gen_read_compatible_match_arm_body(field: &Field, var_name: &Ident) -> TokenS
                 if ref_flag != fory_core::types::RefFlag::NotNullValue as i8 {
                     return Err(Error::InvalidRef("Expected NotNullValue for trait object field".into()));
                 }
-                let fory_type_id = context.reader.read_varuint32()?;
+                let fory_type_id = context.read_type_id()?;
                 let harness = context.get_type_resolver()
                     .get_harness(fory_type_id)
                     .ok_or_else(|| Error::TypeError("Type not registered for trait object field".into()))?;
@@ -342,44 +400,76 @@ fn gen_read_compatible_match_arm_body(field: &Field, var_name: &Ident) -> TokenS
             }
         }
         StructField::None => {
-            let generic_tree = parse_generic_tree(ty);
-            let local_nullable = generic_tree.name == "Option";
-            let _base_ty = match &ty {
-                Type::Path(type_path) => &type_path.path.segments.first().unwrap().ident,
-                _ => panic!("Unsupported type"),
+            let body = gen_read_compatible_value(field, var_name);
+            let Some(conversion) = integer_conversion(field) else {
+                return body;
             };
-            if local_nullable {
-                quote! {
-                    if _field.field_type.nullable {
-                        #var_name = Some(fory_core::serializer::read_ref_info_data::<#ty>(context, true, false, false)?);
-                    } else {
-                        #var_name = Some(
-                            fory_core::serializer::read_ref_info_data::<#ty>(context, true, true, false)?
-                        );
-                    }
+            // the field ids of integers whose width changed are kept, see `assign_field_ids`
+            let IntegerConversion {
+                elem,
+                type_id: local_type_id,
+                array,
+                nullable,
+            } = conversion;
+            let read = match (array, nullable) {
+                (true, false) => quote! {
+                    Some(fory_core::serializer::number::read_converted_int_array::<#elem>(context, &_field.field_type)?)
+                },
+                (true, true) => quote! {
+                    Some(fory_core::serializer::number::read_nullable_converted_int_array::<#elem>(context, &_field.field_type)?)
+                },
+                (false, false) => quote! {
+                    fory_core::serializer::number::read_converted_int::<#elem>(context, &_field.field_type)?
+                },
+                (false, true) => quote! {
+                    Some(fory_core::serializer::number::read_nullable_converted_int::<#elem>(context, &_field.field_type)?)
+                },
+            };
+            quote! {
+                if _field.field_type.type_id != #local_type_id {
+                    #var_name = #read;
+                } else {
+                    #body
                 }
+            }
+        }
+    }
+}
+
+fn gen_read_compatible_value(field: &Field, var_name: &Ident) -> TokenStream {
+    let ty = &field.ty;
+    let generic_tree = parse_generic_tree(ty);
+    let local_nullable = generic_tree.name == "Option";
+    if local_nullable {
+        quote! {
+            if _field.field_type.nullable {
+                #var_name = Some(fory_core::serializer::read_ref_info_data::<#ty>(context, true, false, false)?);
             } else {
-                let dec_by_option = need_declared_by_option(field);
-                if dec_by_option {
-                    quote! {
-                        if !_field.field_type.nullable {
-                            #var_name = Some(fory_core::serializer::read_ref_info_data::<#ty>(context, true, true, false)?);
-                        } else {
-                            #var_name = fory_core::serializer::read_ref_info_data::<Option<#ty>>(context, true, false, false)?
-                        }
-                    }
+                #var_name = Some(
+                    fory_core::serializer::read_ref_info_data::<#ty>(context, true, true, false)?
+                );
+            }
+        }
+    } else {
+        let dec_by_option = need_declared_by_option(field);
+        if dec_by_option {
+            quote! {
+                if !_field.field_type.nullable {
+                    #var_name = Some(fory_core::serializer::read_ref_info_data::<#ty>(context, true, true, false)?);
                 } else {
-                    let null_flag = RefFlag::Null as i8;
-                    quote! {
-                        if !_field.field_type.nullable {
-                            #var_name = fory_core::serializer::read_ref_info_data::<#ty>(context, true, true, false)?;
-                        } else {
-                            if context.reader.read_i8()? == #null_flag {
-                                #var_name = <#ty as fory_core::serializer::ForyDefault>::fory_default();
-                            } else {
-                                #var_name = fory_core::serializer::read_ref_info_data::<#ty>(context, true, true, false)?;
-                            }
-                        }
+                    #var_name = fory_core::serializer::read_ref_info_data::<Option<#ty>>(context, true, false, false)?
+                }
+            }
+        } else {
+            let null_flag = RefFlag::Null as i8;
+            quote! {
+                if !_field.field_type.nullable {
+                    #var_name = fory_core::serializer::read_ref_info_data::<#ty>(context, true, true, false)?;
+                } else {
+                    if context.reader.read_i8()? == #null_flag {
+                        #var_name = <#ty as fory_core::serializer::ForyDefault>::fory_default();
+                    } else {
+                        #var_name = fory_core::serializer::read_ref_info_data::<#ty>(context, true, true, false)?;
                     }
                 }
             }
@@ -387,16 +477,26 @@ fn gen_read_compatible_match_arm_body(field: &Field, var_name: &Ident) -> TokenS
     }
 }
 
-pub fn gen_read(struct_ident: &Ident) -> TokenStream {
-    quote! {
-        let ref_flag = context.reader.read_i8()?;
-        if ref_flag == (fory_core::types::RefFlag::NotNullValue as i8) || ref_flag == (fory_core::types::RefFlag::RefValue as i8) {
+pub fn gen_read(compact: bool) -> TokenStream {
+    let read_positional = quote! {
+        <Self as fory_core::serializer::Serializer>::fory_read_type_info(context, false)?;
+        <Self as fory_core::serializer::Serializer>::fory_read_data(context, false)
+    };
+    let read_value = if compact {
+        read_positional
+    } else {
+        quote! {
             if context.is_compatible() {
-                <#struct_ident as fory_core::serializer::Serializer>::fory_read_compatible(context)
+                <Self as fory_core::serializer::Serializer>::fory_read_compatible(context)
             } else {
-                <Self as fory_core::serializer::Serializer>::fory_read_type_info(context, false)?;
-                <Self as fory_core::serializer::Serializer>::fory_read_data(context, false)
+                #read_positional
             }
+        }
+    };
+    quote! {
+        let ref_flag = context.reader.read_i8()?;
+        if ref_flag == (fory_core::types::RefFlag::NotNullValue as i8) || ref_flag == (fory_core::types::RefFlag::RefValue as i8) {
+            #read_value
         } else if ref_flag == (fory_core::types::RefFlag::Null as i8) {
             Ok(<Self as fory_core::serializer::ForyDefault>::fory_default())
         } else if ref_flag == (fory_core::types::RefFlag::Ref as i8) {
@@ -408,6 +508,70 @@ pub fn gen_read(struct_ident: &Ident) -> TokenStream {
     }
 }
 
+/// Counterpart of [`gen_read_data`] for a generic struct, which reads its fields in the
+/// order of its registered type meta, see `StructSerializer::fory_sorts_fields_at_runtime`.
+pub fn gen_read_data_in_meta_order(fields: &[&Field]) -> TokenStream {
+    if fields.is_empty() {
+        return gen_read_data(fields);
+    }
+    let slots: Vec<Ident> = fields
+        .iter()
+        .map(|field| format_ident!("__fory_slot_{}", field.ident.as_ref().unwrap()))
+        .collect();
+    let declare_ts = fields.iter().zip(slots.iter()).map(|(field, slot)| {
+        let ty = &field.ty;
+        quote! {
+            let mut #slot: Option<#ty> = None;
+        }
+    });
+    let arms = fields
+        .iter()
+        .zip(slots.iter())
+        .enumerate()
+        .map(|(i, (field, slot))| {
+            let field_id = i as i16;
+            let private_ident = create_private_field_name(field);
+            let read_field = gen_read_field_binding(field);
+            quote! {
+                #field_id => {
+                    #read_field
+                    #slot = Some(#private_ident);
+                }
+            }
+        });
+    let unwrap_ts = fields.iter().zip(slots.iter()).map(|(field, slot)| {
+        let private_ident = create_private_field_name(field);
+        let name = field_name(field);
+        quote! {
+            let #private_ident = #slot.ok_or(fory_core::error::Error::MissingField { name: #name })?;
+        }
+    });
+    let field_idents = fields.iter().map(|field| {
+        let private_ident = create_private_field_name(field);
+        let original_ident = field_member(field);
+        quote! {
+            #original_ident: #private_ident
+        }
+    });
+    quote! {
+        let meta = context
+            .get_type_resolver()
+            .get_type_info(std::any::TypeId::of::<Self>())?
+            .get_type_meta();
+        #(#declare_ts)*
+        for field in meta.get_field_infos() {
+            match field.field_id {
+                #(#arms)*
+                _ => unreachable!("field ids are assigned by the sorted field names"),
+            }
+        }
+        #(#unwrap_ts)*
+        Ok(Self {
+            #(#field_idents),*
+        })
+    }
+}
+
 pub fn gen_read_compatible(fields: &[&Field]) -> TokenStream {
     let declare_ts: Vec<TokenStream> = declare_var(fields);
     let assign_ts: Vec<TokenStream> = assign_value(fields);
@@ -418,7 +582,11 @@ pub fn gen_read_compatible(fields: &[&Field]) -> TokenStream {
         .map(|(i, field)| {
             let var_name = create_private_field_name(field);
             let field_id = i as i16;
-            let body = gen_read_compatible_match_arm_body(field, &var_name);
+            let body = wrap_field_encrypt_read(
+                field,
+                gen_read_compatible_match_arm_body(field, &var_name),
+                None,
+            );
             quote! {
                 #field_id => {
                     #body
@@ -428,7 +596,7 @@ pub fn gen_read_compatible(fields: &[&Field]) -> TokenStream {
         .collect();
 
     quote! {
-        let remote_type_id = context.reader.read_varuint32()?;
+        let remote_type_id = context.read_type_id()?;
         let meta_index = context.reader.read_varuint32()?;
         let meta = context.get_meta(meta_index as usize);
         let fields = {
@@ -457,3 +625,78 @@ pub fn gen_read_compatible(fields: &[&Field]) -> TokenStream {
         }
     }
 }
+
+pub fn gen_merge_compatible(fields: &[&Field]) -> TokenStream {
+    let declare_ts: Vec<TokenStream> = declare_var(fields);
+    let present_idents: Vec<Ident> = fields
+        .iter()
+        .map(|field| format_ident!("__fory_present_{}", field.ident.as_ref().unwrap()))
+        .collect();
+
+    let match_arms: Vec<TokenStream> = fields
+        .iter()
+        .zip(present_idents.iter())
+        .enumerate()
+        .map(|(i, (field, present))| {
+            let var_name = create_private_field_name(field);
+            let field_id = i as i16;
+            let body = wrap_field_encrypt_read(
+                field,
+                gen_read_compatible_match_arm_body(field, &var_name),
+                None,
+            );
+            quote! {
+                #field_id => {
+                    #body
+                    #present = true;
+                }
+            }
+        })
+        .collect();
+
+    let merge_ts: Vec<TokenStream> = fields
+        .iter()
+        .zip(present_idents.iter())
+        .map(|(field, present)| {
+            let name = field_member(field);
+            let value = field_value(field);
+            quote! {
+                if #present {
+                    self.#name = #value;
+                }
+            }
+        })
+        .collect();
+
+    quote! {
+        let ref_flag = context.reader.read_i8()?;
+        if ref_flag == (fory_core::types::RefFlag::Null as i8) {
+            return Ok(());
+        }
+        if ref_flag != (fory_core::types::RefFlag::NotNullValue as i8) && ref_flag != (fory_core::types::RefFlag::RefValue as i8) {
+            return Err(fory_core::error::Error::InvalidRef(format!("Unknown ref flag, value:{ref_flag}").into()));
+        }
+        let remote_type_id = context.read_type_id()?;
+        let meta_index = context.reader.read_varuint32()?;
+        let meta = context.get_meta(meta_index as usize).clone();
+        let local_type_hash = context.get_type_resolver().get_type_info(std::any::TypeId::of::<Self>())?.get_type_meta().get_hash();
+        if meta.get_hash() == local_type_hash {
+            *self = <Self as fory_core::serializer::Serializer>::fory_read_data(context, false)?;
+            return Ok(());
+        }
+        #(#declare_ts)*
+        #(let mut #present_idents = false;)*
+        for _field in meta.get_field_infos().iter() {
+            match _field.field_id {
+                #(#match_arms)*
+                _ => {
+                    let field_type = &_field.field_type;
+                    let read_ref_flag = fory_core::serializer::skip::get_read_ref_flag(&field_type);
+                    fory_core::serializer::skip::skip_field_value(context, &field_type, read_ref_flag)?;
+                }
+            }
+        }
+        #(#merge_ts)*
+        Ok(())
+    }
+}
@@ -36,8 +36,160 @@ fn has_existing_default(ast: &syn::DeriveInput, trait_name: &str) -> bool {
     })
 }
 
+/// Options given by `#[fory(...)]` on a struct.
+#[derive(Default)]
+struct ContainerAttrs {
+    /// `transparent`
+    transparent: bool,
+    /// `validate = "path::to::fn"`
+    validate: Option<syn::Path>,
+    /// `compact`
+    compact: bool,
+    /// `repr`, read by `derive_enum`
+    repr: bool,
+}
+
+fn parse_container_attrs(ast: &syn::DeriveInput) -> ContainerAttrs {
+    let mut attrs = ContainerAttrs::default();
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("fory")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("transparent") {
+                attrs.transparent = true;
+                Ok(())
+            } else if meta.path.is_ident("compact") {
+                attrs.compact = true;
+                Ok(())
+            } else if meta.path.is_ident("repr") {
+                attrs.repr = true;
+                Ok(())
+            } else if meta.path.is_ident("validate") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                attrs.validate = Some(value.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported fory attribute"))
+            }
+        })
+        .unwrap_or_else(|e| panic!("{}", e));
+    }
+    attrs
+}
+
+/// Wraps a read that yields `Self` in the checks requested by `#[fory(validate = "...")]`
+/// on the struct and its fields.
+fn wrap_validate(
+    container_validate: Option<&syn::Path>,
+    fields: &[&syn::Field],
+    read_ts: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let field_checks: Vec<_> = fields
+        .iter()
+        .filter_map(|field| {
+            let validate = crate::object::util::field_validate(field)?;
+            let member = crate::object::util::field_member(field);
+            let field_name = crate::object::util::field_name(field);
+            Some(quote! {
+                #validate(&value.#member).map_err(|e| {
+                    fory_core::error::Error::ValidationFailed(format!("{}: {}", #field_name, e).into())
+                })?;
+            })
+        })
+        .collect();
+    if container_validate.is_none() && field_checks.is_empty() {
+        return read_ts;
+    }
+    let container_check = container_validate.map(|validate| {
+        quote! {
+            #validate(&value).map_err(|e| fory_core::error::Error::ValidationFailed(e.into()))?;
+        }
+    });
+    quote! {
+        let read = |context: &mut fory_core::resolver::context::ReadContext| -> Result<Self, fory_core::error::Error> {
+            #read_ts
+        };
+        let value = read(context)?;
+        #(#field_checks)*
+        #container_check
+        Ok(value)
+    }
+}
+
+/// Prefixes the data of a `#[fory(compact)]` struct with its schema hash in compatible
+/// mode, since it is read by position like in consistent mode.
+fn wrap_compact(
+    compact: bool,
+    write_data_ts: proc_macro2::TokenStream,
+    read_data_ts: proc_macro2::TokenStream,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if !compact {
+        return (write_data_ts, read_data_ts);
+    }
+    (
+        quote! {
+            if context.is_compatible() {
+                fory_core::serializer::struct_::write_compact_schema_hash::<Self>(context)?;
+            }
+            #write_data_ts
+        },
+        quote! {
+            if context.is_compatible() {
+                fory_core::serializer::struct_::read_compact_schema_hash::<Self>(context)?;
+            }
+            #read_data_ts
+        },
+    )
+}
+
+/// Splits the generics of `ast` for an impl, with every type parameter bounded by
+/// `bounds`, so that e.g. `Wrapper<T>` gets `impl<T> ... for Wrapper<T> where T: bounds`.
+fn split_generics(
+    ast: &syn::DeriveInput,
+    bounds: proc_macro2::TokenStream,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let mut generics = ast.generics.clone();
+    let params: Vec<_> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#param: #bounds));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    (
+        quote! { #impl_generics },
+        quote! { #ty_generics },
+        quote! { #where_clause },
+    )
+}
+
+fn serializer_bounds() -> proc_macro2::TokenStream {
+    quote! { fory_core::serializer::Serializer + fory_core::serializer::ForyDefault }
+}
+
 pub fn derive_serializer(ast: &syn::DeriveInput) -> TokenStream {
     let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = split_generics(ast, serializer_bounds());
+    let container_attrs = parse_container_attrs(ast);
+    if container_attrs.transparent {
+        return derive_transparent(ast);
+    }
+    if container_attrs.compact && !matches!(ast.data, syn::Data::Struct(_)) {
+        panic!("#[fory(compact)] is only supported on structs");
+    }
+    if container_attrs.repr && !matches!(ast.data, syn::Data::Enum(_)) {
+        panic!("#[fory(repr)] is only supported on enums");
+    }
+    let ast = &crate::object::util::name_tuple_fields(ast);
+    // the field types of a generic struct are only known once it is monomorphized, so its
+    // fields are ordered by their runtime type ids
+    let is_generic = ast.generics.type_params().next().is_some();
     use crate::object::util::{clear_struct_context, set_struct_context};
     set_struct_context(&name.to_string());
 
@@ -55,10 +207,14 @@ pub fn derive_serializer(ast: &syn::DeriveInput) -> TokenStream {
             syn::Data::Struct(s) => {
                 let fields = sorted_fields(&s.fields);
                 (
-                    misc::gen_actual_type_id(),
+                    misc::gen_actual_type_id(container_attrs.compact),
                     misc::gen_get_sorted_field_names(&fields),
                     misc::gen_field_fields_info(&fields),
-                    read::gen_read_compatible(&fields),
+                    wrap_validate(
+                        container_attrs.validate.as_ref(),
+                        &fields,
+                        read::gen_read_compatible(&fields),
+                    ),
                 )
             }
             syn::Data::Enum(s) => (
@@ -71,6 +227,10 @@ pub fn derive_serializer(ast: &syn::DeriveInput) -> TokenStream {
                 panic!("Union is not supported")
             }
         };
+    let repr_type_id_ts = match &ast.data {
+        syn::Data::Enum(_) => derive_enum::gen_repr_type_id(&ast.attrs),
+        _ => quote! { None },
+    };
     // Serializer
     let (
         reserved_space_ts,
@@ -83,22 +243,58 @@ pub fn derive_serializer(ast: &syn::DeriveInput) -> TokenStream {
     ) = match &ast.data {
         syn::Data::Struct(s) => {
             let fields = sorted_fields(&s.fields);
+            let (write_data_ts, read_data_ts) = if is_generic {
+                (
+                    write::gen_write_data_in_meta_order(&fields),
+                    read::gen_read_data_in_meta_order(&fields),
+                )
+            } else if crate::object::util::has_user_type_fields(&fields) {
+                // a field of a user type may be an enum written by variant name or
+                // fixed-width, which the type meta records as a string or an integer, so
+                // the fields follow the type meta once any enum is
+                let (write_in_order, read_in_order) =
+                    (write::gen_write_data(&fields), read::gen_read_data(&fields));
+                let (write_in_meta_order, read_in_meta_order) = (
+                    write::gen_write_data_in_meta_order(&fields),
+                    read::gen_read_data_in_meta_order(&fields),
+                );
+                (
+                    quote! {
+                        if context.get_type_resolver().has_retyped_enums() {
+                            #write_in_meta_order
+                        } else {
+                            #write_in_order
+                        }
+                    },
+                    quote! {
+                        if context.get_type_resolver().has_retyped_enums() {
+                            #read_in_meta_order
+                        } else {
+                            #read_in_order
+                        }
+                    },
+                )
+            } else {
+                (write::gen_write_data(&fields), read::gen_read_data(&fields))
+            };
+            let (write_data_ts, read_data_ts) =
+                wrap_compact(container_attrs.compact, write_data_ts, read_data_ts);
             (
                 write::gen_reserved_space(&fields),
                 write::gen_write_type_info(),
                 read::gen_read_type_info(),
-                write::gen_write_data(&fields),
-                read::gen_read_data(&fields),
+                write_data_ts,
+                wrap_validate(container_attrs.validate.as_ref(), &fields, read_data_ts),
                 write::gen_write(),
-                read::gen_read(name),
+                read::gen_read(container_attrs.compact),
             )
         }
         syn::Data::Enum(e) => (
             derive_enum::gen_reserved_space(),
             derive_enum::gen_write_type_info(),
             derive_enum::gen_read_type_info(),
-            derive_enum::gen_write_data(e),
-            derive_enum::gen_read_data(e),
+            derive_enum::gen_write_data(e, &ast.attrs),
+            derive_enum::gen_read_data(e, &ast.attrs),
             derive_enum::gen_write(e),
             derive_enum::gen_read(e),
         ),
@@ -107,15 +303,30 @@ pub fn derive_serializer(ast: &syn::DeriveInput) -> TokenStream {
         }
     };
 
-    // Allocate a unique type ID once and share it between both functions
-    let type_idx = misc::allocate_type_id();
+    // Allocate a unique type ID once and share it between both functions. The
+    // monomorphizations of a generic struct would share it, so they are looked up by
+    // their Rust type id instead.
+    let (type_idx, get_type_id_ts) = if is_generic {
+        (
+            quote! { fory_core::resolver::type_resolver::NO_TYPE_INDEX },
+            quote! {
+                Ok(type_resolver.get_type_info(std::any::TypeId::of::<Self>())?.get_type_id())
+            },
+        )
+    } else {
+        let type_idx = misc::allocate_type_id();
+        (
+            quote! { #type_idx },
+            quote! { type_resolver.get_type_id(&std::any::TypeId::of::<Self>(), #type_idx) },
+        )
+    };
 
     let gen = quote! {
         use fory_core::serializer::ForyDefault as _;
 
         #default_impl
 
-        impl fory_core::serializer::StructSerializer for #name {
+        impl #impl_generics fory_core::serializer::StructSerializer for #name #ty_generics #where_clause {
             fn fory_type_index() -> u32 {
                 #type_idx
             }
@@ -128,13 +339,21 @@ pub fn derive_serializer(ast: &syn::DeriveInput) -> TokenStream {
                 #get_sorted_field_names_ts
             }
 
+            fn fory_sorts_fields_at_runtime() -> bool {
+                #is_generic
+            }
+
+            fn fory_repr_type_id() -> Option<u32> {
+                #repr_type_id_ts
+            }
+
             fn fory_fields_info(type_resolver: &fory_core::resolver::type_resolver::TypeResolver) -> Result<Vec<fory_core::meta::FieldInfo>, fory_core::error::Error> {
                 #fields_info_ts
             }
         }
-        impl fory_core::serializer::Serializer for #name {
+        impl #impl_generics fory_core::serializer::Serializer for #name #ty_generics #where_clause {
             fn fory_get_type_id(type_resolver: &fory_core::resolver::type_resolver::TypeResolver) -> Result<u32, fory_core::error::Error> {
-                type_resolver.get_type_id(&std::any::TypeId::of::<Self>(), #type_idx)
+                #get_type_id_ts
             }
 
             fn fory_type_id_dyn(&self, type_resolver: &fory_core::resolver::type_resolver::TypeResolver) -> Result<u32, fory_core::error::Error> {
@@ -165,6 +384,8 @@ pub fn derive_serializer(ast: &syn::DeriveInput) -> TokenStream {
                 #read_data_ts
             }
 
+            fory_core::read_null_as_default!();
+
             fn fory_write(&self, context: &mut fory_core::resolver::context::WriteContext, is_field: bool) -> Result<(), fory_core::error::Error> {
                 #write_ts
             }
@@ -183,8 +404,193 @@ pub fn derive_serializer(ast: &syn::DeriveInput) -> TokenStream {
     code
 }
 
+/// Generates a serializer for a single-field struct that is byte-for-byte the
+/// serializer of its field, so the wrapper and the bare inner type can be read
+/// as each other. No `StructSerializer` is generated, since the wrapper has no
+/// type meta of its own and must not be registered.
+fn derive_transparent(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = split_generics(ast, serializer_bounds());
+    let field = match &ast.data {
+        syn::Data::Struct(s) if s.fields.len() == 1 => s.fields.iter().next().unwrap(),
+        _ => panic!("#[fory(transparent)] requires a struct with exactly one field"),
+    };
+    let ty = &field.ty;
+    let member = match &field.ident {
+        Some(ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(syn::Index::from(0)),
+    };
+
+    let default_impl = if has_existing_default(ast, "ForyDefault") {
+        quote! {}
+    } else if has_existing_default(ast, "Default") {
+        quote! {
+            impl #impl_generics fory_core::serializer::ForyDefault for #name #ty_generics #where_clause {
+                fn fory_default() -> Self {
+                    Self::default()
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl #impl_generics fory_core::serializer::ForyDefault for #name #ty_generics #where_clause {
+                fn fory_default() -> Self {
+                    Self { #member: <#ty as fory_core::serializer::ForyDefault>::fory_default() }
+                }
+            }
+            impl #impl_generics std::default::Default for #name #ty_generics #where_clause {
+                fn default() -> Self {
+                    <Self as fory_core::serializer::ForyDefault>::fory_default()
+                }
+            }
+        }
+    };
+
+    let gen = quote! {
+        #default_impl
+
+        impl #impl_generics fory_core::serializer::Serializer for #name #ty_generics #where_clause {
+            fn fory_write(&self, context: &mut fory_core::resolver::context::WriteContext, is_field: bool) -> Result<(), fory_core::error::Error> {
+                <#ty as fory_core::serializer::Serializer>::fory_write(&self.#member, context, is_field)
+            }
+
+            fn fory_read(context: &mut fory_core::resolver::context::ReadContext, is_field: bool) -> Result<Self, fory_core::error::Error> {
+                Ok(Self { #member: <#ty as fory_core::serializer::Serializer>::fory_read(context, is_field)? })
+            }
+
+            fn fory_read_without_default(context: &mut fory_core::resolver::context::ReadContext, is_field: bool) -> Result<Self, fory_core::error::Error> {
+                Self::fory_read(context, is_field)
+            }
+
+            fory_core::read_null_as_default!();
+
+            fn fory_is_option() -> bool {
+                <#ty as fory_core::serializer::Serializer>::fory_is_option()
+            }
+
+            fn fory_is_none(&self) -> bool {
+                <#ty as fory_core::serializer::Serializer>::fory_is_none(&self.#member)
+            }
+
+            fn fory_is_polymorphic() -> bool {
+                <#ty as fory_core::serializer::Serializer>::fory_is_polymorphic()
+            }
+
+            fn fory_is_shared_ref() -> bool {
+                <#ty as fory_core::serializer::Serializer>::fory_is_shared_ref()
+            }
+
+            fn fory_get_type_id(type_resolver: &fory_core::resolver::type_resolver::TypeResolver) -> Result<u32, fory_core::error::Error> {
+                <#ty as fory_core::serializer::Serializer>::fory_get_type_id(type_resolver)
+            }
+
+            fn fory_type_id_dyn(&self, type_resolver: &fory_core::resolver::type_resolver::TypeResolver) -> Result<u32, fory_core::error::Error> {
+                <#ty as fory_core::serializer::Serializer>::fory_type_id_dyn(&self.#member, type_resolver)
+            }
+
+            fn fory_reserved_space() -> usize {
+                <#ty as fory_core::serializer::Serializer>::fory_reserved_space()
+            }
+
+            fn fory_write_type_info(context: &mut fory_core::resolver::context::WriteContext, is_field: bool) -> Result<(), fory_core::error::Error> {
+                <#ty as fory_core::serializer::Serializer>::fory_write_type_info(context, is_field)
+            }
+
+            fn fory_read_type_info(context: &mut fory_core::resolver::context::ReadContext, is_field: bool) -> Result<(), fory_core::error::Error> {
+                <#ty as fory_core::serializer::Serializer>::fory_read_type_info(context, is_field)
+            }
+
+            fn fory_read_compatible(context: &mut fory_core::resolver::context::ReadContext) -> Result<Self, fory_core::error::Error> {
+                Ok(Self { #member: <#ty as fory_core::serializer::Serializer>::fory_read_compatible(context)? })
+            }
+
+            fn fory_write_data(&self, context: &mut fory_core::resolver::context::WriteContext, is_field: bool) -> Result<(), fory_core::error::Error> {
+                <#ty as fory_core::serializer::Serializer>::fory_write_data(&self.#member, context, is_field)
+            }
+
+            fn fory_read_data(context: &mut fory_core::resolver::context::ReadContext, is_field: bool) -> Result<Self, fory_core::error::Error> {
+                Ok(Self { #member: <#ty as fory_core::serializer::Serializer>::fory_read_data(context, is_field)? })
+            }
+
+            fn fory_skip(context: &mut fory_core::resolver::context::ReadContext) -> Result<(), fory_core::error::Error> {
+                <#ty as fory_core::serializer::Serializer>::fory_skip(context)
+            }
+
+            fn fory_cmp_serialized(&self, other: &Self, context: &mut fory_core::resolver::context::WriteContext) -> Result<std::cmp::Ordering, fory_core::error::Error> {
+                <#ty as fory_core::serializer::Serializer>::fory_cmp_serialized(&self.#member, &other.#member, context)
+            }
+
+            fn fory_cmp_serialized_bytes() -> bool {
+                <#ty as fory_core::serializer::Serializer>::fory_cmp_serialized_bytes()
+            }
+
+            fn as_any(&self) -> &dyn std::any::Any {
+                self
+            }
+        }
+    };
+    gen.into()
+}
+
+pub fn derive_merge(ast: &syn::DeriveInput) -> TokenStream {
+    let ast = &crate::object::util::name_tuple_fields(ast);
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = split_generics(ast, serializer_bounds());
+    use crate::object::util::{clear_struct_context, set_struct_context};
+    set_struct_context(&name.to_string());
+
+    let merge_ts = match &ast.data {
+        syn::Data::Struct(s) => {
+            let fields = sorted_fields(&s.fields);
+            read::gen_merge_compatible(&fields)
+        }
+        _ => panic!("ForyMerge can only be derived for structs"),
+    };
+
+    let gen = quote! {
+        impl #impl_generics fory_core::serializer::Merge for #name #ty_generics #where_clause {
+            fn fory_merge(&mut self, context: &mut fory_core::resolver::context::ReadContext) -> Result<(), fory_core::error::Error> {
+                #merge_ts
+            }
+        }
+    };
+    let code = gen.into();
+    clear_struct_context();
+    code
+}
+
+pub fn derive_diff(ast: &syn::DeriveInput) -> TokenStream {
+    let ast = &crate::object::util::name_tuple_fields(ast);
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = split_generics(ast, quote! { PartialEq });
+    let fields = match &ast.data {
+        syn::Data::Struct(s) => &s.fields,
+        _ => panic!("ForyDiff can only be derived for structs"),
+    };
+    let checks = fields.iter().map(|field| {
+        let member = crate::object::util::field_member(field);
+        let field_name = crate::object::util::field_name(field);
+        quote! {
+            if self.#member != other.#member {
+                changes.push(fory_core::serializer::FieldChange { field: #field_name });
+            }
+        }
+    });
+    let gen = quote! {
+        impl #impl_generics fory_core::serializer::Diff for #name #ty_generics #where_clause {
+            fn fory_diff(&self, other: &Self) -> Vec<fory_core::serializer::FieldChange> {
+                let mut changes = Vec::new();
+                #(#checks)*
+                changes
+            }
+        }
+    };
+    gen.into()
+}
+
 fn generate_default_impl(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
     let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = split_generics(ast, serializer_bounds());
     let has_existing_default = has_existing_default(ast, "Default");
 
     match &ast.data {
@@ -197,7 +603,7 @@ fn generate_default_impl(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
             };
 
             let field_inits = fields.iter().map(|field| {
-                let ident = &field.ident;
+                let ident = crate::object::util::field_member(field);
                 let ty = &field.ty;
 
                 match classify_trait_object_field(ty) {
@@ -238,7 +644,7 @@ fn generate_default_impl(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
 
             if has_existing_default {
                 quote! {
-                   impl fory_core::serializer::ForyDefault for #name {
+                   impl #impl_generics fory_core::serializer::ForyDefault for #name #ty_generics #where_clause {
                         fn fory_default() -> Self {
                             Self::default()
                         }
@@ -246,16 +652,16 @@ fn generate_default_impl(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
                 }
             } else {
                 quote! {
-                    impl fory_core::serializer::ForyDefault for #name {
+                    impl #impl_generics fory_core::serializer::ForyDefault for #name #ty_generics #where_clause {
                         fn fory_default() -> Self {
                             Self {
                                 #(#field_inits),*
                             }
                         }
                     }
-                    impl std::default::Default for #name {
+                    impl #impl_generics std::default::Default for #name #ty_generics #where_clause {
                         fn default() -> Self {
-                            Self::fory_default()
+                            <Self as fory_core::serializer::ForyDefault>::fory_default()
                         }
                     }
                 }
@@ -269,25 +675,30 @@ fn generate_default_impl(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
                 .any(|v| v.attrs.iter().any(|attr| attr.path().is_ident("default")));
 
             // For C-like enums, implement Default by returning the first variant
+            // that is not the `#[fory(unknown)]` catch-all
             // Only if there's no #[default] attribute (which means Default is being derived)
             if !has_default_variant {
-                if let Some(first_variant) = e.variants.first() {
+                if let Some(first_variant) = e
+                    .variants
+                    .iter()
+                    .find(|v| !derive_enum::is_unknown_variant(v))
+                {
                     let variant_ident = &first_variant.ident;
                     quote! {
-                        impl fory_core::serializer::ForyDefault for #name {
+                        impl #impl_generics fory_core::serializer::ForyDefault for #name #ty_generics #where_clause {
                             fn fory_default() -> Self {
                                 Self::#variant_ident
                             }
                         }
 
-                        impl std::default::Default for #name {
+                        impl #impl_generics std::default::Default for #name #ty_generics #where_clause {
                             fn default() -> Self {
                                 Self::#variant_ident
                             }
                         }
                     }
                 } else {
-                    // impl fory_core::serializer::ForyDefault for #name {
+                    // impl #impl_generics fory_core::serializer::ForyDefault for #name #ty_generics #where_clause {
                     //     fn fory_default() -> Self {
                     //         panic!("No unit-like variants found in enum {}", stringify!(#name));
                     //     }
@@ -296,7 +707,7 @@ fn generate_default_impl(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
                 }
             } else {
                 quote! {
-                    impl fory_core::serializer::ForyDefault for #name {
+                    impl #impl_generics fory_core::serializer::ForyDefault for #name #ty_generics #where_clause {
                         fn fory_default() -> Self {
                             Self::default()
                         }
@@ -214,10 +214,13 @@ pub(super) fn classify_trait_object_field(ty: &Type) -> StructField {
 pub(super) struct TypeNode {
     pub name: String,
     pub generics: Vec<TypeNode>,
+    /// Const arguments such as the `8` of `ArrayVec<u8, 8>`, which follow the type
+    /// arguments and have no field type of their own.
+    pub consts: Vec<String>,
 }
 
 pub(super) fn try_primitive_vec_type(node: &TypeNode) -> Option<TokenStream> {
-    if node.name != "Vec" {
+    if node.name != "Vec" && node.name != "ArrayVec" {
         return None;
     }
     let child = node.generics.first()?;
@@ -251,7 +254,12 @@ pub(super) fn try_vec_of_option_primitive(node: &TypeNode) -> Option<TokenStream
 
 impl fmt::Display for TypeNode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.generics.is_empty() {
+        // the name of an array or a tuple is already the whole type, its elements are only
+        // kept for the meta
+        if (self.generics.is_empty() && self.consts.is_empty())
+            || self.name.starts_with('[')
+            || self.name.starts_with('(')
+        {
             write!(f, "{}", self.name)
         } else {
             write!(
@@ -261,6 +269,7 @@ impl fmt::Display for TypeNode {
                 self.generics
                     .iter()
                     .map(|g| g.to_string())
+                    .chain(self.consts.iter().cloned())
                     .collect::<Vec<_>>()
                     .join(",")
             )
@@ -284,32 +293,52 @@ pub(super) fn parse_generic_tree(ty: &Type) -> TypeNode {
         return TypeNode {
             name: "TraitObject".to_string(),
             generics: vec![],
+            consts: vec![],
         };
     }
 
     let name = extract_type_name(ty);
 
-    let generics = if let Type::Path(type_path) = ty {
+    let mut generics = vec![];
+    let mut consts = vec![];
+    if let Type::Path(type_path) = ty {
         if let PathArguments::AngleBracketed(args) =
             &type_path.path.segments.last().unwrap().arguments
         {
-            args.args
-                .iter()
-                .filter_map(|arg| {
-                    if let GenericArgument::Type(ty) = arg {
-                        Some(parse_generic_tree(ty))
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        } else {
-            vec![]
+            for arg in &args.args {
+                match arg {
+                    GenericArgument::Type(ty) => generics.push(parse_generic_tree(ty)),
+                    GenericArgument::Const(expr) => consts.push(quote!(#expr).to_string()),
+                    _ => {}
+                }
+            }
         }
-    } else {
-        vec![]
-    };
-    TypeNode { name, generics }
+    } else if let Type::Array(array) = ty {
+        // `[Option<T>; N]` is a `FIXED_LIST`, whose field type needs its element, and
+        // both arrays record their length
+        let elem = parse_generic_tree(&array.elem);
+        if elem.name == "Option" {
+            generics.push(elem);
+        }
+        let len = &array.len;
+        consts.push(quote!(#len).to_string());
+    } else if let Type::Tuple(tuple) = ty {
+        // a tuple whose elements are of the same type records it as its element type
+        generics = tuple.elems.iter().map(parse_generic_tree).collect();
+    }
+    // `Box<str>` and `Cow<'static, str>` have their own serializers, `str` has none
+    if generics.iter().any(|node| node.name == "str") {
+        return TypeNode {
+            name: quote!(#ty).to_string(),
+            generics: vec![],
+            consts: vec![],
+        };
+    }
+    TypeNode {
+        name,
+        generics,
+        consts,
+    }
 }
 
 pub(super) fn generic_tree_to_tokens(node: &TypeNode) -> TokenStream {
@@ -337,7 +366,31 @@ pub(super) fn generic_tree_to_tokens(node: &TypeNode) -> TokenStream {
     let primitive_vec = try_primitive_vec_type(base_node);
 
     // Recursively generate children token streams
-    let children_tokens: Vec<TokenStream> = if primitive_vec.is_none() {
+    let children_tokens: Vec<TokenStream> = if base_node.name.starts_with('(') {
+        // a tuple is a `LIST` whose elements each carry their own type info, unless they
+        // are of the same type, see `fory_core::serializer::tuple::is_same_type`
+        let first = &base_node.generics[0];
+        let first_ty: syn::Type = syn::parse_str(&first.to_string()).unwrap();
+        let elem_tys: Vec<syn::Type> = base_node
+            .generics
+            .iter()
+            .map(|node| syn::parse_str(&node.to_string()).unwrap())
+            .collect();
+        let first_tokens = generic_tree_to_tokens(first);
+        vec![quote! {
+            if fory_core::serializer::tuple::is_same_type::<#first_ty>(
+                &[#(std::any::TypeId::of::<#elem_tys>()),*]
+            ) {
+                #first_tokens
+            } else {
+                fory_core::meta::FieldType::new(
+                    fory_core::types::TypeId::UNKNOWN as u32,
+                    true,
+                    Vec::new(),
+                )
+            }
+        }]
+    } else if primitive_vec.is_none() {
         base_node
             .generics
             .iter()
@@ -354,16 +407,24 @@ pub(super) fn generic_tree_to_tokens(node: &TypeNode) -> TokenStream {
         ts
     } else {
         quote! {
-            <#ty as fory_core::serializer::Serializer>::fory_get_type_id(type_resolver)?
+            fory_core::serializer::enum_::field_type_id::<#ty>(type_resolver)?
         }
     };
 
+    let with_length = match base_node.consts.first() {
+        Some(len) if base_node.name.starts_with('[') => {
+            let len: syn::Expr = syn::parse_str(len).unwrap();
+            quote! { .with_length((#len) as u32) }
+        }
+        _ => quote! {},
+    };
+
     quote! {
         fory_core::meta::FieldType::new(
             #get_type_id,
             #nullable,
             vec![#(#children_tokens),*] as Vec<fory_core::meta::FieldType>
-        )
+        )#with_length
     }
 }
 
@@ -378,15 +439,22 @@ type FieldGroups = (
     FieldGroup,
 );
 
-const PRIMITIVE_TYPE_NAMES: [&str; 7] = ["bool", "i8", "i16", "i32", "i64", "f32", "f64"];
+const PRIMITIVE_TYPE_NAMES: [&str; 11] = [
+    "bool", "i8", "i16", "i32", "i64", "u8", "u16", "u32", "u64", "f32", "f64",
+];
 
-fn get_primitive_type_id(ty: &str) -> u32 {
+pub(super) fn get_primitive_type_id(ty: &str) -> u32 {
     match ty {
         "bool" => TypeId::BOOL as u32,
         "i8" => TypeId::INT8 as u32,
         "i16" => TypeId::INT16 as u32,
         "i32" => TypeId::INT32 as u32,
         "i64" => TypeId::INT64 as u32,
+        // unsigned integers are written like the signed ones of the same width
+        "u8" => TypeId::INT8 as u32,
+        "u16" => TypeId::INT16 as u32,
+        "u32" => TypeId::INT32 as u32,
+        "u64" => TypeId::INT64 as u32,
         "f32" => TypeId::FLOAT32 as u32,
         "f64" => TypeId::FLOAT64 as u32,
         _ => unreachable!("Unknown primitive type: {}", ty),
@@ -413,19 +481,39 @@ fn group_fields_by_type(fields: &[&Field]) -> FieldGroups {
     // First handle Forward fields separately to avoid borrow checker issues
     for field in fields {
         if is_forward_field(&field.ty) {
-            let ident = field.ident.as_ref().unwrap().to_string();
-            other_fields.push((ident, "Forward".to_string(), TypeId::UNKNOWN as u32));
+            other_fields.push((
+                field_name(field),
+                "Forward".to_string(),
+                TypeId::UNKNOWN as u32,
+            ));
+        } else if field_encrypt(field) {
+            // the type meta records the ciphertext as binary, see `gen_field_fields_info`
+            internal_type_fields.push((
+                field_name(field),
+                extract_type_name(&field.ty),
+                TypeId::BINARY as u32,
+            ));
+        } else if field_with(field).is_some() {
+            // the type meta records the codec output as binary, see `gen_field_fields_info`
+            internal_type_fields.push((
+                field_name(field),
+                extract_type_name(&field.ty),
+                TypeId::BINARY as u32,
+            ));
+        } else if let Some(type_id) = fixed_width_type_id(field) {
+            primitive_fields.push((field_name(field), extract_type_name(&field.ty), type_id));
         }
     }
 
     fn get_other_internal_type_id(ty: &str) -> u32 {
         match ty {
-            "String" => TypeId::STRING as u32,
+            "String" | "Cow<'static,str>" => TypeId::STRING as u32,
             "NaiveDate" => TypeId::LOCAL_DATE as u32,
             "NaiveDateTime" => TypeId::TIMESTAMP as u32,
             "Duration" => TypeId::DURATION as u32,
+            "DurationSecs" => TypeId::FLOAT64 as u32,
             "Decimal" => TypeId::DECIMAL as u32,
-            "Vec<u8>" | "bytes" => TypeId::BINARY as u32,
+            "Vec<u8>" | "bytes" | "CString" => TypeId::BINARY as u32,
             "Vec<bool>" => TypeId::BOOL_ARRAY as u32,
             "Vec<i8>" => TypeId::INT8_ARRAY as u32,
             "Vec<i16>" => TypeId::INT16_ARRAY as u32,
@@ -434,6 +522,7 @@ fn group_fields_by_type(fields: &[&Field]) -> FieldGroups {
             "Vec<f16>" => TypeId::FLOAT16_ARRAY as u32,
             "Vec<f32>" => TypeId::FLOAT32_ARRAY as u32,
             "Vec<f64>" => TypeId::FLOAT64_ARRAY as u32,
+            _ if ty.starts_with("[u8;") => TypeId::FIXED_BINARY as u32,
             _ => 0,
         }
     }
@@ -445,6 +534,8 @@ fn group_fields_by_type(fields: &[&Field]) -> FieldGroups {
             let internal_type_id = get_other_internal_type_id(ty);
             internal_type_fields.push((ident, ty.to_string(), internal_type_id));
         } else if ty.starts_with("Vec<")
+            || ty.starts_with("[Option<")
+            || ty.starts_with('(')
             || ty.starts_with("VecDeque<")
             || ty.starts_with("LinkedList<")
             || ty.starts_with("BinaryHeap<")
@@ -452,7 +543,10 @@ fn group_fields_by_type(fields: &[&Field]) -> FieldGroups {
             list_fields.push((ident, ty.to_string(), TypeId::LIST as u32));
         } else if ty.starts_with("HashSet<") || ty.starts_with("BTreeSet<") {
             set_fields.push((ident, ty.to_string(), TypeId::SET as u32));
-        } else if ty.starts_with("HashMap<") || ty.starts_with("BTreeMap<") {
+        } else if ty.starts_with("HashMap<")
+            || ty.starts_with("BTreeMap<")
+            || ty.starts_with("OrderedHashMap<")
+        {
             map_fields.push((ident, ty.to_string(), TypeId::MAP as u32));
         } else {
             other_fields.push((ident, ty.to_string(), TypeId::UNKNOWN as u32));
@@ -460,20 +554,31 @@ fn group_fields_by_type(fields: &[&Field]) -> FieldGroups {
     };
 
     for field in fields {
-        let ident = field.ident.as_ref().unwrap().to_string();
+        let ident = field_name(field);
 
-        // Skip if already handled as Forward field
-        if is_forward_field(&field.ty) {
+        // Skip if already handled as Forward, encrypted, codec or fixed-width field
+        if is_forward_field(&field.ty)
+            || field_encrypt(field)
+            || field_with(field).is_some()
+            || field_endian(field).is_some()
+        {
             continue;
         }
 
-        let ty: String = field
+        let mut ty: String = field
             .ty
             .to_token_stream()
             .to_string()
             .chars()
             .filter(|c| !c.is_whitespace())
             .collect::<String>();
+        // `ArrayVec<T, N>` is written like `Vec<T>`, so it is grouped like one
+        if let Some((elem, _)) = ty
+            .strip_prefix("ArrayVec<")
+            .and_then(|args| args.rsplit_once(','))
+        {
+            ty = format!("Vec<{elem}>");
+        }
         // handle Option<Primitive> specially
         if let Some(inner) = extract_option_inner(&ty) {
             if PRIMITIVE_TYPE_NAMES.contains(&inner) {
@@ -495,8 +600,10 @@ fn group_fields_by_type(fields: &[&Field]) -> FieldGroups {
             TypeId::INT16 => 2,
             TypeId::INT32 => 4,
             TypeId::VAR_INT32 => 4,
+            TypeId::FIXED_INT32 => 4,
             TypeId::INT64 => 8,
             TypeId::VAR_INT64 => 8,
+            TypeId::FIXED_INT64 => 8,
             TypeId::FLOAT16 => 2,
             TypeId::FLOAT32 => 4,
             TypeId::FLOAT64 => 8,
@@ -556,6 +663,12 @@ fn group_fields_by_type(fields: &[&Field]) -> FieldGroups {
     )
 }
 
+/// Whether any field falls in the last group of [`get_sorted_field_names`], whose types the
+/// derive cannot classify, such as user structs and enums.
+pub(super) fn has_user_type_fields(fields: &[&Field]) -> bool {
+    !group_fields_by_type(fields).6.is_empty()
+}
+
 pub(crate) fn get_sorted_field_names(fields: &[&Field]) -> Vec<String> {
     let (
         primitive_fields,
@@ -592,3 +705,235 @@ pub(crate) fn skip_ref_flag(ty: &Type) -> bool {
     // !T::fory_is_option() && PRIMITIVE_TYPES.contains(&elem_type_id)
     PRIMITIVE_TYPE_NAMES.contains(&extract_type_name(ty).as_str())
 }
+
+/// Options given by `#[fory(...)]` on a struct field.
+#[derive(Default)]
+struct FieldAttrs {
+    /// `endian = "big" | "little"` on a number field
+    endian: Option<TokenStream>,
+    /// `with = "path::to::module"`
+    with: Option<syn::Path>,
+    /// `validate = "path::to::fn"`
+    validate: Option<syn::Path>,
+    /// `encrypt`
+    encrypt: bool,
+}
+
+fn parse_field_attrs(field: &Field) -> FieldAttrs {
+    let mut attrs = FieldAttrs::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("fory"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("endian") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                attrs.endian = Some(match value.value().as_str() {
+                    "big" => quote! { fory_core::types::Endian::Big },
+                    "little" => quote! { fory_core::types::Endian::Little },
+                    other => {
+                        return Err(meta.error(format!(
+                            "unsupported endian `{other}`, expected \"big\" or \"little\""
+                        )))
+                    }
+                });
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                attrs.with = Some(value.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("validate") {
+                let value: syn::LitStr = meta.value()?.parse()?;
+                attrs.validate = Some(value.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("encrypt") {
+                attrs.encrypt = true;
+                Ok(())
+            } else {
+                Err(meta.error("unsupported fory field attribute"))
+            }
+        })
+        .unwrap_or_else(|e| panic!("{}", e));
+    }
+    attrs
+}
+
+/// The module named by `#[fory(with = "...")]`, whose `serialize` and `deserialize`
+/// functions replace the default handling of the field.
+pub(super) fn field_with(field: &Field) -> Option<syn::Path> {
+    parse_field_attrs(field).with
+}
+
+/// How a field converts integers that the peer wrote with another width, see
+/// `Fory::with_numeric_conversion`.
+pub(super) struct IntegerConversion {
+    /// `i8` to `i64`, or the element type of a `Vec` of them
+    pub elem: Ident,
+    /// the local type id of the integer or array
+    pub type_id: u32,
+    pub array: bool,
+    pub nullable: bool,
+}
+
+/// Returns how `field` converts integers of another width, or `None` if its read cannot,
+/// in which case the type meta does not keep its field id for such a peer.
+pub(super) fn integer_conversion(field: &Field) -> Option<IntegerConversion> {
+    let attrs = parse_field_attrs(field);
+    if attrs.endian.is_some() || attrs.with.is_some() || attrs.encrypt {
+        return None;
+    }
+    let node = parse_generic_tree(&field.ty);
+    let (nullable, node) = match node.name.as_str() {
+        "Option" => (true, node.generics.first()?),
+        _ => (false, &node),
+    };
+    let (array, elem) = match node.name.as_str() {
+        "Vec" => (true, node.generics.first()?),
+        _ => (false, node),
+    };
+    let type_id = match (elem.name.as_str(), array) {
+        ("i8", false) => TypeId::INT8,
+        ("i16", false) => TypeId::INT16,
+        ("i32", false) => TypeId::INT32,
+        ("i64", false) => TypeId::INT64,
+        ("i8", true) => TypeId::INT8_ARRAY,
+        ("i16", true) => TypeId::INT16_ARRAY,
+        ("i32", true) => TypeId::INT32_ARRAY,
+        ("i64", true) => TypeId::INT64_ARRAY,
+        _ => return None,
+    };
+    if !elem.generics.is_empty() {
+        return None;
+    }
+    Some(IntegerConversion {
+        elem: format_ident!("{}", elem.name),
+        type_id: type_id as u32,
+        array,
+        nullable,
+    })
+}
+
+/// Prefix of the identifiers [`name_tuple_fields`] gives to the fields of a tuple struct.
+const TUPLE_FIELD_PREFIX: &str = "__fory_field_";
+
+/// Gives each field of a tuple struct an identifier made from its position, so that the
+/// rest of the derive can treat it like a struct with named fields. The original member
+/// and the name written in the type meta are recovered with [`field_member`] and
+/// [`field_name`].
+pub(super) fn name_tuple_fields(ast: &syn::DeriveInput) -> syn::DeriveInput {
+    let mut ast = ast.clone();
+    if let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Unnamed(unnamed),
+        ..
+    }) = &mut ast.data
+    {
+        let mut named = syn::punctuated::Punctuated::new();
+        for (i, field) in unnamed.unnamed.iter().enumerate() {
+            let mut field = field.clone();
+            field.ident = Some(format_ident!("{}{}", TUPLE_FIELD_PREFIX, i));
+            field.colon_token = Some(Default::default());
+            named.push(field);
+        }
+        let brace_token = Default::default();
+        if let syn::Data::Struct(s) = &mut ast.data {
+            s.fields = syn::Fields::Named(syn::FieldsNamed { brace_token, named });
+            s.semi_token = None;
+        }
+    }
+    ast
+}
+
+/// The member that accesses `field`, which is its position for a tuple struct.
+pub(super) fn field_member(field: &Field) -> syn::Member {
+    let ident = field.ident.as_ref().expect("should be field name");
+    match ident.to_string().strip_prefix(TUPLE_FIELD_PREFIX) {
+        Some(index) => syn::Member::Unnamed(syn::Index::from(index.parse::<usize>().unwrap())),
+        None => syn::Member::Named(ident.clone()),
+    }
+}
+
+/// The name of `field` in the type meta, which is its position for a tuple struct.
+pub fn field_name(field: &Field) -> String {
+    let name = field
+        .ident
+        .as_ref()
+        .expect("should be field name")
+        .to_string();
+    match name.strip_prefix(TUPLE_FIELD_PREFIX) {
+        Some(index) => index.to_string(),
+        None => name,
+    }
+}
+
+/// The function named by `#[fory(validate = "...")]`, which checks the field after the
+/// struct is read.
+pub(super) fn field_validate(field: &Field) -> Option<syn::Path> {
+    parse_field_attrs(field).validate
+}
+
+/// Whether `field` is marked `#[fory(encrypt)]`.
+pub(super) fn field_encrypt(field: &Field) -> bool {
+    parse_field_attrs(field).encrypt
+}
+
+/// Wraps the write of a field marked `#[fory(encrypt)]` so that its bytes are encrypted.
+pub(super) fn wrap_field_encrypt_write(field: &Field, ts: TokenStream) -> TokenStream {
+    if !parse_field_attrs(field).encrypt {
+        return ts;
+    }
+    quote! {
+        fory_core::serializer::cipher::write_encrypted(context, |context| {
+            #ts
+            Ok(())
+        })?;
+    }
+}
+
+/// Wraps the read of a field marked `#[fory(encrypt)]` so that it is read from the
+/// decrypted bytes. With a `binding`, `ts` declares it and the wrapped read declares it
+/// in turn; otherwise `ts` assigns variables declared outside.
+pub(super) fn wrap_field_encrypt_read(
+    field: &Field,
+    ts: TokenStream,
+    binding: Option<&Ident>,
+) -> TokenStream {
+    if !parse_field_attrs(field).encrypt {
+        return ts;
+    }
+    match binding {
+        Some(binding) => quote! {
+            let #binding = fory_core::serializer::cipher::read_encrypted(context, |context| {
+                #ts
+                Ok(#binding)
+            })?;
+        },
+        None => quote! {
+            fory_core::serializer::cipher::read_encrypted(context, |context| {
+                #ts
+                Ok(())
+            })?;
+        },
+    }
+}
+
+/// The byte order given by `#[fory(endian = "...")]`, in which the field is written
+/// fixed-width with `fory_core::serializer::number::FixedWidth`.
+pub(super) fn field_endian(field: &Field) -> Option<TokenStream> {
+    let endian = parse_field_attrs(field).endian?;
+    let ty = extract_type_name(&field.ty);
+    if ty == "bool" || !PRIMITIVE_TYPE_NAMES.contains(&ty.as_str()) {
+        panic!("`endian` only applies to number fields, found `{ty}`");
+    }
+    Some(endian)
+}
+
+/// The type id recorded for a field with `#[fory(endian = "...")]`, see `field_endian`.
+fn fixed_width_type_id(field: &Field) -> Option<u32> {
+    field_endian(field)?;
+    Some(match extract_type_name(&field.ty).as_str() {
+        "i32" | "u32" => TypeId::FIXED_INT32 as u32,
+        "i64" | "u64" => TypeId::FIXED_INT64 as u32,
+        ty => get_primitive_type_id(ty),
+    })
+}
@@ -16,8 +16,8 @@
 // under the License.
 
 use super::util::{
-    classify_trait_object_field, create_wrapper_types_arc, create_wrapper_types_rc, skip_ref_flag,
-    StructField,
+    classify_trait_object_field, create_wrapper_types_arc, create_wrapper_types_rc, field_endian,
+    field_member, field_with, skip_ref_flag, wrap_field_encrypt_write, StructField,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -101,12 +101,24 @@ pub fn gen_write_type_info() -> TokenStream {
 
 fn gen_write_field(field: &Field) -> TokenStream {
     let ty = &field.ty;
-    let ident = &field.ident;
+    let member = field_member(field);
+    if let Some(with) = field_with(field) {
+        return quote! {
+            fory_core::serializer::codec::write_with(context, |context| {
+                #with::serialize(&self.#member, context)
+            })?;
+        };
+    }
+    if let Some(endian) = field_endian(field) {
+        return quote! {
+            fory_core::serializer::number::FixedWidth::write_fixed(self.#member, context, #endian);
+        };
+    }
     match classify_trait_object_field(ty) {
         StructField::BoxDyn(_) => {
             quote! {
                 {
-                    let any_ref = self.#ident.as_any();
+                    let any_ref = self.#member.as_any();
                     let concrete_type_id = any_ref.type_id();
                     let fory_type_id = context.get_type_resolver()
                         .get_fory_type_id(concrete_type_id)
@@ -131,7 +143,7 @@ fn gen_write_field(field: &Field) -> TokenStream {
             let trait_ident = types.trait_ident;
             quote! {
                 {
-                    let wrapper = #wrapper_ty::from(self.#ident.clone() as std::rc::Rc<dyn #trait_ident>);
+                    let wrapper = #wrapper_ty::from(self.#member.clone() as std::rc::Rc<dyn #trait_ident>);
                     fory_core::serializer::Serializer::fory_write(&wrapper, context, true)?;
                 }
             }
@@ -142,7 +154,7 @@ fn gen_write_field(field: &Field) -> TokenStream {
             let trait_ident = types.trait_ident;
             quote! {
                 {
-                    let wrapper = #wrapper_ty::from(self.#ident.clone() as std::sync::Arc<dyn #trait_ident>);
+                    let wrapper = #wrapper_ty::from(self.#member.clone() as std::sync::Arc<dyn #trait_ident>);
                     fory_core::serializer::Serializer::fory_write(&wrapper, context, true)?;
                 }
             }
@@ -153,7 +165,7 @@ fn gen_write_field(field: &Field) -> TokenStream {
             let trait_ident = types.trait_ident;
             quote! {
                 {
-                    let wrapper_vec: Vec<#wrapper_ty> = self.#ident.iter()
+                    let wrapper_vec: Vec<#wrapper_ty> = self.#member.iter()
                         .map(|item| #wrapper_ty::from(item.clone() as std::rc::Rc<dyn #trait_ident>))
                         .collect();
                     fory_core::serializer::Serializer::fory_write(&wrapper_vec, context, true)?;
@@ -166,7 +178,7 @@ fn gen_write_field(field: &Field) -> TokenStream {
             let trait_ident = types.trait_ident;
             quote! {
                 {
-                    let wrapper_vec: Vec<#wrapper_ty> = self.#ident.iter()
+                    let wrapper_vec: Vec<#wrapper_ty> = self.#member.iter()
                         .map(|item| #wrapper_ty::from(item.clone() as std::sync::Arc<dyn #trait_ident>))
                         .collect();
                     fory_core::serializer::Serializer::fory_write(&wrapper_vec, context, true)?;
@@ -179,7 +191,7 @@ fn gen_write_field(field: &Field) -> TokenStream {
             let trait_ident = types.trait_ident;
             quote! {
                 {
-                    let wrapper_map: std::collections::HashMap<#key_ty, #wrapper_ty> = self.#ident.iter()
+                    let wrapper_map: std::collections::HashMap<#key_ty, #wrapper_ty> = self.#member.iter()
                         .map(|(k, v)| (k.clone(), #wrapper_ty::from(v.clone() as std::rc::Rc<dyn #trait_ident>)))
                         .collect();
                     fory_core::serializer::Serializer::fory_write(&wrapper_map, context, true)?;
@@ -192,7 +204,7 @@ fn gen_write_field(field: &Field) -> TokenStream {
             let trait_ident = types.trait_ident;
             quote! {
                 {
-                    let wrapper_map: std::collections::HashMap<#key_ty, #wrapper_ty> = self.#ident.iter()
+                    let wrapper_map: std::collections::HashMap<#key_ty, #wrapper_ty> = self.#member.iter()
                         .map(|(k, v)| (k.clone(), #wrapper_ty::from(v.clone() as std::sync::Arc<dyn #trait_ident>)))
                         .collect();
                     fory_core::serializer::Serializer::fory_write(&wrapper_map, context, true)?;
@@ -202,27 +214,64 @@ fn gen_write_field(field: &Field) -> TokenStream {
         StructField::Forward => {
             quote! {
                 {
-                    fory_core::serializer::Serializer::fory_write(&self.#ident, context, true)?;
+                    fory_core::serializer::Serializer::fory_write(&self.#member, context, true)?;
                 }
             }
         }
         _ => {
             let skip_ref_flag = skip_ref_flag(ty);
             quote! {
-                fory_core::serializer::write_ref_info_data::<#ty>(&self.#ident, context, true, #skip_ref_flag, false)?;
+                fory_core::serializer::write_ref_info_data::<#ty>(&self.#member, context, true, #skip_ref_flag, false)?;
             }
         }
     }
 }
 
 pub fn gen_write_data(fields: &[&Field]) -> TokenStream {
-    let write_fields_ts: Vec<_> = fields.iter().map(|field| gen_write_field(field)).collect();
+    let write_fields_ts: Vec<_> = fields
+        .iter()
+        .map(|field| wrap_field_encrypt_write(field, gen_write_field(field)))
+        .collect();
     quote! {
         #(#write_fields_ts)*
         Ok(())
     }
 }
 
+/// Counterpart of [`gen_write_data`] for a generic struct, which writes its fields in the
+/// order of its registered type meta, see `StructSerializer::fory_sorts_fields_at_runtime`.
+pub fn gen_write_data_in_meta_order(fields: &[&Field]) -> TokenStream {
+    if fields.is_empty() {
+        return gen_write_data(fields);
+    }
+    let arms: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let field_id = i as i16;
+            let write_field = wrap_field_encrypt_write(field, gen_write_field(field));
+            quote! {
+                #field_id => {
+                    #write_field
+                }
+            }
+        })
+        .collect();
+    quote! {
+        let meta = context
+            .get_type_resolver()
+            .get_type_info(std::any::TypeId::of::<Self>())?
+            .get_type_meta();
+        for field in meta.get_field_infos() {
+            match field.field_id {
+                #(#arms)*
+                _ => unreachable!("field ids are assigned by the sorted field names"),
+            }
+        }
+        Ok(())
+    }
+}
+
 pub fn gen_write() -> TokenStream {
     quote! {
         fory_core::serializer::struct_::write::<Self>(self, context, is_field)
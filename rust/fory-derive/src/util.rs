@@ -23,13 +23,13 @@ pub fn sorted_fields(fields: &Fields) -> Vec<&Field> {
 }
 
 pub fn get_sorted_fields<'a>(fields: &[&'a Field]) -> Vec<&'a Field> {
-    use crate::object::util::get_sorted_field_names;
+    use crate::object::util::{field_name, get_sorted_field_names};
 
     let sorted_names = get_sorted_field_names(fields);
     let mut sorted_fields = Vec::with_capacity(fields.len());
 
     for name in &sorted_names {
-        if let Some(field) = fields.iter().find(|f| *f.ident.as_ref().unwrap() == name) {
+        if let Some(field) = fields.iter().find(|f| field_name(f) == *name) {
             sorted_fields.push(*field);
         }
     }
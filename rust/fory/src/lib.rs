@@ -717,6 +717,8 @@
 //! }
 //!
 //! impl Serializer for CustomType {
+//!     fory::read_null_as_default!();
+//!
 //!     fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
 //!         context.writer.write_i32(self.value);
 //!         context.writer.write_varuint32(self.name.len() as u32);
@@ -876,6 +878,32 @@
 //! - `chrono::NaiveDate` - Date without timezone
 //! - `chrono::NaiveDateTime` - Timestamp without timezone
 //!
+//! With the `time` feature of `fory-core`, the `time` crate is supported as well:
+//!
+//! - `time::Date` - Date, written like `chrono::NaiveDate`
+//! - `time::OffsetDateTime` - Timestamp followed by its UTC offset
+//! - `time::Duration` - Seconds and nanoseconds
+//!
+//! ### Compact Strings (requires the `compact_str` feature of `fory-core`)
+//!
+//! - `compact_str::CompactString` - Written exactly like `String`
+//!
+//! ### URLs (requires the `url` feature of `fory-core`)
+//!
+//! - `url::Url` - Written as its string form and parsed again on read
+//!
+//! ### Versions (requires the `semver` feature of `fory-core`)
+//!
+//! - `semver::Version` - Major, minor and patch numbers, then pre-release and build strings
+//!
+//! ### Saturating Integers (requires the `saturating` feature of `fory-core` and Rust 1.74)
+//!
+//! - `std::num::Saturating<T>` - Written exactly like `T`
+//!
+//! ### Bounded Vectors (requires the `arrayvec` feature of `fory-core`)
+//!
+//! - `arrayvec::ArrayVec<T, N>` - Written like `Vec<T>`, more than `N` elements fail to read
+//!
 //! ### Custom Types
 //!
 //! - Structs with `#[derive(ForyObject)]` - Object graph serialization
@@ -1047,7 +1075,9 @@
 //! - **[GitHub Repository](https://github.com/apache/fory)** - Source code and issue tracking
 
 pub use fory_core::{
-    error::Error, fory::Fory, register_trait_type, row::from_row, row::to_row, types::TypeId,
-    ArcWeak, ForyDefault, RcWeak, ReadContext, Serializer, TypeResolver, WriteContext,
+    assert_fory_compatible, error::Error, fory::Fory, read_null_as_default, register_generic,
+    register_trait_type, row::from_row, row::to_row, types::TypeId, ArcWeak, Cipher, Diff,
+    FieldChange, ForyDefault, Merge, OrderedHashMap, PreEncoded, RcWeak, ReadContext, Serializer,
+    SortedSet, TypeResolver, WriteContext,
 };
-pub use fory_derive::{ForyObject, ForyRow};
+pub use fory_derive::{ForyDiff, ForyMerge, ForyObject, ForyRow};
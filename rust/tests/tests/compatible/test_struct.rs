@@ -15,8 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use fory_core::error::Error;
 use fory_core::fory::Fory;
-use fory_derive::ForyObject;
+use fory_derive::{ForyMerge, ForyObject};
 use std::collections::{HashMap, HashSet};
 
 // RUSTFLAGS="-Awarnings" cargo expand -p fory-tests --test test_struct
@@ -276,7 +277,7 @@ fn nullable_container() {
     assert_eq!(item2.f4, item1.f4.unwrap());
     assert_eq!(item2.f5.unwrap(), item1.f5);
     assert_eq!(item2.f6, item1.f6.unwrap());
-    assert_eq!(item2.f7, Vec::default());
+    assert_eq!(item2.f7, Vec::<i8>::default());
     assert_eq!(item2.f8, HashSet::default());
     assert_eq!(item2.f9, HashMap::default());
     assert_eq!(item2.last, item1.last);
@@ -589,3 +590,607 @@ fn boxed() {
     let item2_f6: Option<i32> = fory2.deserialize(&bytes).unwrap();
     assert_eq!(item2.f6, item2_f6);
 }
+
+#[test]
+fn merge_partial_update() {
+    #[derive(ForyObject, ForyMerge, Debug, PartialEq)]
+    struct User {
+        name: String,
+        age: i32,
+        email: Option<String>,
+    }
+
+    #[derive(ForyObject, Debug)]
+    struct UserAgeUpdate {
+        age: i32,
+    }
+
+    let mut fory1 = Fory::default().compatible(true);
+    let mut fory2 = Fory::default().compatible(true);
+    fory1.register::<UserAgeUpdate>(999).unwrap();
+    fory2.register::<User>(999).unwrap();
+
+    let mut user = User {
+        name: "alice".to_string(),
+        age: 30,
+        email: Some("alice@example.com".to_string()),
+    };
+    let bin = fory1.serialize(&UserAgeUpdate { age: 31 }).unwrap();
+    fory2.merge_into(&bin, &mut user).unwrap();
+    assert_eq!(
+        user,
+        User {
+            name: "alice".to_string(),
+            age: 31,
+            email: Some("alice@example.com".to_string()),
+        }
+    );
+
+    // A payload of the same struct carries every field and replaces the value.
+    let full = User {
+        name: "bob".to_string(),
+        age: 40,
+        email: None,
+    };
+    let bin = fory2.serialize(&full).unwrap();
+    fory2.merge_into(&bin, &mut user).unwrap();
+    assert_eq!(user, full);
+
+    let mut fory3 = Fory::default();
+    fory3.register::<User>(999).unwrap();
+    assert!(fory3.merge_into(&bin, &mut user).is_err());
+}
+
+#[test]
+fn external_schema() {
+    #[derive(ForyObject, Debug, PartialEq, Clone)]
+    struct Sample {
+        sensor: String,
+        value: f64,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Batch {
+        id: i64,
+        first: Sample,
+        samples: Vec<Sample>,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct BatchV2 {
+        id: i64,
+        first: Sample,
+        samples: Vec<Sample>,
+        note: String,
+    }
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Sample>(1).unwrap();
+    fory.register::<Batch>(2).unwrap();
+    let sample = Sample {
+        sensor: "t1".to_string(),
+        value: 21.5,
+    };
+    let batch = Batch {
+        id: 7,
+        first: sample.clone(),
+        samples: vec![sample.clone(), sample],
+    };
+
+    let schema = fory.write_schema::<Batch>().unwrap();
+    let data = fory.serialize_without_schema(&batch).unwrap();
+    let full = fory.serialize(&batch).unwrap();
+    assert!(data.len() + schema.len() <= full.len() + 8);
+    assert!(data.len() < full.len());
+    let from_schema: Batch = fory.deserialize_with_schema(&schema, &data).unwrap();
+    let from_full: Batch = fory.deserialize(&full).unwrap();
+    assert_eq!(from_schema, from_full);
+    assert_eq!(from_schema, batch);
+
+    // the reader resolves the writer's schema against its own types
+    let mut fory_v2 = Fory::default().compatible(true);
+    fory_v2.register::<Sample>(1).unwrap();
+    fory_v2.register::<BatchV2>(2).unwrap();
+    let evolved: BatchV2 = fory_v2.deserialize_with_schema(&schema, &data).unwrap();
+    assert_eq!(evolved.samples, batch.samples);
+    assert_eq!(evolved.note, "");
+
+    assert!(Fory::default().write_schema::<Batch>().is_err());
+}
+
+#[test]
+fn unsigned_fields() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Unsigned1 {
+        a: u8,
+        b: u16,
+        c: u32,
+        d: u64,
+        e: Option<u32>,
+        name: String,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Unsigned2 {
+        b: u16,
+        d: u64,
+        name: String,
+    }
+    let mut fory1 = Fory::default().compatible(true);
+    fory1.register::<Unsigned1>(999).unwrap();
+    let value = Unsigned1 {
+        a: u8::MAX,
+        b: u16::MAX,
+        c: u32::MAX,
+        d: u64::MAX,
+        e: Some(7),
+        name: "n".to_string(),
+    };
+    let bin = fory1.serialize(&value).unwrap();
+    let obj: Unsigned1 = fory1.deserialize(&bin).unwrap();
+    assert_eq!(obj, value);
+
+    let mut fory2 = Fory::default().compatible(true);
+    fory2.register::<Unsigned2>(999).unwrap();
+    let obj: Unsigned2 = fory2.deserialize(&bin).unwrap();
+    assert_eq!(
+        obj,
+        Unsigned2 {
+            b: u16::MAX,
+            d: u64::MAX,
+            name: "n".to_string(),
+        }
+    );
+}
+
+#[test]
+fn numeric_conversion() {
+    #[derive(ForyObject, Debug)]
+    struct Counter1 {
+        small: i64,
+        large: i64,
+        widened: i8,
+        last: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Counter2 {
+        small: i32,
+        large: i32,
+        widened: i64,
+        last: i32,
+    }
+    let mut fory1 = Fory::default().compatible(true);
+    fory1.register::<Counter1>(999).unwrap();
+    let counter = Counter1 {
+        small: -42,
+        large: (1 << 32) + 1,
+        widened: -7,
+        last: 5,
+    };
+    let bin = fory1.serialize(&counter).unwrap();
+
+    // Without conversion the changed fields are skipped, as before.
+    let mut fory2 = Fory::default().compatible(true);
+    fory2.register::<Counter2>(999).unwrap();
+    let obj: Counter2 = fory2.deserialize(&bin).unwrap();
+    assert_eq!((obj.small, obj.large, obj.widened, obj.last), (0, 0, 0, 5));
+
+    // Lenient conversion truncates values that do not fit.
+    let mut lenient = Fory::default()
+        .compatible(true)
+        .with_numeric_conversion(true);
+    lenient.register::<Counter2>(999).unwrap();
+    let obj: Counter2 = lenient.deserialize(&bin).unwrap();
+    assert_eq!(
+        (obj.small, obj.large, obj.widened, obj.last),
+        (-42, 1, -7, 5)
+    );
+
+    let mut strict = Fory::default()
+        .compatible(true)
+        .with_numeric_conversion(true)
+        .with_strict_numerics(true);
+    strict.register::<Counter2>(999).unwrap();
+    let result: Result<Counter2, Error> = strict.deserialize(&bin);
+    assert!(matches!(
+        result,
+        Err(Error::NumericOverflow(value, "i32")) if value == (1 << 32) + 1
+    ));
+    let fits = Counter1 {
+        large: i32::MIN as i64,
+        ..counter
+    };
+    let obj: Counter2 = strict
+        .deserialize(&fory1.serialize(&fits).unwrap())
+        .unwrap();
+    assert_eq!(
+        (obj.small, obj.large, obj.widened, obj.last),
+        (-42, i32::MIN, -7, 5)
+    );
+}
+
+#[test]
+fn numeric_conversion_option() {
+    #[derive(ForyObject, Debug)]
+    struct Reading1 {
+        small: i32,
+        maybe: Option<i64>,
+        absent: Option<i64>,
+        last: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Reading2 {
+        small: Option<i16>,
+        maybe: Option<i32>,
+        absent: Option<i32>,
+        last: i32,
+    }
+    let mut fory1 = Fory::default().compatible(true);
+    fory1.register::<Reading1>(999).unwrap();
+    let reading = Reading1 {
+        small: -3,
+        maybe: Some(70_000),
+        absent: None,
+        last: 5,
+    };
+    let bin = fory1.serialize(&reading).unwrap();
+
+    let mut plain = Fory::default().compatible(true);
+    plain.register::<Reading2>(999).unwrap();
+    let obj: Reading2 = plain.deserialize(&bin).unwrap();
+    assert_eq!(
+        (obj.small, obj.maybe, obj.absent, obj.last),
+        (None, None, None, 5)
+    );
+
+    let mut lenient = Fory::default()
+        .compatible(true)
+        .with_numeric_conversion(true);
+    lenient.register::<Reading2>(999).unwrap();
+    let obj: Reading2 = lenient.deserialize(&bin).unwrap();
+    assert_eq!(
+        (obj.small, obj.maybe, obj.absent, obj.last),
+        (Some(-3), Some(70_000), None, 5)
+    );
+}
+
+#[test]
+fn numeric_conversion_array() {
+    #[derive(ForyObject, Debug)]
+    struct Samples1 {
+        values: Vec<i32>,
+        last: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Samples2 {
+        values: Vec<i64>,
+        last: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Samples3 {
+        values: Vec<i16>,
+        last: i32,
+    }
+    let mut fory1 = Fory::default().compatible(true);
+    fory1.register::<Samples1>(999).unwrap();
+    let samples = Samples1 {
+        values: vec![i32::MIN, -1, 0, 70_000, i32::MAX],
+        last: 5,
+    };
+    let bin = fory1.serialize(&samples).unwrap();
+
+    let mut plain = Fory::default().compatible(true);
+    plain.register::<Samples2>(999).unwrap();
+    let obj: Samples2 = plain.deserialize(&bin).unwrap();
+    assert!(obj.values.is_empty());
+    assert_eq!(obj.last, 5);
+
+    let mut widening = Fory::default()
+        .compatible(true)
+        .with_numeric_conversion(true);
+    widening.register::<Samples2>(999).unwrap();
+    let obj: Samples2 = widening.deserialize(&bin).unwrap();
+    assert_eq!(
+        obj.values,
+        vec![i32::MIN as i64, -1, 0, 70_000, i32::MAX as i64]
+    );
+    assert_eq!(obj.last, 5);
+
+    let mut strict = Fory::default()
+        .compatible(true)
+        .with_numeric_conversion(true)
+        .with_strict_numerics(true);
+    strict.register::<Samples3>(999).unwrap();
+    let result: Result<Samples3, Error> = strict.deserialize(&bin);
+    assert!(matches!(
+        result,
+        Err(Error::NumericOverflow(value, "i16")) if value == i32::MIN as i64
+    ));
+    let fits = Samples1 {
+        values: vec![-3, 300],
+        last: 5,
+    };
+    let obj: Samples3 = strict
+        .deserialize(&fory1.serialize(&fits).unwrap())
+        .unwrap();
+    assert_eq!(obj.values, vec![-3, 300]);
+}
+
+#[test]
+fn numeric_conversion_optional_array() {
+    #[derive(ForyObject, Debug)]
+    struct Samples1 {
+        values: Vec<i32>,
+        last: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Samples2 {
+        values: Option<Vec<i64>>,
+        last: i32,
+    }
+    let mut fory1 = Fory::default().compatible(true);
+    fory1.register::<Samples1>(999).unwrap();
+    let samples = Samples1 {
+        values: vec![1, 2],
+        last: 5,
+    };
+    let bin = fory1.serialize(&samples).unwrap();
+
+    let mut plain = Fory::default().compatible(true);
+    plain.register::<Samples2>(999).unwrap();
+    let obj: Samples2 = plain.deserialize(&bin).unwrap();
+    assert_eq!(obj.values, None);
+    assert_eq!(obj.last, 5);
+
+    let mut widening = Fory::default()
+        .compatible(true)
+        .with_numeric_conversion(true);
+    widening.register::<Samples2>(999).unwrap();
+    let obj: Samples2 = widening.deserialize(&bin).unwrap();
+    assert_eq!(obj.values, Some(vec![1, 2]));
+    assert_eq!(obj.last, 5);
+}
+
+#[test]
+fn field_name_hash() {
+    use fory_core::types::HashKind;
+
+    #[derive(ForyObject, Debug)]
+    struct Profile1 {
+        display_name: String,
+        account_identifier: i64,
+        removed_preference: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Profile2 {
+        account_identifier: i64,
+        display_name: String,
+        added_preference: Vec<String>,
+    }
+    let profile = Profile1 {
+        display_name: "ana".to_string(),
+        account_identifier: 42,
+        removed_preference: 7,
+    };
+    let mut plain = Fory::default().compatible(true);
+    plain.register::<Profile1>(999).unwrap();
+    let plain_bin = plain.serialize(&profile).unwrap();
+
+    for kind in [HashKind::Hash32, HashKind::Hash64] {
+        let mut fory1 = Fory::default().compatible(true).with_field_name_hash(kind);
+        fory1.register::<Profile1>(999).unwrap();
+        let bin = fory1.serialize(&profile).unwrap();
+        assert!(bin.len() < plain_bin.len());
+
+        let mut fory2 = Fory::default().compatible(true).with_field_name_hash(kind);
+        fory2.register::<Profile2>(999).unwrap();
+        let obj: Profile2 = fory2.deserialize(&bin).unwrap();
+        assert_eq!(obj.display_name, "ana");
+        assert_eq!(obj.account_identifier, 42);
+        assert!(obj.added_preference.is_empty());
+    }
+}
+
+#[test]
+fn field_name_hash_tag_id() {
+    use fory_core::buffer::Reader;
+    use fory_core::meta::FieldInfo;
+    use fory_core::types::TypeId;
+
+    // | TAG_ID:2bits | tag_id 5:4bits | not nullable | no ref tracking |, then an INT32 type
+    // and no name, as written by peers that number their fields
+    let bytes = [(0b11 << 6) | (5 << 2), TypeId::INT32 as u8, 0xff];
+    let mut reader = Reader::new(&bytes);
+    let field_info = FieldInfo::from_bytes(&mut reader).unwrap();
+    assert_eq!(field_info.name_hash, Some(5));
+    assert_eq!(field_info.field_type.type_id, TypeId::INT32 as u32);
+    assert_eq!(reader.get_cursor(), 2);
+}
+
+#[test]
+fn field_name_hash_collision() {
+    use fory_core::types::HashKind;
+
+    // both names have the same 32-bit hash
+    #[derive(ForyObject, Debug)]
+    struct Colliding {
+        f27778: i32,
+        f64308: i32,
+    }
+    let mut fory = Fory::default()
+        .compatible(true)
+        .with_field_name_hash(HashKind::Hash32);
+    let err = fory.register::<Colliding>(999).unwrap_err();
+    assert!(matches!(err, Error::TypeError(_)));
+    assert!(err.to_string().contains("f27778"));
+
+    let mut fory = Fory::default()
+        .compatible(true)
+        .with_field_name_hash(HashKind::Hash64);
+    fory.register::<Colliding>(999).unwrap();
+    let bin = fory
+        .serialize(&Colliding {
+            f27778: 1,
+            f64308: 2,
+        })
+        .unwrap();
+    let obj: Colliding = fory.deserialize(&bin).unwrap();
+    assert_eq!((obj.f27778, obj.f64308), (1, 2));
+}
+
+#[test]
+fn deserialize_projection() {
+    #[derive(ForyObject, Debug)]
+    struct Wide {
+        f1: i32,
+        f2: i64,
+        f3: String,
+        f4: bool,
+        f5: f64,
+        f6: Vec<String>,
+        f7: HashMap<String, i32>,
+        f8: String,
+        f9: i32,
+        f10: Vec<i64>,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Narrow {
+        f3: String,
+        f9: i32,
+        missing: String,
+    }
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Wide>(900).unwrap();
+    fory.register::<Narrow>(901).unwrap();
+    let wide = Wide {
+        f1: 1,
+        f2: 2,
+        f3: "three".to_string(),
+        f4: true,
+        f5: 5.0,
+        f6: vec!["six".to_string()],
+        f7: HashMap::from([("seven".to_string(), 7)]),
+        f8: "eight".to_string(),
+        f9: 9,
+        f10: vec![10, 10],
+    };
+    let bin = fory.serialize(&wide).unwrap();
+    let narrow: Narrow = fory.deserialize_projection::<Wide, Narrow>(&bin).unwrap();
+    assert_eq!(
+        narrow,
+        Narrow {
+            f3: "three".to_string(),
+            f9: 9,
+            missing: String::new(),
+        }
+    );
+
+    // the data must have been written as the source type
+    let bin = fory.serialize(&narrow).unwrap();
+    let err = fory
+        .deserialize_projection::<Wide, Narrow>(&bin)
+        .unwrap_err();
+    assert!(matches!(err, Error::TypeMismatch(..)));
+
+    let fory = Fory::default();
+    assert!(matches!(
+        fory.deserialize_projection::<Wide, Narrow>(&bin),
+        Err(Error::InvalidData(_))
+    ));
+}
+
+#[test]
+fn compact_nested_struct() {
+    mod v1 {
+        use fory_derive::ForyObject;
+
+        #[derive(ForyObject, Debug, PartialEq)]
+        #[fory(compact)]
+        pub struct Point {
+            pub x: i32,
+            pub y: i64,
+        }
+
+        #[derive(ForyObject, Debug, PartialEq)]
+        pub struct Point3 {
+            pub x: i32,
+            pub y: i64,
+        }
+
+        #[derive(ForyObject, Debug, PartialEq)]
+        pub struct Shape {
+            pub name: String,
+            pub origin: Point,
+        }
+
+        #[derive(ForyObject, Debug, PartialEq)]
+        pub struct LooseShape {
+            pub name: String,
+            pub origin: Point3,
+        }
+    }
+    mod v2 {
+        use fory_derive::ForyObject;
+
+        #[derive(ForyObject, Debug, PartialEq)]
+        #[fory(compact)]
+        pub struct Point {
+            pub x: i32,
+            pub y: i64,
+        }
+
+        #[derive(ForyObject, Debug, PartialEq)]
+        pub struct Shape {
+            pub origin: Point,
+            pub name: String,
+            pub layer: i32,
+        }
+    }
+    mod reordered {
+        use fory_derive::ForyObject;
+
+        #[derive(ForyObject, Debug, PartialEq)]
+        #[fory(compact)]
+        pub struct Point {
+            pub y: i32,
+            pub x: i64,
+        }
+
+        #[derive(ForyObject, Debug, PartialEq)]
+        pub struct Shape {
+            pub name: String,
+            pub origin: Point,
+        }
+    }
+
+    let mut fory1 = Fory::default().compatible(true);
+    fory1.register::<v1::Point>(300).unwrap();
+    fory1.register::<v1::Point3>(301).unwrap();
+    fory1.register::<v1::Shape>(302).unwrap();
+    fory1.register::<v1::LooseShape>(303).unwrap();
+    let mut fory2 = Fory::default().compatible(true);
+    fory2.register::<v2::Point>(300).unwrap();
+    fory2.register::<v2::Shape>(302).unwrap();
+    let mut fory3 = Fory::default().compatible(true);
+    fory3.register::<reordered::Point>(300).unwrap();
+    fory3.register::<reordered::Shape>(302).unwrap();
+
+    let shape = v1::Shape {
+        name: "square".to_string(),
+        origin: v1::Point { x: 3, y: -4 },
+    };
+    let bin = fory1.serialize(&shape).unwrap();
+    assert_eq!(fory1.deserialize::<v1::Shape>(&bin).unwrap(), shape);
+    // the parent still evolves, and the compact struct needs no type meta of its own
+    let evolved: v2::Shape = fory2.deserialize(&bin).unwrap();
+    assert_eq!(evolved.name, "square");
+    assert_eq!(evolved.origin, v2::Point { x: 3, y: -4 });
+    assert_eq!(evolved.layer, 0);
+    let loose = v1::LooseShape {
+        name: "square".to_string(),
+        origin: v1::Point3 { x: 3, y: -4 },
+    };
+    assert!(bin.len() < fory1.serialize(&loose).unwrap().len());
+
+    let err = fory3.deserialize::<reordered::Shape>(&bin).unwrap_err();
+    assert!(matches!(err, Error::SchemaMismatch(..)));
+}
@@ -399,6 +399,7 @@ fn ext() {
         }
     }
     impl Serializer for ExtItem {
+        fory_core::read_null_as_default!();
         fn fory_write_data(
             &self,
             context: &mut WriteContext,
@@ -454,6 +455,7 @@ fn skip_ext() {
         id: i32,
     }
     impl Serializer for ExtItem {
+        fory_core::read_null_as_default!();
         fn fory_write_data(
             &self,
             context: &mut WriteContext,
@@ -524,6 +526,7 @@ fn compatible_ext() {
         id: i32,
     }
     impl Serializer for ExtItem {
+        fory_core::read_null_as_default!();
         fn fory_write_data(
             &self,
             context: &mut WriteContext,
@@ -593,3 +596,55 @@ fn compatible_ext() {
         );
     }
 }
+
+#[test]
+fn unknown_enum_variant() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    enum ColorV1 {
+        Green,
+        Red,
+        #[fory(unknown)]
+        Unknown(u32),
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    enum ColorV2 {
+        Green,
+        Red,
+        Blue,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct PaletteV1 {
+        f1: ColorV1,
+        f2: Vec<ColorV1>,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct PaletteV2 {
+        f1: ColorV2,
+        f2: Vec<ColorV2>,
+    }
+    let mut fory1 = Fory::default().compatible(true);
+    fory1.register::<ColorV1>(101).unwrap();
+    fory1.register::<PaletteV1>(102).unwrap();
+    let mut fory2 = Fory::default().compatible(true);
+    fory2.register::<ColorV2>(101).unwrap();
+    fory2.register::<PaletteV2>(102).unwrap();
+
+    let palette = PaletteV2 {
+        f1: ColorV2::Blue,
+        f2: vec![ColorV2::Red, ColorV2::Blue],
+    };
+    let bytes = fory2.serialize(&palette).unwrap();
+    let decoded: PaletteV1 = fory1.deserialize(&bytes).unwrap();
+    assert_eq!(
+        decoded,
+        PaletteV1 {
+            f1: ColorV1::Unknown(2),
+            f2: vec![ColorV1::Red, ColorV1::Unknown(2)],
+        }
+    );
+    assert_eq!(ColorV1::default(), ColorV1::Green);
+
+    // the raw ordinal is written back, so the newer peer still sees its variant
+    let bytes = fory1.serialize(&decoded).unwrap();
+    assert_eq!(fory2.deserialize::<PaletteV2>(&bytes).unwrap(), palette);
+}
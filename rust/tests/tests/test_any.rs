@@ -15,9 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use fory_core::error::Error;
 use fory_core::fory::Fory;
+use fory_core::serializer::any::RawValue;
+use fory_core::types::{TypeId, UnknownTypePolicy};
 use fory_derive::ForyObject;
 use std::any::Any;
+use std::ops::ControlFlow;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::vec;
@@ -268,3 +272,111 @@ fn test_rc_by_name() {
         &deserialized_vec[1]
     ));
 }
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Event {
+    id: i32,
+    name: String,
+    tags: Vec<String>,
+}
+
+fn event_payload() -> Vec<u8> {
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Event>(10).unwrap();
+    let value: Vec<Box<dyn Any>> = vec![
+        Box::new("head".to_string()),
+        Box::new(Event {
+            id: 7,
+            name: "click".to_string(),
+            tags: vec!["a".to_string(), "b".to_string()],
+        }),
+        Box::new(3i32),
+    ];
+    fory.serialize(&value).unwrap()
+}
+
+#[test]
+fn test_unknown_type_preserve_round_trip() {
+    let bytes = event_payload();
+    let proxy = Fory::default()
+        .compatible(true)
+        .with_unknown_type_policy(UnknownTypePolicy::Preserve);
+    let value: Vec<Box<dyn Any>> = proxy.deserialize(&bytes).unwrap();
+    assert_eq!(value[0].downcast_ref::<String>().unwrap(), "head");
+    let raw = value[1].downcast_ref::<RawValue>().unwrap();
+    assert_eq!(raw.type_id(), (10 << 8) + TypeId::COMPATIBLE_STRUCT as u32);
+    assert_eq!(value[2].downcast_ref::<i32>().unwrap(), &3);
+    assert_eq!(proxy.serialize(&value).unwrap(), bytes);
+}
+
+#[test]
+fn test_unknown_type_skip_and_error() {
+    let bytes = event_payload();
+    let skipping = Fory::default()
+        .compatible(true)
+        .with_unknown_type_policy(UnknownTypePolicy::Skip);
+    let value: Vec<Box<dyn Any>> = skipping.deserialize(&bytes).unwrap();
+    assert!(value[1].downcast_ref::<()>().is_some());
+    assert_eq!(value[2].downcast_ref::<i32>().unwrap(), &3);
+
+    let strict = Fory::default().compatible(true);
+    let result: Result<Vec<Box<dyn Any>>, _> = strict.deserialize(&bytes);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_type_observer_rejects_disallowed_type() {
+    #[derive(ForyObject, Debug)]
+    struct Command {
+        name: String,
+    }
+
+    let allowed = [
+        TypeId::LIST as u32,
+        TypeId::STRING as u32,
+        TypeId::INT32 as u32,
+    ];
+    let new_fory = |observe: bool| {
+        let fory = Fory::default();
+        let mut fory = if observe {
+            fory.with_type_observer(Box::new(move |type_id| {
+                if allowed.contains(&type_id) {
+                    ControlFlow::Continue(())
+                } else {
+                    ControlFlow::Break(())
+                }
+            }))
+        } else {
+            fory
+        };
+        fory.register::<Command>(100).unwrap();
+        fory
+    };
+    let writer = new_fory(false);
+    let reader = new_fory(true);
+
+    let safe: Vec<Box<dyn Any>> = vec![Box::new("ls".to_string()), Box::new(1i32)];
+    let obj: Vec<Box<dyn Any>> = reader
+        .deserialize(&writer.serialize(&safe).unwrap())
+        .unwrap();
+    assert_eq!(obj[1].downcast_ref::<i32>(), Some(&1));
+
+    let unsafe_values: Vec<Box<dyn Any>> = vec![
+        Box::new("ls".to_string()),
+        Box::new(Command {
+            name: "rm".to_string(),
+        }),
+    ];
+    let bytes = writer.serialize(&unsafe_values).unwrap();
+    let command_id = writer.type_id_of::<Command>().unwrap();
+    assert!(matches!(
+        reader.deserialize::<Vec<Box<dyn Any>>>(&bytes),
+        Err(Error::TypeRejected(id)) if id == command_id
+    ));
+
+    let bytes = writer.serialize(&2i64).unwrap();
+    assert!(matches!(
+        reader.deserialize::<i64>(&bytes),
+        Err(Error::TypeRejected(id)) if id == TypeId::INT64 as u32
+    ));
+}
@@ -0,0 +1,220 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+
+#[test]
+fn test_byte_array_round_trip() {
+    let fory = Fory::default();
+    let hash: [u8; 32] = std::array::from_fn(|i| (i * 7) as u8);
+    let bin = fory.serialize(&hash).unwrap();
+    assert_eq!(fory.deserialize::<[u8; 32]>(&bin).unwrap(), hash);
+
+    // written as exactly the 32 bytes, the length is only in the type info
+    let empty = fory.serialize(&[0u8; 0]).unwrap();
+    assert_eq!(bin.len(), empty.len() + 32);
+    assert!(bin.ends_with(&hash));
+
+    let keys = vec![[1u8; 16], [2u8; 16]];
+    let bin = fory.serialize(&keys).unwrap();
+    assert_eq!(fory.deserialize::<Vec<[u8; 16]>>(&bin).unwrap(), keys);
+    let one_key = fory.serialize(&vec![[1u8; 16]]).unwrap();
+    assert_eq!(bin.len(), one_key.len() + 16);
+}
+
+#[test]
+fn test_byte_array_field() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Block {
+        height: i64,
+        hash: [u8; 32],
+        parent: [u8; 32],
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Block>(100).unwrap();
+    let block = Block {
+        height: 7,
+        hash: [0xab; 32],
+        parent: [0xcd; 32],
+    };
+    let bin = fory.serialize(&block).unwrap();
+    assert_eq!(fory.deserialize::<Block>(&bin).unwrap(), block);
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Block>(100).unwrap();
+    let bin = fory.serialize(&block).unwrap();
+    assert_eq!(fory.deserialize::<Block>(&bin).unwrap(), block);
+}
+
+#[test]
+fn test_byte_array_field_compatible() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Block {
+        height: i64,
+        hash: [u8; 32],
+        name: String,
+        parent: [u8; 32],
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct BlockSkipped {
+        height: i64,
+        name: String,
+    }
+
+    let block = Block {
+        height: 7,
+        hash: [0xab; 32],
+        name: "tip".to_string(),
+        parent: [0xcd; 32],
+    };
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Block>(100).unwrap();
+    let bin = fory.serialize(&block).unwrap();
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<BlockSkipped>(100).unwrap();
+    let obj: BlockSkipped = fory.deserialize(&bin).unwrap();
+    assert_eq!(
+        obj,
+        BlockSkipped {
+            height: 7,
+            name: "tip".to_string(),
+        }
+    );
+
+    // a field of another length is a field of another type, and is skipped
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct ShortBlock {
+        height: i64,
+        hash: [u8; 16],
+        name: String,
+    }
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<ShortBlock>(100).unwrap();
+    let obj: ShortBlock = fory.deserialize(&bin).unwrap();
+    assert_eq!(
+        obj,
+        ShortBlock {
+            height: 7,
+            hash: [0; 16],
+            name: "tip".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_byte_array_length_mismatch() {
+    let fory = Fory::default();
+    let bin = fory.serialize(&[9u8; 16]).unwrap();
+    assert!(matches!(
+        fory.deserialize::<[u8; 32]>(&bin),
+        Err(Error::InvalidData(_))
+    ));
+    let bin = fory.serialize(&[9u8; 32]).unwrap();
+    assert!(matches!(
+        fory.deserialize::<[u8; 32]>(&bin[..bin.len() - 1]),
+        Err(Error::BufferOutOfBound(..))
+    ));
+}
+
+#[test]
+fn test_option_array_round_trip() {
+    let fory = Fory::default();
+    let slots = [Some(1), None, Some(3)];
+    let bin = fory.serialize(&slots).unwrap();
+    assert_eq!(fory.deserialize::<[Option<i32>; 3]>(&bin).unwrap(), slots);
+
+    // each element is a presence flag followed by the value, without a length prefix
+    let empty = fory.serialize(&[None::<i32>; 0]).unwrap();
+    let none = fory.serialize(&[None::<i32>; 3]).unwrap();
+    assert_eq!(none.len(), empty.len() + 3);
+    assert_eq!(
+        fory.deserialize::<[Option<i32>; 3]>(&none).unwrap(),
+        [None; 3]
+    );
+    assert!(matches!(
+        fory.deserialize::<Vec<Option<i32>>>(&bin),
+        Err(Error::TypeMismatch(..))
+    ));
+
+    assert!(matches!(
+        fory.deserialize::<[Option<i32>; 4]>(&none),
+        Err(Error::InvalidData(_))
+    ));
+
+    let names = [Some("a".to_string()), None];
+    let bin = fory.serialize(&names).unwrap();
+    assert_eq!(
+        fory.deserialize::<[Option<String>; 2]>(&bin).unwrap(),
+        names
+    );
+}
+
+#[test]
+fn test_option_array_field_compatible() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Slots {
+        id: i32,
+        slots: [Option<String>; 3],
+        name: String,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct SlotsSkipped {
+        id: i32,
+        name: String,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct SlotsAsVec {
+        id: i32,
+        slots: Vec<Option<String>>,
+        name: String,
+    }
+
+    let value = Slots {
+        id: 7,
+        slots: [Some("a".to_string()), None, Some("c".to_string())],
+        name: "slots".to_string(),
+    };
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Slots>(100).unwrap();
+    let bin = fory.serialize(&value).unwrap();
+    assert_eq!(fory.deserialize::<Slots>(&bin).unwrap(), value);
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<SlotsSkipped>(100).unwrap();
+    let obj: SlotsSkipped = fory.deserialize(&bin).unwrap();
+    assert_eq!(
+        obj,
+        SlotsSkipped {
+            id: 7,
+            name: "slots".to_string(),
+        }
+    );
+
+    // a `Vec` is a field of another type, and is skipped
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<SlotsAsVec>(100).unwrap();
+    let obj: SlotsAsVec = fory.deserialize(&bin).unwrap();
+    assert!(obj.slots.is_empty());
+    assert_eq!(obj.name, "slots");
+}
@@ -0,0 +1,78 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrayvec::ArrayVec;
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Frame {
+    header: ArrayVec<u8, 8>,
+    samples: ArrayVec<i32, 4>,
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Reading {
+    sensor: String,
+    samples: ArrayVec<i32, 4>,
+    tags: ArrayVec<String, 2>,
+}
+
+#[test]
+fn test_arrayvec_round_trip() {
+    let fory = Fory::default();
+    let full: ArrayVec<u8, 8> = (1..=8).collect();
+    let bin = fory.serialize(&full).unwrap();
+    assert_eq!(fory.deserialize::<ArrayVec<u8, 8>>(&bin).unwrap(), full);
+    assert_eq!(fory.deserialize::<Vec<u8>>(&bin).unwrap(), full.to_vec());
+
+    let mut fory = Fory::default();
+    fory.register::<Frame>(100).unwrap();
+    let frame = Frame {
+        header: full,
+        samples: [-1, 0, i32::MAX].into_iter().collect(),
+    };
+    let bin = fory.serialize(&frame).unwrap();
+    assert_eq!(fory.deserialize::<Frame>(&bin).unwrap(), frame);
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Reading>(101).unwrap();
+    let reading = Reading {
+        sensor: "thermo".to_string(),
+        samples: [20, 21, 19, 22].into_iter().collect(),
+        tags: ["indoor".to_string()].into_iter().collect(),
+    };
+    let bin = fory.serialize(&reading).unwrap();
+    assert_eq!(fory.deserialize::<Reading>(&bin).unwrap(), reading);
+}
+
+#[test]
+fn test_arrayvec_rejects_too_many_elements() {
+    let fory = Fory::default();
+    let bin = fory.serialize(&(1..=9).collect::<Vec<u8>>()).unwrap();
+    assert!(matches!(
+        fory.deserialize::<ArrayVec<u8, 8>>(&bin),
+        Err(Error::CapacityExceeded(9, 8))
+    ));
+
+    let bin = fory.serialize(&vec![7i32; 5]).unwrap();
+    assert!(matches!(
+        fory.deserialize::<ArrayVec<i32, 4>>(&bin),
+        Err(Error::CapacityExceeded(5, 4))
+    ));
+}
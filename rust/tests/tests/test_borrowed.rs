@@ -0,0 +1,154 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::serializer::borrowed::{BorrowedSerializer, ByteView};
+use fory_core::{ReadContext, Reader, Writer};
+use std::borrow::Cow;
+use std::ffi::CString;
+
+fn points_into(s: &[u8], bytes: &[u8]) -> bool {
+    bytes.as_ptr_range().contains(&s.as_ptr())
+}
+
+#[test]
+fn test_borrowed_str_reads_in_place() {
+    let fory = Fory::default();
+    let bytes = fory.serialize(&"a borrowed string".to_string()).unwrap();
+    let s: &str = fory.deserialize_borrowed(&bytes).unwrap();
+    assert_eq!(s, "a borrowed string");
+    assert!(points_into(s.as_bytes(), &bytes));
+}
+
+#[test]
+fn test_borrowed_utf8() {
+    let fory = Fory::default().compress_string(true);
+    let bytes = fory.serialize(&"你好, wörld".to_string()).unwrap();
+    let s: &str = fory.deserialize_borrowed(&bytes).unwrap();
+    assert_eq!(s, "你好, wörld");
+    assert!(points_into(s.as_bytes(), &bytes));
+}
+
+#[test]
+fn test_borrowed_falls_back_to_owned() {
+    let fory = Fory::default();
+    for value in ["héllo", "你好"] {
+        let bytes = fory.serialize(&value.to_string()).unwrap();
+        let s: Cow<str> = fory.deserialize_borrowed(&bytes).unwrap();
+        assert!(matches!(s, Cow::Owned(_)));
+        assert_eq!(s, value);
+        assert!(matches!(
+            fory.deserialize_borrowed::<&str>(&bytes),
+            Err(Error::EncodingError(_))
+        ));
+    }
+}
+
+#[test]
+fn test_borrowed_bytes() {
+    let fory = Fory::default();
+    let bytes = fory.serialize(&CString::new("raw bytes").unwrap()).unwrap();
+    let b: &[u8] = fory.deserialize_borrowed(&bytes).unwrap();
+    assert_eq!(b, b"raw bytes");
+    assert!(points_into(b, &bytes));
+}
+
+#[test]
+fn test_borrowed_type_mismatch() {
+    let fory = Fory::default();
+    let bytes = fory.serialize(&"text".to_string()).unwrap();
+    assert!(matches!(
+        fory.deserialize_borrowed::<&[u8]>(&bytes),
+        Err(Error::TypeMismatch(_, _))
+    ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_borrowed_os_str() {
+    use std::ffi::{OsStr, OsString};
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+    use std::path::Path;
+
+    let fory = Fory::default();
+    // not valid UTF-8, which a path on Unix may be
+    let path = OsString::from_vec(b"/tmp/caf\xe9.txt".to_vec());
+    let bytes = fory.serialize(&path).unwrap();
+    let s: &OsStr = fory.deserialize_borrowed(&bytes).unwrap();
+    assert_eq!(s, path.as_os_str());
+    assert!(points_into(s.as_bytes(), &bytes));
+    assert_eq!(Path::new(s).extension(), Some(OsStr::new("txt")));
+    assert_eq!(fory.deserialize::<OsString>(&bytes).unwrap(), path);
+}
+
+#[test]
+fn test_byte_view_points_into_input() {
+    let fory = Fory::default();
+    let payload: Vec<u8> = (0..=255u8).cycle().take(64 * 1024).collect();
+    let bytes = fory.serialize(&payload).unwrap();
+    let view: ByteView = fory.deserialize_borrowed(&bytes).unwrap();
+    assert_eq!(&*view, payload.as_slice());
+    let offset = bytes.len() - payload.len();
+    assert_eq!(view.as_bytes().as_ptr(), bytes[offset..].as_ptr());
+
+    let mut binary = Vec::new();
+    fory.serialize_slice_to(payload.as_slice(), &mut binary)
+        .unwrap();
+    let view: ByteView = fory.deserialize_borrowed(&binary).unwrap();
+    assert_eq!(&*view, payload.as_slice());
+    assert!(points_into(&view, &binary));
+
+    let bytes = fory.serialize(&Vec::<u8>::new()).unwrap();
+    let view: ByteView = fory.deserialize_borrowed(&bytes).unwrap();
+    assert!(view.is_empty());
+
+    let bytes = fory.serialize(&vec![Some(1u8), None]).unwrap();
+    assert!(matches!(
+        fory.deserialize_borrowed::<ByteView>(&bytes),
+        Err(Error::InvalidData(_))
+    ));
+}
+
+#[test]
+fn test_read_bytes_view() {
+    let fory = Fory::default();
+    let mut writer = Writer::default();
+    writer.write_varuint32(3);
+    writer.write_bytes(b"abcdef");
+    let data = writer.dump();
+
+    let mut context = ReadContext::new_from_fory(Reader::new(&data), &fory);
+    let view = ByteView::fory_read_borrowed_data(&mut context, &data).unwrap();
+    assert_eq!(view.as_bytes(), b"abc");
+    assert_eq!(view.as_ptr(), data[1..].as_ptr());
+    let rest = context.read_bytes_view(3).unwrap();
+    assert_eq!(rest, b"def");
+    assert_eq!(rest.as_ptr(), data[4..].as_ptr());
+    assert!(matches!(
+        context.read_bytes_view(1),
+        Err(Error::BufferOutOfBound(..))
+    ));
+
+    // a copy of the input is not the buffer the context reads
+    let copy = data.clone();
+    let mut context = ReadContext::new_from_fory(Reader::new(&data), &fory);
+    assert!(matches!(
+        ByteView::fory_read_borrowed_data(&mut context, &copy),
+        Err(Error::InvalidData(_))
+    ));
+}
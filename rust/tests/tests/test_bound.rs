@@ -0,0 +1,56 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use std::ops::Bound;
+
+#[test]
+fn test_bound_variants() {
+    let fory = Fory::default();
+    for value in [
+        Bound::Included("a".to_string()),
+        Bound::Excluded("z".to_string()),
+        Bound::Unbounded,
+    ] {
+        let bin = fory.serialize(&value).unwrap();
+        let obj: Bound<String> = fory.deserialize(&bin).expect("deserialize");
+        assert_eq!(value, obj);
+    }
+}
+
+#[test]
+fn test_bound_pair() {
+    let fory = Fory::default();
+    let value: Vec<Bound<i64>> = vec![Bound::Included(10), Bound::Excluded(20)];
+    let bin = fory.serialize(&value).unwrap();
+    let obj: Vec<Bound<i64>> = fory.deserialize(&bin).expect("deserialize");
+    assert_eq!(value, obj);
+}
+
+#[test]
+fn test_bound_unknown_tag() {
+    let fory = Fory::default();
+    let mut bin = fory.serialize(&Bound::<i32>::Unbounded).unwrap();
+    let tag_offset = bin.len() - 1;
+    assert_eq!(bin[tag_offset], 2);
+    bin[tag_offset] = 3;
+    assert!(matches!(
+        fory.deserialize::<Bound<i32>>(&bin),
+        Err(Error::InvalidData(_))
+    ));
+}
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use fory_core::error::Error;
 use fory_core::fory::Fory;
 use fory_derive::ForyObject;
 use std::collections::HashMap;
@@ -127,9 +128,13 @@ fn test_box_option() {
         .expect("Should deserialize Box<Option<String>>");
     assert_eq!(*value, *deserialized);
 
-    // Note: Box<Option<None>> is not supported due to the way Option's serializer works
-    // The Option serializer expects None cases to be handled at the serialize() level,
-    // not at the write() level, but Box calls write() directly on the inner type.
+    // Test Box<Option<String>> with None value
+    let value: Box<Option<String>> = Box::new(None);
+    let bin = fory.serialize(&value).unwrap();
+    let deserialized: Box<Option<String>> = fory
+        .deserialize(&bin)
+        .expect("Should deserialize Box<Option<String>>");
+    assert_eq!(*value, *deserialized);
 }
 
 #[test]
@@ -144,3 +149,112 @@ fn test_nested_box() {
         .expect("Should deserialize Box<Box<i32>>");
     assert_eq!(**value, **deserialized);
 }
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Node {
+    value: i32,
+    next: Option<Box<Node>>,
+}
+
+fn build_list(depth: i32) -> Option<Box<Node>> {
+    let mut head = None;
+    for value in (0..depth).rev() {
+        head = Some(Box::new(Node { value, next: head }));
+    }
+    head
+}
+
+#[test]
+fn test_option_box_recursive() {
+    for compatible in [false, true] {
+        let mut fory = Fory::default().compatible(compatible);
+        fory.register::<Node>(100).unwrap();
+        let list = *build_list(8).unwrap();
+        let bin = fory.serialize(&list).unwrap();
+        let deserialized: Node = fory.deserialize(&bin).expect("Should deserialize Node");
+        assert_eq!(list, deserialized);
+    }
+}
+
+#[test]
+fn test_option_box_recursive_default_limit() {
+    let mut fory = Fory::default();
+    fory.register::<Node>(100).unwrap();
+    // the root plus 256 nested boxes fits the default max_box_depth on a test thread's stack
+    let list = *build_list(257).unwrap();
+    let bin = fory.serialize(&list).unwrap();
+    let deserialized: Node = fory.deserialize(&bin).expect("Should deserialize Node");
+    assert_eq!(list, deserialized);
+
+    let longer = *build_list(258).unwrap();
+    let bin = fory.serialize(&longer).unwrap();
+    let err = fory.deserialize::<Node>(&bin).unwrap_err();
+    assert!(matches!(err, Error::DepthExceed(_)), "{err}");
+}
+
+#[test]
+fn test_option_box_recursive_deep() {
+    std::thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(|| {
+            for compatible in [false, true] {
+                // the 999 boxes below the root are nested one level deeper each
+                let mut fory = Fory::default().compatible(compatible).max_box_depth(999);
+                fory.register::<Node>(100).unwrap();
+                let list = *build_list(1000).unwrap();
+                let bin = fory.serialize(&list).unwrap();
+                let deserialized: Node = fory.deserialize(&bin).expect("Should deserialize Node");
+                assert_eq!(list, deserialized);
+
+                let mut shallow = Fory::default().compatible(compatible).max_box_depth(998);
+                shallow.register::<Node>(100).unwrap();
+                let err = shallow.deserialize::<Node>(&bin).unwrap_err();
+                assert!(matches!(err, Error::DepthExceed(_)), "{err}");
+                // unlink iteratively to avoid a recursive drop of 1000 boxes
+                for mut node in [list, deserialized] {
+                    let mut next = node.next.take();
+                    while let Some(mut boxed) = next {
+                        next = boxed.next.take();
+                    }
+                }
+            }
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn test_box_str() {
+    let fory = Fory::default();
+    let bin = fory.serialize(&"Hello, Box<str>!".to_string()).unwrap();
+    let boxed: Box<str> = fory.deserialize(&bin).expect("Should deserialize Box<str>");
+    assert_eq!(&*boxed, "Hello, Box<str>!");
+    assert_eq!(fory.serialize(&boxed).unwrap(), bin);
+
+    let value: Vec<Box<str>> = vec!["a".into(), "你好".into(), "".into()];
+    let bin = fory.serialize(&value).unwrap();
+    let strings: Vec<String> = fory.deserialize(&bin).unwrap();
+    assert_eq!(strings, vec!["a", "你好", ""]);
+    let obj: Vec<Box<str>> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, value);
+}
+
+#[test]
+fn test_box_str_field() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Label {
+        text: Box<str>,
+        alt: Option<Box<str>>,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Label>(100).unwrap();
+    let label = Label {
+        text: "title".into(),
+        alt: Some("subtitle".into()),
+    };
+    let bin = fory.serialize(&label).unwrap();
+    let obj: Label = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, label);
+}
@@ -16,6 +16,10 @@
 // under the License.
 
 use fory_core::buffer::{Reader, Writer};
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::resolver::context::{ReadContext, WriteContext};
+use fory_core::serializer::Serializer;
 
 #[test]
 fn test_varint32() {
@@ -91,5 +95,332 @@ fn test_varuint36_small() {
         let mut reader = Reader::new(buf.as_slice());
         let value = reader.read_varuint36small().unwrap();
         assert_eq!(value, data, "failed for data {}", data);
+        assert_eq!(reader.get_cursor(), buf.len(), "failed for data {}", data);
     }
 }
+
+#[test]
+fn test_varuint36_small_baseline_bytes() {
+    // (value, bytes as written before the 3- and 5-byte forms dropped their trailing
+    // padding, number of trailing padding bytes)
+    let cases: [(u64, &[u8], usize); 8] = [
+        (0x7F, &[0x7F], 0),
+        (0x3FFF, &[0xFF, 0x7F], 0),
+        (0x4000, &[0x80, 0x80, 0x01, 0x00], 1),
+        (0x1F_FFFF, &[0xFF, 0xFF, 0x7F, 0x00], 1),
+        (0x20_0000, &[0x80, 0x80, 0x80, 0x01], 0),
+        (0xFFF_FFFF, &[0xFF, 0xFF, 0xFF, 0x7F], 0),
+        (0x1000_0000, &[0x80, 0x80, 0x80, 0x80, 0x01, 0, 0, 0], 3),
+        (0xF_FFFF_FFFF, &[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0], 3),
+    ];
+    for (value, baseline, padding) in cases {
+        let encoded = &baseline[..baseline.len() - padding];
+        // the old bytes still read as the same value, which ends before the padding
+        let mut reader = Reader::new(baseline);
+        assert_eq!(reader.read_varuint36small().unwrap(), value);
+        assert_eq!(reader.get_cursor(), encoded.len(), "failed for {value:#x}");
+
+        // and the new bytes are the old ones without it, whether or not the reader
+        // takes its 8-byte fast path
+        let mut writer = Writer::default();
+        writer.write_varuint36_small(value);
+        assert_eq!(writer.dump(), encoded, "failed for {value:#x}");
+        let mut padded = encoded.to_vec();
+        padded.resize(encoded.len() + 8, 0xAB);
+        for bytes in [encoded, padded.as_slice()] {
+            let mut reader = Reader::new(bytes);
+            assert_eq!(reader.read_varuint36small().unwrap(), value);
+            assert_eq!(reader.get_cursor(), encoded.len(), "failed for {value:#x}");
+        }
+    }
+}
+
+#[test]
+fn test_string_length_prefix() {
+    let fory = Fory::default();
+    for s in ["", "short", &"Hello, World! ".repeat(1000), "caf\u{e9}"] {
+        let s = s.to_string();
+        let mut context = WriteContext::new_from_fory(Writer::default(), &fory);
+        s.fory_write_data(&mut context, true).unwrap();
+        let bytes = context.writer.dump();
+        let mut reader = Reader::new(bytes.as_slice());
+        let header = reader.read_varuint36small().unwrap();
+        // latin1 encoding, one byte per char
+        assert_eq!(header, (s.chars().count() as u64) << 2);
+        assert_eq!(reader.slice_after_cursor().len(), s.chars().count());
+        if s.is_ascii() {
+            assert_eq!(reader.slice_after_cursor(), s.as_bytes());
+        }
+    }
+    let strings: Vec<String> = (0..10)
+        .map(|i| format!("{}-{}", i, "Hello, World! ".repeat(1000 * i)))
+        .collect();
+    let bytes = fory.serialize(&strings).unwrap();
+    assert_eq!(fory.deserialize::<Vec<String>>(&bytes).unwrap(), strings);
+}
+
+#[test]
+fn test_string_max_len() {
+    let fory = Fory::default().with_string_max_len(1024);
+    let bytes = fory.serialize(&"x".repeat(1024)).unwrap();
+    assert_eq!(
+        fory.deserialize::<String>(&bytes).unwrap(),
+        "x".repeat(1024)
+    );
+
+    // replace the length prefix of an empty string with one declaring 2GB of utf8
+    let mut bytes = fory.serialize(&String::new()).unwrap();
+    assert_eq!(bytes.pop(), Some(0));
+    let mut writer = Writer::default();
+    writer.write_varuint36_small((1u64 << 31) << 2 | 2);
+    bytes.extend_from_slice(&writer.dump());
+    assert!(matches!(
+        fory.deserialize::<String>(&bytes),
+        Err(Error::StringTooLong(len, 1024)) if len == 1 << 31
+    ));
+    assert!(matches!(
+        fory.deserialize::<Vec<String>>(&fory.serialize(&vec!["y".repeat(2048)]).unwrap()),
+        Err(Error::StringTooLong(2048, 1024))
+    ));
+
+    // borrowed strings are held to the same limit
+    let bytes = fory.serialize(&"z".repeat(2048)).unwrap();
+    assert!(matches!(
+        fory.deserialize_borrowed::<std::borrow::Cow<str>>(&bytes),
+        Err(Error::StringTooLong(2048, 1024))
+    ));
+
+    // as are map keys rebuilt from a shared prefix, whose suffix alone is short
+    let keys: std::collections::BTreeMap<String, i32> =
+        (0..2).map(|i| ("k".repeat(1000 + i), 0)).collect();
+    let sharing = Fory::default().with_map_key_prefix_sharing(true);
+    let bytes = sharing.serialize(&keys).unwrap();
+    assert_eq!(
+        Fory::default()
+            .with_string_max_len(1001)
+            .deserialize::<std::collections::BTreeMap<String, i32>>(&bytes)
+            .unwrap(),
+        keys
+    );
+    assert!(matches!(
+        Fory::default()
+            .with_string_max_len(1000)
+            .deserialize::<std::collections::BTreeMap<String, i32>>(&bytes),
+        Err(Error::StringTooLong(1001, 1000))
+    ));
+}
+
+#[test]
+fn test_bytes_written() {
+    let fory = Fory::default();
+    let mut context = WriteContext::new_from_fory(Writer::default(), &fory);
+    assert_eq!(context.bytes_written(), 0);
+    let mut total = 0;
+    for i in 0..5 {
+        let value: Vec<String> = (0..i).map(|j| "x".repeat(j * 100)).collect();
+        let bytes = fory.serialize_with_context(&value, &mut context).unwrap();
+        // the context keeps appending, so the buffer holds every value so far
+        assert_eq!(context.bytes_written(), bytes.len());
+        assert_eq!(
+            context.bytes_written() - total,
+            fory.serialize(&value).unwrap().len()
+        );
+        total = context.bytes_written();
+    }
+    context.reset();
+    assert_eq!(context.bytes_written(), 0);
+}
+
+#[test]
+fn test_read_primitive_array() {
+    let fory = Fory::default();
+    let ints = [1i32, -2, i32::MAX, i32::MIN];
+    // Start at an odd offset so the source is not aligned for i32.
+    let mut bytes = vec![0xffu8];
+    bytes.extend(ints.iter().flat_map(|v| v.to_le_bytes()));
+    bytes.extend(3.5f64.to_le_bytes());
+
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes), &fory);
+    context.reader.read_u8().unwrap();
+    assert_eq!(context.read_primitive_array::<i32>(4).unwrap(), ints);
+    assert_eq!(context.read_primitive_array::<f64>(1).unwrap(), [3.5]);
+    assert_eq!(context.reader.slice_after_cursor().len(), 0);
+
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes[..8]), &fory);
+    let result = context.read_primitive_array::<i32>(3);
+    assert!(matches!(result, Err(Error::UnexpectedEof(_))));
+    let result = context.read_primitive_array::<i64>(usize::MAX);
+    assert!(matches!(result, Err(Error::UnexpectedEof(_))));
+}
+
+#[test]
+fn test_read_context_set_position() {
+    let fory = Fory::default();
+    let bytes = fory
+        .serialize(&vec!["first".to_string(), "second".to_string()])
+        .unwrap();
+
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes), &fory);
+    let start = context.position();
+    let first: Vec<String> = fory.deserialize_with_context(&mut context).unwrap();
+    assert_eq!(context.position(), bytes.len());
+
+    context.set_position(start).unwrap();
+    let again: Vec<String> = fory.deserialize_with_context(&mut context).unwrap();
+    assert_eq!(again, first);
+    assert_eq!(context.position(), bytes.len());
+
+    assert!(matches!(
+        context.set_position(bytes.len() + 1),
+        Err(Error::BufferOutOfBound(_, _, _))
+    ));
+    assert_eq!(context.position(), bytes.len());
+    context.set_position(bytes.len()).unwrap();
+}
+
+#[test]
+fn test_exact_presize() {
+    use std::collections::HashMap;
+
+    let growing = Fory::default();
+    let presized = Fory::default().with_exact_presize(true);
+    let map: HashMap<String, Vec<i32>> = (0..100)
+        .map(|i| (format!("key-{}", i), (0..i).collect()))
+        .collect();
+    let expected = growing.serialize(&map).unwrap();
+    assert_eq!(presized.serialized_size(&map).unwrap(), expected.len());
+    for _ in 0..2 {
+        let bytes = presized.serialize(&map).unwrap();
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes.capacity(), bytes.len());
+    }
+    let small = presized.serialize(&7i8).unwrap();
+    assert_eq!(small.capacity(), small.len());
+    let value: HashMap<String, Vec<i32>> = presized.deserialize(&expected).unwrap();
+    assert_eq!(value, map);
+}
+
+#[test]
+fn test_serialize_to_custom_writer() {
+    use fory_core::buffer::ByteWriter;
+
+    #[derive(Default)]
+    struct CountingWriter {
+        count: usize,
+    }
+
+    impl ByteWriter for CountingWriter {
+        fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+            self.count += bytes.len();
+            Ok(())
+        }
+
+        fn position(&self) -> usize {
+            self.count
+        }
+    }
+
+    let fory = Fory::default();
+    let value: Vec<String> = (0..100).map(|i| format!("item-{i}")).collect();
+    let mut counter = CountingWriter::default();
+    let len = fory.serialize_to(&value, &mut counter).unwrap();
+    assert_eq!(len, fory.serialize(&value).unwrap().len());
+    assert_eq!(counter.position(), len);
+
+    // the default varint encoding matches the one of `Writer`
+    let mut out = Vec::new();
+    let mut writer = Writer::default();
+    for v in [0, 127, 128, 16_383, 16_384, u32::MAX] {
+        ByteWriter::write_varuint32(&mut out, v).unwrap();
+        writer.write_varuint32(v);
+    }
+    assert_eq!(out, writer.dump());
+
+    // a `Vec<u8>` sink appends after what it already holds
+    let mut out = vec![0xff];
+    fory.serialize_to(&42i32, &mut out).unwrap();
+    assert_eq!(out[0], 0xff);
+    assert_eq!(fory.deserialize::<i32>(&out[1..]).unwrap(), 42);
+}
+
+#[test]
+fn test_deserialize_from_custom_reader() {
+    use fory_core::buffer::ByteReader;
+    use std::collections::HashMap;
+
+    /// Yields at most three bytes per read, like a slow network stream.
+    struct ChunkedReader {
+        data: Vec<u8>,
+        position: usize,
+    }
+
+    impl ByteReader for ChunkedReader {
+        fn read_bytes(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+            let len = buf.len().min(3).min(self.remaining());
+            buf[..len].copy_from_slice(&self.data[self.position..self.position + len]);
+            self.position += len;
+            Ok(len)
+        }
+
+        fn remaining(&self) -> usize {
+            self.data.len() - self.position
+        }
+
+        fn position(&self) -> usize {
+            self.position
+        }
+
+        fn set_position(&mut self, position: usize) -> Result<(), Error> {
+            self.position = position;
+            Ok(())
+        }
+    }
+
+    let fory = Fory::default();
+    let value: HashMap<String, Vec<i64>> =
+        HashMap::from([("a".to_string(), vec![1, 2, 3]), ("b".to_string(), vec![])]);
+    let mut reader = ChunkedReader {
+        data: fory.serialize(&value).unwrap(),
+        position: 0,
+    };
+    let obj: HashMap<String, Vec<i64>> = fory.deserialize_from(&mut reader).unwrap();
+    assert_eq!(obj, value);
+    assert_eq!(reader.remaining(), 0);
+
+    // values read one at a time leave the ones after them in the source
+    let large: Vec<i64> = (0..1000).collect();
+    let mut data = fory.serialize(&large).unwrap();
+    data.extend(fory.serialize(&"next".to_string()).unwrap());
+    let mut reader = ChunkedReader { data, position: 0 };
+    let obj: Vec<i64> = fory.deserialize_from(&mut reader).unwrap();
+    assert_eq!(obj, large);
+    let obj: String = fory.deserialize_from(&mut reader).unwrap();
+    assert_eq!(obj, "next");
+    assert_eq!(reader.remaining(), 0);
+    assert!(matches!(
+        fory.deserialize_from::<String, _>(&mut reader),
+        Err(Error::UnexpectedEof(_))
+    ));
+
+    let mut data = fory.serialize(&large).unwrap();
+    data.truncate(data.len() - 1);
+    let mut reader = ChunkedReader { data, position: 0 };
+    assert!(matches!(
+        fory.deserialize_from::<Vec<i64>, _>(&mut reader),
+        Err(Error::UnexpectedEof(_))
+    ));
+
+    // the default varint decoding matches the one of `Reader`
+    let mut writer = Writer::default();
+    writer.write_varuint32(300);
+    let mut reader = ChunkedReader {
+        data: writer.dump(),
+        position: 0,
+    };
+    assert_eq!(ByteReader::read_varuint32(&mut reader).unwrap(), 300);
+    assert!(ByteReader::read_u8(&mut reader).is_err());
+
+    let bytes = fory.serialize(&"slice".to_string()).unwrap();
+    let obj: String = fory.deserialize_from(&mut Reader::new(&bytes)).unwrap();
+    assert_eq!(obj, "slice");
+}
@@ -0,0 +1,162 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::serializer::cipher::Cipher;
+use fory_derive::ForyObject;
+use std::collections::HashMap;
+
+/// Not a real cipher: XORs with a key byte and appends a check byte, so the ciphertext is
+/// longer than the plaintext and a wrong key is detected.
+struct XorCipher(u8);
+
+impl Cipher for XorCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut ciphertext: Vec<u8> = plaintext.iter().map(|b| b ^ self.0).collect();
+        ciphertext.push(plaintext.iter().fold(self.0, |sum, b| sum.wrapping_add(*b)));
+        Ok(ciphertext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+        let (check, body) = ciphertext
+            .split_last()
+            .ok_or_else(|| Error::Cipher("empty ciphertext".into()))?;
+        let plaintext: Vec<u8> = body.iter().map(|b| b ^ self.0).collect();
+        if plaintext.iter().fold(self.0, |sum, b| sum.wrapping_add(*b)) != *check {
+            return Err(Error::Cipher("check byte mismatch".into()));
+        }
+        Ok(plaintext)
+    }
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Account {
+    name: String,
+    #[fory(encrypt)]
+    iban: String,
+    #[fory(encrypt)]
+    pin: i32,
+    #[fory(encrypt)]
+    notes: HashMap<String, String>,
+}
+
+// a later version of `Account`, read field by field in compatible mode
+#[derive(ForyObject, Debug, PartialEq)]
+struct AccountV2 {
+    name: String,
+    #[fory(encrypt)]
+    iban: String,
+    #[fory(encrypt)]
+    pin: i32,
+    #[fory(encrypt)]
+    notes: HashMap<String, String>,
+    opened: i64,
+}
+
+fn account() -> Account {
+    Account {
+        name: "ada".to_string(),
+        iban: "DE89370400440532013000".to_string(),
+        pin: 4711,
+        notes: HashMap::from([("tier".to_string(), "gold".to_string())]),
+    }
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[test]
+fn test_encrypted_field_round_trip() {
+    for compatible in [false, true] {
+        let mut fory = Fory::default()
+            .compatible(compatible)
+            .with_cipher(Box::new(XorCipher(0x5a)));
+        fory.register::<Account>(100).unwrap();
+        let bin = fory.serialize(&account()).unwrap();
+        assert!(contains(&bin, b"ada"));
+        assert!(!contains(&bin, b"DE89370400440532013000"));
+        assert!(!contains(&bin, b"gold"));
+        assert_eq!(fory.deserialize::<Account>(&bin).unwrap(), account());
+    }
+
+    let mut fory = Fory::default()
+        .compatible(true)
+        .with_cipher(Box::new(XorCipher(0x5a)));
+    fory.register::<Account>(100).unwrap();
+    let bin = fory.serialize(&account()).unwrap();
+    let mut v2_fory = Fory::default()
+        .compatible(true)
+        .with_cipher(Box::new(XorCipher(0x5a)));
+    v2_fory.register::<AccountV2>(100).unwrap();
+    let read = v2_fory.deserialize::<AccountV2>(&bin).unwrap();
+    assert_eq!(read.iban, account().iban);
+    assert_eq!(read.pin, 4711);
+    assert_eq!(read.notes, account().notes);
+    assert_eq!(read.opened, 0);
+}
+
+#[test]
+fn test_encrypted_field_skipped() {
+    // a peer that only knows the public fields, and has no cipher
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct PublicAccount {
+        name: String,
+    }
+
+    let mut fory = Fory::default()
+        .compatible(true)
+        .with_cipher(Box::new(XorCipher(0x5a)));
+    fory.register::<Account>(100).unwrap();
+    let bin = fory.serialize(&account()).unwrap();
+    let mut public_fory = Fory::default().compatible(true);
+    public_fory.register::<PublicAccount>(100).unwrap();
+    assert_eq!(
+        public_fory.deserialize::<PublicAccount>(&bin).unwrap(),
+        PublicAccount {
+            name: "ada".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_encrypted_field_needs_cipher() {
+    let mut fory = Fory::default().with_cipher(Box::new(XorCipher(0x5a)));
+    fory.register::<Account>(100).unwrap();
+    let bin = fory.serialize(&account()).unwrap();
+
+    let mut plain_fory = Fory::default();
+    plain_fory.register::<Account>(100).unwrap();
+    assert!(matches!(
+        plain_fory.deserialize::<Account>(&bin),
+        Err(Error::Cipher(_))
+    ));
+    assert!(matches!(
+        plain_fory.serialize(&account()),
+        Err(Error::Cipher(_))
+    ));
+
+    let mut wrong_fory = Fory::default().with_cipher(Box::new(XorCipher(0x33)));
+    wrong_fory.register::<Account>(100).unwrap();
+    assert!(matches!(
+        wrong_fory.deserialize::<Account>(&bin),
+        Err(Error::Cipher(_))
+    ));
+}
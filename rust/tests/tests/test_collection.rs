@@ -15,8 +15,12 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use fory_core::{Fory, Serializer};
+use fory_core::error::Error;
+use fory_core::serializer::Clear;
+use fory_core::types::TypeId;
+use fory_core::{Fory, ForyDefault, Serializer, SortedSet};
 use fory_derive::ForyObject;
+use indexmap::IndexSet;
 use std::collections::{BTreeSet, BinaryHeap, HashSet};
 
 #[test]
@@ -119,3 +123,244 @@ fn test_heap_container() {
     assert_eq!(deserialized.binary_heap.len(), 3);
     assert_eq!(deserialized.binary_heap.peek(), Some(&3));
 }
+
+#[test]
+fn test_index_set_keeps_insertion_order() {
+    let fory = Fory::default();
+    let set: IndexSet<String> = ["zeta", "alpha", "mid", "beta"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let bin = fory.serialize(&set).unwrap();
+    let obj: IndexSet<String> = fory.deserialize(&bin).unwrap();
+    assert!(obj.iter().eq(set.iter()));
+
+    let mut target: IndexSet<String> = IndexSet::with_capacity(100);
+    target.insert("stale".to_string());
+    fory.deserialize_into(&bin, &mut target).unwrap();
+    assert!(target.iter().eq(set.iter()));
+    assert!(target.capacity() >= 100);
+}
+
+#[test]
+fn test_index_set_rejects_duplicates() {
+    let fory = Fory::default();
+    let mut bin = fory
+        .serialize(&vec!["a".to_string(), "a".to_string()])
+        .unwrap();
+    // Rewrite the LIST type id after the head and ref flag into SET.
+    assert_eq!(bin[5], TypeId::LIST as u8);
+    bin[5] = TypeId::SET as u8;
+    let result: Result<IndexSet<String>, Error> = fory.deserialize(&bin);
+    assert!(matches!(result, Err(Error::InvalidData(_))));
+}
+
+#[test]
+fn test_sparse_null_encoding() {
+    let dense = Fory::default();
+    let sparse = Fory::default().with_sparse_null_encoding(true);
+    let values: Vec<Option<i64>> = (0..1000i64)
+        .map(|i| {
+            if i % 100 == 0 {
+                None
+            } else {
+                Some(i * 1_000_003)
+            }
+        })
+        .collect();
+    let dense_bin = dense.serialize(&values).unwrap();
+    let sparse_bin = sparse.serialize(&values).unwrap();
+    // one ref flag byte per element is replaced by one bit
+    assert!(sparse_bin.len() < dense_bin.len() - 800);
+    let obj: Vec<Option<i64>> = dense.deserialize(&sparse_bin).unwrap();
+    assert_eq!(obj, values);
+
+    // nulls take less than a byte each, and are still reserved for up front
+    let nulls: Vec<Option<i64>> = vec![None; 1001];
+    let mut obj: Vec<Option<i64>> = Vec::new();
+    sparse
+        .deserialize_into(&sparse.serialize(&nulls).unwrap(), &mut obj)
+        .unwrap();
+    assert_eq!(obj, nulls);
+    assert_eq!(obj.capacity(), nulls.len());
+
+    let strings = vec![None, Some("a".to_string()), None, Some(String::new())];
+    let bin = sparse.serialize(&strings).unwrap();
+    let obj: Vec<Option<String>> = sparse.deserialize(&bin).unwrap();
+    assert_eq!(obj, strings);
+    let mut target: Vec<Option<String>> = Vec::with_capacity(16);
+    sparse.deserialize_into(&bin, &mut target).unwrap();
+    assert_eq!(target, strings);
+
+    let nones: HashSet<Option<i32>> = [None, Some(1)].into_iter().collect();
+    let bin = sparse.serialize(&nones).unwrap();
+    let obj: HashSet<Option<i32>> = sparse.deserialize(&bin).unwrap();
+    assert_eq!(obj, nones);
+}
+
+#[test]
+fn test_write_collection_streaming() {
+    use fory_core::buffer::Writer;
+    use fory_core::resolver::context::WriteContext;
+    use fory_core::serializer::collection::{
+        write_collection_streaming, write_collection_type_info,
+    };
+    use fory_core::types::RefFlag;
+    use std::collections::VecDeque;
+
+    fn serialize_stream<T: Serializer + ForyDefault>(
+        fory: &Fory,
+        iter: impl Iterator<Item = T>,
+    ) -> Vec<u8> {
+        let mut context = WriteContext::new_from_fory(Writer::default(), fory);
+        fory.write_head::<VecDeque<T>>(false, &mut context.writer);
+        context.writer.write_i8(RefFlag::NotNullValue as i8);
+        write_collection_type_info(&mut context, false, TypeId::LIST as u32).unwrap();
+        write_collection_streaming(iter, &mut context, false).unwrap();
+        context.writer.dump()
+    }
+
+    let fory = Fory::default();
+    let bin = serialize_stream(&fory, (0..1_000_000i64).map(|i| i * 3));
+    let obj: VecDeque<i64> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj.len(), 1_000_000);
+    assert!(obj.iter().enumerate().all(|(i, v)| *v == i as i64 * 3));
+
+    let bin = serialize_stream(&fory, [Some(1), None, Some(3)].into_iter());
+    let obj: VecDeque<Option<i32>> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, [Some(1), None, Some(3)]);
+
+    let bin = serialize_stream(&fory, std::iter::empty::<String>());
+    let obj: VecDeque<String> = fory.deserialize(&bin).unwrap();
+    assert!(obj.is_empty());
+}
+
+fn refill<C: Extend<char> + Clear>(target: &mut C, text: &str) {
+    target.clear();
+    target.extend(text.chars());
+}
+
+#[test]
+fn test_clear_string_keeps_capacity() {
+    let fory = Fory::default();
+    let bin = fory.serialize(&"refilled".to_string()).unwrap();
+    let text: String = fory.deserialize(&bin).unwrap();
+
+    let mut target = String::with_capacity(64);
+    target.push_str("previous contents");
+    refill(&mut target, &text);
+    assert_eq!(target, "refilled");
+    assert_eq!(target.capacity(), 64);
+
+    let mut chars: Vec<char> = Vec::with_capacity(64);
+    refill(&mut chars, &text);
+    assert_eq!(chars.iter().collect::<String>(), text);
+    assert_eq!(chars.capacity(), 64);
+}
+
+#[test]
+fn test_read_collection_follows_same_type_flag() {
+    use std::any::Any;
+
+    let fory = Fory::default();
+    // every element carries its own type info, since the writer's element type is polymorphic
+    let polymorphic: Vec<Box<dyn Any>> =
+        vec![Box::new("alpha".to_string()), Box::new("beta".to_string())];
+    let bin = fory.serialize(&polymorphic).unwrap();
+    let strings: Vec<String> = fory.deserialize(&bin).unwrap();
+    assert_eq!(strings, ["alpha", "beta"]);
+    let mut target = vec!["stale".to_string()];
+    fory.deserialize_into(&bin, &mut target).unwrap();
+    assert_eq!(target, ["alpha", "beta"]);
+
+    // the element type info is written once after the header
+    let bin = fory.serialize(&strings).unwrap();
+    let strings: Vec<String> = fory.deserialize(&bin).unwrap();
+    assert_eq!(strings, ["alpha", "beta"]);
+    let err = fory.deserialize::<Vec<Box<dyn Any>>>(&bin).unwrap_err();
+    assert!(matches!(err, Error::TypeError(_)));
+}
+
+#[test]
+fn test_resume_after_more_data() {
+    let fory = Fory::default();
+    let strings: Vec<String> = (0..20).map(|i| format!("item-{i}")).collect();
+    let bin = fory.serialize(&strings).unwrap();
+
+    // every cut, including one inside a varint length, asks for more data
+    for cut in 0..bin.len() {
+        let err = fory
+            .deserialize_resumable::<Vec<String>>(&bin[..cut])
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::NeedMoreData(n) if n == cut),
+            "cut {cut}: {err}"
+        );
+    }
+
+    // the caller keeps the consumed prefix and retries once the next chunk arrives
+    let (first, second) = bin.split_at(bin.len() / 2);
+    let mut received = first.to_vec();
+    assert!(matches!(
+        fory.deserialize_resumable::<Vec<String>>(&received),
+        Err(Error::NeedMoreData(_))
+    ));
+    received.extend_from_slice(second);
+    let (resumed, consumed) = fory
+        .deserialize_resumable::<Vec<String>>(&received)
+        .unwrap();
+    assert_eq!(resumed, strings);
+    assert_eq!(consumed, bin.len());
+
+    // bytes of the next value are left for the following call
+    let next = fory.serialize(&vec!["next".to_string()]).unwrap();
+    received.extend_from_slice(&next);
+    let (resumed, consumed) = fory
+        .deserialize_resumable::<Vec<String>>(&received)
+        .unwrap();
+    assert_eq!(resumed, strings);
+    assert_eq!(consumed, bin.len());
+    let (rest, rest_consumed) = fory
+        .deserialize_resumable::<Vec<String>>(&received[consumed..])
+        .unwrap();
+    assert_eq!(rest, ["next"]);
+    assert_eq!(rest_consumed, next.len());
+}
+
+#[test]
+fn test_sorted_set_delta_encoding() {
+    let fory = Fory::default();
+    let clustered: Vec<i64> = (1_000_000..1_001_000).chain(5_000_000..5_000_500).collect();
+    let hash_set: HashSet<i64> = clustered.iter().copied().collect();
+    let sorted_set: SortedSet<i64> = clustered.iter().copied().collect();
+
+    let hash_bytes = fory.serialize(&hash_set).unwrap();
+    let sorted_bytes = fory.serialize(&sorted_set).unwrap();
+    // one byte per gap, against a varint of about three bytes per element
+    assert!(sorted_bytes.len() * 2 < hash_bytes.len());
+
+    let read = fory.deserialize_sorted_set::<i64>(&sorted_bytes).unwrap();
+    assert_eq!(read, sorted_set);
+    assert_eq!(read.len(), 1500);
+    assert!(read.contains(&1_000_500));
+    assert!(read.contains(&5_000_499));
+    assert!(!read.contains(&1_001_000));
+    assert!(!read.contains(&-1));
+}
+
+#[test]
+fn test_sorted_set_rejects_unsorted_data() {
+    let fory = Fory::default();
+    let set: SortedSet<i8> = [-3, 1, 2].into_iter().collect();
+    let mut bytes = fory.serialize(&set).unwrap();
+    assert_eq!(
+        fory.deserialize_sorted_set::<i8>(&bytes)
+            .unwrap()
+            .as_slice(),
+        &[-3, 1, 2]
+    );
+    // the last gap of 1 becomes 0, a duplicate element
+    *bytes.last_mut().unwrap() = 0;
+    let err = fory.deserialize_sorted_set::<i8>(&bytes).unwrap_err();
+    assert!(matches!(err, Error::InvalidData(_)));
+}
@@ -0,0 +1,54 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use compact_str::CompactString;
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Tag {
+    id: i32,
+    label: CompactString,
+}
+
+#[test]
+fn test_compact_str_cross_read_string() {
+    let fory = Fory::default();
+    for s in [
+        "",
+        "short",
+        "a string long enough to be stored on the heap",
+        "日本語",
+    ] {
+        let bin = fory.serialize(&s.to_string()).unwrap();
+        let compact: CompactString = fory.deserialize(&bin).unwrap();
+        assert_eq!(compact, s);
+        assert_eq!(fory.serialize(&compact).unwrap(), bin);
+        assert_eq!(fory.deserialize::<String>(&bin).unwrap(), s);
+    }
+
+    for compatible in [false, true] {
+        let mut fory = Fory::default().compatible(compatible);
+        fory.register::<Tag>(100).unwrap();
+        let tag = Tag {
+            id: 1,
+            label: CompactString::from("urgent"),
+        };
+        let bin = fory.serialize(&tag).unwrap();
+        assert_eq!(fory.deserialize::<Tag>(&bin).unwrap(), tag);
+    }
+}
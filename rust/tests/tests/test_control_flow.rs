@@ -0,0 +1,66 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use std::ops::ControlFlow;
+
+#[test]
+fn test_control_flow_continue() {
+    let fory = Fory::default();
+    let value: ControlFlow<String, i32> = ControlFlow::Continue(5);
+    let bin = fory.serialize(&value).unwrap();
+    let obj: ControlFlow<String, i32> = fory.deserialize(&bin).expect("deserialize");
+    assert_eq!(value, obj);
+}
+
+#[test]
+fn test_control_flow_break() {
+    let fory = Fory::default();
+    let value: ControlFlow<String, i32> = ControlFlow::Break("done".to_string());
+    let bin = fory.serialize(&value).unwrap();
+    let obj: ControlFlow<String, i32> = fory.deserialize(&bin).expect("deserialize");
+    assert_eq!(value, obj);
+}
+
+#[test]
+fn test_control_flow_in_vec() {
+    let fory = Fory::default();
+    let value: Vec<ControlFlow<String, i32>> = vec![
+        ControlFlow::Continue(1),
+        ControlFlow::Break("stop".to_string()),
+        ControlFlow::Continue(-3),
+    ];
+    let bin = fory.serialize(&value).unwrap();
+    let obj: Vec<ControlFlow<String, i32>> = fory.deserialize(&bin).expect("deserialize");
+    assert_eq!(value, obj);
+}
+
+#[test]
+fn test_control_flow_unknown_tag() {
+    let fory = Fory::default();
+    let value: ControlFlow<i32, i32> = ControlFlow::Break(7);
+    let mut bin = fory.serialize(&value).unwrap();
+    // the tag is followed by the payload ref flag, type id and one byte varint
+    let tag_offset = bin.len() - 4;
+    assert_eq!(bin[tag_offset], 1);
+    bin[tag_offset] = 2;
+    assert!(matches!(
+        fory.deserialize::<ControlFlow<i32, i32>>(&bin),
+        Err(Error::InvalidData(_))
+    ));
+}
@@ -614,6 +614,7 @@ struct MyExt {
     id: i32,
 }
 impl Serializer for MyExt {
+    fory_core::read_null_as_default!();
     fn fory_write_data(
         &self,
         context: &mut WriteContext,
@@ -0,0 +1,76 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::serializer::duration::DurationSecs;
+use fory_derive::ForyObject;
+use std::time::Duration;
+
+fn diff(a: Duration, b: Duration) -> Duration {
+    a.max(b) - a.min(b)
+}
+
+#[test]
+fn test_duration_secs_round_trip() {
+    let fory = Fory::default();
+    let value = DurationSecs(Duration::from_millis(1500));
+    let bytes = fory.serialize(&value).unwrap();
+    assert_eq!(fory.deserialize::<f64>(&bytes).unwrap(), 1.5);
+    let obj: DurationSecs = fory.deserialize(&bytes).unwrap();
+    assert!(diff(obj.0, value.0) < Duration::from_nanos(1));
+
+    // beyond 2^53 ns the f64 rounds away the last nanoseconds
+    let long = Duration::new(200 * 24 * 3600, 123_456_789);
+    let obj: DurationSecs = fory
+        .deserialize(&fory.serialize(&DurationSecs(long)).unwrap())
+        .unwrap();
+    assert!(diff(obj.0, long) < Duration::from_micros(10));
+}
+
+#[test]
+fn test_duration_secs_rejects_invalid_seconds() {
+    let fory = Fory::default();
+    for secs in [-1.0, f64::NAN, f64::INFINITY, 1e30] {
+        let bytes = fory.serialize(&secs).unwrap();
+        assert!(matches!(
+            fory.deserialize::<DurationSecs>(&bytes),
+            Err(Error::InvalidData(_))
+        ));
+    }
+}
+
+#[test]
+fn test_duration_secs_field() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Timeout {
+        name: String,
+        after: DurationSecs,
+        retry: Option<DurationSecs>,
+    }
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Timeout>(100).unwrap();
+    let timeout = Timeout {
+        name: "connect".to_string(),
+        after: Duration::from_millis(250).into(),
+        retry: Some(Duration::from_secs(3).into()),
+    };
+    let obj: Timeout = fory
+        .deserialize(&fory.serialize(&timeout).unwrap())
+        .unwrap();
+    assert_eq!(obj, timeout);
+}
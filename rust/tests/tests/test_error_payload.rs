@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::fory::Fory;
+use fory_core::serializer::error_payload::SerializableError;
+use fory_derive::ForyObject;
+use std::fmt;
+
+#[derive(Debug)]
+struct Timeout;
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after 30s")
+    }
+}
+
+impl std::error::Error for Timeout {}
+
+#[derive(Debug)]
+struct QueryFailed(Timeout);
+
+impl fmt::Display for QueryFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "query failed")
+    }
+}
+
+impl std::error::Error for QueryFailed {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Shard {
+    id: i32,
+    region: String,
+}
+
+#[test]
+fn test_serializable_error_round_trip() {
+    let mut fory = Fory::default();
+    fory.register::<Shard>(100).unwrap();
+    let error = SerializableError::new(503, "database unavailable")
+        .with_detail("table", "users".to_string())
+        .with_detail("shard", 3i32)
+        .with_detail("shard", 7i32)
+        .with_detail(
+            "owner",
+            Shard {
+                id: 7,
+                region: "eu".to_string(),
+            },
+        );
+    let bytes = fory.serialize(&error).unwrap();
+    let read: SerializableError = fory.deserialize(&bytes).unwrap();
+    assert_eq!(read.code, 503);
+    assert_eq!(read.message, "database unavailable");
+    let keys: Vec<&str> = read
+        .details
+        .iter()
+        .flatten()
+        .map(|(key, _)| key.as_str())
+        .collect();
+    assert_eq!(keys, ["table", "shard", "owner"]);
+    assert_eq!(read.detail::<String>("table").unwrap(), "users");
+    assert_eq!(read.detail::<i32>("shard"), Some(&7));
+    assert_eq!(read.detail::<String>("shard"), None);
+    assert_eq!(
+        read.detail::<Shard>("owner"),
+        Some(&Shard {
+            id: 7,
+            region: "eu".to_string(),
+        })
+    );
+    assert_eq!(read.to_string(), "error 503: database unavailable");
+
+    let errors = vec![SerializableError::new(1, ""), error];
+    let read: Vec<SerializableError> = fory.deserialize(&fory.serialize(&errors).unwrap()).unwrap();
+    assert_eq!(read.len(), 2);
+    assert!(read[0].details.is_none());
+    assert_eq!(read[1].detail::<i32>("shard"), Some(&7));
+}
+
+#[test]
+fn test_serializable_error_from_and_to_std_error() {
+    let fory = Fory::default();
+    let error = SerializableError::from_error(500, &QueryFailed(Timeout));
+    assert_eq!(error.message, "query failed: timed out after 30s");
+    let read: SerializableError = fory.deserialize(&fory.serialize(&error).unwrap()).unwrap();
+    let boxed: Box<dyn std::error::Error> = Box::new(read);
+    assert_eq!(
+        boxed.to_string(),
+        "error 500: query failed: timed out after 30s"
+    );
+    assert_eq!(boxed.downcast_ref::<SerializableError>().unwrap().code, 500);
+}
@@ -0,0 +1,67 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Event {
+    seq: i64,
+    kind: String,
+}
+
+fn event(seq: i64) -> Event {
+    Event {
+        seq,
+        kind: format!("kind-{}", seq % 3),
+    }
+}
+
+#[test]
+fn test_append_and_read_events() {
+    let mut fory = Fory::default();
+    fory.register::<Event>(1).unwrap();
+
+    let mut log = Vec::new();
+    for seq in 0..100 {
+        fory.append_event(&mut log, &event(seq)).unwrap();
+    }
+    let events: Vec<Event> = fory.read_events(&log).collect::<Result<_, _>>().unwrap();
+    assert_eq!(events, (0..100).map(event).collect::<Vec<_>>());
+    assert_eq!(fory.read_events::<Event>(&[]).count(), 0);
+}
+
+#[test]
+fn test_truncated_final_event() {
+    let mut fory = Fory::default();
+    fory.register::<Event>(1).unwrap();
+
+    let mut log = Vec::new();
+    fory.append_event(&mut log, &event(0)).unwrap();
+    fory.append_event(&mut log, &event(1)).unwrap();
+    let last_start = log.len();
+    fory.append_event(&mut log, &event(2)).unwrap();
+    // a crash in the middle of the last payload, and in the middle of its length prefix
+    for end in [log.len() - 1, last_start + 2] {
+        let events: Vec<Result<Event, Error>> = fory.read_events(&log[..end]).collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].as_ref().unwrap(), &event(0));
+        assert_eq!(events[1].as_ref().unwrap(), &event(1));
+        assert!(matches!(events[2], Err(Error::UnexpectedEof(_))));
+    }
+}
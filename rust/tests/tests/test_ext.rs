@@ -60,6 +60,7 @@ fn test_use() {
     }
 
     impl Serializer for Item {
+        fory_core::read_null_as_default!();
         fn fory_write_data(&self, context: &mut WriteContext, is_field: bool) -> Result<(), Error> {
             write_data(&self.f1, context, is_field)
         }
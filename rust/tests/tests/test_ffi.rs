@@ -0,0 +1,64 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+use std::ffi::CString;
+
+#[test]
+fn test_cstring_round_trip() {
+    let fory = Fory::default();
+    let value = CString::new("/usr/lib/libc.so.6").unwrap();
+    let bin = fory.serialize(&value).unwrap();
+    let obj: CString = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, value);
+
+    let empty = CString::default();
+    let bin = fory.serialize(&empty).unwrap();
+    let obj: CString = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, empty);
+}
+
+#[test]
+fn test_cstring_rejects_interior_nul() {
+    let fory = Fory::default();
+    let mut bin = fory.serialize(&CString::new("abc").unwrap()).unwrap();
+    let len = bin.len();
+    bin[len - 2] = 0;
+    let result: Result<CString, Error> = fory.deserialize(&bin);
+    assert!(matches!(result, Err(Error::InteriorNul(1))));
+}
+
+#[test]
+fn test_cstring_field() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Symbol {
+        name: CString,
+        address: i64,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Symbol>(100).unwrap();
+    let value = Symbol {
+        name: CString::new("malloc").unwrap(),
+        address: 0x7fff_0000,
+    };
+    let bin = fory.serialize(&value).unwrap();
+    let obj: Symbol = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, value);
+}
@@ -0,0 +1,89 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::impl_flags_serializer;
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Permissions: u32 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+        const EXECUTE = 1 << 2;
+    }
+}
+
+impl_flags_serializer!(Permissions);
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct PermissionsV2: u32 {
+        const READ = 1;
+        const WRITE = 1 << 1;
+        const EXECUTE = 1 << 2;
+        const ADMIN = 1 << 31;
+    }
+}
+
+impl_flags_serializer!(PermissionsV2);
+
+#[test]
+fn test_flags_round_trip() {
+    let fory = Fory::default();
+    for flags in [
+        Permissions::empty(),
+        Permissions::READ | Permissions::EXECUTE,
+        Permissions::all(),
+    ] {
+        let bytes = fory.serialize(&flags).unwrap();
+        assert_eq!(fory.deserialize::<Permissions>(&bytes).unwrap(), flags);
+    }
+
+    let bytes = fory
+        .serialize(&vec![Permissions::READ, Permissions::WRITE])
+        .unwrap();
+    let decoded: Vec<Permissions> = fory.deserialize(&bytes).unwrap();
+    assert_eq!(decoded, vec![Permissions::READ, Permissions::WRITE]);
+
+    // the bits are an ordinary number to peers
+    let bytes = fory.serialize(&Permissions::all()).unwrap();
+    assert_eq!(fory.deserialize::<i32>(&bytes).unwrap(), 0b111);
+}
+
+#[test]
+fn test_flags_unknown_bits() {
+    let newer = PermissionsV2::READ | PermissionsV2::ADMIN;
+    let bytes = Fory::default().serialize(&newer).unwrap();
+
+    let lenient = Fory::default();
+    assert_eq!(
+        lenient.deserialize::<Permissions>(&bytes).unwrap(),
+        Permissions::READ
+    );
+
+    let strict = Fory::default().with_strict_flags(true);
+    assert!(matches!(
+        strict.deserialize::<Permissions>(&bytes),
+        Err(Error::InvalidData(_))
+    ));
+    let known = Fory::default().serialize(&PermissionsV2::WRITE).unwrap();
+    assert_eq!(
+        strict.deserialize::<Permissions>(&known).unwrap(),
+        Permissions::WRITE
+    );
+}
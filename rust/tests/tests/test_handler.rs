@@ -0,0 +1,93 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::serializer::handler::HandlerRef;
+use fory_derive::ForyObject;
+use std::sync::Arc;
+
+type Handler = dyn Fn(i32) -> i32 + Send + Sync;
+type Op = HandlerRef<Handler>;
+
+fn new_fory() -> Fory {
+    let mut fory = Fory::default();
+    fory.register_handler::<Handler>(1, Arc::new(|x| x + 1))
+        .unwrap();
+    fory.register_handler::<Handler>(2, Arc::new(|x| x * 2))
+        .unwrap();
+    fory
+}
+
+#[test]
+fn test_handler_ref_round_trip() {
+    let fory = new_fory();
+    let double = fory.handler_ref::<Handler>(2).unwrap();
+    let bytes = fory.serialize(&double).unwrap();
+    let decoded: Op = fory.deserialize(&bytes).unwrap();
+    assert_eq!(decoded.id(), 2);
+    assert_eq!((decoded.get().unwrap())(21), 42);
+
+    // another peer resolves the ids to its own handlers
+    let ops = vec![
+        fory.handler_ref::<Handler>(1).unwrap(),
+        fory.handler_ref::<Handler>(2).unwrap(),
+    ];
+    let bytes = fory.serialize(&ops).unwrap();
+    let decoded: Vec<Op> = new_fory().deserialize(&bytes).unwrap();
+    let results: Vec<i32> = decoded.iter().map(|op| (op.get().unwrap())(10)).collect();
+    assert_eq!(results, vec![11, 20]);
+}
+
+#[test]
+fn test_handler_ref_field() {
+    #[derive(ForyObject, Debug)]
+    struct Task {
+        input: i32,
+        op: Op,
+    }
+
+    let mut fory = new_fory();
+    fory.register::<Task>(100).unwrap();
+    let task = Task {
+        input: 5,
+        op: fory.handler_ref::<Handler>(1).unwrap(),
+    };
+    let bytes = fory.serialize(&task).unwrap();
+    let decoded: Task = fory.deserialize(&bytes).unwrap();
+    assert_eq!((decoded.op.get().unwrap())(decoded.input), 6);
+}
+
+#[test]
+fn test_handler_ref_errors() {
+    let mut fory = new_fory();
+    assert!(matches!(
+        fory.register_handler::<Handler>(1, Arc::new(|x| x)),
+        Err(Error::TypeError(_))
+    ));
+    assert!(fory.handler_ref::<Handler>(3).is_err());
+    assert!(fory.handler_ref::<dyn Fn() + Send + Sync>(1).is_err());
+
+    let bytes = fory
+        .serialize(&fory.handler_ref::<Handler>(2).unwrap())
+        .unwrap();
+    let reader = Fory::default();
+    assert!(matches!(
+        reader.deserialize::<Op>(&bytes),
+        Err(Error::TypeError(_))
+    ));
+}
@@ -0,0 +1,94 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::types::FORMAT_VERSION;
+
+#[test]
+fn test_header_round_trip() {
+    let fory = Fory::default().with_header(true);
+    let bytes = fory.serialize(&"hello".to_string()).unwrap();
+    assert_eq!(bytes[0], FORMAT_VERSION);
+    let plain = Fory::default().serialize(&"hello".to_string()).unwrap();
+    assert_eq!(&bytes[1..], &plain[..]);
+    let value: String = fory.deserialize(&bytes).unwrap();
+    assert_eq!(value, "hello");
+}
+
+#[test]
+fn test_header_rejects_other_version() {
+    let v1 = Fory::default().with_header(true).with_format_version(1);
+    let v2 = Fory::default().with_header(true).with_format_version(2);
+    let bytes = v1.serialize(&42i32).unwrap();
+    let result: Result<i32, Error> = v2.deserialize(&bytes);
+    assert!(matches!(result, Err(Error::UnsupportedVersion(1, 2))));
+    let value: i32 = v1.deserialize(&bytes).unwrap();
+    assert_eq!(value, 42);
+}
+
+mod schema_v1 {
+    #[derive(fory_derive::ForyObject, Debug, PartialEq)]
+    pub struct Reading {
+        pub sensor: String,
+        pub value: i32,
+    }
+}
+
+mod schema_v2 {
+    #[derive(fory_derive::ForyObject, Debug, PartialEq)]
+    pub struct Reading {
+        pub sensor: String,
+        pub value: i64,
+    }
+}
+
+#[test]
+fn test_schema_hash_rejects_changed_field_type() {
+    let mut v1 = Fory::default().with_schema_hash(true);
+    v1.register::<schema_v1::Reading>(100).unwrap();
+    let mut v2 = Fory::default().with_schema_hash(true);
+    v2.register::<schema_v2::Reading>(100).unwrap();
+
+    let reading = schema_v1::Reading {
+        sensor: "t1".to_string(),
+        value: 21,
+    };
+    let bytes = v1.serialize(&reading).unwrap();
+    assert_eq!(
+        v1.deserialize::<schema_v1::Reading>(&bytes).unwrap(),
+        reading
+    );
+    let result: Result<schema_v2::Reading, Error> = v2.deserialize(&bytes);
+    assert!(matches!(result, Err(Error::SchemaMismatch(_, _))));
+
+    // the hash sits right after the 4 header bytes
+    let other = v2
+        .serialize(&schema_v2::Reading {
+            sensor: "t1".to_string(),
+            value: 21,
+        })
+        .unwrap();
+    assert_ne!(bytes[4..12], other[4..12]);
+
+    let plain = Fory::default().serialize(&42i32).unwrap();
+    let hashed = Fory::default()
+        .with_schema_hash(true)
+        .serialize(&42i32)
+        .unwrap();
+    assert_eq!(hashed.len(), plain.len() + 8);
+}
@@ -0,0 +1,87 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::Fory;
+use fory_derive::ForyObject;
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Address {
+    city: String,
+    zip: Option<i32>,
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Person {
+    name: String,
+    age: i32,
+    score: f64,
+    avatar: Vec<u8>,
+    tags: Vec<String>,
+    attributes: HashMap<String, i64>,
+    address: Address,
+    previous: Option<Address>,
+}
+
+#[test]
+fn test_to_debug_json() {
+    let mut fory = Fory::default().compatible(true);
+    fory.register_by_name::<Address>("Address").unwrap();
+    fory.register::<Person>(100).unwrap();
+    let person = Person {
+        name: "ada".to_string(),
+        age: 36,
+        score: f64::NAN,
+        avatar: vec![0xde, 0xad, 0xbe, 0xef],
+        tags: vec!["math".to_string(), "engines".to_string()],
+        attributes: HashMap::from([("height".to_string(), 165)]),
+        address: Address {
+            city: "London".to_string(),
+            zip: None,
+        },
+        previous: None,
+    };
+    let value = fory.to_debug_json(&person).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "$type": "100",
+            "name": "ada",
+            "age": 36,
+            "score": "NaN",
+            "avatar": "3q2+7w==",
+            "tags": ["math", "engines"],
+            "attributes": {"height": 165},
+            "address": {"$type": "Address", "city": "London", "zip": null},
+            "previous": null,
+        })
+    );
+}
+
+#[test]
+fn test_to_debug_json_requires_compatible_mode() {
+    let mut fory = Fory::default();
+    fory.register::<Address>(100).unwrap();
+    let address = Address {
+        city: "Paris".to_string(),
+        zip: Some(75001),
+    };
+    let err = fory.to_debug_json(&address).unwrap_err();
+    assert!(matches!(err, Error::InvalidData(_)));
+}
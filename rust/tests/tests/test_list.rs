@@ -16,7 +16,9 @@
 // under the License.
 
 use fory_core::fory::Fory;
+use fory_core::serializer::collection::MAX_DEQUE_CAPACITY_HINT;
 use fory_derive::ForyObject;
+use std::borrow::Cow;
 use std::collections::{LinkedList, VecDeque};
 
 #[test]
@@ -63,6 +65,110 @@ fn test_vecdeque_f64() {
     assert_eq!(deque, obj);
 }
 
+#[test]
+fn test_vecdeque_capacity_hint() {
+    let fory = Fory::default().with_deque_capacity_hint(true);
+    // wrap the ring buffer around so that the front is not at the start of the allocation
+    let mut deque: VecDeque<String> = VecDeque::with_capacity(100);
+    for i in 0..90 {
+        deque.push_back(i.to_string());
+    }
+    deque.drain(..80);
+    for i in 0..20 {
+        deque.push_front(format!("f{i}"));
+    }
+    let capacity = deque.capacity();
+    let bin = fory.serialize(&deque).unwrap();
+
+    let mut target: VecDeque<String> = VecDeque::new();
+    fory.deserialize_into(&bin, &mut target).unwrap();
+    assert!(target.iter().eq(deque.iter()));
+    assert_eq!(target.front().map(String::as_str), Some("f19"));
+    assert_eq!(target.back().map(String::as_str), Some("89"));
+    assert!(target.capacity() >= capacity);
+
+    let obj: VecDeque<String> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, deque);
+    assert!(obj.capacity() >= capacity);
+
+    // the hint is flagged in the header, so a reader without the option, or reading
+    // another list type, skips past it
+    let obj: VecDeque<String> = Fory::default().deserialize(&bin).unwrap();
+    assert_eq!(obj, deque);
+    let obj: Vec<String> = Fory::default().deserialize(&bin).unwrap();
+    assert!(obj.iter().eq(deque.iter()));
+    assert!(obj.capacity() >= capacity);
+
+    // a forged hint reserves no more than the cap
+    let mut huge = VecDeque::<i32>::with_capacity(1 << 24);
+    huge.push_back(1);
+    let forged = fory.serialize(&huge).unwrap();
+    let obj: VecDeque<i32> = fory.deserialize(&forged).unwrap();
+    assert_eq!(obj, huge);
+    assert!(obj.capacity() < 1 << 24);
+    assert!(obj.capacity() >= MAX_DEQUE_CAPACITY_HINT);
+}
+
+#[test]
+fn test_vecdeque_capacity_hint_compatible() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Ring {
+        id: i32,
+        items: VecDeque<i32>,
+        names: VecDeque<String>,
+        tail: String,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct RingAsVec {
+        id: i32,
+        items: Vec<i32>,
+        names: Vec<String>,
+        tail: String,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct RingSkipped {
+        id: i32,
+        tail: String,
+    }
+    let mut fory = Fory::default()
+        .compatible(true)
+        .with_deque_capacity_hint(true);
+    fory.register::<Ring>(100).unwrap();
+    let mut items = VecDeque::with_capacity(32);
+    items.extend([1, 2, 3]);
+    let mut names = VecDeque::with_capacity(32);
+    names.extend(["a".to_string(), "b".to_string()]);
+    let value = Ring {
+        id: 7,
+        items,
+        names,
+        tail: "end".to_string(),
+    };
+    let bin = fory.serialize(&value).unwrap();
+    let obj: Ring = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, value);
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<RingAsVec>(100).unwrap();
+    let obj: RingAsVec = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj.id, 7);
+    // a `Vec<i32>` is a primitive array rather than a list, so that field is skipped
+    assert!(obj.items.is_empty());
+    assert_eq!(obj.names, vec!["a".to_string(), "b".to_string()]);
+    assert_eq!(obj.tail, "end");
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<RingSkipped>(100).unwrap();
+    let obj: RingSkipped = fory.deserialize(&bin).unwrap();
+    assert_eq!(
+        obj,
+        RingSkipped {
+            id: 7,
+            tail: "end".to_string(),
+        }
+    );
+}
+
 #[test]
 fn test_linkedlist_i32() {
     let fory = Fory::default();
@@ -138,3 +244,402 @@ fn test_struct_with_collections() {
     let obj: CollectionStruct = fory.deserialize(&bin).expect("deserialize");
     assert_eq!(data, obj);
 }
+
+#[test]
+fn test_cow_slice_round_trip() {
+    let fory = Fory::default();
+    let borrowed: Cow<'static, [i32]> = Cow::Borrowed(&[1, 2, 3]);
+    let bin = fory.serialize(&borrowed).unwrap();
+    assert_eq!(bin, fory.serialize(&vec![1, 2, 3]).unwrap());
+    let obj: Cow<'static, [i32]> = fory.deserialize(&bin).unwrap();
+    assert!(matches!(obj, Cow::Owned(_)));
+    assert_eq!(obj, borrowed);
+
+    let strings: Cow<'static, [String]> = Cow::Owned(vec!["a".to_string(), "b".to_string()]);
+    let bin = fory.serialize(&strings).unwrap();
+    let obj: Cow<'static, [String]> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, strings);
+}
+
+#[test]
+fn test_cow_slice_deserialize_into_keeps_capacity() {
+    let fory = Fory::default();
+    let bin = fory.serialize(&vec![7, 8, 9]).unwrap();
+
+    let mut target: Cow<'static, [i32]> = Cow::Owned(Vec::with_capacity(1000));
+    fory.deserialize_into(&bin, &mut target).unwrap();
+    assert_eq!(&*target, &[7, 8, 9]);
+    match &target {
+        Cow::Owned(vec) => assert_eq!(vec.capacity(), 1000),
+        Cow::Borrowed(_) => panic!("expected an owned value"),
+    }
+
+    let mut target: Cow<'static, [i32]> = Cow::Borrowed(&[1]);
+    fory.deserialize_into(&bin, &mut target).unwrap();
+    assert!(matches!(target, Cow::Owned(_)));
+    assert_eq!(&*target, &[7, 8, 9]);
+
+    let bin = fory.serialize(&vec!["x".to_string()]).unwrap();
+    let mut target: Cow<'static, [String]> = Cow::Owned(Vec::with_capacity(64));
+    fory.deserialize_into(&bin, &mut target).unwrap();
+    assert_eq!(&*target, &["x".to_string()]);
+    match &target {
+        Cow::Owned(vec) => assert_eq!(vec.capacity(), 64),
+        Cow::Borrowed(_) => panic!("expected an owned value"),
+    }
+}
+
+/// Replaces the length of the one-element list in `one` with `len`, finding it where the
+/// serialized `one` and `two`, the same list with a second element, first differ.
+fn forge_list_len(one: &[u8], two: &[u8], len: u32) -> Vec<u8> {
+    let at = one.iter().zip(two).position(|(a, b)| a != b).unwrap();
+    let mut forged = one[..at].to_vec();
+    let mut rest = len;
+    while rest >= 0x80 {
+        forged.push((rest as u8) | 0x80);
+        rest >>= 7;
+    }
+    forged.push(rest as u8);
+    forged.extend_from_slice(&one[at + 1..]);
+    forged
+}
+
+#[test]
+fn test_vec_deserialize_into_forged_len() {
+    let fory = Fory::default();
+    let one = fory.serialize(&vec!["x".to_string()]).unwrap();
+    let two = fory
+        .serialize(&vec!["x".to_string(), "x".to_string()])
+        .unwrap();
+    let forged = forge_list_len(&one, &two, u32::MAX >> 1);
+    let mut target: Vec<String> = Vec::new();
+    assert!(fory.deserialize_into(&forged, &mut target).is_err());
+    assert!(target.capacity() < 1024);
+}
+
+#[test]
+fn test_vec_forged_len() {
+    let fory = Fory::default();
+    let one = fory.serialize(&vec![Some(1i64)]).unwrap();
+    let two = fory.serialize(&vec![Some(1i64), Some(1)]).unwrap();
+    let forged = forge_list_len(&one, &two, u32::MAX >> 1);
+    assert!(fory.deserialize::<Vec<Option<i64>>>(&forged).is_err());
+}
+
+#[test]
+fn test_vec_deque_forged_len() {
+    let fory = Fory::default();
+    let one = fory.serialize(&VecDeque::from(["x".to_string()])).unwrap();
+    let two = fory
+        .serialize(&VecDeque::from(["x".to_string(), "x".to_string()]))
+        .unwrap();
+    let forged = forge_list_len(&one, &two, u32::MAX >> 1);
+    assert!(fory.deserialize::<VecDeque<String>>(&forged).is_err());
+    let mut ring: VecDeque<String> = VecDeque::new();
+    assert!(fory.deserialize_into(&forged, &mut ring).is_err());
+    assert!(ring.capacity() < 1024);
+}
+
+#[test]
+fn test_vec_exact_capacity() {
+    let fory = Fory::default();
+
+    let strings: Vec<String> = (0..1000).map(|i| i.to_string()).collect();
+    let obj: Vec<String> = fory
+        .deserialize(&fory.serialize(&strings).unwrap())
+        .unwrap();
+    assert_eq!(obj, strings);
+    assert_eq!(obj.capacity(), 1000);
+
+    let options: Vec<Option<i64>> = (0..1000).map(|i| (i % 3 != 0).then_some(i)).collect();
+    let obj: Vec<Option<i64>> = fory
+        .deserialize(&fory.serialize(&options).unwrap())
+        .unwrap();
+    assert_eq!(obj, options);
+    assert_eq!(obj.capacity(), 1000);
+
+    let ints: Vec<i32> = (0..1000).collect();
+    let obj: Vec<i32> = fory.deserialize(&fory.serialize(&ints).unwrap()).unwrap();
+    assert_eq!(obj.capacity(), 1000);
+
+    let single = vec!["only".to_string()];
+    let obj: Vec<String> = fory.deserialize(&fory.serialize(&single).unwrap()).unwrap();
+    assert_eq!(obj.capacity(), 1);
+}
+
+#[test]
+fn test_vec_deque_deserialize_into_keeps_capacity() {
+    let fory = Fory::default();
+    let mut ring: VecDeque<String> = VecDeque::with_capacity(16);
+    let capacity = ring.capacity();
+
+    let first: VecDeque<String> = (0..10).map(|i| i.to_string()).collect();
+    fory.deserialize_into(&fory.serialize(&first).unwrap(), &mut ring)
+        .unwrap();
+    assert_eq!(ring, first);
+    assert_eq!(ring.capacity(), capacity);
+
+    let second: VecDeque<String> = (0..capacity).map(|i| format!("v{i}")).collect();
+    fory.deserialize_into(&fory.serialize(&second).unwrap(), &mut ring)
+        .unwrap();
+    assert_eq!(ring, second);
+    assert_eq!(ring.capacity(), capacity);
+
+    let fresh: VecDeque<String> = fory.deserialize(&fory.serialize(&first).unwrap()).unwrap();
+    assert_eq!(fresh, first);
+}
+
+#[test]
+fn test_dictionary_strings() {
+    let column: Vec<String> = (0..1000).map(|i| format!("status-{}", i % 7)).collect();
+    let plain = Fory::default();
+    let dictionary = Fory::default().with_dictionary_strings(true);
+    let plain_bytes = plain.serialize(&column).unwrap();
+    let bytes = dictionary.serialize(&column).unwrap();
+    assert!(bytes.len() * 4 < plain_bytes.len());
+
+    // readers decode either layout regardless of their own setting
+    for fory in [&plain, &dictionary] {
+        assert_eq!(fory.deserialize::<Vec<String>>(&bytes).unwrap(), column);
+        assert_eq!(
+            fory.deserialize::<Vec<String>>(&plain_bytes).unwrap(),
+            column
+        );
+    }
+    let mut target: Vec<String> = Vec::with_capacity(2000);
+    plain.deserialize_into(&bytes, &mut target).unwrap();
+    assert_eq!(target, column);
+    assert_eq!(target.capacity(), 2000);
+
+    let empty: Vec<String> = Vec::new();
+    let bytes = dictionary.serialize(&empty).unwrap();
+    assert_eq!(plain.deserialize::<Vec<String>>(&bytes).unwrap(), empty);
+
+    // a forged length fails on the missing indices rather than on the allocation
+    let one = dictionary.serialize(&vec!["x".to_string()]).unwrap();
+    let two = dictionary
+        .serialize(&vec!["x".to_string(), "x".to_string()])
+        .unwrap();
+    let forged = forge_list_len(&one, &two, u32::MAX >> 1);
+    assert!(plain.deserialize::<Vec<String>>(&forged).is_err());
+    assert!(plain.deserialize::<LinkedList<String>>(&forged).is_err());
+}
+
+#[test]
+fn test_dictionary_strings_field_skip() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Row {
+        id: i32,
+        tags: Vec<String>,
+        name: String,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct RowV2 {
+        id: i32,
+        name: String,
+    }
+    let mut writer = Fory::default()
+        .compatible(true)
+        .with_dictionary_strings(true);
+    writer.register::<Row>(100).unwrap();
+    let mut reader = Fory::default().compatible(true);
+    reader.register::<RowV2>(100).unwrap();
+
+    let row = Row {
+        id: 1,
+        tags: vec!["a".to_string(), "b".to_string(), "a".to_string()],
+        name: "row".to_string(),
+    };
+    let bytes = writer.serialize(&row).unwrap();
+    assert_eq!(writer.deserialize::<Row>(&bytes).unwrap(), row);
+    assert_eq!(
+        reader.deserialize::<RowV2>(&bytes).unwrap(),
+        RowV2 {
+            id: 1,
+            name: "row".to_string(),
+        }
+    );
+}
+
+/// A port that is never zero, so it deliberately has no `ForyDefault`.
+#[derive(Debug, PartialEq)]
+struct Port(std::num::NonZeroU16);
+
+impl fory_core::Serializer for Port {
+    fn fory_read_null() -> Result<Self, fory_core::error::Error> {
+        Err(fory_core::error::Error::InvalidData("null port".into()))
+    }
+
+    fn fory_write_data(
+        &self,
+        context: &mut fory_core::WriteContext,
+        is_field: bool,
+    ) -> Result<(), fory_core::error::Error> {
+        self.0.get().fory_write_data(context, is_field)
+    }
+
+    fn fory_read_data(
+        context: &mut fory_core::ReadContext,
+        is_field: bool,
+    ) -> Result<Self, fory_core::error::Error> {
+        let port = u16::fory_read_data(context, is_field)?;
+        std::num::NonZeroU16::new(port)
+            .map(Port)
+            .ok_or_else(|| fory_core::error::Error::InvalidData("port 0".into()))
+    }
+
+    fn fory_get_type_id(_: &fory_core::TypeResolver) -> Result<u32, fory_core::error::Error> {
+        Ok(fory_core::TypeId::INT16 as u32)
+    }
+
+    fn fory_type_id_dyn(
+        &self,
+        _: &fory_core::TypeResolver,
+    ) -> Result<u32, fory_core::error::Error> {
+        Ok(fory_core::TypeId::INT16 as u32)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn fory_write_type_info(
+        context: &mut fory_core::WriteContext,
+        is_field: bool,
+    ) -> Result<(), fory_core::error::Error> {
+        fory_core::serializer::write_type_info::<Self>(context, is_field)
+    }
+
+    fn fory_read_type_info(
+        context: &mut fory_core::ReadContext,
+        is_field: bool,
+    ) -> Result<(), fory_core::error::Error> {
+        fory_core::serializer::read_type_info::<Self>(context, is_field)
+    }
+}
+
+#[test]
+fn test_vec_of_type_without_default() {
+    let fory = Fory::default();
+    let ports: Vec<Port> = [80, 443, 8080]
+        .into_iter()
+        .map(|p| Port(std::num::NonZeroU16::new(p).unwrap()))
+        .collect();
+    let bin = fory.serialize(&ports).unwrap();
+    let obj: Vec<Port> = fory.deserialize(&bin).expect("deserialize");
+    assert_eq!(ports, obj);
+
+    // without a default to fall back on, a null element is an error
+    let bin = fory.serialize(&vec![Some(80u16), None]).unwrap();
+    assert!(fory.deserialize::<Vec<Port>>(&bin).is_err());
+    let obj: Vec<Option<u16>> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, vec![Some(80), None]);
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Samples {
+    tag: String,
+    values: Vec<f64>,
+    counts: Vec<i16>,
+}
+
+fn payload_offset(bytes: &[u8], payload: &[u8]) -> usize {
+    bytes
+        .windows(payload.len())
+        .position(|window| window == payload)
+        .expect("payload is written verbatim")
+}
+
+#[test]
+fn test_array_alignment() {
+    let values: Vec<f64> = vec![1.5, -2.25, 1e300];
+    let payload: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+    for tag in ["", "a", "abc", "abcdefg"] {
+        let mut fory = Fory::default().with_array_alignment(8);
+        fory.register::<Samples>(100).unwrap();
+        let samples = Samples {
+            tag: tag.to_string(),
+            values: values.clone(),
+            counts: vec![3, 4],
+        };
+        let bytes = fory.serialize(&samples).unwrap();
+        assert_eq!(payload_offset(&bytes, &payload) % 8, 0);
+        assert_eq!(fory.deserialize::<Samples>(&bytes).unwrap(), samples);
+
+        let mut out = tag.as_bytes().to_vec();
+        let start = out.len();
+        fory.serialize_slice_to(&values, &mut out).unwrap();
+        assert_eq!(payload_offset(&out[start..], &payload) % 8, 0);
+        assert_eq!(fory.deserialize::<Vec<f64>>(&out[start..]).unwrap(), values);
+    }
+
+    let fory = Fory::default().with_array_alignment(64);
+    let bytes = fory.serialize(&values).unwrap();
+    assert_eq!(payload_offset(&bytes, &payload) % 64, 0);
+    assert_eq!(fory.deserialize::<Vec<f64>>(&bytes).unwrap(), values);
+    assert_eq!(
+        fory.deserialize::<Vec<f64>>(&fory.serialize(&Vec::<f64>::new()).unwrap())
+            .unwrap(),
+        Vec::<f64>::new()
+    );
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct TaggedCounts {
+    tag: String,
+    counts: Vec<i16>,
+}
+
+#[test]
+fn test_array_alignment_skips_unknown_arrays() {
+    let mut writer = Fory::default().compatible(true).with_array_alignment(16);
+    writer.register::<Samples>(100).unwrap();
+    let mut reader = Fory::default().compatible(true).with_array_alignment(16);
+    reader.register::<TaggedCounts>(100).unwrap();
+    let samples = Samples {
+        tag: "t".to_string(),
+        values: vec![0.5; 7],
+        counts: vec![1, 2, 3],
+    };
+    let bytes = writer.serialize(&samples).unwrap();
+    assert_eq!(
+        reader.deserialize::<TaggedCounts>(&bytes).unwrap(),
+        TaggedCounts {
+            tag: "t".to_string(),
+            counts: vec![1, 2, 3],
+        }
+    );
+}
+
+#[test]
+fn test_array_alignment_mismatch() {
+    let values = vec![1.5f64, 2.5];
+    let aligned = Fory::default().with_array_alignment(8);
+    let unaligned = Fory::default();
+
+    // the header marks the padding, so a reader skips it whatever its own alignment
+    let bytes = aligned.serialize(&values).unwrap();
+    assert_eq!(unaligned.deserialize::<Vec<f64>>(&bytes).unwrap(), values);
+    let bytes = unaligned.serialize(&values).unwrap();
+    assert_eq!(aligned.deserialize::<Vec<f64>>(&bytes).unwrap(), values);
+
+    let mut aligned = Fory::default().with_array_alignment(8);
+    aligned.register::<Samples>(100).unwrap();
+    let mut unaligned = Fory::default();
+    unaligned.register::<Samples>(100).unwrap();
+    let samples = Samples {
+        tag: "abc".to_string(),
+        values,
+        counts: vec![3, 4],
+    };
+    let bytes = aligned.serialize(&samples).unwrap();
+    assert_eq!(unaligned.deserialize::<Samples>(&bytes).unwrap(), samples);
+    let bytes = unaligned.serialize(&samples).unwrap();
+    assert_eq!(aligned.deserialize::<Samples>(&bytes).unwrap(), samples);
+}
+
+#[test]
+#[should_panic(expected = "power of two")]
+fn test_array_alignment_rejects_non_power_of_two() {
+    let _ = Fory::default().with_array_alignment(12);
+}
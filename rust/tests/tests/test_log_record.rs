@@ -0,0 +1,97 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::serializer::log_record::{LogLevel, LogRecord};
+use fory_derive::ForyObject;
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Peer {
+    host: String,
+    port: i32,
+}
+
+#[test]
+fn test_log_record_round_trip() {
+    let mut fory = Fory::default();
+    fory.register::<Peer>(100).unwrap();
+    let record = LogRecord::new(LogLevel::Error, "net::client", "request failed")
+        .with_field("attempt", 3i32)
+        .with_field("latency", 1.25f64)
+        .with_field("retrying", true)
+        .with_field("user", "ada".to_string())
+        .with_field(
+            "peer",
+            Peer {
+                host: "example.org".to_string(),
+                port: 443,
+            },
+        );
+    let bytes = fory.serialize(&record).unwrap();
+    let read: LogRecord = fory.deserialize(&bytes).unwrap();
+    assert_eq!(read.level, LogLevel::Error);
+    assert_eq!(read.target, "net::client");
+    assert_eq!(read.message, "request failed");
+    let keys: Vec<&str> = read.fields.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["attempt", "latency", "retrying", "user", "peer"]);
+    assert_eq!(read.field::<i32>("attempt"), Some(&3));
+    assert_eq!(read.field::<f64>("latency"), Some(&1.25));
+    assert_eq!(read.field::<bool>("retrying"), Some(&true));
+    assert_eq!(
+        read.field::<String>("user").map(String::as_str),
+        Some("ada")
+    );
+    assert_eq!(
+        read.field::<Peer>("peer"),
+        Some(&Peer {
+            host: "example.org".to_string(),
+            port: 443,
+        })
+    );
+    // a field holding another type is not returned
+    assert_eq!(read.field::<i64>("attempt"), None);
+
+    let records = vec![
+        LogRecord::new(LogLevel::Info, "app", "started"),
+        LogRecord::new(LogLevel::Trace, "app", "tick").with_field("n", 1i64),
+    ];
+    let read: Vec<LogRecord> = fory
+        .deserialize(&fory.serialize(&records).unwrap())
+        .unwrap();
+    assert_eq!(read.len(), 2);
+    assert_eq!(read[1].level, LogLevel::Trace);
+    assert_eq!(read[1].field::<i64>("n"), Some(&1));
+}
+
+#[test]
+fn test_log_record_unknown_level() {
+    let fory = Fory::default();
+    let mut bytes = fory
+        .serialize(&LogRecord::new(LogLevel::Debug, "app", ""))
+        .unwrap();
+    let trace = fory
+        .serialize(&LogRecord::new(LogLevel::Trace, "app", ""))
+        .unwrap();
+    // the level is the only byte that differs
+    let level = (0..bytes.len()).find(|i| bytes[*i] != trace[*i]).unwrap();
+    bytes[level] = 9;
+    assert!(matches!(
+        fory.deserialize::<LogRecord>(&bytes),
+        Err(Error::InvalidData(_))
+    ));
+}
@@ -16,8 +16,12 @@
 // under the License.
 
 use fory_core::fory::Fory;
+use fory_core::serializer::write_data_to_vec;
+use fory_core::types::{RefFlag, TypeId};
+use fory_core::{WriteContext, Writer};
 use fory_derive::ForyObject;
 use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
 
 #[test]
 fn test_hashmap_string() {
@@ -67,3 +71,458 @@ fn test_struct_with_maps() {
     let obj: MapContainer = fory.deserialize(&bin).expect("deserialize");
     assert_eq!(container, obj);
 }
+
+#[derive(ForyObject, PartialEq, Eq, Hash, Debug)]
+struct MyStructKey {
+    id: i32,
+    name: String,
+    weight: i64,
+}
+
+#[test]
+fn test_deterministic_struct_keyed_hashmap() {
+    let mut fory = Fory::default().deterministic(true);
+    fory.register::<MyStructKey>(100).unwrap();
+    let keys: Vec<MyStructKey> = (0..64)
+        .map(|i| MyStructKey {
+            id: i % 8,
+            name: format!("key{}", i % 5),
+            weight: -(i as i64),
+        })
+        .collect();
+    let mut forward = HashMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        forward.insert(
+            MyStructKey {
+                id: key.id,
+                name: key.name.clone(),
+                weight: key.weight,
+            },
+            i as i32,
+        );
+    }
+    let mut backward = HashMap::new();
+    for (i, key) in keys.iter().enumerate().rev() {
+        backward.insert(
+            MyStructKey {
+                id: key.id,
+                name: key.name.clone(),
+                weight: key.weight,
+            },
+            i as i32,
+        );
+    }
+    assert_eq!(forward, backward);
+    let forward_bytes = fory.serialize(&forward).unwrap();
+    let backward_bytes = fory.serialize(&backward).unwrap();
+    assert_eq!(forward_bytes, backward_bytes);
+    let obj: HashMap<MyStructKey, i32> = fory.deserialize(&forward_bytes).unwrap();
+    assert_eq!(forward, obj);
+}
+
+#[derive(ForyObject, Debug, PartialEq, Eq, Hash)]
+struct SharedNameKey {
+    name: Rc<String>,
+    id: i32,
+}
+
+#[test]
+fn test_deterministic_shared_ref_keyed_hashmap() {
+    let mut fory = Fory::default().deterministic(true);
+    fory.register::<SharedNameKey>(101).unwrap();
+    let names: Vec<Rc<String>> = (0..4).map(|i| Rc::new(format!("name{}", i))).collect();
+    // keys share their names, so that a name written while comparing keys would be
+    // written as a reference to it afterwards
+    let map: HashMap<SharedNameKey, i32> = (0..32)
+        .map(|i| {
+            let key = SharedNameKey {
+                name: names[i % 4].clone(),
+                id: (i * 7 % 32) as i32,
+            };
+            (key, i as i32)
+        })
+        .collect();
+    let bytes = fory.serialize(&map).unwrap();
+    let obj: HashMap<SharedNameKey, i32> = fory.deserialize(&bytes).unwrap();
+    assert_eq!(map, obj);
+}
+
+#[test]
+fn test_hashmap_deserialize_into_keeps_capacity() {
+    let fory = Fory::default();
+    let small: HashMap<String, i32> = (0..4).map(|i| (format!("k{}", i), i)).collect();
+    let large: HashMap<String, i32> = (0..500).map(|i| (format!("k{}", i), i)).collect();
+    let small_bytes = fory.serialize(&small).unwrap();
+    let large_bytes = fory.serialize(&large).unwrap();
+
+    let mut target: HashMap<String, i32> = HashMap::with_capacity(1000);
+    target.insert("stale".to_string(), -1);
+    let old_capacity = target.capacity();
+    fory.deserialize_into(&small_bytes, &mut target).unwrap();
+    assert_eq!(target, small);
+    assert!(target.capacity() >= old_capacity.max(small.len()));
+
+    let mut target: HashMap<String, i32> = HashMap::with_capacity(8);
+    let old_capacity = target.capacity();
+    fory.deserialize_into(&large_bytes, &mut target).unwrap();
+    assert_eq!(target, large);
+    assert!(target.capacity() >= old_capacity.max(large.len()));
+
+    let old_capacity = target.capacity();
+    fory.deserialize_into(&small_bytes, &mut target).unwrap();
+    assert_eq!(target, small);
+    assert!(target.capacity() >= old_capacity.max(small.len()));
+}
+
+#[test]
+fn test_deserialize_into_forged_input() {
+    let fory = Fory::default();
+    // replace the length of an empty map with one declaring 2^31 entries
+    let mut bytes = fory.serialize(&HashMap::<String, i32>::new()).unwrap();
+    assert_eq!(bytes.pop(), Some(0));
+    let mut writer = Writer::default();
+    writer.write_varuint32(1 << 31);
+    bytes.extend_from_slice(&writer.dump());
+    let mut target: HashMap<String, i32> = HashMap::new();
+    assert!(fory.deserialize_into(&bytes, &mut target).is_err());
+    assert!(target.capacity() < 1024);
+
+    // and the ref flag that follows the header with one that does not exist
+    let mut bytes = fory.serialize(&vec![1i32, 2, 3]).unwrap();
+    assert_eq!(
+        bytes[4],
+        fory_core::types::RefFlag::NotNullValue as i8 as u8
+    );
+    bytes[4] = 7;
+    let mut target: Vec<i32> = Vec::new();
+    assert!(matches!(
+        fory.deserialize_into(&bytes, &mut target),
+        Err(fory_core::error::Error::InvalidRef(_))
+    ));
+}
+
+#[test]
+fn test_btreemap_key_prefix_sharing() {
+    let mut map: BTreeMap<String, i32> = (0..300)
+        .map(|i| (format!("/srv/data/warehouse/partitions/region-{:04}", i), i))
+        .collect();
+    // keys that only differ inside a multi-byte char must not split it
+    map.insert("/srv/data/\u{e9}t\u{e9}".to_string(), -1);
+    map.insert("/srv/data/\u{e8}t\u{e9}".to_string(), -2);
+    map.insert(String::new(), -3);
+
+    let plain = Fory::default().serialize(&map).unwrap();
+    let fory = Fory::default().with_map_key_prefix_sharing(true);
+    let shared = fory.serialize(&map).unwrap();
+    assert!(shared.len() * 3 < plain.len());
+
+    let obj: BTreeMap<String, i32> = fory.deserialize(&shared).unwrap();
+    assert_eq!(map, obj);
+    // the layout is self-describing, so any reader decodes it
+    let obj: BTreeMap<String, i32> = Fory::default().deserialize(&shared).unwrap();
+    assert_eq!(map, obj);
+    let obj: HashMap<String, i32> = Fory::default().deserialize(&shared).unwrap();
+    assert_eq!(map.into_iter().collect::<HashMap<_, _>>(), obj);
+}
+
+#[test]
+fn test_skip_prefix_shared_btreemap_field() {
+    #[derive(ForyObject, Debug)]
+    struct Index {
+        entries: BTreeMap<String, i32>,
+        version: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Version {
+        version: i32,
+    }
+    let mut writer = Fory::default()
+        .compatible(true)
+        .with_map_key_prefix_sharing(true);
+    writer.register::<Index>(200).unwrap();
+    let mut reader = Fory::default().compatible(true);
+    reader.register::<Version>(200).unwrap();
+
+    let index = Index {
+        entries: (0..10)
+            .map(|i| (format!("com.example.key{}", i), i))
+            .collect(),
+        version: 7,
+    };
+    let bytes = writer.serialize(&index).unwrap();
+    let obj: Version = reader.deserialize(&bytes).unwrap();
+    assert_eq!(obj.version, 7);
+}
+
+#[test]
+fn test_deterministic_integer_keyed_hashmap() {
+    let fory = Fory::default().deterministic(true);
+    let keys: Vec<u64> = (0..200).map(|i| i * 7919 % 1000 + (i << 20)).collect();
+    let forward: HashMap<u64, String> = keys.iter().map(|k| (*k, k.to_string())).collect();
+    let mut backward = HashMap::with_capacity(1024);
+    for k in keys.iter().rev() {
+        backward.insert(*k, k.to_string());
+    }
+    assert_eq!(forward, backward);
+    let forward_bytes = fory.serialize(&forward).unwrap();
+    assert_eq!(forward_bytes, fory.serialize(&backward).unwrap());
+    let obj: HashMap<u64, String> = fory.deserialize(&forward_bytes).unwrap();
+    assert_eq!(forward, obj);
+}
+
+#[test]
+fn test_deterministic_tuple_keyed_hashmap() {
+    let fory = Fory::default().deterministic(true);
+    // Keys sharing their first element are only ordered by the second one.
+    let keys: Vec<(u32, u32)> = (0..100).map(|i| (i % 3, 1000 - i)).collect();
+    let forward: HashMap<(u32, u32), i32> = keys.iter().map(|k| (*k, k.0 as i32)).collect();
+    let mut backward = HashMap::with_capacity(512);
+    for k in keys.iter().rev() {
+        backward.insert(*k, k.0 as i32);
+    }
+    assert_eq!(forward, backward);
+    let forward_bytes = fory.serialize(&forward).unwrap();
+    assert_eq!(forward_bytes, fory.serialize(&backward).unwrap());
+    let obj: HashMap<(u32, u32), i32> = fory.deserialize(&forward_bytes).unwrap();
+    assert_eq!(forward, obj);
+}
+
+#[test]
+fn test_tuple_keys_written_contiguously() {
+    let fory = Fory::default();
+    let mut context = WriteContext::new_from_fory(Writer::default(), &fory);
+    // a `LIST` of two elements of the same declared type, written one after the other, with
+    // the header bits `IS_SAME_TYPE | DECL_ELEMENT_TYPE`
+    let mut expected = vec![2, 0b1100];
+    for value in [7u32, 9] {
+        expected.extend(write_data_to_vec(&value, &mut context).unwrap());
+    }
+    assert_eq!(
+        write_data_to_vec(&(7u32, 9u32), &mut context).unwrap(),
+        expected
+    );
+    // and of two elements that are not, each with its ref flag and type info
+    let mut expected = vec![2, 0];
+    expected.extend([RefFlag::NotNullValue as u8, TypeId::INT32 as u8]);
+    expected.extend(write_data_to_vec(&7u32, &mut context).unwrap());
+    expected.extend([RefFlag::NotNullValue as u8, TypeId::INT64 as u8]);
+    expected.extend(write_data_to_vec(&9u64, &mut context).unwrap());
+    assert_eq!(
+        write_data_to_vec(&(7u32, 9u64), &mut context).unwrap(),
+        expected
+    );
+    // so that a tuple reads as a list of its elements
+    let bytes = fory.serialize(&("a".to_string(), "b".to_string())).unwrap();
+    assert_eq!(fory.deserialize::<Vec<String>>(&bytes).unwrap(), ["a", "b"]);
+
+    let fory = Fory::default().deterministic(true);
+    let entries: Vec<((String, u32), Vec<i32>)> = (0..50)
+        .map(|i| ((format!("k{}", i % 4), i), vec![i as i32]))
+        .collect();
+    let map: HashMap<_, _> = entries.iter().cloned().collect();
+    let reversed: HashMap<_, _> = entries.iter().rev().cloned().collect();
+    let bytes = fory.serialize(&map).unwrap();
+    assert_eq!(bytes, fory.serialize(&reversed).unwrap());
+    let obj: HashMap<(String, u32), Vec<i32>> = fory.deserialize(&bytes).unwrap();
+    assert_eq!(obj, map);
+
+    let grid: HashMap<(u32, u32), String> = (0..4).map(|i| ((i, i * 2), i.to_string())).collect();
+    let bytes = fory.serialize(&grid).unwrap();
+    let obj: HashMap<(u32, u32), String> = fory.deserialize(&bytes).unwrap();
+    assert_eq!(obj, grid);
+    let pairs = [(Some(1), None), (None, Some(2))];
+    let bytes = fory.serialize(&pairs.to_vec()).unwrap();
+    let obj: Vec<(Option<i32>, Option<i32>)> = fory.deserialize(&bytes).unwrap();
+    assert_eq!(obj, pairs);
+}
+
+#[test]
+fn test_tuple_fields_compatible() {
+    #[derive(ForyObject, Debug, PartialEq, Clone)]
+    struct Stop {
+        name: String,
+    }
+    #[derive(ForyObject, Debug, PartialEq, Clone, Default)]
+    enum Mode {
+        #[default]
+        Bus,
+        Rail,
+    }
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Route {
+        span: (u32, String),
+        first: (Stop, Mode, Option<i64>),
+        weights: HashMap<(String, u32), i32>,
+        hops: Vec<(i32, Option<String>)>,
+        cells: HashMap<(u32, u32), String>,
+        version: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Version {
+        version: i32,
+    }
+    let mut writer = Fory::default().compatible(true);
+    writer.register::<Stop>(201).unwrap();
+    writer.register::<Mode>(202).unwrap();
+    writer.register::<Route>(200).unwrap();
+    let mut reader = Fory::default().compatible(true);
+    reader.register::<Version>(200).unwrap();
+
+    let route = Route {
+        span: (3, "east".to_string()),
+        first: (
+            Stop {
+                name: "depot".to_string(),
+            },
+            Mode::Rail,
+            None,
+        ),
+        weights: HashMap::from([(("a".to_string(), 1), 10), (("b".to_string(), 2), 20)]),
+        hops: vec![(1, Some("x".to_string())), (2, None)],
+        cells: HashMap::from([((0, 1), "a".to_string()), ((2, 3), "b".to_string())]),
+        version: 7,
+    };
+    let bytes = writer.serialize(&route).unwrap();
+    let obj: Route = writer.deserialize(&bytes).unwrap();
+    assert_eq!(obj, route);
+    let obj: Version = reader.deserialize(&bytes).unwrap();
+    assert_eq!(obj.version, 7);
+}
+
+#[test]
+fn test_ordered_hash_map() {
+    use fory_core::OrderedHashMap;
+
+    let mut map = OrderedHashMap::new();
+    for key in ["zeta", "alpha", "mu", "beta", "omega", "gamma"] {
+        map.insert(key.to_string(), key.len() as i32);
+    }
+    map.insert("mu".to_string(), 20);
+    map.remove(&"beta".to_string());
+    let expected = ["zeta", "alpha", "mu", "omega", "gamma"];
+
+    for deterministic in [false, true] {
+        let fory = Fory::default().deterministic(deterministic);
+        let bin = fory.serialize(&map).unwrap();
+        let first: OrderedHashMap<String, i32> = fory.deserialize(&bin).unwrap();
+        let second: OrderedHashMap<String, i32> = fory.deserialize(&bin).unwrap();
+        assert_eq!(first, map);
+        assert_eq!(first.keys().collect::<Vec<_>>(), expected);
+        assert_eq!(second.keys().collect::<Vec<_>>(), expected);
+        assert_eq!(first.get(&"mu".to_string()), Some(&20));
+        // written as a plain map whose entries are in insertion order
+        let plain: HashMap<String, i32> = fory.deserialize(&bin).unwrap();
+        assert_eq!(&plain, map.as_map());
+    }
+}
+
+#[test]
+fn test_skip_ordered_hash_map_field() {
+    use fory_core::OrderedHashMap;
+
+    #[derive(ForyObject, Debug)]
+    struct Index {
+        entries: OrderedHashMap<String, i32>,
+        version: i32,
+    }
+    #[derive(ForyObject, Debug)]
+    struct Version {
+        version: i32,
+    }
+    let mut writer = Fory::default().compatible(true);
+    writer.register::<Index>(200).unwrap();
+    let mut reader = Fory::default().compatible(true);
+    reader.register::<Version>(200).unwrap();
+
+    let index = Index {
+        entries: (0..10).map(|i| (format!("key{}", i), i)).collect(),
+        version: 7,
+    };
+    let bytes = writer.serialize(&index).unwrap();
+    let obj: Version = reader.deserialize(&bytes).unwrap();
+    assert_eq!(obj.version, 7);
+}
+
+#[test]
+fn test_btreemap_is_canonical() {
+    let fory = Fory::default();
+    let keys: Vec<i32> = (0..200).map(|i| (i * 7919) % 1000).collect();
+    let forward: BTreeMap<i32, String> = keys.iter().map(|k| (*k, k.to_string())).collect();
+    let backward: BTreeMap<i32, String> = keys.iter().rev().map(|k| (*k, k.to_string())).collect();
+    assert_eq!(
+        fory.serialize(&forward).unwrap(),
+        fory.serialize(&backward).unwrap()
+    );
+
+    // the same holds for maps nested in structs, without the deterministic mode
+    let mut fory = Fory::default();
+    fory.register::<MapContainer>(100).unwrap();
+    let container = |order: &[&str]| MapContainer {
+        hash_map: HashMap::new(),
+        btree_map: order
+            .iter()
+            .map(|k| (k.to_string(), k.len() as i32))
+            .collect(),
+    };
+    assert_eq!(
+        fory.serialize(&container(&["a", "bb", "ccc"])).unwrap(),
+        fory.serialize(&container(&["ccc", "a", "bb"])).unwrap()
+    );
+}
+
+#[test]
+fn test_hashmap_merge_into_keeps_other_keys() {
+    let fory = Fory::default();
+    let update: HashMap<String, i32> = HashMap::from([("d".to_string(), 4), ("e".to_string(), 5)]);
+    let bytes = fory.serialize(&update).unwrap();
+    let mut target: HashMap<String, i32> = HashMap::from([
+        ("a".to_string(), 1),
+        ("b".to_string(), 2),
+        ("c".to_string(), 3),
+    ]);
+    fory.merge_map_into(&bytes, &mut target).unwrap();
+    let expected: HashMap<String, i32> = ["a", "b", "c", "d", "e"]
+        .iter()
+        .zip(1..)
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+    assert_eq!(target, expected);
+
+    let overwrite: HashMap<String, i32> = HashMap::from([("a".to_string(), 10)]);
+    fory.merge_map_into(&fory.serialize(&overwrite).unwrap(), &mut target)
+        .unwrap();
+    assert_eq!(target.len(), 5);
+    assert_eq!(target["a"], 10);
+    assert_eq!(target["e"], 5);
+}
+
+#[test]
+fn test_hashmap_deserialize_as_sorted_vec() {
+    let fory = Fory::default();
+    let map: HashMap<String, i32> = (0..100).map(|i| (format!("key{i}"), i)).collect();
+    let bytes = fory.serialize(&map).unwrap();
+    let entries: Vec<(String, i32)> = fory.deserialize_as_sorted_vec(&bytes).unwrap();
+    assert_eq!(entries.len(), map.len());
+    assert!(entries.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert!(entries.iter().all(|(key, value)| map[key] == *value));
+    let index = entries
+        .binary_search_by(|(key, _)| key.as_str().cmp("key42"))
+        .unwrap();
+    assert_eq!(entries[index].1, 42);
+
+    let empty: Vec<(i64, String)> = fory
+        .deserialize_as_sorted_vec(&fory.serialize(&HashMap::<i64, String>::new()).unwrap())
+        .unwrap();
+    assert!(empty.is_empty());
+
+    // a forged length fails on the missing entries rather than on the allocation
+    let one = fory.serialize(&HashMap::from([(1i64, 1i64)])).unwrap();
+    let two = fory
+        .serialize(&HashMap::from([(1i64, 1i64), (2, 2)]))
+        .unwrap();
+    let at = one.iter().zip(&two).position(|(a, b)| a != b).unwrap();
+    let mut forged = one[..at].to_vec();
+    forged.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x07]);
+    forged.extend_from_slice(&one[at + 1..]);
+    assert!(fory.deserialize_as_sorted_vec::<i64, i64>(&forged).is_err());
+}
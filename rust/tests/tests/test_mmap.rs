@@ -0,0 +1,125 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::buffer::Reader;
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::mmap::MmapWriter;
+use fory_core::resolver::context::ReadContext;
+use memmap2::{Mmap, MmapMut};
+use std::fs::OpenOptions;
+
+#[test]
+fn test_mmap_growable_round_trip() {
+    let fory = Fory::default();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("index.bin");
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap();
+    let small: Vec<u64> = vec![1, 2, 3];
+    let large: Vec<u64> = (0..10_000).map(|i| i * 1_000_003 + (1 << 63)).collect();
+
+    let mut writer = MmapWriter::growable(file).unwrap();
+    let small_offset = writer.serialize(&fory, &small).unwrap();
+    let large_offset = writer.serialize(&fory, &large).unwrap();
+    assert_eq!(small_offset, 0);
+    // the small value is written in place, the large one after growing the map
+    assert_eq!(
+        &writer.as_slice()[..large_offset],
+        &fory.serialize(&small).unwrap()[..]
+    );
+    assert_eq!(
+        &writer.as_slice()[large_offset..],
+        &fory.serialize(&large).unwrap()[..]
+    );
+    assert!(writer.capacity() > 4096);
+    let len = writer.finish().unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), len as u64);
+
+    let file = OpenOptions::new().read(true).open(&path).unwrap();
+    let mmap = unsafe { Mmap::map(&file).unwrap() };
+    assert_eq!(
+        fory.deserialize::<Vec<u64>>(&mmap[small_offset..large_offset])
+            .unwrap(),
+        small
+    );
+    let mut context = ReadContext::new_from_fory(Reader::new(&mmap[large_offset..]), &fory);
+    assert_eq!(
+        fory.deserialize_with_context::<Vec<u64>>(&mut context)
+            .unwrap(),
+        large
+    );
+    assert_eq!(context.reader.slice_after_cursor().len(), 0);
+}
+
+#[test]
+fn test_mmap_fixed_overflow() {
+    let fory = Fory::default();
+    let values: Vec<u64> = (0..100).collect();
+    let mut writer = MmapWriter::fixed(MmapMut::map_anon(64).unwrap());
+    assert!(matches!(
+        writer.serialize(&fory, &values),
+        Err(Error::BufferOutOfBound(..))
+    ));
+    assert!(writer.is_empty());
+
+    let mut writer = MmapWriter::fixed(MmapMut::map_anon(1024).unwrap());
+    writer.serialize(&fory, &values).unwrap();
+    assert_eq!(
+        fory.deserialize::<Vec<u64>>(writer.as_slice()).unwrap(),
+        values
+    );
+}
+
+#[test]
+fn test_mmap_serialize_slice() {
+    let fory = Fory::default();
+    let ints: Vec<i32> = (0..1000).map(|i| i * 31 - 500).collect();
+    let bytes: Vec<u8> = (0..=255).collect();
+    let mut writer = MmapWriter::fixed(MmapMut::map_anon(8192).unwrap());
+    let ints_offset = writer.serialize_slice(&fory, &ints).unwrap();
+    let bytes_offset = writer.serialize_slice(&fory, &bytes).unwrap();
+    let mmap = writer.as_slice();
+
+    // the same bytes as the staged path
+    assert_eq!(
+        &mmap[ints_offset..bytes_offset],
+        &fory.serialize(&ints).unwrap()[..]
+    );
+    assert_eq!(
+        fory.deserialize::<Vec<i32>>(&mmap[ints_offset..bytes_offset])
+            .unwrap(),
+        ints
+    );
+    // bytes are written as BINARY and read back in place
+    let read: &[u8] = fory.deserialize_borrowed(&mmap[bytes_offset..]).unwrap();
+    assert_eq!(read, &bytes[..]);
+
+    // NaNs to canonicalize take the regular path
+    let fory = Fory::default().with_canonical_nan(true);
+    let floats = [1.0, f64::from_bits(0xfff8_0000_dead_beef)];
+    let mut out = Vec::new();
+    fory.serialize_slice_to(&floats, &mut out).unwrap();
+    assert_eq!(out, fory.serialize(&floats.to_vec()).unwrap());
+    let read: Vec<f64> = fory.deserialize(&out).unwrap();
+    assert_eq!(read[1].to_bits(), f64::NAN.to_bits());
+}
@@ -0,0 +1,177 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+
+#[test]
+fn test_canonical_nan() {
+    let quiet = f64::NAN;
+    let other = f64::from_bits(0xfff8_0000_dead_beef);
+    assert!(other.is_nan());
+    assert_ne!(quiet.to_bits(), other.to_bits());
+
+    let fory = Fory::default();
+    assert_ne!(
+        fory.serialize(&quiet).unwrap(),
+        fory.serialize(&other).unwrap()
+    );
+
+    let fory = Fory::default().with_canonical_nan(true);
+    assert_eq!(
+        fory.serialize(&quiet).unwrap(),
+        fory.serialize(&other).unwrap()
+    );
+    let other32 = f32::from_bits(0xffc0_1234);
+    assert_eq!(
+        fory.serialize(&f32::NAN).unwrap(),
+        fory.serialize(&other32).unwrap()
+    );
+    assert_eq!(
+        fory.serialize(&vec![1.0, quiet]).unwrap(),
+        fory.serialize(&vec![1.0, other]).unwrap()
+    );
+    let read: f64 = fory.deserialize(&fory.serialize(&other).unwrap()).unwrap();
+    assert_eq!(read.to_bits(), f64::NAN.to_bits());
+    // other values are untouched
+    let read: Vec<f32> = fory
+        .deserialize(&fory.serialize(&vec![-0.0f32, 2.5]).unwrap())
+        .unwrap();
+    assert_eq!(read[0].to_bits(), (-0.0f32).to_bits());
+    assert_eq!(read[1], 2.5);
+}
+
+#[test]
+fn test_canonical_nan_field() {
+    #[derive(ForyObject, Debug)]
+    struct Reading {
+        value: f64,
+    }
+
+    let mut fory = Fory::default().with_canonical_nan(true);
+    fory.register::<Reading>(100).unwrap();
+    let a = fory.serialize(&Reading { value: f64::NAN }).unwrap();
+    let b = fory
+        .serialize(&Reading {
+            value: -f64::from_bits(0x7ff0_0000_0000_0001),
+        })
+        .unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_float_specials_round_trip_bitwise() {
+    let fory = Fory::default();
+    let doubles = [
+        0.0,
+        -0.0,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NAN,
+        f64::from_bits(0x7ff0_0000_0000_0001), // signaling NaN
+        f64::from_bits(0xfff8_0000_dead_beef),
+        f64::MIN_POSITIVE,
+        f64::from_bits(1), // smallest subnormal
+        -f64::from_bits(0x000f_ffff_ffff_ffff),
+        f64::MAX,
+        f64::MIN,
+    ];
+    for value in doubles {
+        let read: f64 = fory.deserialize(&fory.serialize(&value).unwrap()).unwrap();
+        assert_eq!(read.to_bits(), value.to_bits());
+    }
+    let read: Vec<f64> = fory
+        .deserialize(&fory.serialize(&doubles.to_vec()).unwrap())
+        .unwrap();
+    assert!(read
+        .iter()
+        .zip(&doubles)
+        .all(|(a, b)| a.to_bits() == b.to_bits()));
+
+    let floats = [
+        -0.0f32,
+        f32::INFINITY,
+        f32::NEG_INFINITY,
+        f32::from_bits(0x7f80_0001),
+        f32::from_bits(1),
+        -f32::from_bits(0x007f_ffff),
+    ];
+    for value in floats {
+        let read: f32 = fory.deserialize(&fory.serialize(&value).unwrap()).unwrap();
+        assert_eq!(read.to_bits(), value.to_bits());
+    }
+}
+
+#[derive(ForyObject, Debug, Clone, Copy)]
+#[fory(transparent)]
+struct FloatKey(f64);
+
+impl PartialEq for FloatKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for FloatKey {}
+
+impl std::hash::Hash for FloatKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state)
+    }
+}
+
+#[test]
+fn test_deterministic_float_keyed_map() {
+    use std::collections::HashMap;
+
+    let fory = Fory::default().deterministic(true);
+    let keys = [
+        f64::NAN,
+        0.0,
+        -0.0,
+        f64::INFINITY,
+        -f64::NAN,
+        f64::NEG_INFINITY,
+        f64::from_bits(1),
+        -1.5,
+        2.5,
+    ];
+    let forward: HashMap<FloatKey, String> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, k)| (FloatKey(*k), format!("entry-{i}")))
+        .collect();
+    let mut backward = HashMap::with_capacity(64);
+    for (i, k) in keys.iter().enumerate().rev() {
+        backward.insert(FloatKey(*k), format!("entry-{i}"));
+    }
+    let bytes = fory.serialize(&forward).unwrap();
+    assert_eq!(bytes, fory.serialize(&backward).unwrap());
+    let read: HashMap<FloatKey, String> = fory.deserialize(&bytes).unwrap();
+    assert_eq!(read, forward);
+
+    // entries follow `total_cmp`: -NaN, -inf, -1.5, -0.0, 0.0, subnormal, 2.5, inf, NaN
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by_key(|i| {
+        let value = format!("entry-{i}");
+        bytes
+            .windows(value.len())
+            .position(|w| w == value.as_bytes())
+            .unwrap()
+    });
+    assert_eq!(order, vec![4, 5, 7, 2, 1, 6, 8, 3, 0]);
+}
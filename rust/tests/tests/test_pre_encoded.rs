@@ -0,0 +1,92 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::PreEncoded;
+use fory_derive::ForyObject;
+use std::collections::HashMap;
+
+#[derive(ForyObject, Debug, PartialEq, Clone)]
+struct Footer {
+    links: Vec<String>,
+    labels: HashMap<String, String>,
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Page {
+    title: String,
+    footer: Footer,
+    views: i64,
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct CachedPage {
+    title: String,
+    footer: PreEncoded,
+    views: i64,
+}
+
+#[test]
+fn test_pre_encoded_field_matches_normal_output() {
+    let mut fory = Fory::default();
+    fory.register::<Footer>(1).unwrap();
+    fory.register::<Page>(2).unwrap();
+    let mut cached_fory = Fory::default();
+    cached_fory.register::<Footer>(1).unwrap();
+    cached_fory.register::<CachedPage>(2).unwrap();
+
+    let footer = Footer {
+        links: vec!["about".to_string(), "contact".to_string()],
+        labels: HashMap::from([("lang".to_string(), "en".to_string())]),
+    };
+    let pre_encoded = cached_fory.pre_encode(&footer).unwrap();
+    for (title, views) in [("home", 1), ("news", 42)] {
+        let page = Page {
+            title: title.to_string(),
+            footer: footer.clone(),
+            views,
+        };
+        let cached = CachedPage {
+            title: title.to_string(),
+            footer: pre_encoded.clone(),
+            views,
+        };
+        let bytes = cached_fory.serialize(&cached).unwrap();
+        assert_eq!(bytes, fory.serialize(&page).unwrap());
+        assert_eq!(fory.deserialize::<Page>(&bytes).unwrap(), page);
+    }
+}
+
+#[test]
+fn test_pre_encoded_is_write_only() {
+    let mut fory = Fory::default();
+    fory.register::<Footer>(1).unwrap();
+    fory.register::<CachedPage>(2).unwrap();
+    let cached = CachedPage {
+        title: "home".to_string(),
+        footer: fory.pre_encode(&"text".to_string()).unwrap(),
+        views: 1,
+    };
+    let bytes = fory.serialize(&cached).unwrap();
+    assert!(matches!(
+        fory.deserialize::<CachedPage>(&bytes),
+        Err(Error::TypeError(_))
+    ));
+    let fory = Fory::default().compatible(true);
+    assert!(matches!(fory.pre_encode(&1i32), Err(Error::InvalidData(_))));
+}
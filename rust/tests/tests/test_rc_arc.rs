@@ -17,7 +17,10 @@
 
 //! Tests for Rc and Arc serialization support in Fory
 
+use fory_core::buffer::{Reader, Writer};
 use fory_core::fory::Fory;
+use fory_core::resolver::context::{ReadContext, WriteContext};
+use fory_derive::ForyObject;
 use std::collections::HashMap;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -364,3 +367,112 @@ fn test_rc_multiple_shared_references() {
     assert!(Rc::ptr_eq(&deserialized[1], &deserialized[3]));
     assert!(!Rc::ptr_eq(&deserialized[0], &deserialized[1]));
 }
+
+#[test]
+fn test_max_ref_count() {
+    use fory_core::error::Error;
+
+    let values: Vec<Rc<String>> = (0..100).map(|i| Rc::new(i.to_string())).collect();
+    let bin = Fory::default().serialize(&values).unwrap();
+
+    let limited = Fory::default().max_ref_count(10);
+    let result: Result<Vec<Rc<String>>, Error> = limited.deserialize(&bin);
+    assert!(matches!(result, Err(Error::TooManyRefs(10))));
+
+    let exact = Fory::default().max_ref_count(100);
+    let obj: Vec<Rc<String>> = exact.deserialize(&bin).unwrap();
+    assert_eq!(obj, values);
+
+    // repeated references to one value take a single entry
+    let shared = Rc::new("shared".to_string());
+    let repeated: Vec<Rc<String>> = vec![shared.clone(); 100];
+    let bin = Fory::default().serialize(&repeated).unwrap();
+    let obj: Vec<Rc<String>> = Fory::default().max_ref_count(1).deserialize(&bin).unwrap();
+    assert!(Rc::ptr_eq(&obj[0], &obj[99]));
+
+    let arcs: Vec<Arc<i32>> = (0..5).map(Arc::new).collect();
+    let bin = Fory::default().serialize(&arcs).unwrap();
+    let result: Result<Vec<Arc<i32>>, Error> = Fory::default().max_ref_count(4).deserialize(&bin);
+    assert!(matches!(result, Err(Error::TooManyRefs(4))));
+}
+
+#[test]
+fn test_arc_str_sharing() {
+    use fory_derive::ForyObject;
+
+    #[derive(ForyObject, Debug)]
+    struct Labels {
+        primary: Arc<str>,
+        fallback: Arc<str>,
+        other: Arc<str>,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Labels>(1).unwrap();
+
+    let shared: Arc<str> = Arc::from("a fairly long label shared by two fields");
+    let labels = Labels {
+        primary: shared.clone(),
+        fallback: shared,
+        other: Arc::from("other"),
+    };
+    let bin = fory.serialize(&labels).unwrap();
+    let labels: Labels = fory.deserialize(&bin).unwrap();
+    assert_eq!(&*labels.primary, "a fairly long label shared by two fields");
+    assert!(Arc::ptr_eq(&labels.primary, &labels.fallback));
+    assert_eq!(&*labels.other, "other");
+    assert!(!Arc::ptr_eq(&labels.primary, &labels.other));
+
+    let text = "a string long enough that writing it twice shows";
+    let shared: Arc<str> = Arc::from(text);
+    let vec = vec![shared.clone(), Arc::from("single"), shared];
+    let bin = fory.serialize(&vec).unwrap();
+    let vec: Vec<Arc<str>> = fory.deserialize(&bin).unwrap();
+    assert_eq!(
+        vec.iter().map(|s| &**s).collect::<Vec<_>>(),
+        [text, "single", text]
+    );
+    assert!(Arc::ptr_eq(&vec[0], &vec[2]));
+    // the string is written once
+    let unshared = vec![text.to_string(), "single".to_string(), text.to_string()];
+    let unshared_len = fory.serialize(&unshared).unwrap().len();
+    assert!(bin.len() < unshared_len - text.len() / 2);
+}
+
+#[test]
+fn test_arc_shared_across_session() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Order {
+        id: i32,
+        customer: Arc<String>,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Order>(100).unwrap();
+    let customer = Arc::new("a customer with a rather long name".to_string());
+    let first = Order {
+        id: 1,
+        customer: customer.clone(),
+    };
+    let second = Order {
+        id: 2,
+        customer: customer.clone(),
+    };
+
+    let mut context = WriteContext::new_from_fory(Writer::default(), &fory);
+    fory.serialize_with_context(&first, &mut context).unwrap();
+    let bytes = fory.serialize_with_context(&second, &mut context).unwrap();
+    let occurrences = bytes
+        .windows(customer.len())
+        .filter(|window| *window == customer.as_bytes())
+        .count();
+    assert_eq!(occurrences, 1);
+    assert!(bytes.len() < 2 * fory.serialize(&first).unwrap().len());
+
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes), &fory);
+    let read_first: Order = fory.deserialize_with_context(&mut context).unwrap();
+    let read_second: Order = fory.deserialize_with_context(&mut context).unwrap();
+    assert_eq!(read_first, first);
+    assert_eq!(read_second, second);
+    assert!(Arc::ptr_eq(&read_first.customer, &read_second.customer));
+}
@@ -513,3 +513,82 @@ fn test_deref_mut_wrapper() {
     cat_arc.set_name("Mittens".to_string());
     assert_eq!(cat_arc.name(), "Mittens");
 }
+
+#[test]
+fn test_shared_trait_object_fields() {
+    #[derive(ForyObject)]
+    struct Household {
+        favorite: Rc<dyn Animal>,
+        oldest: Rc<dyn Animal>,
+        other: Rc<dyn Animal>,
+    }
+    #[derive(ForyObject)]
+    struct SharedHousehold {
+        favorite: Arc<dyn Animal>,
+        oldest: Arc<dyn Animal>,
+        pets: Vec<Arc<dyn Animal>>,
+    }
+
+    let mut fory = fory_compatible();
+    fory.register::<Dog>(200).unwrap();
+    fory.register::<Cat>(201).unwrap();
+    fory.register::<Household>(202).unwrap();
+    fory.register::<SharedHousehold>(203).unwrap();
+
+    let dog: Rc<dyn Animal> = Rc::new(Dog {
+        name: "Rex".to_string(),
+        breed: "Beagle".to_string(),
+    });
+    let household = Household {
+        favorite: dog.clone(),
+        oldest: dog,
+        other: Rc::new(Cat {
+            name: "Tom".to_string(),
+            color: "Grey".to_string(),
+        }),
+    };
+    let obj: Household = fory
+        .deserialize(&fory.serialize(&household).unwrap())
+        .unwrap();
+    assert!(Rc::ptr_eq(&obj.favorite, &obj.oldest));
+    assert!(!Rc::ptr_eq(&obj.favorite, &obj.other));
+    assert_eq!(obj.oldest.name(), "Rex");
+    assert_eq!(obj.other.speak(), "Meow!");
+
+    // the field-by-field compatible read keeps the sharing too
+    #[derive(ForyObject)]
+    struct HouseholdV2 {
+        favorite: Rc<dyn Animal>,
+        oldest: Rc<dyn Animal>,
+        other: Rc<dyn Animal>,
+        address: String,
+    }
+    let mut reader = fory_compatible();
+    reader.register::<Dog>(200).unwrap();
+    reader.register::<Cat>(201).unwrap();
+    reader.register::<HouseholdV2>(202).unwrap();
+    let obj: HouseholdV2 = reader
+        .deserialize(&fory.serialize(&household).unwrap())
+        .unwrap();
+    assert!(Rc::ptr_eq(&obj.favorite, &obj.oldest));
+    assert!(!Rc::ptr_eq(&obj.favorite, &obj.other));
+    assert_eq!(obj.favorite.name(), "Rex");
+    assert_eq!(obj.address, "");
+
+    let cat: Arc<dyn Animal> = Arc::new(Cat {
+        name: "Whiskers".to_string(),
+        color: "Orange".to_string(),
+    });
+    let household = SharedHousehold {
+        favorite: cat.clone(),
+        oldest: cat.clone(),
+        pets: vec![cat.clone(), cat],
+    };
+    let obj: SharedHousehold = fory
+        .deserialize(&fory.serialize(&household).unwrap())
+        .unwrap();
+    assert!(Arc::ptr_eq(&obj.favorite, &obj.oldest));
+    assert!(Arc::ptr_eq(&obj.favorite, &obj.pets[0]));
+    assert!(Arc::ptr_eq(&obj.pets[0], &obj.pets[1]));
+    assert_eq!(obj.favorite.name(), "Whiskers");
+}
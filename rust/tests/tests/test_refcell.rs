@@ -58,3 +58,30 @@ fn test_rc_refcell_in_struct() {
     assert_eq!(deserialized.value, 1);
     assert_eq!(deserialized.child.unwrap().borrow().value, 99);
 }
+
+#[derive(ForyObject, Debug)]
+struct SharedBuffers {
+    front: Rc<RefCell<Vec<i32>>>,
+    back: Rc<RefCell<Vec<i32>>>,
+}
+
+#[test]
+fn test_rc_refcell_shared_fields() {
+    let mut fory = Fory::default();
+    fory.register::<SharedBuffers>(3003).unwrap();
+
+    let shared = Rc::new(RefCell::new(vec![1, 2, 3]));
+    let buffers = SharedBuffers {
+        front: shared.clone(),
+        back: shared.clone(),
+    };
+    let serialized = fory.serialize(&buffers).unwrap();
+    let deserialized: SharedBuffers = fory.deserialize(&serialized).unwrap();
+    assert!(Rc::ptr_eq(&deserialized.front, &deserialized.back));
+    deserialized.front.borrow_mut().push(4);
+    assert_eq!(*deserialized.back.borrow(), vec![1, 2, 3, 4]);
+
+    let _guard = shared.borrow_mut();
+    let err = fory.serialize(&buffers).unwrap_err();
+    assert!(matches!(err, fory_core::error::Error::EncodeError(_)));
+}
@@ -0,0 +1,47 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::fory::Fory;
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+
+#[test]
+fn test_reverse_round_trip() {
+    let fory = Fory::default();
+    let bin = fory.serialize(&Reverse(5u32)).unwrap();
+    let obj: Reverse<u32> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, Reverse(5u32));
+
+    // written exactly like the inner value
+    assert_eq!(bin, fory.serialize(&5u32).unwrap());
+    let bare: u32 = fory.deserialize(&bin).unwrap();
+    assert_eq!(bare, 5);
+    let obj: Reverse<String> = fory
+        .deserialize(&fory.serialize(&"text".to_string()).unwrap())
+        .unwrap();
+    assert_eq!(obj.0, "text");
+}
+
+#[test]
+fn test_reverse_ordered_keys() {
+    let fory = Fory::default();
+    let set: BTreeSet<Reverse<i32>> = [1, 3, 2].into_iter().map(Reverse).collect();
+    let bin = fory.serialize(&set).unwrap();
+    let obj: BTreeSet<Reverse<i32>> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, set);
+    assert_eq!(obj.iter().next(), Some(&Reverse(3)));
+}
@@ -0,0 +1,39 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+#![allow(clippy::incompatible_msrv)]
+
+use fory_core::fory::Fory;
+use std::num::Saturating;
+
+#[test]
+fn test_saturating_round_trip() {
+    let fory = Fory::default();
+    let bin = fory.serialize(&Saturating(u8::MAX)).unwrap();
+    let obj: Saturating<u8> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, Saturating(u8::MAX));
+    assert_eq!(obj + Saturating(1), Saturating(u8::MAX));
+
+    // written exactly like the inner value
+    assert_eq!(bin, fory.serialize(&u8::MAX).unwrap());
+    let bare: u8 = fory.deserialize(&bin).unwrap();
+    assert_eq!(bare, u8::MAX);
+    let obj: Saturating<i64> = fory
+        .deserialize(&fory.serialize(&i64::MIN).unwrap())
+        .unwrap();
+    assert_eq!(obj, Saturating(i64::MIN));
+}
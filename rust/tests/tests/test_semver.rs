@@ -0,0 +1,48 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+use semver::Version;
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Dependency {
+    name: String,
+    version: Version,
+}
+
+#[test]
+fn test_semver_round_trip() {
+    let fory = Fory::default();
+    let version = Version::parse("1.12.300-rc.1+build.5.sha-0a1b").unwrap();
+    let bin = fory.serialize(&version).unwrap();
+    let read: Version = fory.deserialize(&bin).unwrap();
+    assert_eq!(read, version);
+    assert_eq!(read.pre.as_str(), "rc.1");
+    assert_eq!(read.build.as_str(), "build.5.sha-0a1b");
+
+    let mut fory = Fory::default();
+    fory.register::<Dependency>(100).unwrap();
+    for version in ["0.0.0", "2.0.0-alpha", "18446744073709551615.0.1+meta"] {
+        let dependency = Dependency {
+            name: "fory".to_string(),
+            version: Version::parse(version).unwrap(),
+        };
+        let bin = fory.serialize(&dependency).unwrap();
+        assert_eq!(fory.deserialize::<Dependency>(&bin).unwrap(), dependency);
+    }
+}
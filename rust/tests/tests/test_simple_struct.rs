@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use fory_core::error::Error;
 use fory_core::fory::Fory;
 use fory_derive::ForyObject;
 use std::collections::HashMap;
@@ -69,3 +70,738 @@ fn test_simple() {
     assert_eq!(obj.f7, i16::default());
     assert_eq!(animal.last, obj.last);
 }
+
+#[test]
+fn test_field_endian() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Packet {
+        #[fory(endian = "big")]
+        length: i32,
+        #[fory(endian = "little")]
+        checksum: i32,
+        #[fory(endian = "big")]
+        ratio: f64,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Packet>(100).unwrap();
+    let packet = Packet {
+        length: 0x0102_0304,
+        checksum: 0x0A0B_0C0D,
+        ratio: 1.5,
+    };
+    let bin = fory.serialize(&packet).unwrap();
+    let find = |needle: &[u8]| bin.windows(needle.len()).any(|w| w == needle);
+    assert!(find(&0x0102_0304i32.to_be_bytes()));
+    assert!(find(&0x0A0B_0C0Di32.to_le_bytes()));
+    assert!(find(&1.5f64.to_be_bytes()));
+    let obj: Packet = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, packet);
+
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Packet>(100).unwrap();
+    let bin = fory.serialize(&packet).unwrap();
+    let obj: Packet = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, packet);
+}
+
+#[test]
+fn test_field_endian_compatible() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Frame {
+        #[fory(endian = "big")]
+        seq: u32,
+        #[fory(endian = "little")]
+        offset: i64,
+        #[fory(endian = "big")]
+        tag: u16,
+        name: String,
+        count: i32,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct FrameSkipped {
+        name: String,
+        count: i32,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct FrameVarint {
+        seq: u32,
+        offset: i64,
+        tag: u16,
+        name: String,
+        count: i32,
+    }
+
+    let frame = Frame {
+        seq: 0xFFFF_FFF0,
+        offset: -3,
+        tag: 0x0102,
+        name: "frame".to_string(),
+        count: 7,
+    };
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Frame>(101).unwrap();
+    let bin = fory.serialize(&frame).unwrap();
+    assert_eq!(fory.deserialize::<Frame>(&bin).unwrap(), frame);
+
+    // peers without the fields skip them by their fixed width
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<FrameSkipped>(101).unwrap();
+    let obj: FrameSkipped = fory.deserialize(&bin).unwrap();
+    assert_eq!(
+        obj,
+        FrameSkipped {
+            name: "frame".to_string(),
+            count: 7,
+        }
+    );
+
+    // varint fields of the same name do not match, while `i16`s are written alike
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<FrameVarint>(101).unwrap();
+    let obj: FrameVarint = fory.deserialize(&bin).unwrap();
+    assert_eq!(
+        obj,
+        FrameVarint {
+            seq: 0,
+            offset: 0,
+            tag: 0x0201,
+            name: "frame".to_string(),
+            count: 7,
+        }
+    );
+}
+
+#[test]
+fn test_repr_enum() {
+    #[derive(ForyObject, Debug, PartialEq, Default)]
+    #[repr(u16)]
+    #[fory(repr)]
+    enum Opcode {
+        #[default]
+        Nop = 1,
+        Load = 0x0102,
+        Store,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq, Default)]
+    #[repr(u16)]
+    #[fory(repr)]
+    enum OpcodeV2 {
+        #[default]
+        Nop = 1,
+        Jump = 0x0200,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Opcode>(100).unwrap();
+    let bin = fory.serialize(&Opcode::Nop).unwrap();
+    assert!(bin.ends_with(&[1, 0]));
+    let with_load = fory.serialize(&Opcode::Load).unwrap();
+    assert!(with_load.ends_with(&[0x02, 0x01]));
+    assert_eq!(with_load.len(), bin.len());
+    for op in [Opcode::Nop, Opcode::Load, Opcode::Store] {
+        let bin = fory.serialize(&op).unwrap();
+        assert_eq!(fory.deserialize::<Opcode>(&bin).unwrap(), op);
+    }
+
+    let mut fory_v2 = Fory::default();
+    fory_v2.register::<OpcodeV2>(100).unwrap();
+    let bin = fory_v2.serialize(&OpcodeV2::Jump).unwrap();
+    assert!(matches!(
+        fory.deserialize::<Opcode>(&bin),
+        Err(fory_core::error::Error::UnknownEnum(_))
+    ));
+
+    // without the opt-in the discriminants are ignored and the ordinal is written
+    #[derive(ForyObject, Debug, PartialEq, Default)]
+    #[repr(u16)]
+    enum Status {
+        #[default]
+        Idle = 0x0100,
+        Busy = 0x0200,
+    }
+    let mut fory = Fory::default();
+    fory.register::<Status>(100).unwrap();
+    let bin = fory.serialize(&Status::Busy).unwrap();
+    assert!(bin.ends_with(&[1]));
+    assert_eq!(fory.deserialize::<Status>(&bin).unwrap(), Status::Busy);
+}
+
+#[test]
+fn test_skip_repr_enum_field() {
+    #[derive(ForyObject, Debug, PartialEq, Default)]
+    #[repr(u16)]
+    #[fory(repr)]
+    enum Opcode {
+        #[default]
+        Nop = 1,
+        Load = 0x0102,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq, Default)]
+    #[repr(u64)]
+    #[fory(repr)]
+    enum Flags {
+        #[default]
+        Empty = 0,
+        All = u64::MAX,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct V1 {
+        a: i32,
+        op: Opcode,
+        next_op: Option<Opcode>,
+        ops: Vec<Opcode>,
+        flags: Flags,
+        z: String,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct V2 {
+        a: i32,
+        z: String,
+    }
+
+    let mut fory_v1 = Fory::default().compatible(true);
+    fory_v1.register::<Opcode>(100).unwrap();
+    fory_v1.register::<Flags>(101).unwrap();
+    fory_v1.register::<V1>(102).unwrap();
+    let mut fory_v2 = Fory::default().compatible(true);
+    fory_v2.register::<V2>(102).unwrap();
+
+    let v1 = V1 {
+        a: 7,
+        op: Opcode::Load,
+        next_op: Some(Opcode::Load),
+        ops: vec![Opcode::Load, Opcode::Nop],
+        flags: Flags::All,
+        z: "tail".to_string(),
+    };
+    let bin = fory_v1.serialize(&v1).unwrap();
+    let v2: V2 = fory_v2.deserialize(&bin).unwrap();
+    assert_eq!(
+        v2,
+        V2 {
+            a: 7,
+            z: "tail".to_string()
+        }
+    );
+    assert_eq!(fory_v1.deserialize::<V1>(&bin).unwrap(), v1);
+}
+
+#[test]
+fn test_deserialize_into_enum() {
+    #[derive(ForyObject, Debug, PartialEq, Default)]
+    enum State {
+        #[default]
+        Idle,
+        Running,
+        #[fory(unknown)]
+        Other(u32),
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<State>(100).unwrap();
+    // variants carry no payload, so a refill replaces the variant
+    let mut state = State::Idle;
+    fory.deserialize_into(&fory.serialize(&State::Running).unwrap(), &mut state)
+        .unwrap();
+    assert_eq!(state, State::Running);
+    fory.deserialize_into(&fory.serialize(&State::Other(7)).unwrap(), &mut state)
+        .unwrap();
+    assert_eq!(state, State::Other(7));
+    fory.deserialize_into(&fory.serialize(&State::Idle).unwrap(), &mut state)
+        .unwrap();
+    assert_eq!(state, State::Idle);
+}
+
+#[test]
+fn test_enum_as_string() {
+    #[derive(ForyObject, Debug, PartialEq, Default)]
+    enum Level {
+        #[default]
+        Low,
+        Medium,
+        High,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq, Default)]
+    enum LevelV2 {
+        #[default]
+        Low,
+        Critical,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Alert {
+        level: Level,
+        message: String,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Level>(100).unwrap();
+    fory.register_enum_as_string::<Level>().unwrap();
+    fory.register::<Alert>(101).unwrap();
+    let bin = fory.serialize(&Level::High).unwrap();
+    assert!(bin.ends_with(b"High"));
+    for level in [Level::Low, Level::Medium, Level::High] {
+        let bin = fory.serialize(&level).unwrap();
+        assert_eq!(fory.deserialize::<Level>(&bin).unwrap(), level);
+    }
+    let alert = Alert {
+        level: Level::Medium,
+        message: "disk".to_string(),
+    };
+    let bin = fory.serialize(&alert).unwrap();
+    assert_eq!(fory.deserialize::<Alert>(&bin).unwrap(), alert);
+
+    let mut fory_v2 = Fory::default();
+    fory_v2.register::<LevelV2>(100).unwrap();
+    fory_v2.register_enum_as_string::<LevelV2>().unwrap();
+    let bin = fory_v2.serialize(&LevelV2::Critical).unwrap();
+    assert!(matches!(
+        fory.deserialize::<Level>(&bin),
+        Err(fory_core::error::Error::UnknownEnum(_))
+    ));
+
+    assert!(matches!(
+        Fory::default().register_enum_as_string::<Level>(),
+        Err(fory_core::error::Error::TypeError(_))
+    ));
+    assert!(matches!(
+        fory.register_enum_as_string::<Alert>(),
+        Err(fory_core::error::Error::TypeError(_))
+    ));
+
+    // in compatible mode, a peer that lacks the field, or reads it by ordinal, skips it
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct AlertWithoutLevel {
+        message: String,
+    }
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<Level>(100).unwrap();
+    fory.register_enum_as_string::<Level>().unwrap();
+    fory.register::<Alert>(101).unwrap();
+    let alert = Alert {
+        level: Level::High,
+        message: "cpu".to_string(),
+    };
+    let bin = fory.serialize(&alert).unwrap();
+    assert_eq!(fory.deserialize::<Alert>(&bin).unwrap(), alert);
+    let mut reader = Fory::default().compatible(true);
+    reader.register::<AlertWithoutLevel>(101).unwrap();
+    assert_eq!(
+        reader.deserialize::<AlertWithoutLevel>(&bin).unwrap(),
+        AlertWithoutLevel {
+            message: "cpu".to_string(),
+        }
+    );
+    let mut reader = Fory::default().compatible(true);
+    reader.register::<Level>(100).unwrap();
+    reader.register::<Alert>(101).unwrap();
+    assert_eq!(
+        reader.deserialize::<Alert>(&bin).unwrap(),
+        Alert {
+            level: Level::Low,
+            message: "cpu".to_string(),
+        }
+    );
+}
+
+mod timestamp_as_string {
+    use fory_core::error::Error;
+    use fory_core::resolver::context::{ReadContext, WriteContext};
+    use fory_core::serializer::Serializer;
+
+    pub fn serialize(value: &i64, context: &mut WriteContext) -> Result<(), Error> {
+        format!("@{}", value).fory_write(context, true)
+    }
+
+    pub fn deserialize(context: &mut ReadContext) -> Result<i64, Error> {
+        let text = String::fory_read(context, true)?;
+        text.strip_prefix('@')
+            .and_then(|digits| digits.parse().ok())
+            .ok_or_else(|| Error::InvalidData(format!("bad timestamp {:?}", text).into()))
+    }
+}
+
+#[test]
+fn test_field_with_module() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Event {
+        name: String,
+        #[fory(with = "timestamp_as_string")]
+        created_at: i64,
+        retries: i32,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Event>(100).unwrap();
+    let event = Event {
+        name: "deploy".to_string(),
+        created_at: 1_700_000_000,
+        retries: 3,
+    };
+    let bin = fory.serialize(&event).unwrap();
+    assert!(bin.windows(11).any(|w| w == b"@1700000000"));
+    let obj: Event = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, event);
+
+    // a peer with a different schema reads the field through the same module
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct EventV2 {
+        name: String,
+        #[fory(with = "timestamp_as_string")]
+        created_at: i64,
+        retries: i32,
+        owner: String,
+    }
+    let mut writer = Fory::default().compatible(true);
+    writer.register::<Event>(100).unwrap();
+    let mut reader = Fory::default().compatible(true);
+    reader.register::<EventV2>(100).unwrap();
+    let obj: EventV2 = reader
+        .deserialize(&writer.serialize(&event).unwrap())
+        .unwrap();
+    assert_eq!(obj.created_at, event.created_at);
+    assert_eq!(obj.name, event.name);
+    assert_eq!(obj.retries, event.retries);
+
+    // and a peer without the field skips what the module wrote
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct EventWithoutTime {
+        name: String,
+        retries: i32,
+    }
+    let mut reader = Fory::default().compatible(true);
+    reader.register::<EventWithoutTime>(100).unwrap();
+    let obj: EventWithoutTime = reader
+        .deserialize(&writer.serialize(&event).unwrap())
+        .unwrap();
+    assert_eq!(obj.name, event.name);
+    assert_eq!(obj.retries, event.retries);
+}
+
+#[test]
+fn test_type_id_of() {
+    #[derive(ForyObject, Debug)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    for compatible in [false, true] {
+        let mut fory = Fory::default().compatible(compatible);
+        assert_eq!(fory.type_id_of::<Point>(), None);
+        fory.register::<Point>(100).unwrap();
+        let type_id = fory.type_id_of::<Point>().unwrap();
+        assert_eq!(type_id >> 8, 100);
+        let bin = fory.serialize(&Point { x: 1, y: 2 }).unwrap();
+        assert_eq!(fory.peek_type_id(&bin).unwrap(), Some(type_id));
+    }
+
+    let fory = Fory::default();
+    let bin = fory.serialize(&"text".to_string()).unwrap();
+    assert_eq!(
+        fory.peek_type_id(&bin).unwrap(),
+        fory.type_id_of::<String>()
+    );
+}
+
+#[test]
+fn test_deserialize_into_diff() {
+    use fory_core::serializer::FieldChange;
+    use fory_derive::ForyDiff;
+
+    #[derive(ForyObject, ForyDiff, Debug, PartialEq, Clone)]
+    struct Account {
+        owner: String,
+        balance: i64,
+        tags: Vec<String>,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Account>(100).unwrap();
+    let before = Account {
+        owner: "ana".to_string(),
+        balance: 10,
+        tags: vec!["gold".to_string()],
+    };
+    let after = Account {
+        balance: 25,
+        ..before.clone()
+    };
+
+    let mut target = before.clone();
+    let bytes = fory.serialize(&after).unwrap();
+    let changes = fory.deserialize_into_diff(&bytes, &mut target).unwrap();
+    assert_eq!(changes, vec![FieldChange { field: "balance" }]);
+    assert_eq!(target, after);
+
+    let changes = fory.deserialize_into_diff(&bytes, &mut target).unwrap();
+    assert!(changes.is_empty());
+}
+
+#[test]
+fn test_transparent() {
+    #[derive(ForyObject, Debug, PartialEq, Clone, Copy)]
+    #[fory(transparent)]
+    struct Port(u16);
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    #[fory(transparent)]
+    struct Name {
+        value: String,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Endpoint {
+        name: Name,
+        port: Port,
+    }
+
+    for compatible in [false, true] {
+        let fory = Fory::default().compatible(compatible);
+
+        let port = Port(8080);
+        let bytes = fory.serialize(&port).unwrap();
+        assert_eq!(bytes, fory.serialize(&8080u16).unwrap());
+        assert_eq!(fory.deserialize::<Port>(&bytes).unwrap(), port);
+        assert_eq!(fory.deserialize::<u16>(&bytes).unwrap(), 8080);
+        let bytes = fory.serialize(&443u16).unwrap();
+        assert_eq!(fory.deserialize::<Port>(&bytes).unwrap(), Port(443));
+
+        let bytes = fory.serialize(&"api".to_string()).unwrap();
+        let name: Name = fory.deserialize(&bytes).unwrap();
+        assert_eq!(name.value, "api");
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Endpoint>(1).unwrap();
+    let endpoint = Endpoint {
+        name: Name {
+            value: "db".to_string(),
+        },
+        port: Port(5432),
+    };
+    let bytes = fory.serialize(&endpoint).unwrap();
+    assert_eq!(fory.deserialize::<Endpoint>(&bytes).unwrap(), endpoint);
+}
+
+#[test]
+fn test_validate() {
+    use fory_core::error::Error;
+
+    fn check_range(range: &Range) -> Result<(), String> {
+        if range.start > range.end {
+            return Err(format!("start {} after end {}", range.start, range.end));
+        }
+        Ok(())
+    }
+
+    fn non_negative(value: &i32) -> Result<(), String> {
+        if *value < 0 {
+            return Err(format!("{value} is negative"));
+        }
+        Ok(())
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    #[fory(validate = "check_range")]
+    struct Range {
+        #[fory(validate = "non_negative")]
+        start: i32,
+        end: i32,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Unchecked {
+        start: i32,
+        end: i32,
+    }
+
+    for compatible in [false, true] {
+        let mut fory = Fory::default().compatible(compatible);
+        fory.register::<Range>(1).unwrap();
+        let mut unchecked = Fory::default().compatible(compatible);
+        unchecked.register::<Unchecked>(1).unwrap();
+
+        let bin = unchecked
+            .serialize(&Unchecked { start: 1, end: 5 })
+            .unwrap();
+        assert_eq!(
+            fory.deserialize::<Range>(&bin).unwrap(),
+            Range { start: 1, end: 5 }
+        );
+
+        let bin = unchecked
+            .serialize(&Unchecked { start: -1, end: 5 })
+            .unwrap();
+        let err = fory.deserialize::<Range>(&bin).unwrap_err();
+        assert!(matches!(err, Error::ValidationFailed(_)));
+        assert!(err.to_string().contains("start: -1 is negative"));
+
+        let bin = unchecked
+            .serialize(&Unchecked { start: 9, end: 5 })
+            .unwrap();
+        let err = fory.deserialize::<Range>(&bin).unwrap_err();
+        assert!(matches!(err, Error::ValidationFailed(_)));
+        assert!(err.to_string().contains("start 9 after end 5"));
+    }
+}
+
+#[test]
+fn test_register_generic() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Wrapper<T> {
+        value: T,
+    }
+
+    let mut fory = Fory::default();
+    fory_core::register_generic!(fory, Wrapper, [i32, String], 100).unwrap();
+
+    let int_wrapper = Wrapper { value: 42 };
+    let bytes = fory.serialize(&int_wrapper).unwrap();
+    assert_eq!(
+        fory.deserialize::<Wrapper<i32>>(&bytes).unwrap(),
+        int_wrapper
+    );
+
+    let string_wrapper = Wrapper {
+        value: "hello".to_string(),
+    };
+    let bytes = fory.serialize(&string_wrapper).unwrap();
+    assert_eq!(
+        fory.deserialize::<Wrapper<String>>(&bytes).unwrap(),
+        string_wrapper
+    );
+    // The two monomorphizations got distinct ids.
+    assert!(fory.deserialize::<Wrapper<i32>>(&bytes).is_err());
+
+    let mut compatible = Fory::default().compatible(true);
+    fory_core::register_generic!(compatible, Wrapper, [i32, String], 100).unwrap();
+    let bytes = compatible.serialize(&string_wrapper).unwrap();
+    assert_eq!(
+        compatible.deserialize::<Wrapper<String>>(&bytes).unwrap(),
+        string_wrapper
+    );
+    let bytes = compatible.serialize(&int_wrapper).unwrap();
+    assert_eq!(
+        compatible.deserialize::<Wrapper<i32>>(&bytes).unwrap(),
+        int_wrapper
+    );
+
+    // a primitive `T` next to other fields, in both modes
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Labeled<T> {
+        value: T,
+        label: String,
+        count: i64,
+    }
+    for compatible in [false, true] {
+        let mut fory = Fory::default().compatible(compatible);
+        fory_core::register_generic!(fory, Labeled, [i32, String, Option<i32>], 200).unwrap();
+        let labeled = Labeled {
+            value: 7,
+            label: "seven".to_string(),
+            count: -1,
+        };
+        let bytes = fory.serialize(&labeled).unwrap();
+        assert_eq!(fory.deserialize::<Labeled<i32>>(&bytes).unwrap(), labeled);
+        let labeled = Labeled {
+            value: "x".to_string(),
+            label: "ex".to_string(),
+            count: 2,
+        };
+        let bytes = fory.serialize(&labeled).unwrap();
+        assert_eq!(
+            fory.deserialize::<Labeled<String>>(&bytes).unwrap(),
+            labeled
+        );
+        let labeled = Labeled {
+            value: None::<i32>,
+            label: "none".to_string(),
+            count: 3,
+        };
+        let bytes = fory.serialize(&labeled).unwrap();
+        assert_eq!(
+            fory.deserialize::<Labeled<Option<i32>>>(&bytes).unwrap(),
+            labeled
+        );
+    }
+}
+
+#[test]
+fn test_tuple_struct() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Point(f64, f64);
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct NamedPoint {
+        x: f64,
+        y: f64,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Point>(100).unwrap();
+    let point = Point(1.5, -2.5);
+    let bytes = fory.serialize(&point).unwrap();
+    assert_eq!(fory.deserialize::<Point>(&bytes).unwrap(), point);
+
+    // fields are named by position in the type meta
+    let mut tuple_fory = Fory::default().compatible(true);
+    tuple_fory.register::<Point>(100).unwrap();
+    let mut named_fory = Fory::default().compatible(true);
+    named_fory.register::<NamedPoint>(100).unwrap();
+    let tuple_bytes = tuple_fory.serialize(&point).unwrap();
+    let named_bytes = named_fory
+        .serialize(&NamedPoint { x: 1.5, y: -2.5 })
+        .unwrap();
+    assert_ne!(tuple_bytes, named_bytes);
+    assert_eq!(
+        tuple_fory.deserialize::<Point>(&tuple_bytes).unwrap(),
+        point
+    );
+    assert_eq!(
+        named_fory.deserialize::<NamedPoint>(&tuple_bytes).unwrap(),
+        NamedPoint { x: 0.0, y: 0.0 }
+    );
+}
+
+#[test]
+fn test_missing_field() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct UserV1 {
+        id: i32,
+        name: String,
+    }
+
+    // a map sorts after the other fields, so an old buffer ends where it should start
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct UserV2 {
+        id: i32,
+        name: String,
+        labels: HashMap<String, String>,
+    }
+
+    let mut old_fory = Fory::default();
+    old_fory.register::<UserV1>(100).unwrap();
+    let mut new_fory = Fory::default();
+    new_fory.register::<UserV2>(100).unwrap();
+    let bin = old_fory
+        .serialize(&UserV1 {
+            id: 7,
+            name: "ada".to_string(),
+        })
+        .unwrap();
+    let err = new_fory.deserialize::<UserV2>(&bin).unwrap_err();
+    assert!(matches!(err, Error::MissingField { name: "labels" }));
+    assert_eq!(
+        err.to_string(),
+        "Missing field `labels`: the input ended before it"
+    );
+}
@@ -0,0 +1,108 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::buffer::{Reader, Writer};
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_core::resolver::context::{ReadContext, WriteContext};
+use fory_core::serializer::Serializer;
+
+fn write_values(fory: &Fory, write: impl FnOnce(&mut WriteContext)) -> Vec<u8> {
+    let mut context = WriteContext::new_from_fory(Writer::default(), fory);
+    write(&mut context);
+    context.writer.dump()
+}
+
+#[test]
+fn test_skip_bytes() {
+    let fory = Fory::default();
+    let bytes = [1u8, 2, 3, 4];
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes), &fory);
+    context.skip_bytes(3).unwrap();
+    assert_eq!(context.reader.get_cursor(), 3);
+    assert!(matches!(
+        context.skip_bytes(2),
+        Err(Error::UnexpectedEof(_))
+    ));
+    assert_eq!(context.reader.get_cursor(), 3);
+    context.skip_bytes(1).unwrap();
+    assert_eq!(context.reader.slice_after_cursor().len(), 0);
+}
+
+#[test]
+fn test_skip_fixed_width() {
+    let fory = Fory::default();
+    let bytes = write_values(&fory, |context| {
+        true.fory_write_data(context, true).unwrap();
+        7i8.fory_write_data(context, true).unwrap();
+        300i16.fory_write_data(context, true).unwrap();
+        1.5f32.fory_write_data(context, true).unwrap();
+        2.5f64.fory_write_data(context, true).unwrap();
+        42i8.fory_write_data(context, true).unwrap();
+    });
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes), &fory);
+    bool::fory_skip(&mut context).unwrap();
+    assert_eq!(context.reader.get_cursor(), 1);
+    i8::fory_skip(&mut context).unwrap();
+    assert_eq!(context.reader.get_cursor(), 2);
+    i16::fory_skip(&mut context).unwrap();
+    assert_eq!(context.reader.get_cursor(), 4);
+    f32::fory_skip(&mut context).unwrap();
+    assert_eq!(context.reader.get_cursor(), 8);
+    f64::fory_skip(&mut context).unwrap();
+    assert_eq!(context.reader.get_cursor(), 16);
+    assert_eq!(i8::fory_read_data(&mut context, true).unwrap(), 42);
+
+    // a truncated f64 must not be skipped
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes[..12]), &fory);
+    context.skip_bytes(8).unwrap();
+    assert!(matches!(
+        f64::fory_skip(&mut context),
+        Err(Error::UnexpectedEof(_))
+    ));
+}
+
+#[test]
+fn test_skip_variable_length() {
+    let fory = Fory::default();
+    let bytes = write_values(&fory, |context| {
+        1_000_000i32.fory_write_data(context, true).unwrap();
+        (-1i64).fory_write_data(context, true).unwrap();
+        "hello".to_string().fory_write_data(context, true).unwrap();
+        "你好".to_string().fory_write_data(context, true).unwrap();
+        vec![1i32, 2, 3].fory_write_data(context, true).unwrap();
+        42i8.fory_write_data(context, true).unwrap();
+    });
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes), &fory);
+    i32::fory_skip(&mut context).unwrap();
+    i64::fory_skip(&mut context).unwrap();
+    String::fory_skip(&mut context).unwrap();
+    String::fory_skip(&mut context).unwrap();
+    Vec::<i32>::fory_skip(&mut context).unwrap();
+    assert_eq!(i8::fory_read_data(&mut context, true).unwrap(), 42);
+    assert_eq!(context.reader.slice_after_cursor().len(), 0);
+
+    // the string payload is shorter than its length prefix
+    let bytes = write_values(&fory, |context| {
+        "hello".to_string().fory_write_data(context, true).unwrap();
+    });
+    let mut context = ReadContext::new_from_fory(Reader::new(&bytes[..3]), &fory);
+    assert!(matches!(
+        String::fory_skip(&mut context),
+        Err(Error::UnexpectedEof(_))
+    ));
+}
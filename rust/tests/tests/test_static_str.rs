@@ -0,0 +1,158 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+use std::borrow::Cow;
+
+fn method_fory() -> Fory {
+    let mut fory = Fory::default();
+    fory.register_static_string(0, "GET").unwrap();
+    fory.register_static_string(1, "POST").unwrap();
+    fory
+}
+
+#[test]
+fn test_static_string_is_written_as_id() {
+    let fory = method_fory();
+    let bin = fory.serialize(&Cow::Borrowed("POST")).unwrap();
+    // same header, ref flag and one byte type id as an `i8`, so the data is a single byte
+    assert_eq!(bin.len(), fory.serialize(&1i8).unwrap().len());
+    let obj: Cow<'static, str> = fory.deserialize(&bin).unwrap();
+    assert!(matches!(obj, Cow::Borrowed("POST")));
+
+    let owned: Cow<'static, str> = Cow::Owned("GET".to_string());
+    let obj: Cow<'static, str> = fory.deserialize(&fory.serialize(&owned).unwrap()).unwrap();
+    assert!(matches!(obj, Cow::Borrowed("GET")));
+}
+
+#[test]
+fn test_unregistered_string_falls_back() {
+    let fory = method_fory();
+    let value: Cow<'static, str> = Cow::Borrowed("PATCH");
+    let bin = fory.serialize(&value).unwrap();
+    let obj: Cow<'static, str> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, "PATCH");
+    assert!(matches!(obj, Cow::Owned(_)));
+
+    // a peer that does not know the id rejects it
+    let bin = fory.serialize(&Cow::Borrowed("GET")).unwrap();
+    let result: Result<Cow<'static, str>, Error> = Fory::default().deserialize(&bin);
+    assert!(matches!(result, Err(Error::InvalidData(_))));
+}
+
+#[test]
+fn test_static_string_field() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Request {
+        method: Cow<'static, str>,
+        path: String,
+    }
+
+    let mut fory = method_fory();
+    fory.register::<Request>(100).unwrap();
+    let request = Request {
+        method: Cow::Borrowed("GET"),
+        path: "/index.html".to_string(),
+    };
+    let bin = fory.serialize(&request).unwrap();
+    let obj: Request = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, request);
+}
+
+#[test]
+fn test_static_string_field_compatible() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Request {
+        id: i32,
+        method: Cow<'static, str>,
+        verb: Cow<'static, str>,
+        path: String,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct RequestSkipped {
+        id: i32,
+        path: String,
+    }
+
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct RequestAsString {
+        id: i32,
+        verb: String,
+        path: String,
+    }
+
+    let mut fory = method_fory().compatible(true);
+    fory.register::<Request>(100).unwrap();
+    let request = Request {
+        id: 7,
+        method: Cow::Borrowed("GET"),
+        verb: Cow::Borrowed("PATCH"),
+        path: "/index.html".to_string(),
+    };
+    let bin = fory.serialize(&request).unwrap();
+    assert_eq!(fory.deserialize::<Request>(&bin).unwrap(), request);
+
+    // both the registered id and the literal are skipped as strings
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<RequestSkipped>(100).unwrap();
+    let obj: RequestSkipped = fory.deserialize(&bin).unwrap();
+    assert_eq!((obj.id, obj.path.as_str()), (7, "/index.html"));
+
+    // and a literal reads back as a `String`
+    let mut fory = Fory::default().compatible(true);
+    fory.register::<RequestAsString>(100).unwrap();
+    let obj: RequestAsString = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj.verb, "PATCH");
+    assert_eq!(obj.path, "/index.html");
+}
+
+#[test]
+fn test_register_static_string_twice() {
+    let mut fory = method_fory();
+    assert!(matches!(
+        fory.register_static_string(1, "PUT"),
+        Err(Error::TypeError(_))
+    ));
+    assert!(matches!(
+        fory.register_static_string(2, "GET"),
+        Err(Error::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_borrowed_map_values() {
+    use std::collections::HashMap;
+
+    static GET: &str = "GET";
+    let mut fory = Fory::default();
+    fory.register_static_string(0, GET).unwrap();
+    let map: HashMap<String, Cow<'static, str>> = HashMap::from([
+        ("index".to_string(), Cow::Borrowed(GET)),
+        ("upload".to_string(), Cow::Borrowed("PUT")),
+    ]);
+    let bin = fory.serialize(&map).unwrap();
+    assert!(map.values().all(|v| matches!(v, Cow::Borrowed(_))));
+
+    let obj: HashMap<String, Cow<'static, str>> = fory.deserialize(&bin).unwrap();
+    assert_eq!(obj, map);
+    // the registered string is read back borrowing the very same static
+    assert!(matches!(&obj["index"], Cow::Borrowed(s) if std::ptr::eq(*s, GET)));
+    assert!(matches!(&obj["upload"], Cow::Owned(_)));
+}
@@ -0,0 +1,108 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use chrono::{DateTime, NaiveDate};
+use fory_core::error::Error;
+use fory_core::fory::Fory;
+use fory_derive::ForyObject;
+use time::{Date, Duration, Month, OffsetDateTime, UtcOffset};
+
+#[test]
+fn test_offset_date_time_keeps_offset() {
+    let fory = Fory::default();
+    let offset = UtcOffset::from_hms(5, 30, 0).unwrap();
+    let at = OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_123_456_000)
+        .unwrap()
+        .to_offset(offset);
+    let bin = fory.serialize(&at).unwrap();
+    let read: OffsetDateTime = fory.deserialize(&bin).unwrap();
+    assert_eq!(read, at);
+    assert_eq!(read.offset(), offset);
+    assert_eq!(read.hour(), at.hour());
+
+    // west of UTC and before the epoch, where the microseconds round down
+    let offset = UtcOffset::from_hms(-8, 0, 0).unwrap();
+    let at = OffsetDateTime::from_unix_timestamp_nanos(-1_500_000)
+        .unwrap()
+        .to_offset(offset);
+    let read: OffsetDateTime = fory.deserialize(&fory.serialize(&at).unwrap()).unwrap();
+    assert_eq!(read, at);
+    assert_eq!(read.offset(), offset);
+
+    // sub-microsecond precision is truncated
+    let at = OffsetDateTime::from_unix_timestamp_nanos(1_999).unwrap();
+    let read: OffsetDateTime = fory.deserialize(&fory.serialize(&at).unwrap()).unwrap();
+    assert_eq!(read.unix_timestamp_nanos(), 1_000);
+}
+
+#[test]
+fn test_time_date_matches_chrono() {
+    let fory = Fory::default();
+    let date = Date::from_calendar_date(2024, Month::February, 29).unwrap();
+    let bin = fory.serialize(&date).unwrap();
+    assert_eq!(fory.deserialize::<Date>(&bin).unwrap(), date);
+    assert_eq!(
+        fory.deserialize::<NaiveDate>(&bin).unwrap(),
+        NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+    );
+    let chrono = NaiveDate::from_ymd_opt(1999, 12, 31).unwrap();
+    let read: Date = fory.deserialize(&fory.serialize(&chrono).unwrap()).unwrap();
+    assert_eq!(
+        read,
+        Date::from_calendar_date(1999, Month::December, 31).unwrap()
+    );
+
+    // the instant is written like a chrono timestamp, followed by the offset
+    let at = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+    let naive = DateTime::from_timestamp(1_700_000_000, 0)
+        .unwrap()
+        .naive_utc();
+    let bin = fory.serialize(&at).unwrap();
+    let chrono_bin = fory.serialize(&naive).unwrap();
+    assert_eq!(&bin[..chrono_bin.len()], chrono_bin.as_slice());
+    assert_eq!(bin.len(), chrono_bin.len() + 4);
+}
+
+#[test]
+fn test_time_fields() {
+    #[derive(ForyObject, Debug, PartialEq)]
+    struct Booking {
+        day: Date,
+        start: OffsetDateTime,
+        length: Duration,
+    }
+
+    let mut fory = Fory::default();
+    fory.register::<Booking>(100).unwrap();
+    let booking = Booking {
+        day: Date::from_calendar_date(2025, Month::March, 9).unwrap(),
+        start: OffsetDateTime::from_unix_timestamp(1_741_500_000)
+            .unwrap()
+            .to_offset(UtcOffset::from_hms(1, 0, 0).unwrap()),
+        length: Duration::new(-5400, -250),
+    };
+    let bin = fory.serialize(&booking).unwrap();
+    assert_eq!(fory.deserialize::<Booking>(&bin).unwrap(), booking);
+
+    let mut bin = fory.serialize(&Duration::ZERO).unwrap();
+    let nanos = bin.len() - 4;
+    bin[nanos..].copy_from_slice(&1_000_000_000i32.to_le_bytes());
+    assert!(matches!(
+        fory.deserialize::<Duration>(&bin),
+        Err(Error::InvalidData(_))
+    ));
+}
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use fory_core::error::Error;
 use fory_core::fory::Fory;
 use fory_core::register_trait_type;
 use fory_core::serializer::Serializer;
@@ -633,3 +634,155 @@ fn test_single_item_trait_object_collections() {
     assert_eq!(deserialized.get("only_pet").unwrap().name(), "Loner");
     assert_eq!(deserialized.get("only_pet").unwrap().speak(), "Meow!");
 }
+
+#[test]
+fn test_standalone_option_roundtrip() {
+    for fory in [Fory::default(), fory_compatible()] {
+        let bin = fory.serialize(&Some(5)).unwrap();
+        assert_eq!(fory.deserialize::<Option<i32>>(&bin).unwrap(), Some(5));
+        let bin = fory.serialize(&None::<i32>).unwrap();
+        assert_eq!(fory.deserialize::<Option<i32>>(&bin).unwrap(), None);
+
+        for value in [None, Some(None), Some(Some(5))] {
+            let bin = fory.serialize(&value).unwrap();
+            assert_eq!(
+                fory.deserialize::<Option<Option<i32>>>(&bin).unwrap(),
+                value
+            );
+        }
+        let values = vec![Some(None), None, Some(Some("x".to_string()))];
+        let bin = fory.serialize(&values).unwrap();
+        assert_eq!(
+            fory.deserialize::<Vec<Option<Option<String>>>>(&bin)
+                .unwrap(),
+            values
+        );
+    }
+}
+
+#[test]
+fn test_null_root_into_non_option() {
+    let fory = Fory::default();
+    let bin = fory.serialize(&None::<i32>).unwrap();
+    assert_eq!(fory.deserialize::<Option<i32>>(&bin).unwrap(), None);
+    assert!(matches!(
+        fory.deserialize::<i32>(&bin),
+        Err(Error::UnexpectedNull(_))
+    ));
+    let mut target = 7;
+    assert!(matches!(
+        fory.deserialize_into(&bin, &mut target),
+        Err(Error::UnexpectedNull(_))
+    ));
+
+    // compatible mode converts an `Option<i32>` into an `i32`
+    let fory = fory_compatible();
+    let bin = fory.serialize(&None::<i32>).unwrap();
+    assert_eq!(fory.deserialize::<i32>(&bin).unwrap(), 0);
+}
+
+trait Component: Serializer {
+    fn describe(&self) -> String;
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Health {
+    points: i32,
+}
+
+#[derive(ForyObject, Debug, PartialEq)]
+struct Sprite {
+    path: String,
+}
+
+impl Component for Position {
+    fn describe(&self) -> String {
+        format!("at ({}, {})", self.x, self.y)
+    }
+}
+
+impl Component for Health {
+    fn describe(&self) -> String {
+        format!("{} hp", self.points)
+    }
+}
+
+impl Component for Sprite {
+    fn describe(&self) -> String {
+        format!("drawn from {}", self.path)
+    }
+}
+
+register_trait_type!(Component, Position, Health, Sprite);
+
+#[test]
+fn test_hashmap_heterogeneous_components() {
+    use fory_core::error::Error;
+    use fory_core::types::UnknownTypePolicy;
+    use std::collections::BTreeMap;
+
+    let mut writer = fory_compatible();
+    writer.register::<Position>(8101).unwrap();
+    writer.register::<Health>(8102).unwrap();
+    writer.register::<Sprite>(8103).unwrap();
+    let mut components: HashMap<String, Box<dyn Component>> = HashMap::new();
+    components.insert("position".into(), Box::new(Position { x: 1.5, y: -2.0 }));
+    components.insert("health".into(), Box::new(Health { points: 80 }));
+    components.insert(
+        "sprite".into(),
+        Box::new(Sprite {
+            path: "hero.png".into(),
+        }),
+    );
+    let bin = writer.serialize(&components).unwrap();
+    let read: HashMap<String, Box<dyn Component>> = writer.deserialize(&bin).unwrap();
+    let mut described: Vec<_> = read
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.describe()))
+        .collect();
+    described.sort();
+    assert_eq!(
+        described,
+        [
+            ("health", "80 hp".to_string()),
+            ("position", "at (1.5, -2)".to_string()),
+            ("sprite", "drawn from hero.png".to_string()),
+        ]
+    );
+
+    // a reader that does not know `Sprite` follows its unknown-type policy
+    let register = |mut fory: Fory| {
+        fory.register::<Position>(8101).unwrap();
+        fory.register::<Health>(8102).unwrap();
+        fory
+    };
+    let strict = register(fory_compatible());
+    assert!(matches!(
+        strict.deserialize::<HashMap<String, Box<dyn Component>>>(&bin),
+        Err(Error::TypeError(_))
+    ));
+    let skipping = register(fory_compatible().with_unknown_type_policy(UnknownTypePolicy::Skip));
+    let read: HashMap<String, Box<dyn Component>> = skipping.deserialize(&bin).unwrap();
+    let mut keys: Vec<_> = read.keys().map(String::as_str).collect();
+    keys.sort();
+    assert_eq!(keys, ["health", "position"]);
+
+    let ordered: BTreeMap<String, Box<dyn Component>> = components.into_iter().collect();
+    let bin = writer.serialize(&ordered).unwrap();
+    let read: BTreeMap<String, Box<dyn Component>> = skipping.deserialize(&bin).unwrap();
+    assert_eq!(read.keys().collect::<Vec<_>>(), ["health", "position"]);
+    // outside a map there is no entry to drop
+    let sprite: Box<dyn Component> = Box::new(Sprite {
+        path: "hero.png".into(),
+    });
+    assert!(matches!(
+        skipping.deserialize::<Box<dyn Component>>(&writer.serialize(&sprite).unwrap()),
+        Err(Error::SkippedUnknownType(_))
+    ));
+}
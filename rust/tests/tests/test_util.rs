@@ -16,7 +16,9 @@
 // under the License.
 
 use fory_core::meta::{get_latin1_length, is_latin};
-use fory_core::util::to_utf8;
+use fory_core::util::{check_type_id, to_utf8};
+use fory_core::{assert_fory_compatible, Fory, TypeId};
+use fory_derive::ForyObject;
 
 #[test]
 fn test_to_utf8() {
@@ -126,3 +128,29 @@ fn test_get_latin1_length() {
     assert!(!is_latin(s));
     assert_eq!(get_latin1_length(s), -1);
 }
+
+#[test]
+fn test_assert_fory_compatible() {
+    assert_fory_compatible!(i32, TypeId::INT32);
+    assert_fory_compatible!(i64, TypeId::INT64);
+    assert_fory_compatible!(String, TypeId::STRING);
+    assert_fory_compatible!(Vec<i32>, TypeId::INT32_ARRAY);
+
+    #[derive(ForyObject, Debug)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+    let mut fory = Fory::default();
+    fory.register::<Point>(100).unwrap();
+    assert_fory_compatible!(Point, (100 << 8) + TypeId::STRUCT as u32, &fory);
+
+    assert!(check_type_id::<i32>(&Fory::default(), TypeId::INT64 as u32).is_err());
+    assert!(check_type_id::<Option<i32>>(&Fory::default(), TypeId::INT32 as u32).is_err());
+}
+
+#[test]
+#[should_panic(expected = "does not serialize with fory type id")]
+fn test_assert_fory_compatible_mismatch() {
+    assert_fory_compatible!(i32, TypeId::INT16);
+}